@@ -34,9 +34,25 @@ struct AnimationBenchmarkSample {
     samples_emitted: usize,
 }
 
+#[derive(Debug, Serialize)]
+struct IngestBenchmarkSample {
+    scenario: &'static str,
+    series_count: usize,
+    sample_rate_hz: usize,
+    visible_duration_seconds: f64,
+    batches: usize,
+    samples_published: usize,
+    late_samples: usize,
+    total_ms: f64,
+    avg_batch_ms: f64,
+    max_batch_ms: f64,
+    samples_per_second: f64,
+}
+
 enum Scenario {
     Chart,
     Timeline,
+    Ingest,
 }
 
 fn run_engine_benchmark(
@@ -89,6 +105,82 @@ fn run_engine_benchmark(
     }
 }
 
+fn run_ingest_benchmark(
+    series_count: usize,
+    sample_rate_hz: usize,
+    duration_seconds: usize,
+) -> IngestBenchmarkSample {
+    const BATCHES_PER_SECOND: usize = 60;
+    const LATE_SAMPLE_INTERVAL: usize = 97;
+
+    // A short window keeps the engine trimming on every batch once it fills up.
+    let options = EngineOptions {
+        visible_duration_seconds: 5.0,
+        ..EngineOptions::default()
+    };
+    let visible_duration_seconds = options.visible_duration_seconds;
+    let mut engine = ChartEngine::new(options);
+    let mut rng = StdRng::seed_from_u64(7);
+
+    let batches = duration_seconds * BATCHES_PER_SECOND;
+    let samples_per_batch = sample_rate_hz / BATCHES_PER_SECOND;
+    let step = 1.0 / sample_rate_hz as f64;
+    let mut scratch_samples = Vec::with_capacity(series_count * samples_per_batch);
+    let mut samples_published = 0usize;
+    let mut late_samples = 0usize;
+    let mut batch_accumulator = Duration::ZERO;
+    let mut max_batch = Duration::ZERO;
+
+    for batch in 0..batches {
+        scratch_samples.clear();
+        for series in 0..series_count {
+            for offset in 0..samples_per_batch {
+                let index = batch * samples_per_batch + offset;
+                // Every so often re-deliver a sample a few slots late to exercise inserts.
+                let index = if index.is_multiple_of(LATE_SAMPLE_INTERVAL) && index > 8 {
+                    late_samples += 1;
+                    index - rng.random_range(1..=8)
+                } else {
+                    index
+                };
+                let timestamp_seconds = index as f64 * step;
+                let value = (timestamp_seconds * 3.0 + series as f64).sin() * 10.0
+                    + rng.random_range(-0.5..=0.5);
+
+                scratch_samples.push(ChartSample {
+                    series_id: series as u32,
+                    timestamp_seconds,
+                    value,
+                });
+            }
+        }
+
+        let batch_start = Instant::now();
+        engine.publish_samples(&scratch_samples);
+        let batch_duration = batch_start.elapsed();
+
+        samples_published += scratch_samples.len();
+        batch_accumulator += batch_duration;
+        max_batch = max_batch.max(batch_duration);
+    }
+
+    let total_ms = duration_to_ms(batch_accumulator);
+
+    IngestBenchmarkSample {
+        scenario: "ingest_sliding_window",
+        series_count,
+        sample_rate_hz,
+        visible_duration_seconds,
+        batches,
+        samples_published,
+        late_samples,
+        total_ms,
+        avg_batch_ms: total_ms / batches as f64,
+        max_batch_ms: duration_to_ms(max_batch),
+        samples_per_second: samples_published as f64 / batch_accumulator.as_secs_f64(),
+    }
+}
+
 fn run_timeline_benchmark(track_count: usize, ticks: usize) -> AnimationBenchmarkSample {
     let mut system = TimelineSystem::new();
     let group = system.create_group(TimelineGroupConfig::default());
@@ -156,6 +248,7 @@ fn parse_scenario(arg: &str) -> Option<Scenario> {
     match arg {
         "chart" => Some(Scenario::Chart),
         "timeline" => Some(Scenario::Timeline),
+        "ingest" => Some(Scenario::Ingest),
         other => {
             if let Some(value) = other.strip_prefix("--scenario=") {
                 return parse_scenario(value);
@@ -174,7 +267,7 @@ fn main() {
         Some(arg) => match parse_scenario(&arg) {
             Some(scenario) => scenario,
             None => {
-                eprintln!("Unknown scenario '{arg}'. Expected 'chart' or 'timeline'.");
+                eprintln!("Unknown scenario '{arg}'. Expected 'chart', 'timeline' or 'ingest'.");
                 process::exit(1);
             }
         },
@@ -190,5 +283,9 @@ fn main() {
             let sample = run_timeline_benchmark(10_000, 480);
             println!("{}", serde_json::to_string_pretty(&sample).unwrap());
         }
+        Scenario::Ingest => {
+            let sample = run_ingest_benchmark(4, 50_000, 30);
+            println!("{}", serde_json::to_string_pretty(&sample).unwrap());
+        }
     }
 }
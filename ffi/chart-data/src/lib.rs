@@ -1,5 +1,8 @@
 //! Streaming data structures powering the chart engine.
 
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
 use crossbeam_channel::{Receiver, Sender, TrySendError};
 use serde::{Deserialize, Serialize};
 
@@ -17,26 +20,129 @@ pub struct SeriesSample {
     pub points: Vec<DataPoint>,
 }
 
+/// Backpressure counters shared by every clone of a [`DataBus`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DataBusStats {
+    /// Maximum number of batches the bus can hold.
+    pub capacity: usize,
+    /// Batches currently waiting to be drained.
+    pub queued: usize,
+    /// Batches accepted since the bus was created.
+    pub pushed: u64,
+    /// Batches rejected because the bus was full.
+    pub dropped: u64,
+    /// Points contained in the rejected batches.
+    pub dropped_points: u64,
+    /// Batches handed to consumers through [`DataBus::drain`].
+    pub drained: u64,
+}
+
+#[derive(Debug, Default)]
+struct BusCounters {
+    pushed: AtomicU64,
+    dropped: AtomicU64,
+    dropped_points: AtomicU64,
+    drained: AtomicU64,
+}
+
 /// Non-blocking data ingress channel for the chart engine.
 #[derive(Debug, Clone)]
 pub struct DataBus {
     sender: Sender<SeriesSample>,
     receiver: Receiver<SeriesSample>,
+    counters: Arc<BusCounters>,
 }
 
 impl DataBus {
     pub fn new(capacity: usize) -> Self {
         let (sender, receiver) = crossbeam_channel::bounded(capacity);
-        Self { sender, receiver }
+        Self {
+            sender,
+            receiver,
+            counters: Arc::default(),
+        }
     }
 
     pub fn push(&self, sample: SeriesSample) -> Result<(), TrySendError<SeriesSample>> {
-        self.sender.try_send(sample)
+        match self.sender.try_send(sample) {
+            Ok(()) => {
+                self.counters.pushed.fetch_add(1, Ordering::Relaxed);
+                Ok(())
+            }
+            Err(err) => {
+                let points = match &err {
+                    TrySendError::Full(sample) | TrySendError::Disconnected(sample) => {
+                        sample.points.len() as u64
+                    }
+                };
+                self.counters.dropped.fetch_add(1, Ordering::Relaxed);
+                self.counters
+                    .dropped_points
+                    .fetch_add(points, Ordering::Relaxed);
+                Err(err)
+            }
+        }
     }
 
     pub fn drain(&self, output: &mut Vec<SeriesSample>) {
+        let start = output.len();
         while let Ok(sample) = self.receiver.try_recv() {
             output.push(sample);
         }
+        let drained = (output.len() - start) as u64;
+        if drained > 0 {
+            self.counters.drained.fetch_add(drained, Ordering::Relaxed);
+        }
+    }
+
+    pub fn stats(&self) -> DataBusStats {
+        DataBusStats {
+            capacity: self.receiver.capacity().unwrap_or(usize::MAX),
+            queued: self.receiver.len(),
+            pushed: self.counters.pushed.load(Ordering::Relaxed),
+            dropped: self.counters.dropped.load(Ordering::Relaxed),
+            dropped_points: self.counters.dropped_points.load(Ordering::Relaxed),
+            drained: self.counters.drained.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(series_id: u32, count: usize) -> SeriesSample {
+        SeriesSample {
+            series_id,
+            points: (0..count)
+                .map(|index| DataPoint {
+                    timestamp_ns: index as u64,
+                    value: index as f64,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn stats_track_backpressure_across_clones() {
+        let bus = DataBus::new(2);
+        let producer = bus.clone();
+        assert!(producer.push(sample(1, 3)).is_ok());
+        assert!(producer.push(sample(1, 3)).is_ok());
+        assert!(producer.push(sample(2, 5)).is_err());
+
+        let stats = bus.stats();
+        assert_eq!(stats.capacity, 2);
+        assert_eq!(stats.queued, 2);
+        assert_eq!(stats.pushed, 2);
+        assert_eq!(stats.dropped, 1);
+        assert_eq!(stats.dropped_points, 5);
+
+        let mut drained = Vec::new();
+        bus.drain(&mut drained);
+        assert_eq!(drained.len(), 2);
+        let stats = producer.stats();
+        assert_eq!(stats.queued, 0);
+        assert_eq!(stats.drained, 2);
     }
 }
@@ -11,6 +11,12 @@ pub struct FrameStats {
     pub queue_latency_ms: f32,
     pub encoded_paths: u32,
     pub timestamp: u128,
+    /// Points held by the rendered series before level-of-detail decimation.
+    #[serde(default)]
+    pub source_points: u64,
+    /// Points that reached path building after decimation.
+    #[serde(default)]
+    pub rendered_points: u64,
 }
 
 /// Aggregates frame statistics for later inspection.
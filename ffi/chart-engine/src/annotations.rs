@@ -0,0 +1,549 @@
+//! Reference annotations: value thresholds, event markers and shaded value/time regions.
+//!
+//! Every annotation keeps one cached scene node per pane it lands on. A node is only
+//! re-encoded when the annotation changes or when the part of the pane projection it
+//! depends on moves, so value lines stay cached while live data scrolls the time axis.
+
+use std::collections::BTreeMap;
+
+use vello::{
+    Scene,
+    kurbo::{Affine, Line, Rect, Stroke},
+    peniko::{Brush, Color, Fill},
+};
+use vello_composition::{PlotArea, SceneGraphCache, SceneNodeId};
+
+use crate::{TextStyle, ValueAxis};
+
+const DEFAULT_COLOR: (u8, u8, u8) = (0xF5, 0xA5, 0x24);
+const DEFAULT_STROKE_WIDTH: f64 = 1.5;
+const DEFAULT_FILL_OPACITY: f32 = 0.18;
+const LABEL_INSET: f64 = 4.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AnnotationKind {
+    /// Horizontal line at a value, e.g. an alarm limit.
+    ValueLine { value: f64 },
+    /// Vertical line at a timestamp, e.g. an event.
+    TimeMarker { timestamp_seconds: f64 },
+    /// Shaded region between two values.
+    ValueBand { min: f64, max: f64 },
+    /// Shaded region between two timestamps, e.g. a maintenance window.
+    TimeBand {
+        start_seconds: f64,
+        end_seconds: f64,
+    },
+}
+
+impl AnnotationKind {
+    fn layer(self) -> AnnotationLayer {
+        match self {
+            Self::ValueBand { .. } | Self::TimeBand { .. } => AnnotationLayer::BelowSeries,
+            Self::ValueLine { .. } | Self::TimeMarker { .. } => AnnotationLayer::AboveSeries,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Annotation {
+    pub kind: AnnotationKind,
+    pub label: Option<String>,
+    pub color: Option<Color>,
+    pub stroke_width: f64,
+    /// Opacity of the shaded area of bands; ignored by lines.
+    pub fill_opacity: f32,
+    /// Alternating dash and gap lengths; solid when empty.
+    pub dash_pattern: Vec<f64>,
+    /// Pane the annotation is drawn on; every pane when `None`.
+    pub pane_id: Option<String>,
+}
+
+impl Annotation {
+    pub fn new(kind: AnnotationKind) -> Self {
+        Self {
+            kind,
+            label: None,
+            color: None,
+            stroke_width: DEFAULT_STROKE_WIDTH,
+            fill_opacity: DEFAULT_FILL_OPACITY,
+            dash_pattern: Vec::new(),
+            pane_id: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AnnotationLayer {
+    BelowSeries,
+    AboveSeries,
+}
+
+/// Projection of the pane currently being drawn.
+pub(crate) struct AnnotationPane<'a> {
+    pub(crate) pane_index: usize,
+    pub(crate) pane_id: &'a str,
+    pub(crate) plot_area: PlotArea,
+    pub(crate) range_start: f64,
+    pub(crate) window: f64,
+    pub(crate) value_axis: ValueAxis,
+    pub(crate) label: &'a TextStyle,
+}
+
+/// Inputs an encoded instance depends on; a mismatch triggers a rebuild.
+#[derive(Debug, Clone, PartialEq)]
+struct ProjectionKey {
+    plot: (f64, f64, f64, f64),
+    time: Option<(f64, f64)>,
+    value: Option<ValueAxis>,
+    label_font_size: f32,
+    label_family: Option<String>,
+}
+
+struct Instance {
+    pane_index: usize,
+    node: SceneNodeId,
+    key: Option<ProjectionKey>,
+    paths: u32,
+}
+
+struct AnnotationState {
+    annotation: Annotation,
+    instances: Vec<Instance>,
+}
+
+#[derive(Default)]
+pub(crate) struct AnnotationSet {
+    entries: BTreeMap<u32, AnnotationState>,
+}
+
+impl AnnotationSet {
+    /// Adds a new annotation, returning `false` when `id` is already in use.
+    pub(crate) fn add(&mut self, id: u32, annotation: Annotation) -> bool {
+        if self.entries.contains_key(&id) {
+            return false;
+        }
+        self.entries.insert(
+            id,
+            AnnotationState {
+                annotation,
+                instances: Vec::new(),
+            },
+        );
+        true
+    }
+
+    /// Replaces an existing annotation, returning `false` when `id` is unknown.
+    pub(crate) fn update(&mut self, id: u32, annotation: Annotation) -> bool {
+        let Some(state) = self.entries.get_mut(&id) else {
+            return false;
+        };
+        if state.annotation != annotation {
+            state.annotation = annotation;
+            for instance in &mut state.instances {
+                instance.key = None;
+            }
+        }
+        true
+    }
+
+    /// Removes an annotation and releases its scene nodes.
+    pub(crate) fn remove(&mut self, id: u32, cache: &mut SceneGraphCache) -> bool {
+        let Some(state) = self.entries.remove(&id) else {
+            return false;
+        };
+        for instance in state.instances {
+            cache.dispose_node(instance.node);
+        }
+        true
+    }
+
+    pub(crate) fn clear(&mut self, cache: &mut SceneGraphCache) {
+        for (_, state) in std::mem::take(&mut self.entries) {
+            for instance in state.instances {
+                cache.dispose_node(instance.node);
+            }
+        }
+    }
+
+    /// Appends every annotation on `layer` that targets `pane`, re-encoding stale instances.
+    pub(crate) fn draw_pane(
+        &mut self,
+        cache: &mut SceneGraphCache,
+        parent: SceneNodeId,
+        target: &mut Scene,
+        layer: AnnotationLayer,
+        pane: &AnnotationPane<'_>,
+    ) -> u32 {
+        let mut encoded = 0;
+        for state in self.entries.values_mut() {
+            let annotation = &state.annotation;
+            if annotation.kind.layer() != layer {
+                continue;
+            }
+            if let Some(pane_id) = annotation.pane_id.as_deref()
+                && pane_id != pane.pane_id
+            {
+                continue;
+            }
+
+            let index = match state
+                .instances
+                .iter()
+                .position(|instance| instance.pane_index == pane.pane_index)
+            {
+                Some(index) => index,
+                None => {
+                    state.instances.push(Instance {
+                        pane_index: pane.pane_index,
+                        node: cache.create_node(Some(parent)),
+                        key: None,
+                        paths: 0,
+                    });
+                    state.instances.len() - 1
+                }
+            };
+            let instance = &mut state.instances[index];
+
+            let key = projection_key(annotation.kind, pane);
+            if instance.key.as_ref() != Some(&key)
+                && let Some(scene) = cache.scene_mut(instance.node)
+            {
+                scene.reset();
+                instance.paths = encode(scene, annotation, pane);
+                instance.key = Some(key);
+            }
+
+            if let Some(scene) = cache.scene(instance.node) {
+                target.append(scene, None);
+                encoded += instance.paths;
+            }
+        }
+        encoded
+    }
+}
+
+fn projection_key(kind: AnnotationKind, pane: &AnnotationPane<'_>) -> ProjectionKey {
+    let area = &pane.plot_area;
+    let time_based = matches!(
+        kind,
+        AnnotationKind::TimeMarker { .. } | AnnotationKind::TimeBand { .. }
+    );
+    ProjectionKey {
+        plot: (area.left, area.top, area.width, area.height),
+        time: time_based.then_some((pane.range_start, pane.window)),
+        value: (!time_based).then_some(pane.value_axis),
+        label_font_size: pane.label.font_size,
+        label_family: pane.label.family.clone(),
+    }
+}
+
+fn encode(scene: &mut Scene, annotation: &Annotation, pane: &AnnotationPane<'_>) -> u32 {
+    let area = &pane.plot_area;
+    let color = annotation.color.unwrap_or_else(|| {
+        let (r, g, b) = DEFAULT_COLOR;
+        Color::from_rgba8(r, g, b, 0xFF)
+    });
+    let mut stroke = Stroke::new(annotation.stroke_width.max(0.5));
+    if !annotation.dash_pattern.is_empty() {
+        stroke = stroke.with_dashes(0.0, annotation.dash_pattern.iter().copied());
+    }
+    let time_x = |timestamp: f64| (timestamp - pane.range_start) / pane.window;
+    let value_y = |value: f64| 1.0 - pane.value_axis.normalize(value);
+
+    let mut encoded = 0;
+    let label_origin = match annotation.kind {
+        AnnotationKind::ValueLine { value } => {
+            let t = value_y(value);
+            if !(0.0..=1.0).contains(&t) {
+                return 0;
+            }
+            let y = area.top + t * area.height;
+            scene.stroke(
+                &stroke,
+                Affine::IDENTITY,
+                color,
+                None,
+                &Line::new((area.left, y), (area.right(), y)),
+            );
+            encoded += 1;
+            LabelOrigin::AboveRight(area.right(), y)
+        }
+        AnnotationKind::TimeMarker { timestamp_seconds } => {
+            let t = time_x(timestamp_seconds);
+            if !(0.0..=1.0).contains(&t) {
+                return 0;
+            }
+            let x = area.left + t * area.width;
+            scene.stroke(
+                &stroke,
+                Affine::IDENTITY,
+                color,
+                None,
+                &Line::new((x, area.top), (x, area.bottom())),
+            );
+            encoded += 1;
+            LabelOrigin::BelowRight(x, area.top)
+        }
+        AnnotationKind::ValueBand { min, max } => {
+            let (upper, lower) = (value_y(min.max(max)), value_y(min.min(max)));
+            if upper > 1.0 || lower < 0.0 {
+                return 0;
+            }
+            let y0 = area.top + upper.max(0.0) * area.height;
+            let y1 = area.top + lower.min(1.0) * area.height;
+            encoded += draw_band(
+                scene,
+                Rect::new(area.left, y0, area.right(), y1),
+                color,
+                annotation.fill_opacity,
+                &stroke,
+                [
+                    (0.0..=1.0)
+                        .contains(&upper)
+                        .then(|| Line::new((area.left, y0), (area.right(), y0))),
+                    (0.0..=1.0)
+                        .contains(&lower)
+                        .then(|| Line::new((area.left, y1), (area.right(), y1))),
+                ],
+            );
+            LabelOrigin::BelowRight(area.left, y0)
+        }
+        AnnotationKind::TimeBand {
+            start_seconds,
+            end_seconds,
+        } => {
+            let (start, end) = (
+                time_x(start_seconds.min(end_seconds)),
+                time_x(start_seconds.max(end_seconds)),
+            );
+            if start > 1.0 || end < 0.0 {
+                return 0;
+            }
+            let x0 = area.left + start.max(0.0) * area.width;
+            let x1 = area.left + end.min(1.0) * area.width;
+            encoded += draw_band(
+                scene,
+                Rect::new(x0, area.top, x1, area.bottom()),
+                color,
+                annotation.fill_opacity,
+                &stroke,
+                [
+                    (0.0..=1.0)
+                        .contains(&start)
+                        .then(|| Line::new((x0, area.top), (x0, area.bottom()))),
+                    (0.0..=1.0)
+                        .contains(&end)
+                        .then(|| Line::new((x1, area.top), (x1, area.bottom()))),
+                ],
+            );
+            LabelOrigin::BelowRight(x0, area.top)
+        }
+    };
+
+    if let Some(label) = annotation
+        .label
+        .as_deref()
+        .filter(|label| !label.is_empty())
+        && let Some(layout) = pane.label.layout(label)
+    {
+        let width = f64::from(layout.width);
+        let ascent = f64::from(layout.ascent);
+        let descent = f64::from(layout.height) - ascent;
+        let (x, baseline) = match label_origin {
+            LabelOrigin::AboveRight(right, y) => {
+                (right - LABEL_INSET - width, y - LABEL_INSET - descent)
+            }
+            LabelOrigin::BelowRight(left, top) => (left + LABEL_INSET, top + LABEL_INSET + ascent),
+        };
+        encoded += layout.draw(
+            scene,
+            pane.label.font_size,
+            Affine::translate((x, baseline)),
+            &Brush::Solid(color),
+        );
+    }
+
+    encoded
+}
+
+enum LabelOrigin {
+    /// Right-aligned just above a horizontal line ending at `x`.
+    AboveRight(f64, f64),
+    /// Left-aligned just inside a region starting at `(x, y)`.
+    BelowRight(f64, f64),
+}
+
+fn draw_band(
+    scene: &mut Scene,
+    region: Rect,
+    color: Color,
+    fill_opacity: f32,
+    stroke: &Stroke,
+    edges: [Option<Line>; 2],
+) -> u32 {
+    let mut encoded = 0;
+    if fill_opacity > 0.0 && region.area() > 0.0 {
+        scene.fill(
+            Fill::NonZero,
+            Affine::IDENTITY,
+            color.multiply_alpha(fill_opacity.clamp(0.0, 1.0)),
+            None,
+            &region,
+        );
+        encoded += 1;
+    }
+    for edge in edges.into_iter().flatten() {
+        scene.stroke(stroke, Affine::IDENTITY, color, None, &edge);
+        encoded += 1;
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vello_composition::ValueScale;
+
+    static LABEL: TextStyle = TextStyle {
+        family: None,
+        font_size: 14.0,
+        color: Color::WHITE,
+    };
+
+    fn pane(range_start: f64, value_max: f64) -> AnnotationPane<'static> {
+        AnnotationPane {
+            pane_index: 0,
+            pane_id: "primary",
+            plot_area: PlotArea {
+                left: 0.0,
+                top: 0.0,
+                width: 400.0,
+                height: 200.0,
+            },
+            range_start,
+            window: 60.0,
+            value_axis: ValueAxis::new(ValueScale::Linear, 0.0, value_max),
+            label: &LABEL,
+        }
+    }
+
+    #[test]
+    fn value_lines_stay_cached_while_time_scrolls() {
+        let mut cache = SceneGraphCache::new();
+        let root = cache.create_node(None);
+        let mut set = AnnotationSet::default();
+        assert!(set.add(
+            1,
+            Annotation::new(AnnotationKind::ValueLine { value: 50.0 })
+        ));
+        assert!(!set.add(
+            1,
+            Annotation::new(AnnotationKind::ValueLine { value: 10.0 })
+        ));
+
+        let mut target = Scene::new();
+        let layer = AnnotationLayer::AboveSeries;
+        assert_eq!(
+            set.draw_pane(&mut cache, root, &mut target, layer, &pane(0.0, 100.0)),
+            1
+        );
+        let key = set.entries[&1].instances[0].key.clone();
+        set.draw_pane(&mut cache, root, &mut target, layer, &pane(30.0, 100.0));
+        assert_eq!(set.entries[&1].instances[0].key, key);
+
+        set.draw_pane(&mut cache, root, &mut target, layer, &pane(30.0, 200.0));
+        assert_ne!(set.entries[&1].instances[0].key, key);
+    }
+
+    #[test]
+    fn label_style_changes_re_encode_cached_instances() {
+        let mut cache = SceneGraphCache::new();
+        let root = cache.create_node(None);
+        let mut set = AnnotationSet::default();
+        let mut limit = Annotation::new(AnnotationKind::ValueLine { value: 50.0 });
+        limit.label = Some("Limit".to_string());
+        set.add(1, limit);
+
+        let mut target = Scene::new();
+        let layer = AnnotationLayer::AboveSeries;
+        set.draw_pane(&mut cache, root, &mut target, layer, &pane(0.0, 100.0));
+        let key = set.entries[&1].instances[0].key.clone();
+
+        let larger_label = TextStyle {
+            font_size: 20.0,
+            ..LABEL.clone()
+        };
+        let larger = AnnotationPane {
+            label: &larger_label,
+            ..pane(0.0, 100.0)
+        };
+        set.draw_pane(&mut cache, root, &mut target, layer, &larger);
+        let larger_key = set.entries[&1].instances[0].key.clone();
+        assert_ne!(larger_key, key);
+
+        let serif_label = TextStyle {
+            family: Some("serif".to_string()),
+            ..larger_label.clone()
+        };
+        let serif = AnnotationPane {
+            label: &serif_label,
+            ..larger
+        };
+        set.draw_pane(&mut cache, root, &mut target, layer, &serif);
+        assert_ne!(set.entries[&1].instances[0].key, larger_key);
+    }
+
+    #[test]
+    fn annotations_respect_layer_and_pane_target() {
+        let mut cache = SceneGraphCache::new();
+        let root = cache.create_node(None);
+        let mut set = AnnotationSet::default();
+        let mut band = Annotation::new(AnnotationKind::TimeBand {
+            start_seconds: 10.0,
+            end_seconds: 20.0,
+        });
+        band.pane_id = Some("secondary".to_string());
+        set.add(1, band);
+        set.add(
+            2,
+            Annotation::new(AnnotationKind::TimeMarker {
+                timestamp_seconds: 15.0,
+            }),
+        );
+
+        let mut target = Scene::new();
+        let below = set.draw_pane(
+            &mut cache,
+            root,
+            &mut target,
+            AnnotationLayer::BelowSeries,
+            &pane(0.0, 1.0),
+        );
+        assert_eq!(below, 0, "band targets a different pane");
+        let above = set.draw_pane(
+            &mut cache,
+            root,
+            &mut target,
+            AnnotationLayer::AboveSeries,
+            &pane(0.0, 1.0),
+        );
+        assert_eq!(above, 1);
+
+        assert!(set.remove(2, &mut cache));
+        assert!(!set.remove(2, &mut cache));
+        assert_eq!(set.entries.len(), 1);
+    }
+
+    #[test]
+    fn out_of_range_annotations_encode_nothing() {
+        let mut scene = Scene::new();
+        let marker = Annotation::new(AnnotationKind::TimeMarker {
+            timestamp_seconds: 500.0,
+        });
+        assert_eq!(encode(&mut scene, &marker, &pane(0.0, 1.0)), 0);
+        let band = Annotation::new(AnnotationKind::ValueBand { min: 2.0, max: 3.0 });
+        assert_eq!(encode(&mut scene, &band, &pane(0.0, 1.0)), 0);
+        let clipped = Annotation::new(AnnotationKind::ValueBand { min: 0.5, max: 3.0 });
+        assert_eq!(encode(&mut scene, &clipped, &pane(0.0, 1.0)), 2);
+    }
+}
@@ -0,0 +1,329 @@
+//! Open/high/low/close samples and the candlestick and OHLC bar renderers.
+//!
+//! Candle series keep their full bars in a dedicated buffer and mirror each close into
+//! the regular point buffer, so ranging, hit testing and the cursor tooltip treat them
+//! like any other series.
+
+use vello::{
+    Scene,
+    kurbo::{Affine, Line, Point, Rect, Stroke},
+    peniko::{Brush, Color, Fill},
+};
+use vello_composition::{PlotArea, ValueScale};
+
+use crate::series_buffer::TimedSample;
+use crate::{SeriesKind, ValueAxis, project_time, project_value};
+
+const DEFAULT_UP_COLOR: (u8, u8, u8) = (0x26, 0xA6, 0x9A);
+const DEFAULT_DOWN_COLOR: (u8, u8, u8) = (0xEF, 0x53, 0x50);
+/// Share of the bucket width covered by a candle body or OHLC tick span.
+const BODY_WIDTH_RATIO: f64 = 0.7;
+/// Share of the pane height used by the volume histogram.
+const VOLUME_HEIGHT_RATIO: f64 = 0.2;
+const VOLUME_OPACITY: f32 = 0.3;
+
+/// Multi-value sample for candlestick and OHLC series.
+#[derive(Debug, Clone, Copy)]
+pub struct OhlcSample {
+    pub series_id: u32,
+    pub timestamp_seconds: f64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct OhlcPoint {
+    pub(crate) timestamp_seconds: f64,
+    pub(crate) open: f64,
+    pub(crate) high: f64,
+    pub(crate) low: f64,
+    pub(crate) close: f64,
+    /// `NaN` when the sample carried no volume.
+    pub(crate) volume: f64,
+}
+
+impl From<OhlcSample> for OhlcPoint {
+    /// Widens `high`/`low` so they always bracket the open and close.
+    fn from(sample: OhlcSample) -> Self {
+        let body_high = sample.open.max(sample.close);
+        let body_low = sample.open.min(sample.close);
+        Self {
+            timestamp_seconds: sample.timestamp_seconds,
+            open: sample.open,
+            high: sample.high.max(body_high),
+            low: sample.low.min(body_low),
+            close: sample.close,
+            volume: sample.volume.unwrap_or(f64::NAN),
+        }
+    }
+}
+
+impl TimedSample for OhlcPoint {
+    fn timestamp_seconds(&self) -> f64 {
+        self.timestamp_seconds
+    }
+
+    fn same_values(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+/// Low and high of the bars the scale can represent, or `None` when there are none.
+pub(crate) fn candle_bounds(bars: &[OhlcPoint], scale: ValueScale) -> Option<(f64, f64)> {
+    let mut min = f64::INFINITY;
+    let mut max = f64::NEG_INFINITY;
+    for bar in bars {
+        if scale.accepts(bar.low) {
+            min = min.min(bar.low);
+        }
+        if scale.accepts(bar.high) {
+            max = max.max(bar.high);
+        }
+    }
+
+    (min.is_finite() && max.is_finite()).then_some((min, max))
+}
+
+/// Colours and geometry shared by every bar of a candle series.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct CandleStyle {
+    pub(crate) kind: SeriesKind,
+    pub(crate) up_color: Option<Color>,
+    pub(crate) down_color: Option<Color>,
+    pub(crate) stroke_width: f64,
+    pub(crate) bucket_width_seconds: f64,
+}
+
+impl CandleStyle {
+    fn color_for(&self, bar: &OhlcPoint) -> Color {
+        if bar.close >= bar.open {
+            self.up_color.unwrap_or_else(|| {
+                let (r, g, b) = DEFAULT_UP_COLOR;
+                Color::from_rgba8(r, g, b, 0xFF)
+            })
+        } else {
+            self.down_color.unwrap_or_else(|| {
+                let (r, g, b) = DEFAULT_DOWN_COLOR;
+                Color::from_rgba8(r, g, b, 0xFF)
+            })
+        }
+    }
+}
+
+/// Draws candlesticks or OHLC bars, with a volume histogram along the pane bottom
+/// when any visible bar carries volume. Returns the encoded path count and the label
+/// anchor at the last close.
+pub(crate) fn render_candle_series(
+    scene: &mut Scene,
+    bars: &[OhlcPoint],
+    style: &CandleStyle,
+    plot_area: &PlotArea,
+    range_start: f64,
+    window: f64,
+    value_axis: &ValueAxis,
+) -> (u32, Option<Point>) {
+    if bars.is_empty() {
+        return (0, None);
+    }
+
+    let bucket_width = (style.bucket_width_seconds / window).clamp(0.0, 1.0) * plot_area.width;
+    let half_width = (bucket_width * BODY_WIDTH_RATIO).max(1.0) * 0.5;
+    let stroke = Stroke::new(style.stroke_width);
+    let mut encoded = render_volume(
+        scene,
+        bars,
+        style,
+        plot_area,
+        range_start,
+        window,
+        half_width,
+    );
+    let mut last_point = None;
+
+    for bar in bars {
+        let color = style.color_for(bar);
+        let x = project_time(bar.timestamp_seconds, range_start, window, plot_area);
+        let high_y = project_value(bar.high, value_axis, plot_area);
+        let low_y = project_value(bar.low, value_axis, plot_area);
+        let open_y = project_value(bar.open, value_axis, plot_area);
+        let close_y = project_value(bar.close, value_axis, plot_area);
+
+        scene.stroke(
+            &stroke,
+            Affine::IDENTITY,
+            color,
+            None,
+            &Line::new((x, high_y), (x, low_y)),
+        );
+
+        if style.kind == SeriesKind::Ohlc {
+            let open_x = (x - half_width).max(plot_area.left);
+            let close_x = (x + half_width).min(plot_area.right());
+            scene.stroke(
+                &stroke,
+                Affine::IDENTITY,
+                color,
+                None,
+                &Line::new((open_x, open_y), (x, open_y)),
+            );
+            scene.stroke(
+                &stroke,
+                Affine::IDENTITY,
+                color,
+                None,
+                &Line::new((x, close_y), (close_x, close_y)),
+            );
+        } else {
+            // Keep doji bodies visible as a hairline.
+            let top = open_y.min(close_y);
+            let bottom = open_y.max(close_y).max(top + 1.0);
+            let body = Rect::new(
+                (x - half_width).max(plot_area.left),
+                top,
+                (x + half_width).min(plot_area.right()),
+                bottom,
+            );
+            scene.fill(
+                Fill::NonZero,
+                Affine::IDENTITY,
+                &Brush::Solid(color),
+                None,
+                &body,
+            );
+        }
+        encoded += 3;
+        last_point = Some(Point::new(x, close_y));
+    }
+
+    (encoded, last_point)
+}
+
+fn render_volume(
+    scene: &mut Scene,
+    bars: &[OhlcPoint],
+    style: &CandleStyle,
+    plot_area: &PlotArea,
+    range_start: f64,
+    window: f64,
+    half_width: f64,
+) -> u32 {
+    let max_volume = bars
+        .iter()
+        .map(|bar| bar.volume)
+        .filter(|volume| volume.is_finite() && *volume > 0.0)
+        .fold(0.0, f64::max);
+    if max_volume <= 0.0 {
+        return 0;
+    }
+
+    let height = plot_area.height * VOLUME_HEIGHT_RATIO;
+    let bottom = plot_area.bottom();
+    let mut encoded = 0;
+    for bar in bars
+        .iter()
+        .filter(|bar| bar.volume.is_finite() && bar.volume > 0.0)
+    {
+        let x = project_time(bar.timestamp_seconds, range_start, window, plot_area);
+        let top = bottom - bar.volume / max_volume * height;
+        let rect = Rect::new(
+            (x - half_width).max(plot_area.left),
+            top,
+            (x + half_width).min(plot_area.right()),
+            bottom,
+        );
+        scene.fill(
+            Fill::NonZero,
+            Affine::IDENTITY,
+            style.color_for(bar).multiply_alpha(VOLUME_OPACITY),
+            None,
+            &rect,
+        );
+        encoded += 1;
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bar(timestamp_seconds: f64, open: f64, high: f64, low: f64, close: f64) -> OhlcPoint {
+        OhlcPoint::from(OhlcSample {
+            series_id: 0,
+            timestamp_seconds,
+            open,
+            high,
+            low,
+            close,
+            volume: None,
+        })
+    }
+
+    fn style(kind: SeriesKind) -> CandleStyle {
+        CandleStyle {
+            kind,
+            up_color: Some(Color::WHITE),
+            down_color: Some(Color::BLACK),
+            stroke_width: 1.0,
+            bucket_width_seconds: 1.0,
+        }
+    }
+
+    #[test]
+    fn samples_widen_high_low_to_bracket_the_body() {
+        let point = bar(0.0, 10.0, 9.0, 11.0, 12.0);
+        assert_eq!((point.low, point.high), (10.0, 12.0));
+        assert!(point.volume.is_nan());
+
+        let bars = [bar(0.0, 5.0, 8.0, 4.0, 6.0), bar(1.0, 6.0, 9.0, 2.0, 3.0)];
+        assert_eq!(candle_bounds(&bars, ValueScale::Linear), Some((2.0, 9.0)));
+        assert_eq!(candle_bounds(&[], ValueScale::Linear), None);
+    }
+
+    #[test]
+    fn direction_picks_up_or_down_color() {
+        let style = style(SeriesKind::Candlestick);
+        assert_eq!(style.color_for(&bar(0.0, 1.0, 2.0, 0.0, 2.0)), Color::WHITE);
+        assert_eq!(style.color_for(&bar(0.0, 2.0, 2.0, 0.0, 1.0)), Color::BLACK);
+    }
+
+    #[test]
+    fn volume_is_drawn_only_when_present() {
+        let area = PlotArea {
+            left: 0.0,
+            top: 0.0,
+            width: 100.0,
+            height: 100.0,
+        };
+        let axis = ValueAxis::new(ValueScale::Linear, 0.0, 10.0);
+        let mut bars = vec![bar(2.0, 1.0, 5.0, 0.5, 4.0), bar(6.0, 4.0, 6.0, 2.0, 3.0)];
+        let mut scene = Scene::new();
+
+        let (encoded, anchor) = render_candle_series(
+            &mut scene,
+            &bars,
+            &style(SeriesKind::Ohlc),
+            &area,
+            0.0,
+            10.0,
+            &axis,
+        );
+        assert_eq!(encoded, 6);
+        assert_eq!(anchor, Some(Point::new(60.0, 70.0)));
+
+        bars[0].volume = 100.0;
+        let (encoded, _) = render_candle_series(
+            &mut scene,
+            &bars,
+            &style(SeriesKind::Candlestick),
+            &area,
+            0.0,
+            10.0,
+            &axis,
+        );
+        assert_eq!(encoded, 7);
+    }
+}
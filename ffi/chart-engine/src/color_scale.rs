@@ -0,0 +1,306 @@
+//! Colour scales for heatmap cells and the colour bar drawn beside heatmap panes.
+//!
+//! A scale maps a value through a `(min, max)` domain onto gradient stops. Heatmap
+//! series sharing a pane share one automatic domain so their buckets stay comparable;
+//! either end can be fixed per series.
+
+use hashbrown::HashMap;
+use vello::{
+    Scene,
+    kurbo::{Affine, Rect},
+    peniko::{Brush, Color, ColorStop, ColorStops, Fill, Gradient},
+};
+use vello_composition::AxisTick;
+
+use crate::{SeriesKind, SeriesState, TextStyle};
+
+/// Width of the colour bar itself.
+pub(crate) const COLOR_BAR_WIDTH: f64 = 12.0;
+/// Room taken from the plot for the bar, its gap and its labels.
+pub(crate) const COLOR_BAR_SPACE: f64 = 72.0;
+const COLOR_BAR_LABEL_GAP: f64 = 6.0;
+
+const VIRIDIS: [u32; 9] = [
+    0x440154, 0x472d7b, 0x3b528b, 0x2c728e, 0x21918c, 0x28ae80, 0x5ec962, 0xaddc30, 0xfde725,
+];
+const MAGMA: [u32; 9] = [
+    0x000004, 0x1c1044, 0x4f127b, 0x812581, 0xb5367a, 0xe55064, 0xfb8761, 0xfec287, 0xfcfdbf,
+];
+const INFERNO: [u32; 9] = [
+    0x000004, 0x1f0c48, 0x550f6d, 0x88226a, 0xba3655, 0xe35933, 0xf98e09, 0xf8c931, 0xfcffa4,
+];
+const PLASMA: [u32; 9] = [
+    0x0d0887, 0x4c02a1, 0x7e03a8, 0xa92395, 0xcc4778, 0xe56b5d, 0xf89540, 0xfdc328, 0xf0f921,
+];
+const TURBO: [u32; 9] = [
+    0x30123b, 0x4662d7, 0x36aaf9, 0x1ae4b6, 0x72fe5e, 0xc8ef34, 0xfaba39, 0xf66b19, 0x7a0403,
+];
+const RED_BLUE: [u32; 9] = [
+    0x67001f, 0xb2182b, 0xd6604d, 0xf4a582, 0xf7f7f7, 0x92c5de, 0x4393c3, 0x2166ac, 0x053061,
+];
+const PURPLE_GREEN: [u32; 9] = [
+    0x40004b, 0x762a83, 0x9970ab, 0xc2a5cf, 0xf7f7f7, 0xa6dba0, 0x5aae61, 0x1b7837, 0x00441b,
+];
+const COOL_WARM: [u32; 9] = [
+    0x3b4cc0, 0x6282ea, 0x8db0fe, 0xb8d0f9, 0xdddcdc, 0xf5c4ad, 0xf49a7b, 0xde604d, 0xb40426,
+];
+
+/// Built-in colour maps. The first five are sequential; the rest diverge from a
+/// neutral midpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ColorMap {
+    Viridis,
+    Magma,
+    Inferno,
+    Plasma,
+    Turbo,
+    RedBlue,
+    PurpleGreen,
+    CoolWarm,
+}
+
+impl ColorMap {
+    pub(crate) fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::Viridis),
+            1 => Some(Self::Magma),
+            2 => Some(Self::Inferno),
+            3 => Some(Self::Plasma),
+            4 => Some(Self::Turbo),
+            5 => Some(Self::RedBlue),
+            6 => Some(Self::PurpleGreen),
+            7 => Some(Self::CoolWarm),
+            _ => None,
+        }
+    }
+
+    fn samples(self) -> &'static [u32] {
+        match self {
+            Self::Viridis => &VIRIDIS,
+            Self::Magma => &MAGMA,
+            Self::Inferno => &INFERNO,
+            Self::Plasma => &PLASMA,
+            Self::Turbo => &TURBO,
+            Self::RedBlue => &RED_BLUE,
+            Self::PurpleGreen => &PURPLE_GREEN,
+            Self::CoolWarm => &COOL_WARM,
+        }
+    }
+}
+
+/// Gradient stops sorted by offset in `[0, 1]`.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ColorScale {
+    stops: Vec<(f32, Color)>,
+}
+
+impl ColorScale {
+    pub(crate) fn named(map: ColorMap) -> Self {
+        let samples = map.samples();
+        let last = (samples.len() - 1) as f32;
+        Self {
+            stops: samples
+                .iter()
+                .enumerate()
+                .map(|(index, rgb)| {
+                    let [_, r, g, b] = rgb.to_be_bytes();
+                    (index as f32 / last, Color::from_rgba8(r, g, b, 0xFF))
+                })
+                .collect(),
+        }
+    }
+
+    /// Builds a scale from user stops; offsets must be finite, within `[0, 1]` and
+    /// non-decreasing, and at least two stops are required.
+    pub(crate) fn from_stops(stops: Vec<(f32, Color)>) -> Option<Self> {
+        let valid = stops.len() >= 2
+            && stops
+                .iter()
+                .all(|(offset, _)| offset.is_finite() && (0.0..=1.0).contains(offset))
+            && stops.windows(2).all(|pair| pair[0].0 <= pair[1].0);
+        valid.then_some(Self { stops })
+    }
+
+    pub(crate) fn stops(&self) -> &[(f32, Color)] {
+        &self.stops
+    }
+
+    /// Fades the series colour in with the value, the look of heatmaps without a
+    /// colour map.
+    pub(crate) fn single_hue(color: Color) -> Self {
+        Self {
+            stops: vec![(0.0, color.with_alpha(0.2)), (1.0, color.with_alpha(0.9))],
+        }
+    }
+
+    /// Colour at `t`, clamped to `[0, 1]`.
+    pub(crate) fn sample(&self, t: f64) -> Color {
+        let t = if t.is_finite() {
+            t.clamp(0.0, 1.0) as f32
+        } else {
+            0.0
+        };
+        let index = self.stops.partition_point(|(offset, _)| *offset < t);
+        match (index.checked_sub(1), self.stops.get(index)) {
+            (Some(before), Some(&(offset, color))) => {
+                let (start, from) = self.stops[before];
+                let span = offset - start;
+                if span <= f32::EPSILON {
+                    color
+                } else {
+                    from.lerp_rect(color, (t - start) / span)
+                }
+            }
+            (None, Some(&(_, color))) => color,
+            _ => self.stops[self.stops.len() - 1].1,
+        }
+    }
+
+    fn gradient_stops(&self) -> ColorStops {
+        let stops: Vec<ColorStop> = self
+            .stops
+            .iter()
+            .map(|&(offset, color)| ColorStop::from((offset, color)))
+            .collect();
+        stops.as_slice().into()
+    }
+}
+
+/// Position of `value` inside `domain`, in `[0, 1]`.
+pub(crate) fn normalize(value: f64, (min, max): (f64, f64)) -> f64 {
+    let span = max - min;
+    if span.abs() <= f64::EPSILON {
+        0.5
+    } else {
+        ((value - min) / span).clamp(0.0, 1.0)
+    }
+}
+
+/// Resolves the colour domain of every visible heatmap series in a pane and stores it
+/// on the series. Domain changes rebuild the affected series.
+pub(crate) fn resolve_heatmap_domains(
+    pane_series: &[u32],
+    series: &mut HashMap<u32, SeriesState>,
+    range_start: f64,
+    range_end: f64,
+) {
+    let heatmaps: Vec<u32> = pane_series
+        .iter()
+        .copied()
+        .filter(|id| {
+            series
+                .get(id)
+                .is_some_and(|state| state.visible && state.definition.kind == SeriesKind::Heatmap)
+        })
+        .collect();
+
+    let mut auto_min = f64::INFINITY;
+    let mut auto_max = f64::NEG_INFINITY;
+    for id in &heatmaps {
+        for point in series[id].points.within(range_start, range_end) {
+            if point.value.is_finite() {
+                auto_min = auto_min.min(point.value);
+                auto_max = auto_max.max(point.value);
+            }
+        }
+    }
+    if !auto_min.is_finite() {
+        (auto_min, auto_max) = (0.0, 1.0);
+    }
+
+    for id in &heatmaps {
+        let Some(state) = series.get_mut(id) else {
+            continue;
+        };
+        let domain = (
+            state.definition.color_domain_min.unwrap_or(auto_min),
+            state.definition.color_domain_max.unwrap_or(auto_max),
+        );
+        if state.color_domain != Some(domain) {
+            state.color_domain = Some(domain);
+            state.needs_rebuild = true;
+        }
+    }
+}
+
+/// Draws a vertical colour bar with `min` at the bottom and value labels to its right.
+pub(crate) fn draw_color_bar(
+    scene: &mut Scene,
+    scale: &ColorScale,
+    bar: Rect,
+    ticks: &[AxisTick],
+    text: &TextStyle,
+) -> u32 {
+    let mut gradient = Gradient::new_linear((bar.x0, bar.y1), (bar.x0, bar.y0));
+    gradient.stops = scale.gradient_stops();
+    scene.fill(
+        Fill::NonZero,
+        Affine::IDENTITY,
+        &Brush::Gradient(gradient),
+        None,
+        &bar,
+    );
+    let mut encoded = 1;
+
+    for tick in ticks {
+        let Some(layout) = text.layout(&tick.label) else {
+            continue;
+        };
+        let y = bar.y1 - tick.position * bar.height();
+        let baseline_y = y + f64::from(layout.height) / 2.0;
+        encoded += layout.draw(
+            scene,
+            text.font_size,
+            Affine::translate((bar.x1 + COLOR_BAR_LABEL_GAP, baseline_y)),
+            &Brush::Solid(text.color),
+        );
+    }
+
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn named_maps_span_their_samples() {
+        let viridis = ColorScale::named(ColorMap::Viridis);
+        assert_eq!(
+            viridis.sample(0.0),
+            Color::from_rgba8(0x44, 0x01, 0x54, 0xFF)
+        );
+        assert_eq!(
+            viridis.sample(1.0),
+            Color::from_rgba8(0xfd, 0xe7, 0x25, 0xFF)
+        );
+        assert_eq!(viridis.sample(-3.0), viridis.sample(0.0));
+        assert_eq!(viridis.sample(f64::NAN), viridis.sample(0.0));
+        assert!((0..8).all(|value| ColorMap::from_u32(value).is_some()));
+        assert!(ColorMap::from_u32(8).is_none());
+    }
+
+    #[test]
+    fn custom_stops_interpolate_and_validate() {
+        let scale = ColorScale::from_stops(vec![(0.0, Color::BLACK), (1.0, Color::WHITE)])
+            .expect("valid stops");
+        let middle = scale.sample(0.5).components;
+        assert!(
+            middle[..3]
+                .iter()
+                .all(|channel| (channel - 0.5).abs() < 1e-6)
+        );
+
+        assert!(ColorScale::from_stops(vec![(0.0, Color::BLACK)]).is_none());
+        assert!(ColorScale::from_stops(vec![(0.6, Color::BLACK), (0.4, Color::WHITE)]).is_none());
+        assert!(ColorScale::from_stops(vec![(0.0, Color::BLACK), (1.5, Color::WHITE)]).is_none());
+    }
+
+    #[test]
+    fn values_normalize_through_the_domain() {
+        assert_eq!(normalize(5.0, (0.0, 10.0)), 0.5);
+        assert_eq!(normalize(-5.0, (0.0, 10.0)), 0.0);
+        assert_eq!(normalize(15.0, (0.0, 10.0)), 1.0);
+        assert_eq!(normalize(3.0, (3.0, 3.0)), 0.5);
+    }
+}
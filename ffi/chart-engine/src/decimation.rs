@@ -0,0 +1,260 @@
+//! Level-of-detail reduction applied to dense series before their paths are built.
+
+use std::borrow::Cow;
+
+use crate::SeriesPoint;
+use crate::gaps::finite_runs;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum DecimationMode {
+    #[default]
+    None,
+    /// Keeps the first, last, minimum and maximum sample of every pixel column.
+    MinMax,
+    /// Largest-Triangle-Three-Buckets with one bucket per pixel column.
+    Lttb,
+}
+
+impl DecimationMode {
+    pub(crate) fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::None),
+            1 => Some(Self::MinMax),
+            2 => Some(Self::Lttb),
+            _ => None,
+        }
+    }
+}
+
+/// Reduces `points` to roughly `columns` buckets along the visible time range.
+///
+/// Spans that already fit the pixel budget are returned borrowed and untouched. Gap
+/// markers (non-finite values) are kept, and the runs between them reduced separately.
+pub(crate) fn decimate(
+    points: &[SeriesPoint],
+    mode: DecimationMode,
+    range_start: f64,
+    window: f64,
+    columns: usize,
+) -> Cow<'_, [SeriesPoint]> {
+    let columns = columns.max(1);
+    if mode != DecimationMode::None && points.iter().any(|point| !point.value.is_finite()) {
+        return Cow::Owned(decimate_runs(points, mode, range_start, window, columns));
+    }
+    match mode {
+        DecimationMode::None => Cow::Borrowed(points),
+        DecimationMode::MinMax if points.len() > columns * 4 => {
+            Cow::Owned(min_max_per_column(points, range_start, window, columns))
+        }
+        DecimationMode::Lttb if points.len() > columns.max(3) => {
+            Cow::Owned(largest_triangle_three_buckets(points, columns.max(3)))
+        }
+        _ => Cow::Borrowed(points),
+    }
+}
+
+fn decimate_runs(
+    points: &[SeriesPoint],
+    mode: DecimationMode,
+    range_start: f64,
+    window: f64,
+    columns: usize,
+) -> Vec<SeriesPoint> {
+    let mut output = Vec::new();
+    let mut previous_end = None;
+    for run in finite_runs(points) {
+        if let Some(marker) = previous_end {
+            output.push(points[marker]);
+        }
+        // Min/max buckets are fixed pixel columns; LTTB shares the budget by run length.
+        let budget = match mode {
+            DecimationMode::Lttb => (columns * run.len()).div_ceil(points.len()),
+            _ => columns,
+        };
+        output.extend_from_slice(&decimate(
+            &points[run.clone()],
+            mode,
+            range_start,
+            window,
+            budget,
+        ));
+        previous_end = Some(run.end);
+    }
+    output
+}
+
+fn min_max_per_column(
+    points: &[SeriesPoint],
+    range_start: f64,
+    window: f64,
+    columns: usize,
+) -> Vec<SeriesPoint> {
+    let scale = if window > 0.0 {
+        columns as f64 / window
+    } else {
+        0.0
+    };
+    let last_column = (columns - 1) as f64;
+    let column_of = |point: &SeriesPoint| {
+        ((point.timestamp_seconds - range_start) * scale)
+            .floor()
+            .clamp(0.0, last_column) as usize
+    };
+
+    let mut output = Vec::with_capacity(columns * 4);
+    let mut start = 0;
+    while start < points.len() {
+        let column = column_of(&points[start]);
+        let mut end = start + 1;
+        while end < points.len() && column_of(&points[end]) == column {
+            end += 1;
+        }
+
+        let bucket = &points[start..end];
+        let mut min_index = 0;
+        let mut max_index = 0;
+        for (index, point) in bucket.iter().enumerate() {
+            if point.value < bucket[min_index].value {
+                min_index = index;
+            }
+            if point.value > bucket[max_index].value {
+                max_index = index;
+            }
+        }
+
+        let mut picks = [0, min_index, max_index, bucket.len() - 1];
+        picks.sort_unstable();
+        let mut previous = None;
+        for index in picks {
+            if previous != Some(index) {
+                output.push(bucket[index]);
+                previous = Some(index);
+            }
+        }
+
+        start = end;
+    }
+
+    output
+}
+
+fn largest_triangle_three_buckets(points: &[SeriesPoint], threshold: usize) -> Vec<SeriesPoint> {
+    let len = points.len();
+    let mut output = Vec::with_capacity(threshold);
+    output.push(points[0]);
+
+    let bucket_size = (len - 2) as f64 / (threshold - 2) as f64;
+    let mut anchor = 0;
+
+    for bucket in 0..threshold - 2 {
+        let start = (bucket as f64 * bucket_size) as usize + 1;
+        let end = (((bucket + 1) as f64 * bucket_size) as usize + 1).min(len - 1);
+
+        let next_start = end;
+        let next_end = (((bucket + 2) as f64 * bucket_size) as usize + 1).min(len);
+        let next = &points[next_start..next_end.max(next_start + 1).min(len)];
+        let inv = 1.0 / next.len() as f64;
+        let avg_time = next.iter().map(|p| p.timestamp_seconds).sum::<f64>() * inv;
+        let avg_value = next.iter().map(|p| p.value).sum::<f64>() * inv;
+
+        let a = points[anchor];
+        let mut best_area = -1.0;
+        let mut best_index = start;
+        for (index, point) in points
+            .iter()
+            .enumerate()
+            .take(end.max(start + 1))
+            .skip(start)
+        {
+            let area = ((a.timestamp_seconds - avg_time) * (point.value - a.value)
+                - (a.timestamp_seconds - point.timestamp_seconds) * (avg_value - a.value))
+                .abs();
+            if area > best_area {
+                best_area = area;
+                best_index = index;
+            }
+        }
+
+        output.push(points[best_index]);
+        anchor = best_index;
+    }
+
+    output.push(points[len - 1]);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ramp(count: usize) -> Vec<SeriesPoint> {
+        (0..count)
+            .map(|index| SeriesPoint {
+                timestamp_seconds: index as f64 * 0.001,
+                value: if index % 2 == 0 {
+                    index as f64
+                } else {
+                    -(index as f64)
+                },
+            })
+            .collect()
+    }
+
+    #[test]
+    fn min_max_keeps_extremes_of_each_column() {
+        let points = ramp(10_000);
+        let window = 10.0;
+        let reduced = decimate(&points, DecimationMode::MinMax, 0.0, window, 100);
+        assert!(
+            reduced.len() <= 400,
+            "expected at most four points per column"
+        );
+
+        let max = points.iter().map(|p| p.value).fold(f64::MIN, f64::max);
+        let min = points.iter().map(|p| p.value).fold(f64::MAX, f64::min);
+        assert!(
+            reduced.iter().any(|p| p.value == max),
+            "global max must survive"
+        );
+        assert!(
+            reduced.iter().any(|p| p.value == min),
+            "global min must survive"
+        );
+        assert!(
+            reduced
+                .windows(2)
+                .all(|pair| pair[0].timestamp_seconds <= pair[1].timestamp_seconds),
+            "decimated points must stay in time order"
+        );
+    }
+
+    #[test]
+    fn lttb_hits_threshold_and_keeps_endpoints() {
+        let points = ramp(5_000);
+        let reduced = decimate(&points, DecimationMode::Lttb, 0.0, 5.0, 250);
+        assert_eq!(reduced.len(), 250);
+        assert_eq!(reduced[0].timestamp_seconds, points[0].timestamp_seconds);
+        assert_eq!(
+            reduced[249].timestamp_seconds,
+            points[4_999].timestamp_seconds
+        );
+    }
+
+    #[test]
+    fn sparse_spans_are_borrowed() {
+        let points = ramp(64);
+        let reduced = decimate(&points, DecimationMode::MinMax, 0.0, 1.0, 800);
+        assert!(matches!(reduced, Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn gaps_survive_reduction() {
+        let mut points = ramp(10_000);
+        points[5_000].value = f64::NAN;
+        for mode in [DecimationMode::MinMax, DecimationMode::Lttb] {
+            let reduced = decimate(&points, mode, 0.0, 10.0, 100);
+            assert!(reduced.len() < points.len());
+            assert_eq!(finite_runs(&reduced).len(), 2, "{mode:?} must keep the gap");
+        }
+    }
+}
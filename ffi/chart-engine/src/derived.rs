@@ -0,0 +1,373 @@
+//! Derived series computed from the samples of a source series.
+//!
+//! Each derived series keeps the running state of its operator. Samples appended to
+//! the source extend that state in amortised O(1) (O(window) for the rolling extrema);
+//! inserts and updates in the middle of the source recompute the output from the live
+//! source samples. A non-finite source value is a gap: it is passed through as `NaN`
+//! and the operator starts over after it, except for the integral, which keeps its
+//! running total.
+
+use std::collections::VecDeque;
+
+use crate::SeriesPoint;
+
+/// How a derived series is computed from its source.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DerivedOperator {
+    /// Simple moving average over the last `window` samples.
+    MovingAverage { window: usize },
+    /// Exponential moving average with `alpha = 2 / (period + 1)`, seeded by the first
+    /// sample.
+    ExponentialMovingAverage { period: usize },
+    /// Change per second between consecutive samples.
+    Derivative,
+    /// Trapezoidal running integral over seconds, starting at zero.
+    Integral,
+    /// Rolling maximum and minimum over the last `window` samples.
+    MinMaxEnvelope { window: usize },
+    /// Moving average plus and minus `deviations` population standard deviations.
+    Bollinger { window: usize, deviations: f64 },
+}
+
+impl DerivedOperator {
+    /// Band operators produce an upper and a lower edge.
+    pub fn is_band(&self) -> bool {
+        matches!(self, Self::MinMaxEnvelope { .. } | Self::Bollinger { .. })
+    }
+
+    /// Whether the parameters describe a computable operator: windows and periods of at
+    /// least one sample and a finite, non-negative deviation count.
+    pub fn is_valid(&self) -> bool {
+        match *self {
+            Self::MovingAverage { window } | Self::MinMaxEnvelope { window } => window > 0,
+            Self::ExponentialMovingAverage { period } => period > 0,
+            Self::Bollinger { window, deviations } => {
+                window > 0 && deviations.is_finite() && deviations >= 0.0
+            }
+            Self::Derivative | Self::Integral => true,
+        }
+    }
+}
+
+/// A series computed from `source_series_id`.
+///
+/// Band operators write their upper edge to the derived series id and their lower edge
+/// to `lower_series_id`; pair the two with a band series definition to shade the
+/// envelope.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DerivedSeries {
+    pub source_series_id: u32,
+    pub operator: DerivedOperator,
+    pub lower_series_id: Option<u32>,
+}
+
+/// One computed output sample; `lower` is set for band operators only.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct DerivedSample {
+    pub(crate) timestamp_seconds: f64,
+    pub(crate) value: f64,
+    pub(crate) lower: Option<f64>,
+}
+
+impl DerivedSample {
+    fn gap(timestamp_seconds: f64, band: bool) -> Self {
+        Self {
+            timestamp_seconds,
+            value: f64::NAN,
+            lower: band.then_some(f64::NAN),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct DerivedState {
+    pub(crate) series: DerivedSeries,
+    window: VecDeque<f64>,
+    /// Mean of the window, updated with Welford's sliding recurrence.
+    mean: f64,
+    /// Sum of squared deviations from `mean` over the window.
+    squared_deviations: f64,
+    /// Slides since the window statistics were last recomputed from the samples.
+    slides_since_resync: usize,
+    previous: Option<SeriesPoint>,
+    accumulated: f64,
+}
+
+impl DerivedState {
+    pub(crate) fn new(series: DerivedSeries) -> Self {
+        Self {
+            series,
+            window: VecDeque::new(),
+            mean: 0.0,
+            squared_deviations: 0.0,
+            slides_since_resync: 0,
+            previous: None,
+            accumulated: 0.0,
+        }
+    }
+
+    /// Whether this state, stored under `output_id`, writes to `series_id`.
+    pub(crate) fn writes_to(&self, output_id: u32, series_id: u32) -> bool {
+        output_id == series_id || self.series.lower_series_id == Some(series_id)
+    }
+
+    fn clear_window(&mut self) {
+        self.window.clear();
+        self.mean = 0.0;
+        self.squared_deviations = 0.0;
+        self.slides_since_resync = 0;
+        self.previous = None;
+    }
+
+    /// Extends the operator with a sample appended to the source.
+    pub(crate) fn push(&mut self, point: SeriesPoint) -> Option<DerivedSample> {
+        let band = self.series.operator.is_band();
+        if !point.value.is_finite() {
+            self.clear_window();
+            return Some(DerivedSample::gap(point.timestamp_seconds, band));
+        }
+
+        let sample = |value: f64, lower: Option<f64>| DerivedSample {
+            timestamp_seconds: point.timestamp_seconds,
+            value,
+            lower,
+        };
+        match self.series.operator {
+            DerivedOperator::MovingAverage { window } => {
+                self.slide(point.value, window);
+                (self.window.len() == window).then(|| sample(self.mean, None))
+            }
+            DerivedOperator::ExponentialMovingAverage { period } => {
+                let alpha = 2.0 / (period as f64 + 1.0);
+                let value = match self.previous {
+                    Some(previous) => previous.value + alpha * (point.value - previous.value),
+                    None => point.value,
+                };
+                self.previous = Some(SeriesPoint {
+                    timestamp_seconds: point.timestamp_seconds,
+                    value,
+                });
+                Some(sample(value, None))
+            }
+            DerivedOperator::Derivative => {
+                let previous = self.previous.replace(point)?;
+                let elapsed = point.timestamp_seconds - previous.timestamp_seconds;
+                (elapsed > 0.0).then(|| sample((point.value - previous.value) / elapsed, None))
+            }
+            DerivedOperator::Integral => {
+                if let Some(previous) = self.previous.replace(point) {
+                    let elapsed = point.timestamp_seconds - previous.timestamp_seconds;
+                    self.accumulated += 0.5 * (point.value + previous.value) * elapsed;
+                }
+                Some(sample(self.accumulated, None))
+            }
+            DerivedOperator::MinMaxEnvelope { window } => {
+                self.slide(point.value, window);
+                (self.window.len() == window).then(|| {
+                    let (min, max) = self
+                        .window
+                        .iter()
+                        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+                            (min.min(*value), max.max(*value))
+                        });
+                    sample(max, Some(min))
+                })
+            }
+            DerivedOperator::Bollinger { window, deviations } => {
+                self.slide(point.value, window);
+                (self.window.len() == window).then(|| {
+                    let variance = (self.squared_deviations / window as f64).max(0.0);
+                    let offset = deviations * variance.sqrt();
+                    sample(self.mean + offset, Some(self.mean - offset))
+                })
+            }
+        }
+    }
+
+    fn slide(&mut self, value: f64, window: usize) {
+        self.window.push_back(value);
+        if self.window.len() > window
+            && let Some(expired) = self.window.pop_front()
+        {
+            let mean = self.mean + (value - expired) / self.window.len() as f64;
+            self.squared_deviations += (value - expired) * (value - mean + expired - self.mean);
+            self.mean = mean;
+        } else {
+            let delta = value - self.mean;
+            self.mean += delta / self.window.len() as f64;
+            self.squared_deviations += delta * (value - self.mean);
+        }
+
+        // The recurrences accumulate rounding error; recomputing once per window keeps
+        // it bounded at amortised O(1) per sample.
+        self.slides_since_resync += 1;
+        if self.slides_since_resync >= window {
+            self.resync_window();
+        }
+    }
+
+    fn resync_window(&mut self) {
+        self.slides_since_resync = 0;
+        let count = self.window.len() as f64;
+        self.mean = self.window.iter().sum::<f64>() / count;
+        self.squared_deviations = self
+            .window
+            .iter()
+            .map(|value| (value - self.mean).powi(2))
+            .sum();
+    }
+
+    /// Recomputes the whole output from `source`, leaving the state ready for appends.
+    pub(crate) fn recompute(&mut self, source: &[SeriesPoint]) -> Vec<DerivedSample> {
+        self.clear_window();
+        self.accumulated = 0.0;
+        source
+            .iter()
+            .filter_map(|point| self.push(*point))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(operator: DerivedOperator) -> DerivedState {
+        DerivedState::new(DerivedSeries {
+            source_series_id: 0,
+            operator,
+            lower_series_id: operator.is_band().then_some(2),
+        })
+    }
+
+    fn points(values: &[f64]) -> Vec<SeriesPoint> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(index, value)| SeriesPoint {
+                timestamp_seconds: index as f64,
+                value: *value,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn engine_rejects_empty_windows_and_periods() {
+        let mut engine = crate::ChartEngine::new(crate::EngineOptions::default());
+        for operator in [
+            DerivedOperator::MovingAverage { window: 0 },
+            DerivedOperator::ExponentialMovingAverage { period: 0 },
+            DerivedOperator::MinMaxEnvelope { window: 0 },
+            DerivedOperator::Bollinger {
+                window: 3,
+                deviations: f64::NAN,
+            },
+        ] {
+            let derived = DerivedSeries {
+                source_series_id: 0,
+                operator,
+                lower_series_id: operator.is_band().then_some(2),
+            };
+            assert!(!engine.add_derived_series(1, derived), "{operator:?}");
+        }
+        let derived = DerivedSeries {
+            source_series_id: 0,
+            operator: DerivedOperator::MovingAverage { window: 1 },
+            lower_series_id: None,
+        };
+        assert!(engine.add_derived_series(1, derived));
+    }
+
+    #[test]
+    fn rolling_operators_emit_once_the_window_fills() {
+        let source = points(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        let averages: Vec<f64> = state(DerivedOperator::MovingAverage { window: 3 })
+            .recompute(&source)
+            .iter()
+            .map(|sample| sample.value)
+            .collect();
+        assert_eq!(averages, vec![2.0, 3.0, 4.0]);
+
+        let envelope = state(DerivedOperator::MinMaxEnvelope { window: 2 }).recompute(&source);
+        assert_eq!(envelope.len(), 4);
+        assert_eq!((envelope[0].value, envelope[0].lower), (2.0, Some(1.0)));
+
+        let bands = state(DerivedOperator::Bollinger {
+            window: 2,
+            deviations: 2.0,
+        })
+        .recompute(&points(&[1.0, 3.0]));
+        assert_eq!((bands[0].value, bands[0].lower), (4.0, Some(0.0)));
+    }
+
+    #[test]
+    fn incremental_pushes_match_a_full_recompute() {
+        let source = points(&[4.0, 1.0, 7.0, 3.0, f64::NAN, 2.0, 6.0, 5.0]);
+        for operator in [
+            DerivedOperator::MovingAverage { window: 2 },
+            DerivedOperator::ExponentialMovingAverage { period: 3 },
+            DerivedOperator::Derivative,
+            DerivedOperator::Integral,
+            DerivedOperator::MinMaxEnvelope { window: 3 },
+            DerivedOperator::Bollinger {
+                window: 3,
+                deviations: 2.0,
+            },
+        ] {
+            let full = state(operator).recompute(&source);
+            let mut incremental = state(operator);
+            let pushed: Vec<DerivedSample> = source
+                .iter()
+                .filter_map(|point| incremental.push(*point))
+                .collect();
+            assert_eq!(format!("{full:?}"), format!("{pushed:?}"), "{operator:?}");
+        }
+    }
+
+    #[test]
+    fn rolling_statistics_stay_exact_on_long_offset_streams() {
+        let values: Vec<f64> = (0..20_000)
+            .map(|index| 1.0e9 + f64::from(index % 7) + 0.25 * f64::from(index % 3))
+            .collect();
+        let window = 5;
+        let mut bands = state(DerivedOperator::Bollinger {
+            window,
+            deviations: 1.0,
+        });
+        let mut average = state(DerivedOperator::MovingAverage { window });
+        let mut last = None;
+        let mut last_average = None;
+        for point in points(&values) {
+            last = bands.push(point).or(last);
+            last_average = average.push(point).or(last_average);
+        }
+
+        let tail = &values[values.len() - window..];
+        let mean = tail.iter().sum::<f64>() / window as f64;
+        let deviation =
+            (tail.iter().map(|value| (value - mean).powi(2)).sum::<f64>() / window as f64).sqrt();
+        let last = last.expect("band sample");
+        assert!((last.value - (mean + deviation)).abs() < 1e-6, "{last:?}");
+        assert!(
+            (last.lower.unwrap() - (mean - deviation)).abs() < 1e-6,
+            "{last:?}"
+        );
+        assert!((last_average.expect("average").value - mean).abs() < 1e-6);
+    }
+
+    #[test]
+    fn gaps_pass_through_and_restart_the_operator() {
+        let source = points(&[1.0, 3.0, f64::NAN, 10.0, 14.0]);
+        let derivative = state(DerivedOperator::Derivative).recompute(&source);
+        let values: Vec<f64> = derivative.iter().map(|sample| sample.value).collect();
+        assert_eq!(values[0], 2.0);
+        assert!(values[1].is_nan());
+        assert_eq!(values[2], 4.0);
+
+        let integral = state(DerivedOperator::Integral).recompute(&source);
+        assert_eq!(integral[1].value, 2.0);
+        assert!(integral[2].value.is_nan());
+        assert_eq!(integral[3].value, 2.0);
+        assert_eq!(integral[4].value, 14.0);
+    }
+}
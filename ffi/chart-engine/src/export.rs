@@ -0,0 +1,1076 @@
+//! Headless export of composed chart scenes to PNG and SVG.
+//!
+//! A [`Scene`] only holds the encoding consumed by the GPU pipeline, so export decodes
+//! that encoding back into fills, strokes and clip layers, outlining glyph runs with
+//! skrifa on the way. PNG output rasterizes the commands with the `vello_cpu` sparse
+//! strips renderer; SVG output writes one element per command. Only what the chart
+//! engine encodes is decoded: solid colours, linear and radial gradients, glyph runs and
+//! clip layers with an opacity. Other blend modes draw as normal layers.
+
+use std::{collections::BTreeMap, fmt, fmt::Write as _};
+
+use skrifa::{
+    GlyphId, MetadataProvider,
+    instance::{LocationRef, Size},
+    outline::{DrawSettings, OutlinePen},
+    raw::{FileRef, FontRef, types::F2Dot14},
+};
+use vello::{
+    Scene,
+    kurbo::{Affine, BezPath, Cap, Join, PathEl, Point, Rect, Stroke},
+    peniko::{
+        Brush, Color, ColorStop, Extend, Fill, FontData, Gradient, GradientKind,
+        color::{DynamicColor, Srgb},
+    },
+};
+use vello_cpu::{Level, RenderContext, RenderMode, RenderSettings};
+use vello_encoding::{DrawTag, Encoding, GlyphRun, Patch, PathTag, Style, Transform};
+
+/// Resolution of a scene unit at scale 1, recorded in PNG metadata.
+const BASE_DPI: f64 = 96.0;
+const METERS_PER_INCH: f64 = 0.0254;
+/// Decimals kept for SVG coordinates.
+const SVG_PRECISION: f64 = 1_000.0;
+
+/// Output file format of an export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Png,
+    Svg,
+}
+
+impl ExportFormat {
+    pub(crate) fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::Png),
+            1 => Some(Self::Svg),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExportOptions {
+    /// Output pixels per scene unit; 2.0 writes a 96 DPI chart at 192 DPI.
+    pub scale: f64,
+    /// Colour filled behind the chart; transparent when `None`.
+    pub background: Option<Color>,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            background: None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ExportError {
+    /// The scale is not finite and positive.
+    InvalidScale,
+    /// The scaled output exceeds the 16-bit surface size of the CPU renderer.
+    TooLarge {
+        width: f64,
+        height: f64,
+    },
+    Png(png::EncodingError),
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidScale => f.write_str("export scale must be finite and positive"),
+            Self::TooLarge { width, height } => write!(
+                f,
+                "export size {width}x{height} exceeds {}x{} pixels",
+                u16::MAX,
+                u16::MAX
+            ),
+            Self::Png(error) => write!(f, "PNG encoding failed: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for ExportError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Png(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Encodes `scene`, drawn in a `width` x `height` unit space, in `format`.
+pub fn export_scene(
+    scene: &Scene,
+    width: u32,
+    height: u32,
+    format: ExportFormat,
+    options: &ExportOptions,
+) -> Result<Vec<u8>, ExportError> {
+    if !options.scale.is_finite() || options.scale <= 0.0 {
+        return Err(ExportError::InvalidScale);
+    }
+
+    let commands = decode_scene(scene);
+    match format {
+        ExportFormat::Png => write_png(&commands, width, height, options),
+        ExportFormat::Svg => Ok(write_svg(&commands, width, height, options).into_bytes()),
+    }
+}
+
+/// Brush resolved from a draw tag; `transform` maps brush space into the space of the
+/// geometry it paints.
+#[derive(Debug, Clone)]
+struct Paint {
+    brush: Brush,
+    transform: Affine,
+}
+
+#[derive(Debug, Clone)]
+enum DrawCommand {
+    Fill {
+        path: BezPath,
+        transform: Affine,
+        rule: Fill,
+        paint: Paint,
+    },
+    Stroke {
+        path: BezPath,
+        transform: Affine,
+        stroke: Stroke,
+        paint: Paint,
+    },
+    PushClip {
+        path: BezPath,
+        transform: Affine,
+        alpha: f32,
+    },
+    PopClip,
+}
+
+struct EncodedPath {
+    path: BezPath,
+    /// Transform of the path segments.
+    transform: Affine,
+    /// Transform current at the end of the path, which brush transforms are encoded as.
+    brush_transform: Affine,
+    style: Option<Style>,
+}
+
+fn decode_scene(scene: &Scene) -> Vec<DrawCommand> {
+    let encoding = scene.encoding();
+    let mut paths = decode_paths(encoding).into_iter();
+    // Glyph runs have no encoded paths; they are found through the draw tag of their brush.
+    let glyph_runs: BTreeMap<usize, &GlyphRun> = encoding
+        .resources
+        .patches
+        .iter()
+        .filter_map(|patch| match patch {
+            Patch::GlyphRun { index } => encoding.resources.glyph_runs.get(*index),
+            _ => None,
+        })
+        .map(|run| (run.stream_offsets.draw_tags, run))
+        .collect();
+
+    let mut commands = Vec::new();
+    let mut data_offset = 0;
+    for (tag_index, &tag) in encoding.draw_tags.iter().enumerate() {
+        let offset = data_offset;
+        // Mirrors the draw monoid: bits 2..5 of a tag count its draw data words.
+        data_offset += ((tag.0 >> 2) & 0x7) as usize;
+        let data = encoding
+            .draw_data
+            .get(offset..data_offset)
+            .unwrap_or_default();
+
+        if tag == DrawTag::NOP {
+            continue;
+        }
+        if let Some(run) = glyph_runs.get(&tag_index) {
+            if let Some(brush) = decode_brush(encoding, tag, data, offset) {
+                push_glyph_run(&mut commands, encoding, run, brush);
+            }
+            continue;
+        }
+
+        let Some(encoded) = paths.next() else {
+            break;
+        };
+        if tag == DrawTag::BEGIN_CLIP {
+            commands.push(DrawCommand::PushClip {
+                path: encoded.path,
+                transform: encoded.transform,
+                alpha: data.get(1).map_or(1.0, |bits| f32::from_bits(*bits)),
+            });
+            continue;
+        }
+        if tag == DrawTag::END_CLIP {
+            commands.push(DrawCommand::PopClip);
+            continue;
+        }
+
+        let Some(brush) = decode_brush(encoding, tag, data, offset) else {
+            continue;
+        };
+        let paint = Paint {
+            brush,
+            transform: if encoded.brush_transform == encoded.transform {
+                Affine::IDENTITY
+            } else {
+                encoded.transform.inverse() * encoded.brush_transform
+            },
+        };
+        commands.push(match encoded.style.and_then(|style| stroke_style(&style)) {
+            Some(stroke) => DrawCommand::Stroke {
+                path: encoded.path,
+                transform: encoded.transform,
+                stroke,
+                paint,
+            },
+            None => DrawCommand::Fill {
+                path: encoded.path,
+                transform: encoded.transform,
+                rule: fill_rule(encoded.style.as_ref()),
+                paint,
+            },
+        });
+    }
+    commands
+}
+
+/// Splits the path tag stream into paths, one per `PATH` marker.
+fn decode_paths(encoding: &Encoding) -> Vec<EncodedPath> {
+    let mut paths = Vec::with_capacity(encoding.n_paths as usize);
+    let mut transform = Affine::IDENTITY;
+    let mut transforms = encoding.transforms.iter();
+    let mut style = None;
+    let mut styles = encoding.styles.iter();
+
+    let mut path = BezPath::new();
+    let mut path_transform = None;
+    let mut subpath_start = true;
+    let mut offset = 0;
+    for &tag in &encoding.path_tags {
+        if tag == PathTag::TRANSFORM {
+            transform = transforms.next().map_or(transform, to_affine);
+            continue;
+        }
+        if tag == PathTag::STYLE {
+            style = styles.next().copied().or(style);
+            continue;
+        }
+        if tag == PathTag::PATH {
+            paths.push(EncodedPath {
+                path: std::mem::take(&mut path),
+                transform: path_transform.take().unwrap_or(transform),
+                brush_transform: transform,
+                style,
+            });
+            subpath_start = true;
+            continue;
+        }
+        if !tag.is_path_segment() {
+            continue;
+        }
+
+        let words = if tag.is_f32() { 2 } else { 1 };
+        let count = usize::from(tag.0 & PathTag::SEGMENT_MASK);
+        let points: [Point; 4] = std::array::from_fn(|index| {
+            read_point(&encoding.path_data, offset + index * words, tag.is_f32())
+        });
+        // Segments share their start point with the previous end point; the end of a
+        // subpath also skips the point the next subpath moves to.
+        offset += count * words;
+        path_transform.get_or_insert(transform);
+
+        if tag.is_subpath_end() {
+            offset += words;
+            if style.is_some_and(|style| is_stroke(&style)) {
+                // The last segment of a stroked subpath only marks its caps: a line for
+                // closed subpaths, a quad for open ones.
+                if count == 1 && !subpath_start {
+                    path.close_path();
+                }
+                subpath_start = true;
+                continue;
+            }
+        }
+
+        if subpath_start {
+            path.move_to(points[0]);
+            subpath_start = false;
+        }
+        match count {
+            1 => path.line_to(points[1]),
+            2 => path.quad_to(points[1], points[2]),
+            _ => path.curve_to(points[1], points[2], points[3]),
+        }
+        if tag.is_subpath_end() {
+            path.close_path();
+            subpath_start = true;
+        }
+    }
+    paths
+}
+
+fn read_point(data: &[u32], offset: usize, is_f32: bool) -> Point {
+    if is_f32 {
+        let coordinate = |index: usize| {
+            data.get(index)
+                .map_or(0.0, |bits| f64::from(f32::from_bits(*bits)))
+        };
+        Point::new(coordinate(offset), coordinate(offset + 1))
+    } else {
+        let packed = data.get(offset).copied().unwrap_or_default();
+        let [x0, x1, y0, y1] = packed.to_le_bytes();
+        Point::new(
+            f64::from(i16::from_le_bytes([x0, x1])),
+            f64::from(i16::from_le_bytes([y0, y1])),
+        )
+    }
+}
+
+fn to_affine(transform: &Transform) -> Affine {
+    let [a, b, c, d] = transform.matrix;
+    let [e, f] = transform.translation;
+    Affine::new([a, b, c, d, e, f].map(f64::from))
+}
+
+fn decode_brush(encoding: &Encoding, tag: DrawTag, data: &[u32], offset: usize) -> Option<Brush> {
+    let point = |index: usize| {
+        Some(Point::new(
+            f64::from(f32::from_bits(*data.get(index)?)),
+            f64::from(f32::from_bits(*data.get(index + 1)?)),
+        ))
+    };
+    let gradient = |kind: Option<Gradient>| {
+        let (stops, extend) = ramp(encoding, offset)?;
+        let mut gradient = kind?.with_extend(extend);
+        gradient.stops = stops.into();
+        Some(Brush::Gradient(gradient))
+    };
+
+    match tag {
+        DrawTag::COLOR => data
+            .first()
+            .map(|packed| Brush::Solid(unpack_color(*packed))),
+        DrawTag::LINEAR_GRADIENT => gradient(
+            point(1)
+                .zip(point(3))
+                .map(|(start, end): (Point, Point)| Gradient::new_linear(start, end)),
+        ),
+        DrawTag::RADIAL_GRADIENT => {
+            let radius = |index: usize| data.get(index).map(|bits| f32::from_bits(*bits));
+            gradient(point(1).zip(point(3)).zip(radius(5).zip(radius(6))).map(
+                |((start, end), (start_radius, end_radius)): ((Point, Point), _)| {
+                    Gradient::new_two_point_radial(start, start_radius, end, end_radius)
+                },
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// Colour stops of the gradient whose draw data starts at `offset`.
+fn ramp(encoding: &Encoding, offset: usize) -> Option<(&[ColorStop], Extend)> {
+    encoding
+        .resources
+        .patches
+        .iter()
+        .find_map(|patch| match patch {
+            Patch::Ramp {
+                draw_data_offset,
+                stops,
+                extend,
+            } if *draw_data_offset == offset => encoding
+                .resources
+                .color_stops
+                .get(stops.clone())
+                .map(|stops| (stops, *extend)),
+            _ => None,
+        })
+}
+
+/// Unpacks a premultiplied colour packed as native-endian `[r, g, b, a]` bytes.
+fn unpack_color(packed: u32) -> Color {
+    let [r, g, b, a] = packed.to_ne_bytes();
+    if a == 0 {
+        return Color::TRANSPARENT;
+    }
+    let alpha = f32::from(a) / 255.0;
+    let channel = |value: u8| (f32::from(value) / 255.0 / alpha).min(1.0);
+    Color::new([channel(r), channel(g), channel(b), alpha])
+}
+
+fn fill_rule(style: Option<&Style>) -> Fill {
+    match style {
+        Some(style) if style.flags_and_miter_limit & Style::FLAGS_FILL_BIT != 0 => Fill::EvenOdd,
+        _ => Fill::NonZero,
+    }
+}
+
+fn is_stroke(style: &Style) -> bool {
+    style.flags_and_miter_limit & Style::FLAGS_STYLE_BIT != 0
+}
+
+/// The stroke described by `style`, or `None` for fill styles.
+fn stroke_style(style: &Style) -> Option<Stroke> {
+    if !is_stroke(style) {
+        return None;
+    }
+    let flags = style.flags_and_miter_limit;
+
+    let join = match flags & Style::FLAGS_JOIN_MASK {
+        Style::FLAGS_JOIN_BITS_MITER => Join::Miter,
+        Style::FLAGS_JOIN_BITS_ROUND => Join::Round,
+        _ => Join::Bevel,
+    };
+    let cap = |bits: u32| match bits {
+        Style::FLAGS_CAP_BITS_SQUARE => Cap::Square,
+        Style::FLAGS_CAP_BITS_ROUND => Cap::Round,
+        _ => Cap::Butt,
+    };
+    Some(
+        Stroke::new(f64::from(style.line_width))
+            .with_join(join)
+            .with_start_cap(cap((flags & Style::FLAGS_START_CAP_MASK) >> 2))
+            .with_end_cap(cap(flags & Style::FLAGS_END_CAP_MASK))
+            .with_miter_limit(half_to_f64((flags & Style::MITER_LIMIT_MASK) as u16)),
+    )
+}
+
+/// Widens an IEEE 754 half-precision float, the storage of encoded miter limits.
+fn half_to_f64(bits: u16) -> f64 {
+    let sign = if bits & 0x8000 == 0 { 1.0 } else { -1.0 };
+    let exponent = i32::from((bits >> 10) & 0x1f);
+    let mantissa = f64::from(bits & 0x3ff);
+    sign * match exponent {
+        0 => mantissa * 2f64.powi(-24),
+        0x1f if mantissa == 0.0 => f64::INFINITY,
+        0x1f => f64::NAN,
+        _ => (1.0 + mantissa / 1024.0) * 2f64.powi(exponent - 15),
+    }
+}
+
+fn push_glyph_run(
+    commands: &mut Vec<DrawCommand>,
+    encoding: &Encoding,
+    run: &GlyphRun,
+    brush: Brush,
+) {
+    let Some(font) = font_ref(&run.font) else {
+        return;
+    };
+    let coords: Vec<F2Dot14> = encoding
+        .resources
+        .normalized_coords
+        .get(run.normalized_coords.clone())
+        .unwrap_or_default()
+        .iter()
+        .map(|coord| F2Dot14::from_bits(*coord))
+        .collect();
+    let outlines = font.outline_glyphs();
+    let transform = to_affine(&run.transform);
+    let glyph_transform = run
+        .glyph_transform
+        .as_ref()
+        .map_or(Affine::IDENTITY, to_affine);
+    let stroke = stroke_style(&run.style);
+    let paint = Paint {
+        brush,
+        transform: Affine::IDENTITY,
+    };
+
+    let glyphs = encoding
+        .resources
+        .glyphs
+        .get(run.glyphs.clone())
+        .unwrap_or_default();
+    for glyph in glyphs {
+        let Some(outline) = outlines.get(GlyphId::new(glyph.id)) else {
+            continue;
+        };
+        let mut pen = PathPen::default();
+        let settings = DrawSettings::unhinted(Size::new(run.font_size), LocationRef::new(&coords));
+        if outline.draw(settings, &mut pen).is_err() || pen.0.elements().is_empty() {
+            continue;
+        }
+        // Outlines are y-up; flip them before the glyph transform, as vello does.
+        let placement = Affine::translate((f64::from(glyph.x), f64::from(glyph.y)))
+            * Affine::FLIP_Y
+            * glyph_transform;
+        let mut path = pen.0;
+        path.apply_affine(placement);
+
+        commands.push(match &stroke {
+            Some(stroke) => DrawCommand::Stroke {
+                path,
+                transform,
+                stroke: stroke.clone(),
+                paint: paint.clone(),
+            },
+            None => DrawCommand::Fill {
+                path,
+                transform,
+                rule: Fill::NonZero,
+                paint: paint.clone(),
+            },
+        });
+    }
+}
+
+fn font_ref(font: &FontData) -> Option<FontRef<'_>> {
+    match FileRef::new(font.data.as_ref()).ok()? {
+        FileRef::Font(font) => Some(font),
+        FileRef::Collection(collection) => collection.get(font.index).ok(),
+    }
+}
+
+#[derive(Default)]
+struct PathPen(BezPath);
+
+impl OutlinePen for PathPen {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.0.move_to((f64::from(x), f64::from(y)));
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.0.line_to((f64::from(x), f64::from(y)));
+    }
+
+    fn quad_to(&mut self, cx0: f32, cy0: f32, x: f32, y: f32) {
+        self.0.quad_to(
+            (f64::from(cx0), f64::from(cy0)),
+            (f64::from(x), f64::from(y)),
+        );
+    }
+
+    fn curve_to(&mut self, cx0: f32, cy0: f32, cx1: f32, cy1: f32, x: f32, y: f32) {
+        self.0.curve_to(
+            (f64::from(cx0), f64::from(cy0)),
+            (f64::from(cx1), f64::from(cy1)),
+            (f64::from(x), f64::from(y)),
+        );
+    }
+
+    fn close(&mut self) {
+        self.0.close_path();
+    }
+}
+
+fn pixel_size(width: u32, height: u32, scale: f64) -> Result<(u16, u16), ExportError> {
+    let scaled_width = (f64::from(width) * scale).round();
+    let scaled_height = (f64::from(height) * scale).round();
+    let fits = |value: f64| (1.0..=f64::from(u16::MAX)).contains(&value);
+    if !fits(scaled_width) || !fits(scaled_height) {
+        return Err(ExportError::TooLarge {
+            width: scaled_width,
+            height: scaled_height,
+        });
+    }
+    Ok((scaled_width as u16, scaled_height as u16))
+}
+
+fn write_png(
+    commands: &[DrawCommand],
+    width: u32,
+    height: u32,
+    options: &ExportOptions,
+) -> Result<Vec<u8>, ExportError> {
+    let (pixel_width, pixel_height) = pixel_size(width, height, options.scale)?;
+
+    // The scalar pipeline on one thread keeps output identical across machines.
+    let settings = RenderSettings {
+        level: Level::fallback(),
+        num_threads: 0,
+        ..RenderSettings::default()
+    };
+    let mut context = RenderContext::new_with(pixel_width, pixel_height, settings);
+
+    if let Some(background) = options.background {
+        context.set_paint(background);
+        context.fill_rect(&Rect::new(
+            0.0,
+            0.0,
+            f64::from(pixel_width),
+            f64::from(pixel_height),
+        ));
+    }
+
+    let root = Affine::scale(options.scale);
+    for command in commands {
+        match command {
+            DrawCommand::Fill {
+                path,
+                transform,
+                rule,
+                paint,
+            } => {
+                context.set_transform(root * *transform);
+                context.set_fill_rule(*rule);
+                set_paint(&mut context, paint);
+                context.fill_path(path);
+            }
+            DrawCommand::Stroke {
+                path,
+                transform,
+                stroke,
+                paint,
+            } => {
+                context.set_transform(root * *transform);
+                context.set_stroke(stroke.clone());
+                set_paint(&mut context, paint);
+                context.stroke_path(path);
+            }
+            DrawCommand::PushClip {
+                path,
+                transform,
+                alpha,
+            } => {
+                context.set_transform(root * *transform);
+                if *alpha >= 1.0 {
+                    context.push_clip_layer(path);
+                } else {
+                    context.push_layer(Some(path), None, Some(*alpha), None);
+                }
+            }
+            DrawCommand::PopClip => context.pop_layer(),
+        }
+    }
+
+    context.flush();
+    let mut pixels = vec![0; usize::from(pixel_width) * usize::from(pixel_height) * 4];
+    context.render_to_buffer(
+        &mut pixels,
+        pixel_width,
+        pixel_height,
+        RenderMode::OptimizeQuality,
+    );
+    unpremultiply(&mut pixels);
+    encode_png(
+        &pixels,
+        u32::from(pixel_width),
+        u32::from(pixel_height),
+        options.scale,
+    )
+    .map_err(ExportError::Png)
+}
+
+fn set_paint(context: &mut RenderContext, paint: &Paint) {
+    match &paint.brush {
+        Brush::Solid(color) => context.set_paint(*color),
+        Brush::Gradient(gradient) => context.set_paint(gradient.clone()),
+        // The decoder never produces image brushes.
+        Brush::Image(_) => context.set_paint(Color::TRANSPARENT),
+    }
+    context.set_paint_transform(paint.transform);
+}
+
+fn unpremultiply(pixels: &mut [u8]) {
+    for pixel in pixels.chunks_exact_mut(4) {
+        let alpha = u16::from(pixel[3]);
+        if alpha == 0 || alpha == 255 {
+            continue;
+        }
+        for channel in &mut pixel[..3] {
+            *channel = ((u16::from(*channel) * 255 + alpha / 2) / alpha).min(255) as u8;
+        }
+    }
+}
+
+fn encode_png(
+    pixels: &[u8],
+    width: u32,
+    height: u32,
+    scale: f64,
+) -> Result<Vec<u8>, png::EncodingError> {
+    let mut bytes = Vec::new();
+    let mut encoder = png::Encoder::new(&mut bytes, width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let pixels_per_meter = (BASE_DPI * scale / METERS_PER_INCH).round() as u32;
+    encoder.set_pixel_dims(Some(png::PixelDimensions {
+        xppu: pixels_per_meter,
+        yppu: pixels_per_meter,
+        unit: png::Unit::Meter,
+    }));
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(pixels)?;
+    writer.finish()?;
+    Ok(bytes)
+}
+
+/// SVG number rounded to [`SVG_PRECISION`].
+struct Number(f64);
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rounded = (self.0 * SVG_PRECISION).round() / SVG_PRECISION;
+        // Avoids printing `-0`.
+        write!(f, "{}", rounded + 0.0)
+    }
+}
+
+struct SvgWriter {
+    out: String,
+    next_id: usize,
+}
+
+fn write_svg(commands: &[DrawCommand], width: u32, height: u32, options: &ExportOptions) -> String {
+    let mut writer = SvgWriter {
+        out: String::new(),
+        next_id: 0,
+    };
+    let _ = writeln!(
+        writer.out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {width} {height}">"#,
+        Number(f64::from(width) * options.scale),
+        Number(f64::from(height) * options.scale),
+    );
+    if let Some(background) = options.background {
+        let _ = writeln!(
+            writer.out,
+            r#"<rect width="{width}" height="{height}"{}/>"#,
+            color_attributes("fill", background)
+        );
+    }
+
+    for command in commands {
+        writer.command(command);
+    }
+    writer.out.push_str("</svg>\n");
+    writer.out
+}
+
+impl SvgWriter {
+    fn id(&mut self, prefix: &str) -> String {
+        self.next_id += 1;
+        format!("{prefix}{}", self.next_id)
+    }
+
+    fn command(&mut self, command: &DrawCommand) {
+        match command {
+            DrawCommand::Fill {
+                path,
+                transform,
+                rule,
+                paint,
+            } => {
+                let fill = self.paint("fill", paint);
+                let rule = match rule {
+                    Fill::EvenOdd => r#" fill-rule="evenodd""#,
+                    Fill::NonZero => "",
+                };
+                let _ = writeln!(
+                    self.out,
+                    r#"<path d="{}"{}{fill}{rule}/>"#,
+                    path_data(path),
+                    transform_attribute(*transform),
+                );
+            }
+            DrawCommand::Stroke {
+                path,
+                transform,
+                stroke,
+                paint,
+            } => {
+                let stroke_paint = self.paint("stroke", paint);
+                let _ = writeln!(
+                    self.out,
+                    r#"<path d="{}"{} fill="none"{stroke_paint}{}/>"#,
+                    path_data(path),
+                    transform_attribute(*transform),
+                    stroke_attributes(stroke),
+                );
+            }
+            DrawCommand::PushClip {
+                path,
+                transform,
+                alpha,
+            } => {
+                let id = self.id("clip");
+                let _ = writeln!(
+                    self.out,
+                    r#"<clipPath id="{id}"><path d="{}"{}/></clipPath>"#,
+                    path_data(path),
+                    transform_attribute(*transform),
+                );
+                let opacity = if *alpha < 1.0 {
+                    format!(r#" opacity="{}""#, Number(f64::from(*alpha)))
+                } else {
+                    String::new()
+                };
+                let _ = writeln!(self.out, r#"<g clip-path="url(#{id})"{opacity}>"#);
+            }
+            DrawCommand::PopClip => self.out.push_str("</g>\n"),
+        }
+    }
+
+    /// Attributes painting `attribute` with `paint`, writing gradients as definitions.
+    fn paint(&mut self, attribute: &str, paint: &Paint) -> String {
+        let gradient = match &paint.brush {
+            Brush::Solid(color) => return color_attributes(attribute, *color),
+            Brush::Gradient(gradient) => gradient,
+            Brush::Image(_) => return format!(r#" {attribute}="none""#),
+        };
+
+        let id = self.id("paint");
+        let spread = match gradient.extend {
+            Extend::Pad => "pad",
+            Extend::Repeat => "repeat",
+            Extend::Reflect => "reflect",
+        };
+        let element = match &gradient.kind {
+            GradientKind::Linear(line) => {
+                let _ = write!(
+                    self.out,
+                    r#"<defs><linearGradient id="{id}" gradientUnits="userSpaceOnUse" x1="{}" y1="{}" x2="{}" y2="{}""#,
+                    Number(line.start.x),
+                    Number(line.start.y),
+                    Number(line.end.x),
+                    Number(line.end.y),
+                );
+                "linearGradient"
+            }
+            GradientKind::Radial(radial) => {
+                let _ = write!(
+                    self.out,
+                    r#"<defs><radialGradient id="{id}" gradientUnits="userSpaceOnUse" fx="{}" fy="{}" fr="{}" cx="{}" cy="{}" r="{}""#,
+                    Number(radial.start_center.x),
+                    Number(radial.start_center.y),
+                    Number(f64::from(radial.start_radius)),
+                    Number(radial.end_center.x),
+                    Number(radial.end_center.y),
+                    Number(f64::from(radial.end_radius)),
+                );
+                "radialGradient"
+            }
+            GradientKind::Sweep(_) => return format!(r#" {attribute}="none""#),
+        };
+        let gradient_transform = if paint.transform == Affine::IDENTITY {
+            String::new()
+        } else {
+            format!(r#" gradientTransform="{}""#, matrix(paint.transform))
+        };
+        let _ = writeln!(self.out, r#" spreadMethod="{spread}"{gradient_transform}>"#);
+        for stop in gradient.stops.iter() {
+            let _ = writeln!(
+                self.out,
+                r#"<stop offset="{}"{}/>"#,
+                Number(f64::from(stop.offset)),
+                color_attributes("stop-color", stop_color(stop.color)),
+            );
+        }
+        let _ = writeln!(self.out, "</{element}></defs>");
+        format!(r#" {attribute}="url(#{id})""#)
+    }
+}
+
+fn stop_color(color: DynamicColor) -> Color {
+    color.to_alpha_color::<Srgb>()
+}
+
+/// `attribute` set to the colour, plus an opacity attribute for translucent colours.
+/// Stop colours take their opacity from `stop-opacity`.
+fn color_attributes(attribute: &str, color: Color) -> String {
+    let rgba = color.to_rgba8();
+    let mut attributes = format!(
+        r##" {attribute}="#{:02x}{:02x}{:02x}""##,
+        rgba.r, rgba.g, rgba.b
+    );
+    if rgba.a < u8::MAX {
+        let opacity = match attribute {
+            "stop-color" => "stop-opacity",
+            "stroke" => "stroke-opacity",
+            _ => "fill-opacity",
+        };
+        let _ = write!(
+            attributes,
+            r#" {opacity}="{}""#,
+            Number(f64::from(rgba.a) / 255.0)
+        );
+    }
+    attributes
+}
+
+fn stroke_attributes(stroke: &Stroke) -> String {
+    let mut attributes = format!(r#" stroke-width="{}""#, Number(stroke.width));
+    let cap = match stroke.end_cap {
+        Cap::Butt => None,
+        Cap::Square => Some("square"),
+        Cap::Round => Some("round"),
+    };
+    if let Some(cap) = cap {
+        let _ = write!(attributes, r#" stroke-linecap="{cap}""#);
+    }
+    match stroke.join {
+        Join::Bevel => attributes.push_str(r#" stroke-linejoin="bevel""#),
+        Join::Round => attributes.push_str(r#" stroke-linejoin="round""#),
+        Join::Miter => {
+            let _ = write!(
+                attributes,
+                r#" stroke-miterlimit="{}""#,
+                Number(stroke.miter_limit)
+            );
+        }
+    }
+    attributes
+}
+
+fn transform_attribute(transform: Affine) -> String {
+    if transform == Affine::IDENTITY {
+        String::new()
+    } else {
+        format!(r#" transform="{}""#, matrix(transform))
+    }
+}
+
+fn matrix(transform: Affine) -> String {
+    let [a, b, c, d, e, f] = transform.as_coeffs().map(Number);
+    format!("matrix({a} {b} {c} {d} {e} {f})")
+}
+
+fn path_data(path: &BezPath) -> String {
+    let mut data = String::new();
+    for element in path.elements() {
+        let _ = match element {
+            PathEl::MoveTo(p) => write!(data, "M{} {}", Number(p.x), Number(p.y)),
+            PathEl::LineTo(p) => write!(data, "L{} {}", Number(p.x), Number(p.y)),
+            PathEl::QuadTo(p1, p2) => write!(
+                data,
+                "Q{} {} {} {}",
+                Number(p1.x),
+                Number(p1.y),
+                Number(p2.x),
+                Number(p2.y)
+            ),
+            PathEl::CurveTo(p1, p2, p3) => write!(
+                data,
+                "C{} {} {} {} {} {}",
+                Number(p1.x),
+                Number(p1.y),
+                Number(p2.x),
+                Number(p2.y),
+                Number(p3.x),
+                Number(p3.y)
+            ),
+            PathEl::ClosePath => write!(data, "Z"),
+        };
+    }
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vello::kurbo::Shape;
+
+    #[test]
+    fn scene_decodes_into_fills_strokes_and_clips() {
+        let mut scene = Scene::new();
+        let clip = Rect::new(0.0, 0.0, 50.0, 50.0);
+        scene.push_layer(vello::peniko::Mix::Normal, 1.0, Affine::IDENTITY, &clip);
+        scene.fill(
+            Fill::EvenOdd,
+            Affine::translate((5.0, 5.0)),
+            Color::from_rgba8(0x20, 0x40, 0x80, 0xFF),
+            None,
+            &Rect::new(0.0, 0.0, 10.0, 20.0),
+        );
+        scene.pop_layer();
+        scene.stroke(
+            &Stroke::new(3.0).with_join(Join::Round),
+            Affine::IDENTITY,
+            Color::from_rgba8(0xFF, 0x00, 0x00, 0x80),
+            None,
+            &vello::kurbo::Line::new((0.0, 0.0), (40.0, 0.0)),
+        );
+
+        let commands = decode_scene(&scene);
+        assert_eq!(commands.len(), 4);
+        assert!(matches!(commands[0], DrawCommand::PushClip { alpha, .. } if alpha == 1.0));
+        match &commands[1] {
+            DrawCommand::Fill {
+                path,
+                transform,
+                rule,
+                paint,
+            } => {
+                assert_eq!(path.bounding_box(), Rect::new(0.0, 0.0, 10.0, 20.0));
+                assert_eq!(*transform, Affine::translate((5.0, 5.0)));
+                assert_eq!(*rule, Fill::EvenOdd);
+                assert!(matches!(
+                    paint.brush,
+                    Brush::Solid(color) if color.to_rgba8().b == 0x80
+                ));
+            }
+            other => panic!("expected a fill, got {other:?}"),
+        }
+        assert!(matches!(commands[2], DrawCommand::PopClip));
+        match &commands[3] {
+            DrawCommand::Stroke { path, stroke, .. } => {
+                assert_eq!(path.bounding_box(), Rect::new(0.0, 0.0, 40.0, 0.0));
+                assert_eq!(stroke.width, 3.0);
+                assert_eq!(stroke.join, Join::Round);
+            }
+            other => panic!("expected a stroke, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn packed_colors_unpremultiply() {
+        let color = unpack_color(u32::from_ne_bytes([0x40, 0x00, 0x00, 0x80])).to_rgba8();
+        assert_eq!((color.r, color.g, color.b, color.a), (0x80, 0, 0, 0x80));
+        assert_eq!(unpack_color(0), Color::TRANSPARENT);
+
+        let mut pixels = [0x40, 0x20, 0x00, 0x80, 0x10, 0x20, 0x30, 0xFF];
+        unpremultiply(&mut pixels);
+        assert_eq!(pixels, [0x80, 0x40, 0x00, 0x80, 0x10, 0x20, 0x30, 0xFF]);
+    }
+
+    #[test]
+    fn miter_limits_widen_from_half_floats() {
+        assert_eq!(half_to_f64(0x4400), 4.0);
+        assert_eq!(half_to_f64(0x3c00), 1.0);
+        assert_eq!(half_to_f64(0xc000), -2.0);
+        assert_eq!(half_to_f64(0x7c00), f64::INFINITY);
+    }
+
+    #[test]
+    fn svg_numbers_round_and_skip_identity_transforms() {
+        assert_eq!(Number(10.100_000_381).to_string(), "10.1");
+        assert_eq!(Number(-0.000_1).to_string(), "0");
+        assert_eq!(transform_attribute(Affine::IDENTITY), "");
+        assert_eq!(
+            transform_attribute(Affine::translate((2.5, -1.0))),
+            r#" transform="matrix(1 0 0 1 2.5 -1)""#
+        );
+
+        let mut path = BezPath::new();
+        path.move_to((0.0, 0.0));
+        path.line_to((1.0, 2.0));
+        path.close_path();
+        assert_eq!(path_data(&path), "M0 0L1 2Z");
+    }
+
+    #[test]
+    fn oversized_or_unscaled_exports_are_rejected() {
+        let scene = Scene::new();
+        let options = ExportOptions {
+            scale: 0.0,
+            ..ExportOptions::default()
+        };
+        assert!(matches!(
+            export_scene(&scene, 10, 10, ExportFormat::Png, &options),
+            Err(ExportError::InvalidScale)
+        ));
+        assert!(matches!(
+            pixel_size(40_000, 10, 2.0),
+            Err(ExportError::TooLarge { .. })
+        ));
+        assert_eq!(pixel_size(100, 50, 1.5).ok(), Some((150, 75)));
+    }
+}
@@ -0,0 +1,179 @@
+//! Gap detection for connected series.
+//!
+//! A non-finite sample value is an explicit gap. Series may also set a maximum gap
+//! duration; consecutive samples further apart than that are split by inserting a
+//! `NaN` marker before decimation, so every later stage only has to look for
+//! non-finite values.
+
+use std::{borrow::Cow, ops::Range};
+
+use vello::{
+    Scene,
+    kurbo::{Affine, BezPath, Point, Stroke},
+    peniko::Color,
+};
+
+use crate::SeriesPoint;
+
+const GAP_DASHES: [f64; 2] = [4.0, 4.0];
+const GAP_OPACITY: f32 = 0.6;
+
+/// How the space between two runs of a broken series is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum GapStyle {
+    /// Nothing is drawn across the gap.
+    #[default]
+    Break,
+    /// The run ends are joined by a faded dashed segment.
+    Dashed,
+}
+
+impl GapStyle {
+    pub(crate) fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::Break),
+            1 => Some(Self::Dashed),
+            _ => None,
+        }
+    }
+}
+
+/// Whether two consecutive samples are separated by more than `max_gap_seconds`.
+pub(crate) fn exceeds_gap(a: &SeriesPoint, b: &SeriesPoint, max_gap_seconds: Option<f64>) -> bool {
+    max_gap_seconds.is_some_and(|max_gap| b.timestamp_seconds - a.timestamp_seconds > max_gap)
+}
+
+/// Inserts a `NaN` marker between samples further apart than `max_gap_seconds`.
+///
+/// Spans without such gaps are returned borrowed.
+pub(crate) fn mark_gaps(
+    span: &[SeriesPoint],
+    max_gap_seconds: Option<f64>,
+) -> Cow<'_, [SeriesPoint]> {
+    let breaks = span
+        .windows(2)
+        .filter(|pair| exceeds_gap(&pair[0], &pair[1], max_gap_seconds))
+        .count();
+    if breaks == 0 {
+        return Cow::Borrowed(span);
+    }
+
+    let mut marked = Vec::with_capacity(span.len() + breaks);
+    for (index, point) in span.iter().enumerate() {
+        if let Some(previous) = index.checked_sub(1).map(|previous| &span[previous])
+            && exceeds_gap(previous, point, max_gap_seconds)
+        {
+            marked.push(SeriesPoint {
+                timestamp_seconds: (previous.timestamp_seconds + point.timestamp_seconds) * 0.5,
+                value: f64::NAN,
+            });
+        }
+        marked.push(*point);
+    }
+    Cow::Owned(marked)
+}
+
+/// Index ranges of the maximal runs of `len` samples for which `valid` holds.
+pub(crate) fn runs(len: usize, valid: impl Fn(usize) -> bool) -> Vec<Range<usize>> {
+    let mut runs = Vec::new();
+    let mut start = None;
+    for index in 0..len {
+        match (valid(index), start) {
+            (true, None) => start = Some(index),
+            (false, Some(first)) => {
+                runs.push(first..index);
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(first) = start {
+        runs.push(first..len);
+    }
+    runs
+}
+
+/// Runs of finite samples in `span`.
+pub(crate) fn finite_runs(span: &[SeriesPoint]) -> Vec<Range<usize>> {
+    runs(span.len(), |index| span[index].value.is_finite())
+}
+
+/// Joins the end of each run to the start of the next with a dashed segment.
+///
+/// `points` are the projected samples the runs index into.
+pub(crate) fn draw_gap_bridges(
+    scene: &mut Scene,
+    points: &[Point],
+    runs: &[Range<usize>],
+    color: Color,
+    stroke_width: f64,
+) -> u32 {
+    if runs.len() < 2 {
+        return 0;
+    }
+
+    let mut path = BezPath::new();
+    for pair in runs.windows(2) {
+        path.move_to(points[pair[0].end - 1]);
+        path.line_to(points[pair[1].start]);
+    }
+    scene.stroke(
+        &Stroke::new(stroke_width).with_dashes(0.0, GAP_DASHES),
+        Affine::IDENTITY,
+        color.multiply_alpha(GAP_OPACITY),
+        None,
+        &path,
+    );
+    1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::points;
+
+    #[test]
+    fn non_finite_values_split_runs() {
+        let span = points(&[
+            (0.0, 1.0),
+            (1.0, f64::NAN),
+            (2.0, 2.0),
+            (3.0, 3.0),
+            (4.0, f64::INFINITY),
+        ]);
+        assert_eq!(finite_runs(&span), vec![0..1, 2..4]);
+        assert!(finite_runs(&points(&[(0.0, f64::NAN)])).is_empty());
+    }
+
+    #[test]
+    fn long_gaps_are_marked_between_samples() {
+        let span = points(&[(0.0, 1.0), (1.0, 2.0), (5.0, 3.0)]);
+        assert!(matches!(mark_gaps(&span, None), Cow::Borrowed(_)));
+        assert!(matches!(mark_gaps(&span, Some(4.0)), Cow::Borrowed(_)));
+
+        let marked = mark_gaps(&span, Some(2.0));
+        assert_eq!(marked.len(), 4);
+        assert_eq!(marked[2].timestamp_seconds, 3.0);
+        assert!(marked[2].value.is_nan());
+        assert_eq!(finite_runs(&marked), vec![0..2, 3..4]);
+    }
+
+    #[test]
+    fn bridges_join_consecutive_runs() {
+        let projected = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(2.0, 0.0),
+        ];
+        let mut scene = Scene::new();
+        let unbroken = runs(projected.len(), |_| true);
+        assert_eq!(
+            draw_gap_bridges(&mut scene, &projected, &unbroken, Color::WHITE, 1.0),
+            0
+        );
+        assert_eq!(
+            draw_gap_bridges(&mut scene, &projected, &[0..1, 2..3], Color::WHITE, 1.0),
+            1
+        );
+    }
+}
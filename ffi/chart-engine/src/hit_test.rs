@@ -0,0 +1,737 @@
+//! Maps screen positions back onto the samples drawn in the last frame.
+//!
+//! Rendering records a [`HitPane`] per pane with the projection it used and the
+//! geometry parameters of every series it drew, so queries reproduce exactly what
+//! ended up on screen. The cursor overlay snaps to the same records.
+
+use std::ops::Range;
+
+use vello::{
+    kurbo::{self, BezPath, PathEl, Point, Rect},
+    peniko::Color,
+};
+use vello_composition::PlotArea;
+
+use crate::gaps::exceeds_gap;
+use crate::interpolation::Interpolation;
+use crate::stacking::StackedSpan;
+use crate::{SeriesKind, SeriesPoint, ValueAxis, project_point, project_time, project_value};
+
+/// Geometry of one drawn series, captured while the frame is built.
+#[derive(Debug, Clone)]
+pub(crate) struct HitSeries {
+    pub(crate) series_id: u32,
+    pub(crate) kind: SeriesKind,
+    pub(crate) color: Color,
+    pub(crate) band_lower_id: Option<u32>,
+    pub(crate) stroke_width: f64,
+    pub(crate) marker_size: f64,
+    pub(crate) bar_width_seconds: f64,
+    pub(crate) baseline_value: f64,
+    pub(crate) filled: bool,
+    pub(crate) heatmap_bucket_index: u32,
+    pub(crate) heatmap_bucket_count: u32,
+    /// Axis the series was projected with; the right axis when it is bound to it.
+    pub(crate) value_axis: ValueAxis,
+    /// Stacked geometry when the series belongs to a stack group.
+    pub(crate) stack: Option<StackedSpan>,
+    pub(crate) max_gap_seconds: Option<f64>,
+    /// Shape drawn between samples of line, area and band series.
+    pub(crate) interpolation: Interpolation,
+}
+
+/// Samples traced on either side of the queried pairs, so monotone tangents come out
+/// as they were drawn from the whole run.
+const TRACE_CONTEXT: usize = 3;
+
+/// Flattening tolerance for traced pieces, in pixels.
+const FLATTEN_TOLERANCE: f64 = 0.1;
+
+impl HitSeries {
+    /// Whether the segment between two samples was left out of the drawn path.
+    fn breaks_between(&self, a: &SeriesPoint, b: &SeriesPoint) -> bool {
+        !a.value.is_finite() || !b.value.is_finite() || exceeds_gap(a, b, self.max_gap_seconds)
+    }
+
+    /// Drawn shape between samples `index` and `index + 1`, flattened, for every
+    /// `index` in `pairs` that `breaks_before(index + 1)` does not split. Runs are
+    /// traced with the series interpolation exactly as rendering traces them.
+    fn traced_pairs(
+        &self,
+        pane: &HitPane,
+        span: &[SeriesPoint],
+        pairs: Range<usize>,
+        breaks_before: impl Fn(usize) -> bool,
+    ) -> Vec<(usize, Vec<Point>)> {
+        let start = pairs.start.saturating_sub(TRACE_CONTEXT);
+        let end = (pairs.end + 1 + TRACE_CONTEXT).min(span.len());
+        let mut traced = Vec::new();
+        let mut run_start = start;
+        for index in start + 1..=end {
+            if index < end && !breaks_before(index) {
+                continue;
+            }
+            let run = run_start..index;
+            run_start = index;
+            if run.len() < 2 {
+                continue;
+            }
+            let points: Vec<Point> = span[run.clone()]
+                .iter()
+                .map(|sample| pane.project(self, sample))
+                .collect();
+            for (offset, piece) in self.interpolation.pieces(&points).iter().enumerate() {
+                let pair = run.start + offset;
+                if pairs.contains(&pair) {
+                    traced.push((pair, flatten(piece)));
+                }
+            }
+        }
+        traced
+    }
+
+    /// Samples as drawn: the stacked totals for stack members, the raw data otherwise.
+    pub(crate) fn drawn_span<'s, 'a: 's>(
+        &'s self,
+        lookup: impl Fn(u32) -> Option<&'a [SeriesPoint]>,
+    ) -> Option<&'s [SeriesPoint]> {
+        match &self.stack {
+            Some(stack) => Some(&stack.upper),
+            None => lookup(self.series_id),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct HitPane {
+    pub(crate) pane_index: usize,
+    pub(crate) plot_area: PlotArea,
+    pub(crate) range_start: f64,
+    pub(crate) window: f64,
+    pub(crate) series: Vec<HitSeries>,
+}
+
+/// Sample found under a screen position by [`crate::ChartEngine::hit_test`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HitTestResult {
+    pub series_id: u32,
+    pub pane_index: usize,
+    pub timestamp_seconds: f64,
+    pub value: f64,
+    /// Screen position of the sample (cell centre for heatmaps).
+    pub x: f64,
+    pub y: f64,
+    /// Distance from the query point to the drawn geometry; zero when inside it.
+    pub distance: f64,
+    pub(crate) kind: SeriesKind,
+}
+
+/// Returns the closest drawn sample within `radius` pixels of `position`.
+///
+/// Later series win ties, matching the order they are painted in.
+pub(crate) fn hit_test<'a>(
+    panes: &[HitPane],
+    lookup: impl Fn(u32) -> Option<&'a [SeriesPoint]>,
+    position: Point,
+    radius: f64,
+) -> Option<HitTestResult> {
+    let mut best: Option<HitTestResult> = None;
+
+    for pane in panes {
+        let area = &pane.plot_area;
+        if position.x < area.left - radius
+            || position.x > area.right() + radius
+            || position.y < area.top - radius
+            || position.y > area.bottom() + radius
+        {
+            continue;
+        }
+
+        for series in &pane.series {
+            let Some(span) = series.drawn_span(&lookup) else {
+                continue;
+            };
+
+            let candidate = match series.kind {
+                _ if series.stack.is_some() => hit_stack_member(pane, series, position, radius),
+                SeriesKind::Line | SeriesKind::Area => {
+                    hit_polyline(pane, series, span, position, radius)
+                }
+                SeriesKind::Scatter => hit_markers(pane, series, span, position, radius),
+                SeriesKind::Bar => hit_bars(pane, series, span, position, radius),
+                SeriesKind::Band => {
+                    let lower = series.band_lower_id.and_then(&lookup);
+                    lower.and_then(|lower| hit_band(pane, series, span, lower, position, radius))
+                }
+                SeriesKind::Heatmap => hit_heatmap(pane, series, span, position, radius),
+                SeriesKind::Candlestick | SeriesKind::Ohlc => {
+                    hit_columns(pane, series, span, position, radius)
+                }
+            };
+
+            if let Some(candidate) = candidate
+                && candidate.distance <= radius
+                && best.is_none_or(|best| candidate.distance <= best.distance)
+            {
+                best = Some(candidate);
+            }
+        }
+    }
+
+    best
+}
+
+impl HitPane {
+    pub(crate) fn project(&self, series: &HitSeries, sample: &SeriesPoint) -> Point {
+        project_point(
+            sample,
+            self.range_start,
+            self.window,
+            &self.plot_area,
+            &series.value_axis,
+        )
+    }
+
+    pub(crate) fn time_at(&self, x: f64) -> f64 {
+        let width = self.plot_area.width.max(f64::EPSILON);
+        self.range_start + (x - self.plot_area.left) / width * self.window
+    }
+
+    /// Indices of visible samples whose x lies within `reach` pixels of `x`.
+    fn candidates(&self, span: &[SeriesPoint], x: f64, reach: f64) -> Range<usize> {
+        let range_end = self.range_start + self.window;
+        let start = self.time_at(x - reach).max(self.range_start);
+        let end = self.time_at(x + reach).min(range_end);
+        let lo = span.partition_point(|point| point.timestamp_seconds < start);
+        let hi = span.partition_point(|point| point.timestamp_seconds <= end);
+        lo..hi.max(lo)
+    }
+
+    fn result(
+        &self,
+        series: &HitSeries,
+        sample: &SeriesPoint,
+        at: Point,
+        distance: f64,
+    ) -> HitTestResult {
+        HitTestResult {
+            series_id: series.series_id,
+            pane_index: self.pane_index,
+            timestamp_seconds: sample.timestamp_seconds,
+            value: sample.value,
+            x: at.x,
+            y: at.y,
+            distance,
+            kind: series.kind,
+        }
+    }
+}
+
+fn hit_polyline(
+    pane: &HitPane,
+    series: &HitSeries,
+    span: &[SeriesPoint],
+    position: Point,
+    radius: f64,
+) -> Option<HitTestResult> {
+    let half_stroke = series.stroke_width * 0.5;
+    let range = pane.candidates(span, position.x, radius + half_stroke);
+    // Segments crossing the query column start or end outside the candidate range.
+    let lo = range.start.saturating_sub(1);
+    let hi = (range.end + 1).min(span.len());
+    if lo >= hi {
+        return None;
+    }
+
+    if span.len() == 1 {
+        if !span[0].value.is_finite() {
+            return None;
+        }
+        let point = pane.project(series, &span[0]);
+        let distance = (point.distance(position) - half_stroke).max(0.0);
+        return Some(pane.result(series, &span[0], point, distance));
+    }
+
+    let baseline_y = project_value(series.baseline_value, &series.value_axis, &pane.plot_area);
+    let traced = series.traced_pairs(pane, span, lo..hi - 1, |index| {
+        series.breaks_between(&span[index - 1], &span[index])
+    });
+    let mut best: Option<HitTestResult> = None;
+    for (index, line) in traced {
+        let mut distance = (polyline_distance(position, &line) - half_stroke).max(0.0);
+        if series.filled
+            && let Some(line_y) = polyline_y(&line, position.x)
+            && is_between(position.y, line_y, baseline_y)
+        {
+            distance = 0.0;
+        }
+
+        let (a, b) = (line[0], line[line.len() - 1]);
+        let (sample, point) = if (position.x - a.x).abs() <= (position.x - b.x).abs() {
+            (&span[index], a)
+        } else {
+            (&span[index + 1], b)
+        };
+        if best.is_none_or(|best| distance < best.distance) {
+            best = Some(pane.result(series, sample, point, distance));
+        }
+    }
+
+    best
+}
+
+fn hit_markers(
+    pane: &HitPane,
+    series: &HitSeries,
+    span: &[SeriesPoint],
+    position: Point,
+    radius: f64,
+) -> Option<HitTestResult> {
+    let marker_radius = (series.marker_size / 2.0).max(0.5);
+    let mut best: Option<HitTestResult> = None;
+    for sample in &span[pane.candidates(span, position.x, radius + marker_radius)] {
+        if !sample.value.is_finite() {
+            continue;
+        }
+        let point = pane.project(series, sample);
+        let distance = (point.distance(position) - marker_radius).max(0.0);
+        // Markers are painted in order, so the last overlapping one is on top.
+        if best.is_none_or(|best| distance <= best.distance) {
+            best = Some(pane.result(series, sample, point, distance));
+        }
+    }
+    best
+}
+
+fn hit_bars(
+    pane: &HitPane,
+    series: &HitSeries,
+    span: &[SeriesPoint],
+    position: Point,
+    radius: f64,
+) -> Option<HitTestResult> {
+    let area = &pane.plot_area;
+    let baseline_y = project_value(series.baseline_value, &series.value_axis, area);
+    let normalized_width = (series.bar_width_seconds / pane.window).clamp(0.0, 1.0);
+    let half_width = (normalized_width * area.width).max(1.0) * 0.5;
+
+    let mut best: Option<HitTestResult> = None;
+    for sample in &span[pane.candidates(span, position.x, radius + half_width)] {
+        if !sample.value.is_finite() {
+            continue;
+        }
+        let point = pane.project(series, sample);
+        let x0 = (point.x - half_width).max(area.left);
+        let x1 = (point.x + half_width).min(area.right());
+        let rect = Rect::new(x0, point.y.min(baseline_y), x1, point.y.max(baseline_y));
+        let distance = rect_distance(&rect, position);
+        if best.is_none_or(|best| distance <= best.distance) {
+            best = Some(pane.result(series, sample, point, distance));
+        }
+    }
+    best
+}
+
+/// Stack members hit the geometry they were drawn with: bars and filled series their
+/// slice of the stack, plain lines the stacked total.
+fn hit_stack_member(
+    pane: &HitPane,
+    series: &HitSeries,
+    position: Point,
+    radius: f64,
+) -> Option<HitTestResult> {
+    let stack = series.stack.as_ref()?;
+    match series.kind {
+        SeriesKind::Bar => hit_stacked_bars(pane, series, stack, position, radius),
+        _ if series.filled => hit_band(pane, series, &stack.upper, &stack.lower, position, radius),
+        _ => hit_polyline(pane, series, &stack.upper, position, radius),
+    }
+}
+
+fn hit_stacked_bars(
+    pane: &HitPane,
+    series: &HitSeries,
+    stack: &StackedSpan,
+    position: Point,
+    radius: f64,
+) -> Option<HitTestResult> {
+    let area = &pane.plot_area;
+    let normalized_width = (series.bar_width_seconds / pane.window).clamp(0.0, 1.0);
+    let half_width = (normalized_width * area.width).max(1.0) * 0.5;
+
+    let mut best: Option<HitTestResult> = None;
+    for index in pane.candidates(&stack.upper, position.x, radius + half_width) {
+        let sample = &stack.upper[index];
+        if !sample.value.is_finite() {
+            continue;
+        }
+        let point = pane.project(series, sample);
+        let base_y = project_value(stack.lower[index].value, &series.value_axis, area);
+        let x0 = (point.x - half_width).max(area.left);
+        let x1 = (point.x + half_width).min(area.right());
+        let rect = Rect::new(x0, point.y.min(base_y), x1, point.y.max(base_y));
+        let distance = rect_distance(&rect, position);
+        if best.is_none_or(|best| distance <= best.distance) {
+            best = Some(pane.result(series, sample, point, distance));
+        }
+    }
+    best
+}
+
+fn hit_band(
+    pane: &HitPane,
+    series: &HitSeries,
+    upper: &[SeriesPoint],
+    lower: &[SeriesPoint],
+    position: Point,
+    radius: f64,
+) -> Option<HitTestResult> {
+    // Bands pair upper and lower samples by index, exactly as they are drawn.
+    let count = upper.len().min(lower.len());
+    let upper = &upper[..count];
+    let half_stroke = series.stroke_width * 0.5;
+    let range = pane.candidates(upper, position.x, radius + half_stroke);
+    let lo = range.start.saturating_sub(1);
+    let hi = (range.end + 1).min(count);
+    if hi < lo + 2 {
+        return None;
+    }
+
+    // A gap on either edge breaks the band.
+    let breaks_before = |index: usize| {
+        series.breaks_between(&upper[index - 1], &upper[index])
+            || series.breaks_between(&lower[index - 1], &lower[index])
+    };
+    let upper_lines = series.traced_pairs(pane, upper, lo..hi - 1, breaks_before);
+    let lower_lines = series.traced_pairs(pane, &lower[..count], lo..hi - 1, breaks_before);
+
+    let mut best: Option<HitTestResult> = None;
+    for ((index, upper_line), (_, lower_line)) in upper_lines.into_iter().zip(lower_lines) {
+        let mut distance = (polyline_distance(position, &upper_line)
+            .min(polyline_distance(position, &lower_line))
+            - half_stroke)
+            .max(0.0);
+        if let (Some(upper_y), Some(lower_y)) = (
+            polyline_y(&upper_line, position.x),
+            polyline_y(&lower_line, position.x),
+        ) && is_between(position.y, upper_y, lower_y)
+        {
+            distance = 0.0;
+        }
+
+        let (a, b) = (upper_line[0], upper_line[upper_line.len() - 1]);
+        let (sample, point) = if (position.x - a.x).abs() <= (position.x - b.x).abs() {
+            (&upper[index], a)
+        } else {
+            (&upper[index + 1], b)
+        };
+        if best.is_none_or(|best| distance < best.distance) {
+            best = Some(pane.result(series, sample, point, distance));
+        }
+    }
+    best
+}
+
+/// Candle bars resolve by bucket column: anywhere over a bar's width counts as a hit.
+fn hit_columns(
+    pane: &HitPane,
+    series: &HitSeries,
+    span: &[SeriesPoint],
+    position: Point,
+    radius: f64,
+) -> Option<HitTestResult> {
+    let area = &pane.plot_area;
+    let normalized_width = (series.bar_width_seconds / pane.window).clamp(0.0, 1.0);
+    let half_width = (normalized_width * area.width).max(1.0) * 0.5;
+
+    let mut best: Option<HitTestResult> = None;
+    for sample in &span[pane.candidates(span, position.x, radius + half_width)] {
+        if !sample.value.is_finite() {
+            continue;
+        }
+        let point = pane.project(series, sample);
+        let distance = ((point.x - position.x).abs() - half_width).max(0.0);
+        if best.is_none_or(|best| distance < best.distance) {
+            best = Some(pane.result(series, sample, point, distance));
+        }
+    }
+    best
+}
+
+fn hit_heatmap(
+    pane: &HitPane,
+    series: &HitSeries,
+    span: &[SeriesPoint],
+    position: Point,
+    radius: f64,
+) -> Option<HitTestResult> {
+    if span.is_empty() {
+        return None;
+    }
+
+    let area = &pane.plot_area;
+    let bucket_count = series.heatmap_bucket_count.max(1);
+    let bucket_index = series.heatmap_bucket_index.min(bucket_count - 1);
+    let bucket_height = (area.height / bucket_count as f64).max(1.0);
+    let bucket_top = (area.bottom() - (bucket_index as f64 + 1.0) * bucket_height).max(area.top);
+    let bucket_bottom = (bucket_top + bucket_height).min(area.bottom());
+    if bucket_bottom <= bucket_top {
+        return None;
+    }
+
+    let window = pane.window;
+    let default_delta = (window / span.len() as f64).max(window * 0.01);
+    let cell = |index: usize| {
+        let sample = &span[index];
+        let prev_time = if index > 0 {
+            span[index - 1].timestamp_seconds
+        } else {
+            sample.timestamp_seconds - default_delta
+        };
+        let next_time = span
+            .get(index + 1)
+            .map_or(sample.timestamp_seconds + default_delta, |next| {
+                next.timestamp_seconds
+            });
+        let left = sample.timestamp_seconds
+            - (sample.timestamp_seconds - prev_time).max(default_delta) * 0.5;
+        let right = sample.timestamp_seconds
+            + (next_time - sample.timestamp_seconds).max(default_delta) * 0.5;
+        Rect::new(
+            project_time(left, pane.range_start, window, area),
+            bucket_top,
+            project_time(right, pane.range_start, window, area),
+            bucket_bottom,
+        )
+    };
+
+    // Cells are centred on their samples, so only the neighbours of the query time can contain it.
+    let pivot = span.partition_point(|point| point.timestamp_seconds < pane.time_at(position.x));
+    let first = pivot.saturating_sub(1);
+    let mut best: Option<HitTestResult> = None;
+    for (offset, sample) in span[first..(pivot + 1).min(span.len())].iter().enumerate() {
+        let rect = cell(first + offset);
+        if rect.x1 <= rect.x0 {
+            continue;
+        }
+        let distance = rect_distance(&rect, position);
+        if best.is_none_or(|best| distance <= best.distance) {
+            best = Some(pane.result(series, sample, rect.center(), distance));
+        }
+    }
+    best.filter(|hit| hit.distance <= radius)
+}
+
+fn segment_distance(point: Point, a: Point, b: Point) -> f64 {
+    let ab = b - a;
+    let length_sq = ab.hypot2();
+    if length_sq <= f64::EPSILON {
+        return point.distance(a);
+    }
+    let t = ((point - a).dot(ab) / length_sq).clamp(0.0, 1.0);
+    point.distance(a + ab * t)
+}
+
+/// Flattens a traced piece into the polyline through its vertices.
+fn flatten(piece: &BezPath) -> Vec<Point> {
+    let mut line = Vec::new();
+    kurbo::flatten(piece, FLATTEN_TOLERANCE, |element| match element {
+        PathEl::MoveTo(point) | PathEl::LineTo(point) => line.push(point),
+        _ => {}
+    });
+    line
+}
+
+fn polyline_distance(point: Point, line: &[Point]) -> f64 {
+    line.windows(2)
+        .map(|segment| segment_distance(point, segment[0], segment[1]))
+        .fold(f64::INFINITY, f64::min)
+}
+
+/// Height of a polyline at `x`, if it spans that column.
+fn polyline_y(line: &[Point], x: f64) -> Option<f64> {
+    line.windows(2)
+        .find_map(|segment| interpolate_y(segment[0], segment[1], x))
+}
+
+fn interpolate_y(a: Point, b: Point, x: f64) -> Option<f64> {
+    let (left, right) = if a.x <= b.x { (a, b) } else { (b, a) };
+    if x < left.x || x > right.x {
+        return None;
+    }
+    let span = right.x - left.x;
+    if span <= f64::EPSILON {
+        return Some(left.y.min(right.y));
+    }
+    Some(left.y + (right.y - left.y) * (x - left.x) / span)
+}
+
+fn is_between(value: f64, a: f64, b: f64) -> bool {
+    value >= a.min(b) && value <= a.max(b)
+}
+
+fn rect_distance(rect: &Rect, point: Point) -> f64 {
+    let dx = (rect.x0 - point.x).max(point.x - rect.x1).max(0.0);
+    let dy = (rect.y0 - point.y).max(point.y - rect.y1).max(0.0);
+    dx.hypot(dy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::points;
+    use vello_composition::ValueScale;
+
+    fn pane(series: Vec<HitSeries>) -> HitPane {
+        HitPane {
+            pane_index: 0,
+            plot_area: PlotArea {
+                left: 0.0,
+                top: 0.0,
+                width: 100.0,
+                height: 100.0,
+            },
+            range_start: 0.0,
+            window: 10.0,
+            series,
+        }
+    }
+
+    fn series(series_id: u32, kind: SeriesKind) -> HitSeries {
+        HitSeries {
+            series_id,
+            kind,
+            color: Color::WHITE,
+            band_lower_id: None,
+            stroke_width: 2.0,
+            marker_size: 4.0,
+            bar_width_seconds: 1.0,
+            baseline_value: 0.0,
+            filled: kind == SeriesKind::Area,
+            heatmap_bucket_index: 0,
+            heatmap_bucket_count: 1,
+            value_axis: ValueAxis::new(ValueScale::Linear, 0.0, 10.0),
+            stack: None,
+            max_gap_seconds: None,
+            interpolation: Interpolation::Linear,
+        }
+    }
+
+    #[test]
+    fn line_hits_segment_and_reports_nearest_sample() {
+        let data = points(&[(0.0, 0.0), (5.0, 5.0), (10.0, 5.0)]);
+        let panes = [pane(vec![series(7, SeriesKind::Line)])];
+        // Halfway along the flat segment at value 5 (y = 50), slightly above it.
+        let hit = hit_test(&panes, |_| Some(&data[..]), Point::new(70.0, 47.0), 4.0).unwrap();
+        assert_eq!(hit.series_id, 7);
+        assert_eq!(hit.timestamp_seconds, 5.0);
+        assert_eq!((hit.x, hit.y), (50.0, 50.0));
+        assert!((hit.distance - 2.0).abs() < 1e-9);
+
+        assert!(hit_test(&panes, |_| Some(&data[..]), Point::new(70.0, 20.0), 4.0).is_none());
+    }
+
+    #[test]
+    fn bars_and_areas_hit_their_filled_regions() {
+        let data = points(&[(2.0, 8.0), (6.0, 4.0)]);
+        let bars = [pane(vec![series(1, SeriesKind::Bar)])];
+        let hit = hit_test(&bars, |_| Some(&data[..]), Point::new(21.0, 90.0), 0.0).unwrap();
+        assert_eq!(hit.timestamp_seconds, 2.0);
+        assert_eq!(hit.distance, 0.0);
+        assert!(hit_test(&bars, |_| Some(&data[..]), Point::new(40.0, 90.0), 0.0).is_none());
+
+        let area = [pane(vec![series(2, SeriesKind::Area)])];
+        let hit = hit_test(&area, |_| Some(&data[..]), Point::new(50.0, 90.0), 0.0).unwrap();
+        assert_eq!(hit.series_id, 2);
+        assert_eq!(hit.timestamp_seconds, 6.0);
+    }
+
+    #[test]
+    fn heatmap_cells_and_markers_resolve_to_their_sample() {
+        let data = points(&[(1.0, 3.0), (5.0, 6.0), (9.0, 9.0)]);
+        let heatmap = [pane(vec![series(3, SeriesKind::Heatmap)])];
+        let hit = hit_test(&heatmap, |_| Some(&data[..]), Point::new(38.0, 10.0), 0.0).unwrap();
+        assert_eq!(hit.timestamp_seconds, 5.0);
+        assert_eq!(hit.x, 50.0);
+
+        let scatter = [pane(vec![series(4, SeriesKind::Scatter)])];
+        let hit = hit_test(&scatter, |_| Some(&data[..]), Point::new(53.0, 40.0), 2.0).unwrap();
+        assert_eq!(hit.timestamp_seconds, 5.0);
+        assert!((hit.distance - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn series_project_through_their_own_value_axis() {
+        let data = points(&[(5.0, 50.0)]);
+        let mut right = series(2, SeriesKind::Scatter);
+        right.value_axis = ValueAxis::new(ValueScale::Linear, 0.0, 100.0);
+        let panes = [pane(vec![series(1, SeriesKind::Scatter), right])];
+
+        let hit = hit_test(&panes, |_| Some(&data[..]), Point::new(50.0, 50.0), 2.0).unwrap();
+        assert_eq!(hit.series_id, 2);
+        assert_eq!((hit.x, hit.y), (50.0, 50.0));
+    }
+
+    #[test]
+    fn stacked_members_hit_their_slice_of_the_stack() {
+        let data = points(&[(5.0, 2.0)]);
+        let mut top = series(2, SeriesKind::Bar);
+        top.stack = Some(StackedSpan {
+            upper: points(&[(5.0, 6.0)]),
+            lower: points(&[(5.0, 4.0)]),
+        });
+        let panes = [pane(vec![top])];
+
+        // The raw value would put the bar between y = 80 and 100; stacked it spans 40..60.
+        let hit = hit_test(&panes, |_| Some(&data[..]), Point::new(50.0, 50.0), 0.0).unwrap();
+        assert_eq!((hit.value, hit.y), (6.0, 40.0));
+        assert!(hit_test(&panes, |_| Some(&data[..]), Point::new(50.0, 90.0), 0.0).is_none());
+    }
+
+    #[test]
+    fn lines_hit_their_interpolated_shape() {
+        let data = points(&[(0.0, 0.0), (10.0, 10.0)]);
+        let mut step = series(1, SeriesKind::Line);
+        step.interpolation = Interpolation::StepAfter;
+        // Along the step's flat run at value 0, far from the straight diagonal.
+        let position = Point::new(50.0, 99.0);
+
+        let panes = [pane(vec![step])];
+        let hit = hit_test(&panes, |_| Some(&data[..]), position, 2.0).unwrap();
+        assert_eq!(hit.distance, 0.0);
+        assert_eq!(hit.timestamp_seconds, 0.0);
+
+        let panes = [pane(vec![series(1, SeriesKind::Line)])];
+        assert!(hit_test(&panes, |_| Some(&data[..]), position, 2.0).is_none());
+    }
+
+    #[test]
+    fn non_finite_samples_are_not_hittable() {
+        for value in [f64::NAN, f64::INFINITY] {
+            let data = points(&[(5.0, value), (9.0, 5.0)]);
+            for kind in [
+                SeriesKind::Scatter,
+                SeriesKind::Bar,
+                SeriesKind::Candlestick,
+            ] {
+                let panes = [pane(vec![series(1, kind)])];
+                let hit = hit_test(&panes, |_| Some(&data[..]), Point::new(50.0, 50.0), 2.0);
+                assert!(hit.is_none(), "{kind:?} hit a {value} sample: {hit:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn gaps_are_not_hittable() {
+        let data = points(&[(0.0, 5.0), (2.0, f64::NAN), (4.0, 5.0), (10.0, 5.0)]);
+        let mut line = series(1, SeriesKind::Line);
+        let panes = [pane(vec![line.clone()])];
+        // Across the NaN gap there is no segment to hit; after it the line is continuous.
+        assert!(hit_test(&panes, |_| Some(&data[..]), Point::new(20.0, 50.0), 1.0).is_none());
+        assert!(hit_test(&panes, |_| Some(&data[..]), Point::new(70.0, 50.0), 1.0).is_some());
+
+        line.max_gap_seconds = Some(5.0);
+        let panes = [pane(vec![line])];
+        assert!(hit_test(&panes, |_| Some(&data[..]), Point::new(70.0, 50.0), 1.0).is_none());
+    }
+}
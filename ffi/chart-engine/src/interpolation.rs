@@ -0,0 +1,255 @@
+//! Path shapes between consecutive samples of line, area and band series.
+//!
+//! Interpolation runs on projected points, after gaps have split the series into runs.
+//! Step corners lie on sample coordinates and monotone splines never leave the value
+//! range of the two samples they join, so neither mode draws outside the time/value
+//! box of its samples and `DirtyBounds` built from raw samples stay exact.
+
+use vello::kurbo::{BezPath, PathEl, Point};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum Interpolation {
+    /// Straight segments between samples.
+    #[default]
+    Linear,
+    /// The value changes at the previous sample and holds until the next one.
+    StepBefore,
+    /// The value holds from a sample until the next one, then changes.
+    StepAfter,
+    /// The value changes halfway between samples.
+    StepMiddle,
+    /// Monotone cubic spline (Fritsch–Carlson) that does not overshoot the samples.
+    Monotone,
+}
+
+impl Interpolation {
+    pub(crate) fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::Linear),
+            1 => Some(Self::StepBefore),
+            2 => Some(Self::StepAfter),
+            3 => Some(Self::StepMiddle),
+            4 => Some(Self::Monotone),
+            _ => None,
+        }
+    }
+
+    /// Traces `points` into `path`. With `connect` the run continues the current
+    /// subpath with a straight line to its first point; otherwise it starts a new one.
+    pub(crate) fn trace(self, path: &mut BezPath, points: &[Point], connect: bool) {
+        let Some(&first) = points.first() else {
+            return;
+        };
+        if connect {
+            path.line_to(first);
+        } else {
+            path.move_to(first);
+        }
+
+        match self {
+            Self::Linear => {
+                for point in &points[1..] {
+                    path.line_to(*point);
+                }
+            }
+            Self::StepBefore | Self::StepAfter | Self::StepMiddle => {
+                for pair in points.windows(2) {
+                    let (a, b) = (pair[0], pair[1]);
+                    match self {
+                        Self::StepBefore => path.line_to((a.x, b.y)),
+                        Self::StepAfter => path.line_to((b.x, a.y)),
+                        _ => {
+                            let middle = (a.x + b.x) * 0.5;
+                            path.line_to((middle, a.y));
+                            path.line_to((middle, b.y));
+                        }
+                    }
+                    path.line_to(b);
+                }
+            }
+            Self::Monotone => {
+                let tangents = monotone_tangents(points);
+                for (index, pair) in points.windows(2).enumerate() {
+                    let (a, b) = (pair[0], pair[1]);
+                    let third = (b.x - a.x) / 3.0;
+                    path.curve_to(
+                        Point::new(a.x + third, a.y + tangents[index] * third),
+                        Point::new(b.x - third, b.y - tangents[index + 1] * third),
+                        b,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Traces the same shape as [`Self::trace`] from the last point back to the first,
+    /// always continuing the current subpath.
+    pub(crate) fn trace_reversed(self, path: &mut BezPath, points: &[Point]) {
+        let mut forward = BezPath::new();
+        self.trace(&mut forward, points, false);
+        for element in forward.reverse_subpaths().elements() {
+            match *element {
+                PathEl::MoveTo(point) => path.line_to(point),
+                element => path.push(element),
+            }
+        }
+    }
+
+    /// The shape [`Self::trace`] draws between each consecutive pair of `points`, one
+    /// path per pair starting at its first point.
+    pub(crate) fn pieces(self, points: &[Point]) -> Vec<BezPath> {
+        let mut path = BezPath::new();
+        self.trace(&mut path, points, false);
+        let per_pair = match self {
+            Self::StepBefore | Self::StepAfter => 2,
+            Self::StepMiddle => 3,
+            Self::Linear | Self::Monotone => 1,
+        };
+        path.elements()
+            .get(1..)
+            .unwrap_or_default()
+            .chunks(per_pair)
+            .zip(points)
+            .map(|(elements, &start)| {
+                let mut piece = BezPath::new();
+                piece.move_to(start);
+                for element in elements {
+                    piece.push(*element);
+                }
+                piece
+            })
+            .collect()
+    }
+}
+
+/// Fritsch–Carlson tangents: zero at local extrema and limited so each cubic stays
+/// within the values of its two end points.
+fn monotone_tangents(points: &[Point]) -> Vec<f64> {
+    let slopes: Vec<f64> = points
+        .windows(2)
+        .map(|pair| {
+            let dx = pair[1].x - pair[0].x;
+            if dx.abs() > f64::EPSILON {
+                (pair[1].y - pair[0].y) / dx
+            } else {
+                0.0
+            }
+        })
+        .collect();
+
+    let mut tangents = vec![0.0; points.len()];
+    if let (Some(first), Some(last)) = (slopes.first(), slopes.last()) {
+        tangents[0] = *first;
+        tangents[points.len() - 1] = *last;
+    }
+    for index in 1..slopes.len() {
+        let (before, after) = (slopes[index - 1], slopes[index]);
+        tangents[index] = if before * after <= 0.0 {
+            0.0
+        } else {
+            (before + after) * 0.5
+        };
+    }
+
+    for (index, slope) in slopes.iter().enumerate() {
+        if slope.abs() <= f64::EPSILON {
+            tangents[index] = 0.0;
+            tangents[index + 1] = 0.0;
+            continue;
+        }
+        let alpha = tangents[index] / slope;
+        let beta = tangents[index + 1] / slope;
+        let magnitude = alpha.hypot(beta);
+        if magnitude > 3.0 {
+            let scale = 3.0 / magnitude;
+            tangents[index] = scale * alpha * slope;
+            tangents[index + 1] = scale * beta * slope;
+        }
+    }
+    tangents
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vello::kurbo::Shape;
+
+    fn points(values: &[(f64, f64)]) -> Vec<Point> {
+        values.iter().map(|&(x, y)| Point::new(x, y)).collect()
+    }
+
+    fn vertices(path: &BezPath) -> Vec<Point> {
+        path.elements()
+            .iter()
+            .filter_map(|element| match element {
+                PathEl::MoveTo(point) | PathEl::LineTo(point) => Some(*point),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn steps_place_corners_by_mode() {
+        let run = points(&[(0.0, 0.0), (10.0, 5.0)]);
+        let corner = |mode: Interpolation| {
+            let mut path = BezPath::new();
+            mode.trace(&mut path, &run, false);
+            vertices(&path)
+        };
+        assert_eq!(
+            corner(Interpolation::StepBefore),
+            points(&[(0.0, 0.0), (0.0, 5.0), (10.0, 5.0)])
+        );
+        assert_eq!(
+            corner(Interpolation::StepAfter),
+            points(&[(0.0, 0.0), (10.0, 0.0), (10.0, 5.0)])
+        );
+        assert_eq!(
+            corner(Interpolation::StepMiddle),
+            points(&[(0.0, 0.0), (5.0, 0.0), (5.0, 5.0), (10.0, 5.0)])
+        );
+    }
+
+    #[test]
+    fn monotone_spline_stays_within_sample_bounds() {
+        // A sharp step followed by a plateau overshoots with a plain Catmull-Rom spline.
+        let run = points(&[
+            (0.0, 0.0),
+            (1.0, 0.0),
+            (2.0, 100.0),
+            (3.0, 100.0),
+            (10.0, 90.0),
+        ]);
+        let mut path = BezPath::new();
+        Interpolation::Monotone.trace(&mut path, &run, false);
+        let bounds = path.bounding_box();
+        assert!(
+            bounds.y0 >= -1e-9 && bounds.y1 <= 100.0 + 1e-9,
+            "{bounds:?}"
+        );
+        assert!(bounds.x0 >= 0.0 && bounds.x1 <= 10.0);
+    }
+
+    #[test]
+    fn reversed_trace_continues_the_subpath_backwards() {
+        let run = points(&[(0.0, 0.0), (10.0, 5.0)]);
+        let mut path = BezPath::new();
+        path.move_to((10.0, 10.0));
+        Interpolation::StepAfter.trace_reversed(&mut path, &run);
+        assert_eq!(
+            vertices(&path),
+            points(&[(10.0, 10.0), (10.0, 5.0), (10.0, 0.0), (0.0, 0.0)])
+        );
+    }
+
+    #[test]
+    fn pieces_split_the_trace_per_pair() {
+        let run = points(&[(0.0, 0.0), (10.0, 5.0), (20.0, 0.0)]);
+        let pieces = Interpolation::StepMiddle.pieces(&run);
+        assert_eq!(pieces.len(), 2);
+        assert_eq!(
+            vertices(&pieces[1]),
+            points(&[(10.0, 5.0), (15.0, 5.0), (15.0, 0.0), (20.0, 0.0)])
+        );
+    }
+}
@@ -0,0 +1,521 @@
+//! Series legend: wrap-flowed entries docked beside the plot or floating inside a pane.
+
+use vello::{
+    Scene,
+    kurbo::{Affine, Circle, Line, Point, Rect, RoundedRect, Stroke},
+    peniko::{Brush, Color, Fill},
+};
+use vello_composition::{
+    LabelLayout, LayoutAlignment, LayoutConstraints, LayoutOrientation, LayoutSize,
+    LayoutThickness, MIN_PLOT_DIMENSION, PlotArea, WrapLayoutChild, WrapLayoutOptions,
+    solve_wrap_layout,
+};
+
+use crate::{ChartTheme, SeriesKind};
+
+const SWATCH_WIDTH: f64 = 16.0;
+const SWATCH_HEIGHT: f64 = 10.0;
+const SWATCH_GAP: f64 = 6.0;
+const ITEM_SPACING: f64 = 14.0;
+const LINE_SPACING: f64 = 4.0;
+const PADDING: f64 = 6.0;
+/// Gap between a docked legend and the chart edge, and between a floating legend and its pane.
+const MARGIN: f64 = 8.0;
+const HIDDEN_ALPHA: f32 = 0.3;
+
+/// Where the legend is drawn relative to the plot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LegendPlacement {
+    #[default]
+    None,
+    Top,
+    Bottom,
+    Left,
+    Right,
+    /// Overlaid on a pane, anchored to one of its corners.
+    Floating,
+}
+
+impl LegendPlacement {
+    pub(crate) fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::None),
+            1 => Some(Self::Top),
+            2 => Some(Self::Bottom),
+            3 => Some(Self::Left),
+            4 => Some(Self::Right),
+            5 => Some(Self::Floating),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LegendCorner {
+    #[default]
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl LegendCorner {
+    pub(crate) fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::TopLeft),
+            1 => Some(Self::TopRight),
+            2 => Some(Self::BottomLeft),
+            3 => Some(Self::BottomRight),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LegendOptions {
+    pub placement: LegendPlacement,
+    /// Pane a floating legend is drawn over; out-of-range indices fall back to the last pane.
+    pub floating_pane: usize,
+    pub floating_corner: LegendCorner,
+}
+
+pub(crate) struct LegendEntry {
+    pub(crate) series_id: u32,
+    pub(crate) kind: SeriesKind,
+    pub(crate) color: Color,
+    pub(crate) label: String,
+    pub(crate) visible: bool,
+}
+
+/// Clickable region of a drawn legend entry, in chart coordinates.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct LegendHit {
+    pub(crate) series_id: u32,
+    pub(crate) bounds: Rect,
+}
+
+struct LegendItem {
+    entry: LegendEntry,
+    text: LabelLayout,
+    /// Position relative to the legend origin.
+    bounds: Rect,
+}
+
+pub(crate) struct Legend {
+    items: Vec<LegendItem>,
+    width: f64,
+    height: f64,
+    origin: Point,
+    framed: bool,
+    font_size: f32,
+}
+
+impl Legend {
+    /// Flows `entries` along `orientation`, wrapping once `extent` is exhausted.
+    pub(crate) fn measure(
+        entries: Vec<LegendEntry>,
+        orientation: LayoutOrientation,
+        extent: f64,
+        theme: &ChartTheme,
+    ) -> Option<Self> {
+        let font_size = theme.label.font_size;
+        let mut items = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let Some(text) = theme.label.layout(&entry.label) else {
+                continue;
+            };
+            items.push(LegendItem {
+                entry,
+                text,
+                bounds: Rect::ZERO,
+            });
+        }
+        if items.is_empty() {
+            return None;
+        }
+
+        let children: Vec<WrapLayoutChild> = items
+            .iter()
+            .map(|item| {
+                let (width, height) = item_size(&item.text);
+                WrapLayoutChild::new(LayoutConstraints::tight(width, height))
+            })
+            .collect();
+        let padding = LayoutThickness {
+            left: PADDING,
+            top: PADDING,
+            right: PADDING,
+            bottom: PADDING,
+        };
+        let options = WrapLayoutOptions {
+            orientation,
+            item_spacing: ITEM_SPACING,
+            line_spacing: LINE_SPACING,
+            padding,
+            line_alignment: LayoutAlignment::Start,
+            cross_alignment: LayoutAlignment::Start,
+        };
+        let available = match orientation {
+            LayoutOrientation::Horizontal => LayoutSize::new(extent.max(0.0), f64::INFINITY),
+            LayoutOrientation::Vertical => LayoutSize::new(f64::INFINITY, extent.max(0.0)),
+        };
+        let layout = solve_wrap_layout(&children, options, available);
+
+        let mut width: f64 = 0.0;
+        let mut height: f64 = 0.0;
+        for (item, rect) in items.iter_mut().zip(layout.items.iter()) {
+            let Some(line) = layout.lines.get(rect.line_index as usize) else {
+                continue;
+            };
+            // Wrap rects only carry their offset within a line; centre each one across it.
+            let inset = (line.primary_length - rect.primary_length).max(0.0) * 0.5;
+            let (x, y) = match orientation {
+                LayoutOrientation::Horizontal => (rect.x, line.primary_offset + inset),
+                LayoutOrientation::Vertical => (line.primary_offset, rect.y),
+            };
+            item.bounds = Rect::new(x, y, x + rect.width, y + rect.height);
+            width = width.max(item.bounds.x1);
+            height = height.max(item.bounds.y1);
+        }
+
+        Some(Self {
+            items,
+            width: width + PADDING,
+            height: height + PADDING,
+            origin: Point::ZERO,
+            framed: false,
+            font_size,
+        })
+    }
+
+    /// Places the legend along a chart edge and shrinks `plot` to make room for it.
+    pub(crate) fn dock(
+        &mut self,
+        placement: LegendPlacement,
+        chart_width: f64,
+        chart_height: f64,
+        plot: &mut PlotArea,
+    ) {
+        let min_plot = MIN_PLOT_DIMENSION.min(plot.height).min(plot.width);
+        match placement {
+            LegendPlacement::Top => {
+                // The top margin is empty, so only push the plot down by what it can't absorb.
+                self.origin = Point::new(plot.left, MARGIN);
+                let needed = (self.origin.y + self.height + MARGIN - plot.top).max(0.0);
+                let shift = needed.min((plot.height - min_plot).max(0.0));
+                plot.top += shift;
+                plot.height -= shift;
+            }
+            LegendPlacement::Bottom => {
+                let reserved = (self.height + MARGIN).min((plot.height - min_plot).max(0.0));
+                plot.height -= reserved;
+                self.origin = Point::new(plot.left, chart_height - MARGIN * 0.5 - self.height);
+            }
+            LegendPlacement::Left => {
+                let reserved = (self.width + MARGIN).min((plot.width - min_plot).max(0.0));
+                self.origin = Point::new(MARGIN, plot.top);
+                plot.left += reserved;
+                plot.width -= reserved;
+            }
+            LegendPlacement::Right => {
+                let reserved = (self.width + MARGIN).min((plot.width - min_plot).max(0.0));
+                plot.width -= reserved;
+                self.origin = Point::new(chart_width - MARGIN - self.width, plot.top);
+            }
+            LegendPlacement::None | LegendPlacement::Floating => {}
+        }
+    }
+
+    /// Overlays the legend on `pane`, inset from `corner`.
+    pub(crate) fn float_in(&mut self, corner: LegendCorner, pane: &PlotArea) {
+        let left = match corner {
+            LegendCorner::TopLeft | LegendCorner::BottomLeft => pane.left + MARGIN,
+            LegendCorner::TopRight | LegendCorner::BottomRight => {
+                pane.right() - MARGIN - self.width
+            }
+        };
+        let top = match corner {
+            LegendCorner::TopLeft | LegendCorner::TopRight => pane.top + MARGIN,
+            LegendCorner::BottomLeft | LegendCorner::BottomRight => {
+                pane.bottom() - MARGIN - self.height
+            }
+        };
+        self.origin = Point::new(left.max(pane.left), top.max(pane.top));
+        self.framed = true;
+    }
+
+    pub(crate) fn hits(&self) -> impl Iterator<Item = LegendHit> + '_ {
+        let offset = self.origin.to_vec2();
+        self.items.iter().map(move |item| LegendHit {
+            series_id: item.entry.series_id,
+            bounds: item.bounds + offset,
+        })
+    }
+
+    /// Encodes the legend into `scene`, returning the number of paths drawn.
+    pub(crate) fn draw(&self, scene: &mut Scene, theme: &ChartTheme) -> u32 {
+        let mut encoded = 0;
+        if self.framed {
+            let frame = RoundedRect::new(
+                self.origin.x,
+                self.origin.y,
+                self.origin.x + self.width,
+                self.origin.y + self.height,
+                theme.callout.corner_radius,
+            );
+            scene.fill(
+                Fill::NonZero,
+                Affine::IDENTITY,
+                &Brush::Solid(theme.legend_background),
+                None,
+                &frame,
+            );
+            scene.stroke(
+                &Stroke::new(1.0),
+                Affine::IDENTITY,
+                theme.panel_border,
+                None,
+                &frame,
+            );
+            encoded += 2;
+        }
+
+        for item in &self.items {
+            let bounds = item.bounds + self.origin.to_vec2();
+            let alpha = if item.entry.visible {
+                1.0
+            } else {
+                HIDDEN_ALPHA
+            };
+            let swatch = Rect::new(
+                bounds.x0,
+                bounds.center().y - SWATCH_HEIGHT * 0.5,
+                bounds.x0 + SWATCH_WIDTH,
+                bounds.center().y + SWATCH_HEIGHT * 0.5,
+            );
+            encoded += draw_swatch(
+                scene,
+                item.entry.kind,
+                item.entry.color.multiply_alpha(alpha),
+                swatch,
+            );
+
+            let text_left = swatch.x1 + SWATCH_GAP;
+            let baseline_y =
+                bounds.center().y - f64::from(item.text.height) * 0.5 + f64::from(item.text.ascent);
+            encoded += item.text.draw(
+                scene,
+                self.font_size,
+                Affine::translate((text_left, baseline_y)),
+                &Brush::Solid(theme.label.color.multiply_alpha(alpha)),
+            );
+        }
+
+        encoded
+    }
+}
+
+/// Returns the series whose legend entry contains `point`.
+pub(crate) fn hit_test(hits: &[LegendHit], point: Point) -> Option<u32> {
+    hits.iter()
+        .find(|hit| hit.bounds.contains(point))
+        .map(|hit| hit.series_id)
+}
+
+fn item_size(text: &LabelLayout) -> (f64, f64) {
+    (
+        SWATCH_WIDTH + SWATCH_GAP + f64::from(text.width),
+        f64::from(text.height).max(SWATCH_HEIGHT),
+    )
+}
+
+/// Draws a miniature of the series geometry so entries read like the plot.
+fn draw_swatch(scene: &mut Scene, kind: SeriesKind, color: Color, swatch: Rect) -> u32 {
+    let mid_y = swatch.center().y;
+    match kind {
+        SeriesKind::Line => {
+            scene.stroke(
+                &Stroke::new(2.0),
+                Affine::IDENTITY,
+                color,
+                None,
+                &Line::new((swatch.x0, mid_y), (swatch.x1, mid_y)),
+            );
+            1
+        }
+        SeriesKind::Area => {
+            scene.fill(
+                Fill::NonZero,
+                Affine::IDENTITY,
+                color.multiply_alpha(0.35),
+                None,
+                &swatch,
+            );
+            scene.stroke(
+                &Stroke::new(1.5),
+                Affine::IDENTITY,
+                color,
+                None,
+                &Line::new((swatch.x0, swatch.y0), (swatch.x1, swatch.y0)),
+            );
+            2
+        }
+        SeriesKind::Scatter => {
+            scene.fill(
+                Fill::NonZero,
+                Affine::IDENTITY,
+                color,
+                None,
+                &Circle::new(swatch.center(), SWATCH_HEIGHT * 0.35),
+            );
+            1
+        }
+        SeriesKind::Bar => {
+            let half = swatch.width() * 0.2;
+            let bar = Rect::new(
+                swatch.center().x - half,
+                swatch.y0,
+                swatch.center().x + half,
+                swatch.y1,
+            );
+            scene.fill(Fill::NonZero, Affine::IDENTITY, color, None, &bar);
+            1
+        }
+        SeriesKind::Band => {
+            scene.fill(
+                Fill::NonZero,
+                Affine::IDENTITY,
+                color.multiply_alpha(0.25),
+                None,
+                &swatch,
+            );
+            for y in [swatch.y0, swatch.y1] {
+                scene.stroke(
+                    &Stroke::new(1.0),
+                    Affine::IDENTITY,
+                    color,
+                    None,
+                    &Line::new((swatch.x0, y), (swatch.x1, y)),
+                );
+            }
+            3
+        }
+        SeriesKind::Heatmap => {
+            let cell = swatch.width() / 3.0;
+            for (index, alpha) in [0.35, 0.65, 1.0].into_iter().enumerate() {
+                let x0 = swatch.x0 + cell * index as f64;
+                scene.fill(
+                    Fill::NonZero,
+                    Affine::IDENTITY,
+                    color.multiply_alpha(alpha),
+                    None,
+                    &Rect::new(x0, swatch.y0, x0 + cell, swatch.y1),
+                );
+            }
+            3
+        }
+        SeriesKind::Candlestick | SeriesKind::Ohlc => {
+            let center_x = swatch.center().x;
+            scene.stroke(
+                &Stroke::new(1.0),
+                Affine::IDENTITY,
+                color,
+                None,
+                &Line::new((center_x, swatch.y0), (center_x, swatch.y1)),
+            );
+            let half = swatch.width() * 0.2;
+            let body = Rect::new(
+                center_x - half,
+                swatch.y0 + swatch.height() * 0.25,
+                center_x + half,
+                swatch.y1 - swatch.height() * 0.25,
+            );
+            scene.fill(Fill::NonZero, Affine::IDENTITY, color, None, &body);
+            2
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(count: u32) -> Vec<LegendEntry> {
+        (0..count)
+            .map(|series_id| LegendEntry {
+                series_id,
+                kind: SeriesKind::Line,
+                color: Color::from_rgba8(0xFF, 0x00, 0x00, 0xFF),
+                label: format!("Series {series_id}"),
+                visible: true,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn entries_wrap_onto_new_lines_when_narrow() {
+        let wide = Legend::measure(
+            entries(4),
+            LayoutOrientation::Horizontal,
+            10_000.0,
+            &ChartTheme::default(),
+        )
+        .unwrap();
+        let narrow = Legend::measure(
+            entries(4),
+            LayoutOrientation::Horizontal,
+            120.0,
+            &ChartTheme::default(),
+        )
+        .unwrap();
+        assert!(narrow.height > wide.height);
+        assert!(narrow.width < wide.width);
+
+        let hits: Vec<_> = narrow.hits().collect();
+        for pair in hits.windows(2) {
+            assert!(!pair[0].bounds.overlaps(pair[1].bounds));
+        }
+    }
+
+    #[test]
+    fn docking_reserves_plot_space() {
+        let mut plot = PlotArea {
+            left: 48.0,
+            top: 40.0,
+            width: 600.0,
+            height: 400.0,
+        };
+        let mut legend = Legend::measure(
+            entries(3),
+            LayoutOrientation::Vertical,
+            plot.height,
+            &ChartTheme::default(),
+        )
+        .unwrap();
+        legend.dock(LegendPlacement::Right, 700.0, 500.0, &mut plot);
+        assert!(plot.width < 600.0);
+        assert!(legend.origin.x >= plot.right());
+        assert!(legend.origin.x + legend.width <= 700.0);
+    }
+
+    #[test]
+    fn hit_test_resolves_entry_under_point() {
+        let mut legend = Legend::measure(
+            entries(3),
+            LayoutOrientation::Horizontal,
+            1_000.0,
+            &ChartTheme::default(),
+        )
+        .unwrap();
+        let pane = PlotArea {
+            left: 0.0,
+            top: 0.0,
+            width: 800.0,
+            height: 400.0,
+        };
+        legend.float_in(LegendCorner::TopRight, &pane);
+        let hits: Vec<_> = legend.hits().collect();
+        assert_eq!(hit_test(&hits, hits[1].bounds.center()), Some(1));
+        assert_eq!(hit_test(&hits, Point::new(1.0, 399.0)), None);
+    }
+}
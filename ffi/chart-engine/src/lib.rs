@@ -4,10 +4,32 @@
 #![allow(clippy::missing_docs_in_private_items)]
 #![allow(clippy::too_many_arguments)]
 
+mod annotations;
+mod candles;
+mod color_scale;
+mod decimation;
+mod derived;
+mod export;
+mod gaps;
+mod hit_test;
+mod interpolation;
+mod legend;
+mod overlay;
+mod series_buffer;
+mod snapshot;
+mod stacking;
+#[cfg(test)]
+mod test_support;
+mod theme;
+mod viewport;
+
 use std::{
+    borrow::Cow,
     cell::RefCell,
     ffi::{CString, c_char},
-    fmt, slice, str,
+    fmt,
+    ops::Range,
+    slice, str,
     sync::{OnceLock, RwLock},
     time::{Instant, SystemTime},
 };
@@ -22,15 +44,39 @@ use vello::{
     kurbo::{Affine, BezPath, Circle, Line, Point, Rect, RoundedRect, Stroke},
     peniko::{Brush, Color, ColorStop, Fill, Gradient, color::DynamicColor},
 };
+use vello_chart_data::{DataBus, DataBusStats, DataPoint, SeriesSample};
 use vello_chart_diagnostics::{DiagnosticsCollector, FrameStats};
+
+use crate::annotations::{AnnotationLayer, AnnotationPane, AnnotationSet};
+use crate::candles::{CandleStyle, OhlcPoint, candle_bounds, render_candle_series};
+use crate::color_scale::{
+    COLOR_BAR_SPACE, COLOR_BAR_WIDTH, ColorMap, ColorScale, draw_color_bar, resolve_heatmap_domains,
+};
+use crate::decimation::{DecimationMode, decimate};
+use crate::derived::DerivedState;
+use crate::gaps::{GapStyle, draw_gap_bridges, finite_runs, mark_gaps};
+use crate::hit_test::{HitPane, HitSeries};
+use crate::interpolation::Interpolation;
+use crate::legend::{Legend, LegendEntry, LegendHit};
+use crate::series_buffer::SeriesBuffer;
+use crate::stacking::{StackGroup, StackMode, StackedSpan, render_stacked_bars, stack_spans};
+use crate::theme::minor_positions;
+use crate::viewport::Viewport;
 use vello_composition::{
-    AxisLayout, LabelLayout, LinearLayoutItem, MIN_PLOT_DIMENSION, PlotArea, ScalarConstraint,
-    SceneGraphCache, SceneNodeId, compute_axis_layout, compute_plot_area, label_font, layout_label,
-    solve_linear_layout,
+    AxisLabelOptions, AxisLayout, LayoutOrientation, LinearLayoutItem, MAX_UTC_OFFSET_SECONDS,
+    MIN_PLOT_DIMENSION, PlotArea, ScalarConstraint, SceneGraphCache, SceneNodeId, TimeFormat,
+    ValueFormat, ValueScale, compute_labeled_axis_layout, compute_plot_area, solve_linear_layout,
 };
 
 const MIN_VISIBLE_DURATION_SECS: f64 = 1e-6;
 const SCENE_BUFFER_COUNT: usize = 2;
+/// Draw order of the scene root's children: frame chrome and below-series annotations
+/// under the series; labels, colour bars, legend and above-series annotations over
+/// them; the pointer overlay on top.
+const UNDERLAY_Z: i32 = 0;
+const SERIES_Z: i32 = 1;
+const FOREGROUND_Z: i32 = 2;
+const OVERLAY_Z: i32 = 3;
 const DEFAULT_STROKE_WIDTH: f64 = 1.5;
 const MIN_STROKE_WIDTH: f64 = 0.1;
 const DEFAULT_PALETTE: &[(u8, u8, u8)] = &[
@@ -54,18 +100,34 @@ const SERIES_DEFINITION_FLAG_MARKER_SIZE_SET: u32 = 1 << 3;
 const SERIES_DEFINITION_FLAG_BAR_WIDTH_SET: u32 = 1 << 4;
 const SERIES_DEFINITION_FLAG_BAND_LOWER_SET: u32 = 1 << 5;
 const SERIES_DEFINITION_FLAG_HEATMAP_BUCKETS_SET: u32 = 1 << 6;
+const SERIES_DEFINITION_FLAG_DECIMATION_SET: u32 = 1 << 7;
+const SERIES_DEFINITION_FLAG_VALUE_AXIS_SET: u32 = 1 << 8;
+const SERIES_DEFINITION_FLAG_UP_COLOR_SET: u32 = 1 << 9;
+const SERIES_DEFINITION_FLAG_DOWN_COLOR_SET: u32 = 1 << 10;
+const SERIES_DEFINITION_FLAG_STACK_SET: u32 = 1 << 11;
+const SERIES_DEFINITION_FLAG_MAX_GAP_SET: u32 = 1 << 12;
+const SERIES_DEFINITION_FLAG_GAP_STYLE_SET: u32 = 1 << 13;
+const SERIES_DEFINITION_FLAG_INTERPOLATION_SET: u32 = 1 << 14;
+const SERIES_DEFINITION_FLAG_COLOR_MAP_SET: u32 = 1 << 15;
+const SERIES_DEFINITION_FLAG_COLOR_DOMAIN_MIN_SET: u32 = 1 << 16;
+const SERIES_DEFINITION_FLAG_COLOR_DOMAIN_MAX_SET: u32 = 1 << 17;
+
+const AXIS_LABEL_FLAG_DATE_ON_DAY_CHANGE: u32 = 1 << 0;
+
+const EXPORT_FLAG_BACKGROUND: u32 = 1 << 0;
+
+const ANNOTATION_FLAG_COLOR_SET: u32 = 1 << 0;
+const ANNOTATION_FLAG_LABEL_SET: u32 = 1 << 1;
+const ANNOTATION_FLAG_PANE_SET: u32 = 1 << 2;
+
+const THEME_FLAG_MAJOR_GRID: u32 = 1 << 0;
+const THEME_FLAG_MINOR_GRID: u32 = 1 << 1;
+const THEME_FLAG_AXIS: u32 = 1 << 2;
 
-const LABEL_FONT_SIZE: f32 = 14.0;
-const LABEL_HORIZONTAL_PADDING: f64 = 6.0;
-const LABEL_VERTICAL_PADDING: f64 = 4.0;
-const LABEL_CORNER_RADIUS: f64 = 6.0;
-const LABEL_HORIZONTAL_OFFSET: f64 = 12.0;
-const LABEL_VERTICAL_GAP: f64 = 8.0;
-const AXIS_FONT_SIZE: f32 = 12.0;
 const AXIS_LABEL_LEFT_MARGIN: f64 = 10.0;
 const AXIS_LABEL_BOTTOM_MARGIN: f64 = 18.0;
-const GRID_STROKE_WIDTH: f64 = 1.0;
-const AXIS_STROKE_WIDTH: f64 = 1.0;
+/// Spacing between lines of a multi-line time label.
+const AXIS_LABEL_LINE_HEIGHT: f64 = 15.0;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum SeriesKind {
@@ -75,6 +137,8 @@ enum SeriesKind {
     Bar,
     Band,
     Heatmap,
+    Candlestick,
+    Ohlc,
 }
 
 impl Default for SeriesKind {
@@ -83,6 +147,15 @@ impl Default for SeriesKind {
     }
 }
 
+/// Value axis a series is scaled against. Each pane ranges its left and right axes
+/// independently from the series bound to them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum ValueAxisSide {
+    #[default]
+    Left,
+    Right,
+}
+
 #[derive(Debug, Clone)]
 struct SeriesDefinition {
     kind: SeriesKind,
@@ -94,6 +167,17 @@ struct SeriesDefinition {
     band_lower_id: Option<u32>,
     heatmap_bucket_index: Option<u32>,
     heatmap_bucket_count: Option<u32>,
+    decimation: Option<DecimationMode>,
+    value_axis: Option<ValueAxisSide>,
+    up_color: Option<Color>,
+    down_color: Option<Color>,
+    stack_group: Option<StackGroup>,
+    max_gap_seconds: Option<f64>,
+    gap_style: Option<GapStyle>,
+    interpolation: Option<Interpolation>,
+    color_scale: Option<ColorScale>,
+    color_domain_min: Option<f64>,
+    color_domain_max: Option<f64>,
 }
 
 impl Default for SeriesDefinition {
@@ -108,6 +192,17 @@ impl Default for SeriesDefinition {
             band_lower_id: None,
             heatmap_bucket_index: None,
             heatmap_bucket_count: None,
+            decimation: None,
+            value_axis: None,
+            up_color: None,
+            down_color: None,
+            stack_group: None,
+            max_gap_seconds: None,
+            gap_style: None,
+            interpolation: None,
+            color_scale: None,
+            color_domain_min: None,
+            color_domain_max: None,
         }
     }
 }
@@ -139,6 +234,39 @@ impl SeriesDefinition {
         if other.heatmap_bucket_count.is_some() {
             self.heatmap_bucket_count = other.heatmap_bucket_count;
         }
+        if other.decimation.is_some() {
+            self.decimation = other.decimation;
+        }
+        if other.value_axis.is_some() {
+            self.value_axis = other.value_axis;
+        }
+        if other.up_color.is_some() {
+            self.up_color = other.up_color;
+        }
+        if other.down_color.is_some() {
+            self.down_color = other.down_color;
+        }
+        if other.stack_group.is_some() {
+            self.stack_group = other.stack_group;
+        }
+        if other.max_gap_seconds.is_some() {
+            self.max_gap_seconds = other.max_gap_seconds;
+        }
+        if other.gap_style.is_some() {
+            self.gap_style = other.gap_style;
+        }
+        if other.interpolation.is_some() {
+            self.interpolation = other.interpolation;
+        }
+        if other.color_scale.is_some() {
+            self.color_scale = other.color_scale.clone();
+        }
+        if other.color_domain_min.is_some() {
+            self.color_domain_min = other.color_domain_min;
+        }
+        if other.color_domain_max.is_some() {
+            self.color_domain_max = other.color_domain_max;
+        }
     }
 }
 
@@ -150,6 +278,8 @@ fn series_kind_from_u32(value: u32) -> Result<SeriesKind, VelloChartEngineStatus
         3 => Ok(SeriesKind::Bar),
         4 => Ok(SeriesKind::Band),
         5 => Ok(SeriesKind::Heatmap),
+        6 => Ok(SeriesKind::Candlestick),
+        7 => Ok(SeriesKind::Ohlc),
         _ => {
             set_last_error("Unknown series kind value");
             Err(VelloChartEngineStatus::InvalidArgument)
@@ -157,6 +287,17 @@ fn series_kind_from_u32(value: u32) -> Result<SeriesKind, VelloChartEngineStatus
     }
 }
 
+fn value_axis_side_from_u32(value: u32) -> Result<ValueAxisSide, VelloChartEngineStatus> {
+    match value {
+        0 => Ok(ValueAxisSide::Left),
+        1 => Ok(ValueAxisSide::Right),
+        _ => {
+            set_last_error("Unknown value axis side");
+            Err(VelloChartEngineStatus::InvalidArgument)
+        }
+    }
+}
+
 fn series_kind_to_u32(kind: SeriesKind) -> u32 {
     match kind {
         SeriesKind::Line => 0,
@@ -165,6 +306,31 @@ fn series_kind_to_u32(kind: SeriesKind) -> u32 {
         SeriesKind::Bar => 3,
         SeriesKind::Band => 4,
         SeriesKind::Heatmap => 5,
+        SeriesKind::Candlestick => 6,
+        SeriesKind::Ohlc => 7,
+    }
+}
+
+fn value_scale_from_ffi(
+    value: u32,
+    linear_threshold: f64,
+) -> Result<ValueScale, VelloChartEngineStatus> {
+    match value {
+        0 => Ok(ValueScale::Linear),
+        1 => Ok(ValueScale::Log10),
+        2 => Ok(ValueScale::symlog(linear_threshold)),
+        _ => {
+            set_last_error("Unknown value scale value");
+            Err(VelloChartEngineStatus::InvalidArgument)
+        }
+    }
+}
+
+fn value_scale_to_ffi(scale: ValueScale) -> (u32, f64) {
+    match scale {
+        ValueScale::Linear => (0, 0.0),
+        ValueScale::Log10 => (1, 0.0),
+        ValueScale::SymLog { linear_threshold } => (2, linear_threshold),
     }
 }
 
@@ -172,25 +338,40 @@ fn series_kind_to_u32(kind: SeriesKind) -> u32 {
 #[derive(Debug, Clone)]
 pub struct EngineOptions {
     pub visible_duration_seconds: f64,
+    /// History kept in memory; `None` retains only the visible duration.
+    pub retention_duration_seconds: Option<f64>,
     pub vertical_padding_ratio: f64,
     pub stroke_width: f64,
     pub palette: Vec<Color>,
     pub show_axes: bool,
+    pub legend: LegendOptions,
+    pub axis_labels: AxisLabelOptions,
+    pub theme: ChartTheme,
 }
 
 impl Default for EngineOptions {
     fn default() -> Self {
         Self {
             visible_duration_seconds: 120.0,
+            retention_duration_seconds: None,
             vertical_padding_ratio: 0.08,
             stroke_width: DEFAULT_STROKE_WIDTH,
             palette: Self::default_palette(),
             show_axes: true,
+            legend: LegendOptions::default(),
+            axis_labels: AxisLabelOptions::default(),
+            theme: ChartTheme::default(),
         }
     }
 }
 
 impl EngineOptions {
+    fn retention_seconds(&self) -> f64 {
+        self.retention_duration_seconds
+            .unwrap_or(0.0)
+            .max(self.visible_duration_seconds)
+    }
+
     fn default_palette() -> Vec<Color> {
         DEFAULT_PALETTE
             .iter()
@@ -199,11 +380,22 @@ impl EngineOptions {
     }
 }
 
+pub use crate::annotations::{Annotation, AnnotationKind};
+pub use crate::candles::OhlcSample;
+pub use crate::derived::{DerivedOperator, DerivedSeries};
+pub use crate::export::{ExportError, ExportFormat, ExportOptions, export_scene};
+pub use crate::hit_test::HitTestResult;
+pub use crate::legend::{LegendCorner, LegendOptions, LegendPlacement};
+pub use crate::snapshot::{SNAPSHOT_VERSION, SnapshotError};
+pub use crate::theme::{AxisStyle, CalloutStyle, ChartTheme, LineStyle, TextStyle};
+pub use crate::viewport::ViewportRange;
+
 /// Streaming sample ingested by the engine.
 #[derive(Debug, Clone, Copy)]
 pub struct ChartSample {
     pub series_id: u32,
     pub timestamp_seconds: f64,
+    /// A non-finite value records a gap: line, area and band series break there.
     pub value: f64,
 }
 
@@ -220,6 +412,7 @@ struct CompositionPane {
     height_ratio: f64,
     share_x_with_primary: bool,
     series_ids: Vec<u32>,
+    value_scale: ValueScale,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -232,6 +425,7 @@ struct ResolvedPane {
     height_ratio: f64,
     share_x_with_primary: bool,
     series_ids: Vec<u32>,
+    value_scale: ValueScale,
 }
 
 /// Rendering engine maintaining per-series state and double-buffered scenes.
@@ -250,12 +444,34 @@ pub struct ChartEngine {
     last_buffer: usize,
     scene_invalidated: bool,
     last_dimensions: (u32, u32),
+    data_bus: Option<DataBus>,
+    bus_scratch: Vec<SeriesSample>,
+    underlay_node: SceneNodeId,
+    foreground_node: SceneNodeId,
+    overlay_node: SceneNodeId,
+    /// Detached root of the annotation scenes, which are copied into the underlay and
+    /// foreground rather than composed.
+    annotation_root: SceneNodeId,
+    cursor: Option<Point>,
+    viewport: Viewport,
+    last_plot_area: PlotArea,
+    annotations: AnnotationSet,
+    /// Derived series keyed by the id of the series they write to.
+    derived: HashMap<u32, DerivedState>,
+    derived_scratch: Vec<(u32, SeriesPoint)>,
 }
 
 impl ChartEngine {
     pub fn new(options: EngineOptions) -> Self {
         let mut scene_cache = SceneGraphCache::new();
         let scene_root = scene_cache.create_node(None);
+        let [underlay_node, foreground_node, overlay_node] = [UNDERLAY_Z, FOREGROUND_Z, OVERLAY_Z]
+            .map(|z_index| {
+                let node = scene_cache.create_node(Some(scene_root));
+                scene_cache.set_z_index(node, z_index);
+                node
+            });
+        let annotation_root = scene_cache.create_node(None);
         Self {
             options,
             diagnostics: DiagnosticsCollector::default(),
@@ -271,6 +487,18 @@ impl ChartEngine {
             last_buffer: 0,
             scene_invalidated: true,
             last_dimensions: (0, 0),
+            data_bus: None,
+            bus_scratch: Vec::new(),
+            underlay_node,
+            foreground_node,
+            overlay_node,
+            annotation_root,
+            cursor: None,
+            viewport: Viewport::default(),
+            last_plot_area: PlotArea::default(),
+            annotations: AnnotationSet::default(),
+            derived: HashMap::new(),
+            derived_scratch: Vec::new(),
         }
     }
 
@@ -278,22 +506,96 @@ impl ChartEngine {
         self.ingest_samples(samples.iter().copied());
     }
 
+    /// Ingests open/high/low/close bars for candlestick and OHLC series.
+    pub fn publish_ohlc_samples(&mut self, samples: &[OhlcSample]) {
+        let retention = self.options.retention_seconds();
+        let mut mutated = false;
+        let mut rebuild = Vec::new();
+        for sample in samples {
+            if self.is_derived_output(sample.series_id) {
+                continue;
+            }
+            let bar = OhlcPoint::from(*sample);
+            let (outcome, scene_node) = {
+                let state = self.ensure_series_state(sample.series_id);
+                (state.add_candle(bar, retention), state.scene_node)
+            };
+
+            if !matches!(outcome, AddOutcome::Unchanged) {
+                self.scene_cache.mark_dirty_bounds(
+                    scene_node,
+                    bar.timestamp_seconds,
+                    bar.timestamp_seconds,
+                    bar.low,
+                    bar.high,
+                );
+                mutated = true;
+                let close = SeriesPoint {
+                    timestamp_seconds: bar.timestamp_seconds,
+                    value: bar.close,
+                };
+                self.feed_derived(sample.series_id, close, outcome, &mut rebuild);
+            }
+        }
+        mutated |= self.rebuild_derived(&rebuild);
+
+        if mutated {
+            self.scene_invalidated = true;
+        }
+    }
+
+    /// Attaches a bus whose queued batches are ingested at the start of every frame,
+    /// returning the previously attached bus.
+    pub fn attach_data_bus(&mut self, bus: DataBus) -> Option<DataBus> {
+        self.data_bus.replace(bus)
+    }
+
+    pub fn detach_data_bus(&mut self) -> Option<DataBus> {
+        self.data_bus.take()
+    }
+
+    pub fn data_bus(&self) -> Option<&DataBus> {
+        self.data_bus.as_ref()
+    }
+
+    fn drain_data_bus(&mut self) {
+        let Some(bus) = &self.data_bus else {
+            return;
+        };
+
+        let mut batches = std::mem::take(&mut self.bus_scratch);
+        bus.drain(&mut batches);
+        if !batches.is_empty() {
+            self.ingest_samples(batches.iter().flat_map(|batch| {
+                batch.points.iter().map(move |point| ChartSample {
+                    series_id: batch.series_id,
+                    timestamp_seconds: point.timestamp_ns as f64 * 1e-9,
+                    value: point.value,
+                })
+            }));
+            batches.clear();
+        }
+        self.bus_scratch = batches;
+    }
+
     fn ingest_samples<I>(&mut self, iter: I)
     where
         I: IntoIterator<Item = ChartSample>,
     {
-        let visible_duration = self.options.visible_duration_seconds;
+        let retention = self.options.retention_seconds();
         let mut mutated = false;
+        let mut rebuild = Vec::new();
         for sample in iter {
+            if self.is_derived_output(sample.series_id) {
+                continue;
+            }
+            let point = SeriesPoint {
+                timestamp_seconds: sample.timestamp_seconds,
+                value: sample.value,
+            };
             let (outcome, scene_node) = {
                 let state = self.ensure_series_state(sample.series_id);
-                let outcome = state.add(
-                    SeriesPoint {
-                        timestamp_seconds: sample.timestamp_seconds,
-                        value: sample.value,
-                    },
-                    visible_duration,
-                );
+                let outcome = state.add(point, retention);
                 let node = state.scene_node;
                 (outcome, node)
             };
@@ -302,8 +604,10 @@ impl ChartEngine {
                 self.scene_cache
                     .mark_dirty(scene_node, sample.timestamp_seconds, sample.value);
                 mutated = true;
+                self.feed_derived(sample.series_id, point, outcome, &mut rebuild);
             }
         }
+        mutated |= self.rebuild_derived(&rebuild);
 
         if mutated {
             self.scene_invalidated = true;
@@ -312,6 +616,127 @@ impl ChartEngine {
         tracing::debug!(series = self.series.len(), "ingested streaming samples");
     }
 
+    /// Whether `series_id` is written by a derived series; direct samples to it are
+    /// ignored.
+    fn is_derived_output(&self, series_id: u32) -> bool {
+        !self.derived.is_empty()
+            && self
+                .derived
+                .iter()
+                .any(|(output_id, state)| state.writes_to(*output_id, series_id))
+    }
+
+    /// Forwards a source sample to its derived series. Appends extend them in place;
+    /// any other change queues them in `rebuild` for a full recompute.
+    fn feed_derived(
+        &mut self,
+        source_id: u32,
+        point: SeriesPoint,
+        outcome: AddOutcome,
+        rebuild: &mut Vec<u32>,
+    ) {
+        if self.derived.is_empty() {
+            return;
+        }
+
+        let mut outputs = std::mem::take(&mut self.derived_scratch);
+        for (output_id, state) in &mut self.derived {
+            if state.series.source_series_id != source_id || rebuild.contains(output_id) {
+                continue;
+            }
+            if outcome != AddOutcome::Appended {
+                rebuild.push(*output_id);
+                continue;
+            }
+            if let Some(sample) = state.push(point) {
+                outputs.push((
+                    *output_id,
+                    SeriesPoint {
+                        timestamp_seconds: sample.timestamp_seconds,
+                        value: sample.value,
+                    },
+                ));
+                if let (Some(lower_id), Some(lower)) = (state.series.lower_series_id, sample.lower)
+                {
+                    outputs.push((
+                        lower_id,
+                        SeriesPoint {
+                            timestamp_seconds: sample.timestamp_seconds,
+                            value: lower,
+                        },
+                    ));
+                }
+            }
+        }
+
+        let retention = self.options.retention_seconds();
+        for (series_id, point) in outputs.drain(..) {
+            let state = self.ensure_series_state(series_id);
+            let outcome = state.add(point, retention);
+            let scene_node = state.scene_node;
+            if outcome != AddOutcome::Unchanged {
+                self.scene_cache
+                    .mark_dirty(scene_node, point.timestamp_seconds, point.value);
+            }
+        }
+        self.derived_scratch = outputs;
+    }
+
+    /// Recomputes the listed derived series from the live samples of their sources,
+    /// returning whether any were rebuilt.
+    fn rebuild_derived(&mut self, output_ids: &[u32]) -> bool {
+        let retention = self.options.retention_seconds();
+        let mut rebuilt = false;
+        for output_id in output_ids {
+            let Some(state) = self.derived.get_mut(output_id) else {
+                continue;
+            };
+            let source = self
+                .series
+                .get(&state.series.source_series_id)
+                .map(|source| source.span())
+                .unwrap_or_default();
+            let samples = state.recompute(source);
+            let lower_id = state.series.lower_series_id;
+
+            let upper = samples.iter().map(|sample| SeriesPoint {
+                timestamp_seconds: sample.timestamp_seconds,
+                value: sample.value,
+            });
+            self.replace_series_points(*output_id, upper, retention);
+            if let Some(lower_id) = lower_id {
+                let lower = samples.iter().filter_map(|sample| {
+                    sample.lower.map(|value| SeriesPoint {
+                        timestamp_seconds: sample.timestamp_seconds,
+                        value,
+                    })
+                });
+                self.replace_series_points(lower_id, lower, retention);
+            }
+            rebuilt = true;
+        }
+        rebuilt
+    }
+
+    fn replace_series_points(
+        &mut self,
+        series_id: u32,
+        points: impl IntoIterator<Item = SeriesPoint>,
+        retention: f64,
+    ) {
+        let state = self.ensure_series_state(series_id);
+        let scene_node = state.scene_node;
+        if let Some(bounds) = state.replace_points(points, retention) {
+            self.scene_cache.mark_dirty_bounds(
+                scene_node,
+                bounds.time_min,
+                bounds.time_max,
+                bounds.value_min,
+                bounds.value_max,
+            );
+        }
+    }
+
     fn ensure_series_state(&mut self, series_id: u32) -> &mut SeriesState {
         match self.series.entry(series_id) {
             Entry::Occupied(mut entry) => {
@@ -343,7 +768,7 @@ impl ChartEngine {
                     .get(&series_id)
                     .cloned()
                     .unwrap_or_default();
-                let scene_node = self.scene_cache.create_node(Some(self.scene_root));
+                let scene_node = create_series_node(&mut self.scene_cache, self.scene_root);
                 vacant.insert(SeriesState::new(slot, definition, scene_node))
             }
         }
@@ -447,6 +872,11 @@ impl ChartEngine {
             .filter(|p| !p.is_empty())
             .map(|panes| CompositionState { panes })
             .unwrap_or_default();
+
+        // Pane membership and value scales change how every series projects.
+        for state in self.series.values_mut() {
+            state.needs_rebuild = true;
+        }
         self.scene_invalidated = true;
     }
 
@@ -490,35 +920,106 @@ impl ChartEngine {
 
         {
             let buffer = &mut self.frame_buffers[buffer_index];
-            buffer.scene.reset();
+            buffer.hit_panes.clear();
+            buffer.legend_hits.clear();
+            // Chrome is re-encoded every frame into the scenes of the root's own layers;
+            // series scenes persist in their nodes and are only rebuilt when stale.
+            let [mut underlay, mut foreground] =
+                [self.underlay_node, self.foreground_node].map(|node| {
+                    let mut scene = self
+                        .scene_cache
+                        .scene_mut(node)
+                        .map(std::mem::take)
+                        .unwrap_or_default();
+                    scene.reset();
+                    scene
+                });
+            let mut placed_series = HashSet::new();
 
             let chart_width = f64::from(width);
             let chart_height = f64::from(height);
-            let plot_area = compute_plot_area(chart_width, chart_height);
+            let mut plot_area = compute_plot_area(chart_width, chart_height);
+            let has_right_axis = self
+                .series
+                .values()
+                .any(|state| state.visible && state.on_right_axis());
+            if has_right_axis {
+                reserve_right_axis(&mut plot_area, chart_width);
+            }
+            if self.options.show_axes && self.options.axis_labels.date_on_day_change {
+                reserve_date_row(&mut plot_area);
+            }
+
+            let legend_options = self.options.legend;
+            let mut legend = match legend_options.placement {
+                LegendPlacement::None => None,
+                LegendPlacement::Top | LegendPlacement::Bottom => Legend::measure(
+                    legend_entries(&self.composition, &self.series, &self.options),
+                    LayoutOrientation::Horizontal,
+                    plot_area.width,
+                    &self.options.theme,
+                ),
+                LegendPlacement::Left | LegendPlacement::Right | LegendPlacement::Floating => {
+                    Legend::measure(
+                        legend_entries(&self.composition, &self.series, &self.options),
+                        LayoutOrientation::Vertical,
+                        plot_area.height,
+                        &self.options.theme,
+                    )
+                }
+            };
+            if let Some(legend) = legend.as_mut() {
+                legend.dock(
+                    legend_options.placement,
+                    chart_width,
+                    chart_height,
+                    &mut plot_area,
+                );
+            }
+            let color_bar_left = self
+                .series
+                .values()
+                .any(|state| state.visible && state.definition.kind == SeriesKind::Heatmap)
+                .then(|| reserve_color_bar(&mut plot_area, has_right_axis));
+
+            // Cached series scenes are projected into the plot, so a moved plot rebuilds them.
+            if !same_plot_area(&plot_area, &self.last_plot_area) {
+                for state in self.series.values_mut() {
+                    state.needs_rebuild = true;
+                }
+                self.last_plot_area = plot_area;
+            }
 
             let cpu_start = Instant::now();
-            let mut encoded_paths =
-                draw_background(&mut buffer.scene, chart_width, chart_height, &plot_area);
+            let mut encoded_paths = draw_background(
+                &mut underlay,
+                chart_width,
+                chart_height,
+                &plot_area,
+                &self.options.theme,
+            );
+            let mut source_points = 0u64;
+            let mut rendered_points = 0u64;
 
-            let window = self
-                .options
-                .visible_duration_seconds
-                .max(MIN_VISIBLE_DURATION_SECS);
+            let global_latest = latest_timestamp(&self.series);
+            let has_series = global_latest.is_some();
+            let global_latest = global_latest.unwrap_or(f64::NEG_INFINITY);
+            let viewport = self
+                .viewport
+                .resolve(global_latest, self.options.visible_duration_seconds);
+            let window = (viewport.range_end - viewport.range_start).max(MIN_VISIBLE_DURATION_SECS);
 
-            let mut global_latest = f64::NEG_INFINITY;
             let mut global_min = f64::INFINITY;
             let mut global_max = f64::NEG_INFINITY;
-            let mut has_series = false;
-
-            for state in self.series.values() {
-                let span = state.span();
-                if span.is_empty() {
+            for state in self.series.values().filter(|state| state.visible) {
+                let Some((series_min, series_max)) = state.visible_bounds(
+                    viewport.range_start,
+                    viewport.range_end,
+                    ValueScale::Linear,
+                ) else {
                     continue;
-                }
+                };
 
-                has_series = true;
-                global_latest = global_latest.max(state.latest_timestamp);
-                let (series_min, series_max) = value_bounds(span);
                 global_min = global_min.min(series_min);
                 global_max = global_max.max(series_max);
             }
@@ -559,8 +1060,8 @@ impl ChartEngine {
                 global_value_min = global_min;
                 global_value_max = global_max;
 
-                range_end = global_latest;
-                range_start = range_end - window;
+                range_start = viewport.range_start;
+                range_end = range_start + window;
 
                 let mut resolved_panes = self.composition.resolve(&self.series);
                 if resolved_panes.is_empty() {
@@ -569,6 +1070,7 @@ impl ChartEngine {
                         height_ratio: 1.0,
                         share_x_with_primary: true,
                         series_ids: Vec::new(),
+                        value_scale: ValueScale::Linear,
                     });
                 }
 
@@ -675,86 +1177,159 @@ impl ChartEngine {
                     let share_axis = resolved.share_x_with_primary;
                     let pane_id = resolved.id;
                     let pane_series = resolved.series_ids;
+                    let pane_scale = resolved.value_scale;
 
                     let mut pane_min = f64::INFINITY;
                     let mut pane_max = f64::NEG_INFINITY;
                     let mut pane_has_series = false;
+                    let mut right_min = f64::INFINITY;
+                    let mut right_max = f64::NEG_INFINITY;
+                    let mut pane_has_right_series = false;
+                    let stacks = stack_pane(&pane_series, &mut self.series, range_start, range_end);
+                    resolve_heatmap_domains(&pane_series, &mut self.series, range_start, range_end);
 
                     for series_id in &pane_series {
                         if let Some(state) = self.series.get(series_id) {
-                            let span = state.span();
-                            if span.is_empty() {
+                            if !state.visible {
                                 continue;
                             }
+                            let bounds = match stacks.get(series_id) {
+                                Some(stack) => stack.bounds(range_start, range_end),
+                                None => state.visible_bounds(range_start, range_end, pane_scale),
+                            };
+                            let Some((series_min, series_max)) = bounds else {
+                                continue;
+                            };
 
-                            pane_has_series = true;
-                            let (series_min, series_max) = value_bounds(span);
-                            pane_min = pane_min.min(series_min);
-                            pane_max = pane_max.max(series_max);
+                            if state.on_right_axis() {
+                                pane_has_right_series = true;
+                                right_min = right_min.min(series_min);
+                                right_max = right_max.max(series_max);
+                            } else {
+                                pane_has_series = true;
+                                pane_min = pane_min.min(series_min);
+                                pane_max = pane_max.max(series_max);
+                            }
                         }
                     }
 
                     if !pane_has_series {
-                        pane_min = 0.0;
-                        pane_max = 1.0;
+                        pane_min = f64::NAN;
+                        pane_max = f64::NAN;
                     }
 
-                    if !pane_min.is_finite() || !pane_max.is_finite() {
-                        pane_min = 0.0;
-                        pane_max = 1.0;
-                    }
-
-                    let mut pane_padding =
-                        (pane_max - pane_min).abs() * self.options.vertical_padding_ratio;
-                    if !pane_padding.is_finite() {
-                        pane_padding = 0.0;
-                    }
-                    if pane_padding > 0.0 {
-                        pane_min -= pane_padding;
-                        pane_max += pane_padding;
-                    }
-                    if (pane_max - pane_min).abs() < f64::EPSILON {
-                        pane_max = pane_min + 1.0;
-                    }
-
-                    let pane_value_range = (pane_max - pane_min).max(f64::EPSILON);
-                    let axis_layout = Some(compute_axis_layout(
+                    let (pane_min, pane_max) = pad_value_range(
+                        pane_min,
+                        pane_max,
+                        self.options.vertical_padding_ratio,
+                        pane_scale,
+                    );
+                    let pane_axis = ValueAxis::new(pane_scale, pane_min, pane_max);
+                    let axis_layout = Some(compute_labeled_axis_layout(
                         range_start,
                         range_end,
                         pane_min,
                         pane_max,
                         6,
+                        pane_scale,
+                        &self.options.axis_labels,
                     ));
+                    let right_range = pane_has_right_series.then(|| {
+                        pad_value_range(
+                            right_min,
+                            right_max,
+                            self.options.vertical_padding_ratio,
+                            pane_scale,
+                        )
+                    });
+                    let right_axis_layout = right_range.map(|(min, max)| {
+                        compute_labeled_axis_layout(
+                            range_start,
+                            range_end,
+                            min,
+                            max,
+                            6,
+                            pane_scale,
+                            &self.options.axis_labels,
+                        )
+                    });
 
                     if time_axis_pane_index.is_none() && share_axis {
                         time_axis_pane_index = Some(pane_snapshots.len());
                     }
 
+                    if let Some(layout) = axis_layout.as_ref() {
+                        let time_axis = pane_plot_area.bottom() >= plot_area.bottom() - 0.5;
+                        encoded_paths += draw_axes_and_grid(
+                            &mut underlay,
+                            &pane_plot_area,
+                            layout,
+                            right_axis_layout.as_ref(),
+                            &self.options.theme,
+                            time_axis,
+                        );
+                    }
+
+                    let annotation_pane = AnnotationPane {
+                        pane_index: pane_snapshots.len(),
+                        pane_id: &pane_id,
+                        plot_area: pane_plot_area,
+                        range_start,
+                        window,
+                        value_axis: pane_axis,
+                        label: &self.options.theme.label,
+                    };
+                    encoded_paths += self.annotations.draw_pane(
+                        &mut self.scene_cache,
+                        self.annotation_root,
+                        &mut underlay,
+                        AnnotationLayer::BelowSeries,
+                        &annotation_pane,
+                    );
+
                     let mut pane_dirty: Option<DirtyBounds> = None;
+                    let mut hit_series = Vec::with_capacity(pane_series.len());
+                    let plot_clip = Rect::new(
+                        pane_plot_area.left,
+                        pane_plot_area.top,
+                        pane_plot_area.right(),
+                        pane_plot_area.bottom(),
+                    );
 
                     for series_id in &pane_series {
                         if band_lower_ids.contains(series_id) {
                             continue;
                         }
 
-                        let (series_kind, band_lower_id) = match self.series.get(series_id) {
-                            Some(state) => (state.definition.kind, state.definition.band_lower_id),
-                            None => continue,
-                        };
+                        let (series_kind, band_lower_id, max_gap_seconds) =
+                            match self.series.get(series_id) {
+                                Some(state) => (
+                                    state.definition.kind,
+                                    state.definition.band_lower_id,
+                                    state.definition.max_gap_seconds,
+                                ),
+                                None => continue,
+                            };
 
                         let lower_series_span: Option<Vec<SeriesPoint>> =
                             if series_kind == SeriesKind::Band {
                                 band_lower_id.and_then(|lower_id| {
-                                    self.series
-                                        .get(&lower_id)
-                                        .map(|lower_state| lower_state.span().to_vec())
+                                    self.series.get(&lower_id).map(|lower_state| {
+                                        mark_gaps(
+                                            lower_state.visible_span(range_start, range_end),
+                                            max_gap_seconds,
+                                        )
+                                        .into_owned()
+                                    })
                                 })
                             } else {
                                 None
                             };
 
+                        let stack = stacks.get(series_id);
+
                         if let Some(state) = self.series.get_mut(series_id) {
-                            if state.points.is_empty() {
+                            if state.points.is_empty() || !state.visible {
                                 continue;
                             }
 
@@ -779,23 +1354,98 @@ impl ChartEngine {
                             };
                             let fill_opacity = definition.fill_opacity.unwrap_or(default_fill);
                             let marker_size = definition.marker_size.unwrap_or(4.0).max(0.5);
-                            let fallback_bar_width = if state.points.len() > 1 {
-                                window / state.points.len() as f64
-                            } else {
-                                window * 0.05
+                            // Bars span the mean spacing of the visible samples, so
+                            // retained history off screen does not narrow them.
+                            let visible = state.points.within(range_start, range_end);
+                            let fallback_bar_width = match visible {
+                                [first, .., last] => {
+                                    (last.timestamp_seconds - first.timestamp_seconds)
+                                        / (visible.len() - 1) as f64
+                                }
+                                _ => window * 0.05,
                             };
                             let bar_width_seconds = definition
                                 .bar_width_seconds
                                 .unwrap_or(fallback_bar_width)
                                 .max(window * 0.001);
-                            let baseline_value = definition.baseline.unwrap_or(pane_min);
+                            let (series_axis, series_min) =
+                                match (definition.value_axis.unwrap_or_default(), right_range) {
+                                    (ValueAxisSide::Right, Some((min, max))) => {
+                                        (ValueAxis::new(pane_scale, min, max), min)
+                                    }
+                                    _ => (pane_axis, pane_min),
+                                };
+                            let baseline_value = definition.baseline.unwrap_or(series_min);
+                            let gap_style = definition.gap_style.unwrap_or_default();
+                            let interpolation = definition.interpolation.unwrap_or_default();
+                            let filled = definition.kind == SeriesKind::Area
+                                || (definition.kind == SeriesKind::Line && fill_opacity > 0.0);
+
+                            hit_series.push(HitSeries {
+                                series_id: *series_id,
+                                kind: definition.kind,
+                                color: series_color,
+                                band_lower_id: definition.band_lower_id,
+                                stroke_width,
+                                marker_size,
+                                bar_width_seconds,
+                                baseline_value,
+                                filled,
+                                heatmap_bucket_index: definition.heatmap_bucket_index.unwrap_or(0),
+                                heatmap_bucket_count: definition.heatmap_bucket_count.unwrap_or(1),
+                                value_axis: series_axis,
+                                stack: stack.cloned(),
+                                max_gap_seconds: definition.max_gap_seconds,
+                                interpolation,
+                            });
 
                             if state.needs_rebuild {
                                 if let Some(scene) = self.scene_cache.scene_mut(state.scene_node) {
                                     scene.reset();
 
-                                    let (paths, label_anchor) = {
-                                        let span = state.points.as_slice();
+                                    // Only connected kinds draw the sample past each edge.
+                                    let source = match definition.kind {
+                                        SeriesKind::Line | SeriesKind::Area | SeriesKind::Band => {
+                                            state.points.range(range_start, range_end)
+                                        }
+                                        _ => state.points.within(range_start, range_end),
+                                    };
+                                    let marked = match definition.kind {
+                                        SeriesKind::Line | SeriesKind::Area | SeriesKind::Band => {
+                                            mark_gaps(source, definition.max_gap_seconds)
+                                        }
+                                        _ => Cow::Borrowed(source),
+                                    };
+                                    let span = match definition.kind {
+                                        SeriesKind::Line | SeriesKind::Area => decimate(
+                                            &marked,
+                                            definition.decimation.unwrap_or_default(),
+                                            range_start,
+                                            window,
+                                            pane_plot_area.width.ceil().max(1.0) as usize,
+                                        ),
+                                        _ => Cow::Borrowed(marked.as_ref()),
+                                    };
+                                    state.cached_source_points = source.len() as u64;
+                                    state.cached_rendered_points = span.len() as u64;
+
+                                    let (paths, label_anchor) = if let Some(stack) = stack {
+                                        render_stack_member(
+                                            scene,
+                                            stack,
+                                            &definition,
+                                            series_color,
+                                            &pane_plot_area,
+                                            range_start,
+                                            window,
+                                            &series_axis,
+                                            stroke_width,
+                                            fill_opacity,
+                                            bar_width_seconds,
+                                            baseline_value,
+                                        )
+                                    } else {
+                                        let span = span.as_ref();
                                         match definition.kind {
                                             SeriesKind::Line => render_line_series(
                                                 scene,
@@ -804,11 +1454,12 @@ impl ChartEngine {
                                                 &pane_plot_area,
                                                 range_start,
                                                 window,
-                                                pane_min,
-                                                pane_value_range,
+                                                &series_axis,
                                                 stroke_width,
                                                 fill_opacity,
                                                 baseline_value,
+                                                gap_style,
+                                                interpolation,
                                             ),
                                             SeriesKind::Area => render_area_series(
                                                 scene,
@@ -817,11 +1468,12 @@ impl ChartEngine {
                                                 &pane_plot_area,
                                                 range_start,
                                                 window,
-                                                pane_min,
-                                                pane_value_range,
+                                                &series_axis,
                                                 stroke_width,
                                                 fill_opacity.max(0.05),
                                                 baseline_value,
+                                                gap_style,
+                                                interpolation,
                                             ),
                                             SeriesKind::Scatter => render_scatter_series(
                                                 scene,
@@ -830,8 +1482,7 @@ impl ChartEngine {
                                                 &pane_plot_area,
                                                 range_start,
                                                 window,
-                                                pane_min,
-                                                pane_value_range,
+                                                &series_axis,
                                                 marker_size,
                                             ),
                                             SeriesKind::Bar => render_bar_series(
@@ -841,8 +1492,7 @@ impl ChartEngine {
                                                 &pane_plot_area,
                                                 range_start,
                                                 window,
-                                                pane_min,
-                                                pane_value_range,
+                                                &series_axis,
                                                 bar_width_seconds,
                                                 baseline_value,
                                             ),
@@ -863,29 +1513,46 @@ impl ChartEngine {
                                                         &pane_plot_area,
                                                         range_start,
                                                         window,
-                                                        pane_min,
-                                                        pane_value_range,
+                                                        &series_axis,
                                                         stroke_width,
                                                         band_fill,
+                                                        gap_style,
+                                                        interpolation,
                                                     )
                                                 } else {
                                                     (0, None)
                                                 }
                                             }
+                                            SeriesKind::Candlestick | SeriesKind::Ohlc => {
+                                                render_candle_series(
+                                                    scene,
+                                                    state.candles.within(range_start, range_end),
+                                                    &CandleStyle {
+                                                        kind: definition.kind,
+                                                        up_color: definition.up_color,
+                                                        down_color: definition.down_color,
+                                                        stroke_width,
+                                                        bucket_width_seconds: bar_width_seconds,
+                                                    },
+                                                    &pane_plot_area,
+                                                    range_start,
+                                                    window,
+                                                    &series_axis,
+                                                )
+                                            }
                                             SeriesKind::Heatmap => {
                                                 let bucket_count =
                                                     definition.heatmap_bucket_count.unwrap_or(1);
                                                 render_heatmap_series(
                                                     scene,
                                                     span,
-                                                    series_color,
+                                                    &heatmap_scale(&definition, series_color),
+                                                    state.color_domain.unwrap_or((0.0, 1.0)),
                                                     &pane_plot_area,
                                                     range_start,
                                                     window,
                                                     definition.heatmap_bucket_index.unwrap_or(0),
                                                     bucket_count,
-                                                    pane_min,
-                                                    pane_value_range,
                                                 )
                                             }
                                         }
@@ -898,21 +1565,24 @@ impl ChartEngine {
                             }
 
                             encoded_paths += state.cached_paths;
-                            if let Some(scene) = self.scene_cache.scene(state.scene_node) {
-                                buffer.scene.append(scene, None);
-                            }
+                            source_points += state.cached_source_points;
+                            rendered_points += state.cached_rendered_points;
+                            // Spans overhang the plot by one sample on each side.
+                            state.place_node(&mut self.scene_cache, Some(plot_clip));
+                            placed_series.insert(*series_id);
 
                             if let (Some(label), Some(anchor)) =
                                 (state.style.label.as_deref(), state.label_anchor)
                             {
                                 encoded_paths += draw_series_label(
-                                    &mut buffer.scene,
+                                    &mut foreground,
                                     label,
                                     series_color,
                                     anchor,
                                     chart_width,
                                     chart_height,
                                     &pane_plot_area,
+                                    &self.options.theme,
                                 );
                             }
 
@@ -941,13 +1611,60 @@ impl ChartEngine {
                         }
                     }
 
+                    if let Some(left) = color_bar_left
+                        && let Some((scale, domain)) =
+                            pane_color_bar(&pane_series, &self.series, &self.options)
+                    {
+                        let ticks = compute_labeled_axis_layout(
+                            0.0,
+                            0.0,
+                            domain.0,
+                            domain.1,
+                            5,
+                            ValueScale::Linear,
+                            &self.options.axis_labels,
+                        )
+                        .value_ticks;
+                        let bar = Rect::new(
+                            left,
+                            pane_plot_area.top,
+                            left + COLOR_BAR_WIDTH,
+                            pane_plot_area.bottom(),
+                        );
+                        let text = TextStyle {
+                            color: self.options.theme.label.color,
+                            ..self.options.theme.axis_label.clone()
+                        };
+                        encoded_paths +=
+                            draw_color_bar(&mut foreground, &scale, bar, &ticks, &text);
+                    }
+
+                    encoded_paths += self.annotations.draw_pane(
+                        &mut self.scene_cache,
+                        self.annotation_root,
+                        &mut foreground,
+                        AnnotationLayer::AboveSeries,
+                        &annotation_pane,
+                    );
+
+                    buffer.hit_panes.push(HitPane {
+                        pane_index: pane_snapshots.len(),
+                        plot_area: pane_plot_area,
+                        range_start,
+                        window,
+                        series: hit_series,
+                    });
+
                     pane_snapshots.push(PaneSnapshot {
                         id: pane_id,
                         share_x_with_primary: share_axis,
                         plot_area: pane_plot_area,
                         value_min: pane_min,
                         value_max: pane_max,
+                        right_range,
+                        value_scale: pane_scale,
                         axis_layout,
+                        right_axis_layout,
                         series_ids: pane_series,
                         dirty: pane_dirty,
                     });
@@ -964,12 +1681,68 @@ impl ChartEngine {
                     plot_area,
                     value_min: 0.0,
                     value_max: 1.0,
+                    right_range: None,
+                    value_scale: ValueScale::Linear,
                     axis_layout: None,
+                    right_axis_layout: None,
                     series_ids: Vec::new(),
                     dirty: None,
                 });
             }
 
+            if let Some(mut legend) = legend {
+                if legend_options.placement == LegendPlacement::Floating {
+                    let pane = buffer
+                        .hit_panes
+                        .get(legend_options.floating_pane)
+                        .or(buffer.hit_panes.last())
+                        .map_or(plot_area, |pane| pane.plot_area);
+                    legend.float_in(legend_options.floating_corner, &pane);
+                }
+                encoded_paths += legend.draw(&mut foreground, &self.options.theme);
+                buffer.legend_hits.extend(legend.hits());
+            }
+
+            if let Some(cursor) = self.cursor
+                && let Some(scene) = self.scene_cache.scene_mut(self.overlay_node)
+            {
+                scene.reset();
+                let series = &self.series;
+                encoded_paths += overlay::draw_cursor_overlay(
+                    scene,
+                    cursor,
+                    &buffer.hit_panes,
+                    |series_id| series.get(&series_id).map(SeriesState::span),
+                    |series_id| {
+                        series
+                            .get(&series_id)
+                            .and_then(|state| state.style.label.as_deref())
+                    },
+                    chart_width,
+                    chart_height,
+                    &plot_area,
+                    &self.options.theme,
+                    &self.options.axis_labels,
+                );
+            } else if let Some(scene) = self.scene_cache.scene_mut(self.overlay_node) {
+                scene.reset();
+            }
+
+            for (series_id, state) in &mut self.series {
+                if !placed_series.contains(series_id) {
+                    state.place_node(&mut self.scene_cache, None);
+                }
+            }
+            for (node, scene) in [
+                (self.underlay_node, underlay),
+                (self.foreground_node, foreground),
+            ] {
+                if let Some(target) = self.scene_cache.scene_mut(node) {
+                    *target = scene;
+                }
+            }
+            buffer.scene = self.scene_cache.compose(self.scene_root);
+
             let time_axis_layout = time_axis_pane_index
                 .and_then(|index| pane_snapshots.get(index))
                 .and_then(|pane| pane.axis_layout.as_ref())
@@ -997,11 +1770,15 @@ impl ChartEngine {
                 queue_latency_ms: 0.0,
                 encoded_paths,
                 timestamp: unix_millis(),
+                source_points,
+                rendered_points,
             };
 
             tracing::debug!(
                 version = self.frame_counter,
                 encoded_paths,
+                source_points,
+                rendered_points,
                 cpu_time_ms,
                 width,
                 height,
@@ -1019,7 +1796,9 @@ impl ChartEngine {
 
         &self.frame_buffers[buffer_index]
     }
+
     pub fn render_frame(&mut self, width: u32, height: u32) -> EngineFrame<'_> {
+        self.drain_data_bus();
         let buffer = self.render_internal(width, height);
         EngineFrame {
             version: buffer.version,
@@ -1028,40 +1807,400 @@ impl ChartEngine {
         }
     }
 
-    pub fn diagnostics(&self) -> &DiagnosticsCollector {
-        &self.diagnostics
+    /// Renders a `width` x `height` frame and encodes it without a GPU. Axes drawn by
+    /// host overlays are not part of the engine scene and are not exported, and neither
+    /// is the crosshair cursor overlay.
+    pub fn export_frame(
+        &mut self,
+        width: u32,
+        height: u32,
+        format: ExportFormat,
+        options: &ExportOptions,
+    ) -> Result<Vec<u8>, ExportError> {
+        self.drain_data_bus();
+        let cursor = self.cursor.take();
+        if cursor.is_some() {
+            self.scene_invalidated = true;
+        }
+        let buffer = self.render_internal(width, height);
+        let exported = export_scene(&buffer.scene, width.max(1), height.max(1), format, options);
+        if cursor.is_some() {
+            self.cursor = cursor;
+            self.scene_invalidated = true;
+        }
+        exported
     }
 
-    fn publish_samples_from_ffi(&mut self, samples: &[VelloChartSamplePoint]) {
-        self.ingest_samples(samples.iter().map(|sample| ChartSample {
-            series_id: sample.series_id,
-            timestamp_seconds: sample.timestamp_seconds,
-            value: sample.value,
-        }));
+    /// Serialises series samples, definitions, overrides, the palette, composition
+    /// panes and the viewport into a versioned binary snapshot. Annotations, derived
+    /// series configuration and the data bus are not included.
+    pub fn snapshot(&self) -> Vec<u8> {
+        let mut definitions: Vec<_> = self
+            .series_definitions
+            .iter()
+            .map(|(series_id, definition)| (*series_id, definition.into()))
+            .collect();
+        definitions.sort_by_key(|(series_id, _)| *series_id);
+
+        let mut series: Vec<_> = self
+            .series
+            .iter()
+            .map(|(series_id, state)| snapshot::SnapshotSeries {
+                series_id: *series_id,
+                palette_slot: state.palette_slot as u64,
+                visible: state.visible,
+                label: state.style.label.clone(),
+                stroke_width: state.style.stroke_width,
+                color: state.style.color.map(Into::into),
+                points: state
+                    .span()
+                    .iter()
+                    .map(|point| (point.timestamp_seconds, point.value))
+                    .collect(),
+                candles: state
+                    .candles
+                    .as_slice()
+                    .iter()
+                    .map(|bar| {
+                        (
+                            bar.timestamp_seconds,
+                            bar.open,
+                            bar.high,
+                            bar.low,
+                            bar.close,
+                            bar.volume,
+                        )
+                    })
+                    .collect(),
+            })
+            .collect();
+        series.sort_by_key(|series| series.series_id);
+
+        snapshot::encode(&snapshot::EngineSnapshot {
+            palette: self
+                .options
+                .palette
+                .iter()
+                .map(|color| (*color).into())
+                .collect(),
+            next_palette_slot: self.next_palette_slot as u64,
+            definitions,
+            series,
+            panes: self.composition.panes.iter().map(Into::into).collect(),
+            viewport: self.viewport.stored().into(),
+        })
     }
-}
 
-impl CompositionState {
-    fn resolve(&self, series: &HashMap<u32, SeriesState>) -> Vec<ResolvedPane> {
-        if self.panes.is_empty() {
-            let mut ordered: Vec<_> = series.keys().copied().collect();
-            ordered.sort_unstable();
-            return vec![ResolvedPane {
-                id: "primary".to_string(),
-                height_ratio: 1.0,
-                share_x_with_primary: true,
-                series_ids: ordered,
-            }];
+    /// Replaces the engine state with a snapshot taken by [`Self::snapshot`]. The engine
+    /// is left untouched when the snapshot is rejected. Samples older than the current
+    /// retention are dropped, and derived series are recomputed from their restored
+    /// sources.
+    pub fn restore_snapshot(&mut self, bytes: &[u8]) -> Result<(), SnapshotError> {
+        let snapshot = snapshot::decode(bytes)?;
+        let definitions = snapshot
+            .definitions
+            .into_iter()
+            .map(|(series_id, definition)| Ok((series_id, definition.try_into()?)))
+            .collect::<Result<HashMap<u32, SeriesDefinition>, SnapshotError>>()?;
+        let palette: Vec<Color> = snapshot.palette.into_iter().map(Into::into).collect();
+        let panes: Vec<CompositionPane> = snapshot.panes.into_iter().map(Into::into).collect();
+        let viewport: ViewportRange = snapshot.viewport.try_into()?;
+
+        for (_, state) in self.series.drain() {
+            self.scene_cache.dispose_node(state.scene_node);
+        }
+        self.series_definitions = definitions;
+        self.set_palette(&palette);
+        self.configure_composition(Some(panes));
+
+        let retention = self.options.retention_seconds();
+        for saved in &snapshot.series {
+            let definition = self
+                .series_definitions
+                .get(&saved.series_id)
+                .cloned()
+                .unwrap_or_default();
+            let scene_node = create_series_node(&mut self.scene_cache, self.scene_root);
+            let mut state = SeriesState::new(saved.palette_slot as usize, definition, scene_node);
+            state.style = saved.style();
+            state.visible = saved.visible;
+            for bar in saved.candles() {
+                state.candles.write(bar);
+            }
+            state.replace_points(saved.points(), retention);
+            if retention > 0.0 {
+                state
+                    .candles
+                    .trim_before(state.latest_timestamp - retention);
+            }
+            self.series.insert(saved.series_id, state);
         }
+        self.next_palette_slot = snapshot.next_palette_slot as usize;
+        self.viewport = Viewport::from_stored(viewport);
 
-        let mut resolved = Vec::with_capacity(self.panes.len());
-        let mut assigned = HashSet::new();
+        let derived: Vec<u32> = self.derived.keys().copied().collect();
+        self.rebuild_derived(&derived);
+        self.invalidate_viewport();
+        Ok(())
+    }
 
-        for pane in &self.panes {
-            let ratio = if pane.height_ratio.is_finite() && pane.height_ratio > 0.0 {
-                pane.height_ratio
-            } else {
-                1.0
+    pub fn diagnostics(&self) -> &DiagnosticsCollector {
+        &self.diagnostics
+    }
+
+    /// Adds an annotation under `id`, returning `false` when the id is already taken.
+    pub fn add_annotation(&mut self, id: u32, annotation: Annotation) -> bool {
+        let added = self.annotations.add(id, annotation);
+        self.scene_invalidated |= added;
+        added
+    }
+
+    /// Replaces the annotation stored under `id`, returning `false` when it doesn't exist.
+    pub fn update_annotation(&mut self, id: u32, annotation: Annotation) -> bool {
+        let updated = self.annotations.update(id, annotation);
+        self.scene_invalidated |= updated;
+        updated
+    }
+
+    pub fn remove_annotation(&mut self, id: u32) -> bool {
+        let removed = self.annotations.remove(id, &mut self.scene_cache);
+        self.scene_invalidated |= removed;
+        removed
+    }
+
+    pub fn clear_annotations(&mut self) {
+        self.annotations.clear(&mut self.scene_cache);
+        self.scene_invalidated = true;
+    }
+
+    /// Computes `series_id` from another series, replacing any samples it already has.
+    /// Returns `false` when either output id is already written by a derived series or
+    /// feeds one, when the source is itself derived, when a band operator has no lower
+    /// series id, or when the operator parameters are not [valid](DerivedOperator::is_valid).
+    pub fn add_derived_series(&mut self, series_id: u32, derived: DerivedSeries) -> bool {
+        if !derived.operator.is_valid() {
+            return false;
+        }
+        let lower_id = derived.lower_series_id;
+        let mut outputs = std::iter::once(series_id).chain(lower_id);
+        let conflicts = outputs.any(|id| {
+            id == derived.source_series_id
+                || self.is_derived_output(id)
+                || self
+                    .derived
+                    .values()
+                    .any(|state| state.series.source_series_id == id)
+        });
+        if conflicts
+            || lower_id == Some(series_id)
+            || derived.operator.is_band() != lower_id.is_some()
+            || self.is_derived_output(derived.source_series_id)
+        {
+            return false;
+        }
+
+        self.derived.insert(series_id, DerivedState::new(derived));
+        self.rebuild_derived(&[series_id]);
+        self.scene_invalidated = true;
+        true
+    }
+
+    /// Stops computing `series_id`; the samples it already holds are kept.
+    pub fn remove_derived_series(&mut self, series_id: u32) -> bool {
+        self.derived.remove(&series_id).is_some()
+    }
+
+    pub fn clear_derived_series(&mut self) {
+        self.derived.clear();
+    }
+
+    pub fn set_legend(&mut self, legend: LegendOptions) {
+        if self.options.legend != legend {
+            self.options.legend = legend;
+            self.scene_invalidated = true;
+        }
+    }
+
+    pub fn set_axis_labels(&mut self, axis_labels: AxisLabelOptions) {
+        if self.options.axis_labels != axis_labels {
+            self.options.axis_labels = axis_labels;
+            self.scene_invalidated = true;
+        }
+    }
+
+    /// Restyles the chart chrome. Cached series scenes are kept; annotations are only
+    /// re-encoded when the label size changes.
+    pub fn set_theme(&mut self, theme: ChartTheme) {
+        if self.options.theme != theme {
+            self.options.theme = theme;
+            self.scene_invalidated = true;
+        }
+    }
+
+    pub fn theme(&self) -> &ChartTheme {
+        &self.options.theme
+    }
+
+    /// Returns the series whose legend entry contains `(x, y)` in the last rendered frame.
+    pub fn legend_hit_test(&self, x: f64, y: f64) -> Option<u32> {
+        let buffer = &self.frame_buffers[self.last_buffer];
+        legend::hit_test(&buffer.legend_hits, Point::new(x, y))
+    }
+
+    /// Shows or hides a series. Hidden series keep ingesting samples but are neither drawn
+    /// nor counted towards value ranges; their legend entries stay visible, dimmed.
+    /// Shows or hides a series that has samples; unknown ids are ignored.
+    pub fn set_series_visible(&mut self, series_id: u32, visible: bool) {
+        let Some(state) = self.series.get_mut(&series_id) else {
+            return;
+        };
+        if state.visible == visible {
+            return;
+        }
+        state.visible = visible;
+        // Value ranges shift, so every series re-projects.
+        for state in self.series.values_mut() {
+            state.needs_rebuild = true;
+        }
+        self.scene_invalidated = true;
+    }
+
+    pub fn is_series_visible(&self, series_id: u32) -> bool {
+        self.series
+            .get(&series_id)
+            .is_none_or(|state| state.visible)
+    }
+
+    /// Time range drawn by the most recent data, following live samples unless frozen.
+    pub fn viewport(&self) -> ViewportRange {
+        self.viewport.resolve(
+            latest_timestamp(&self.series).unwrap_or(0.0),
+            self.options.visible_duration_seconds,
+        )
+    }
+
+    /// Toggles live follow mode. Leaving it freezes the view at the current live range.
+    pub fn set_follow_live(&mut self, follow_live: bool) {
+        if self.viewport.follow_live() == follow_live {
+            return;
+        }
+        let latest = latest_timestamp(&self.series).unwrap_or(0.0);
+        self.viewport
+            .set_follow_live(follow_live, latest, self.options.visible_duration_seconds);
+        self.invalidate_viewport();
+    }
+
+    /// Shows `[range_start, range_end]` and stops following live samples.
+    pub fn set_visible_range(&mut self, range_start: f64, range_end: f64) {
+        self.viewport.set_range(range_start, range_end);
+        self.invalidate_viewport();
+    }
+
+    /// Scales the visible duration by `factor` around `anchor_seconds`; factors below one zoom in.
+    pub fn zoom(&mut self, factor: f64, anchor_seconds: f64) {
+        let latest = latest_timestamp(&self.series).unwrap_or(0.0);
+        self.viewport.zoom(
+            factor,
+            anchor_seconds,
+            latest,
+            self.options.visible_duration_seconds,
+        );
+        self.invalidate_viewport();
+    }
+
+    /// Shifts the visible range by `delta_seconds`; negative values scroll back in time.
+    pub fn pan(&mut self, delta_seconds: f64) {
+        let latest = latest_timestamp(&self.series).unwrap_or(0.0);
+        self.viewport
+            .pan(delta_seconds, latest, self.options.visible_duration_seconds);
+        self.invalidate_viewport();
+    }
+
+    /// Changes how much history is kept in memory, trimming immediately when it shrinks.
+    /// Retention never drops below the visible duration.
+    pub fn set_retention_duration(&mut self, retention_seconds: Option<f64>) {
+        self.options.retention_duration_seconds = retention_seconds;
+        let retention = self.options.retention_seconds();
+        let mut trimmed = false;
+        for state in self.series.values_mut() {
+            let cutoff = state.latest_timestamp - retention;
+            if state.points.trim_before(cutoff) + state.candles.trim_before(cutoff) > 0 {
+                state.needs_rebuild = true;
+                trimmed = true;
+            }
+        }
+        if trimmed {
+            self.scene_invalidated = true;
+        }
+    }
+
+    fn invalidate_viewport(&mut self) {
+        // Every series projects against the visible range.
+        for state in self.series.values_mut() {
+            state.needs_rebuild = true;
+        }
+        self.scene_invalidated = true;
+    }
+
+    /// Moves the pointer overlay to `(x, y)`. Only the overlay node is re-encoded;
+    /// cached series scenes are reused as-is.
+    pub fn set_cursor(&mut self, x: f64, y: f64) {
+        let cursor = Point::new(x, y);
+        if self.cursor != Some(cursor) {
+            self.cursor = Some(cursor);
+            self.scene_invalidated = true;
+        }
+    }
+
+    pub fn clear_cursor(&mut self) {
+        if self.cursor.take().is_some() {
+            self.scene_invalidated = true;
+        }
+    }
+
+    /// Finds the drawn sample closest to `(x, y)` in the last rendered frame, considering
+    /// only geometry within `radius` pixels.
+    pub fn hit_test(&self, x: f64, y: f64, radius: f64) -> Option<HitTestResult> {
+        let buffer = &self.frame_buffers[self.last_buffer];
+        hit_test::hit_test(
+            &buffer.hit_panes,
+            |series_id| self.series.get(&series_id).map(SeriesState::span),
+            Point::new(x, y),
+            radius.max(0.0),
+        )
+    }
+
+    fn publish_samples_from_ffi(&mut self, samples: &[VelloChartSamplePoint]) {
+        self.ingest_samples(samples.iter().map(|sample| ChartSample {
+            series_id: sample.series_id,
+            timestamp_seconds: sample.timestamp_seconds,
+            value: sample.value,
+        }));
+    }
+}
+
+impl CompositionState {
+    fn resolve(&self, series: &HashMap<u32, SeriesState>) -> Vec<ResolvedPane> {
+        if self.panes.is_empty() {
+            let mut ordered: Vec<_> = series.keys().copied().collect();
+            ordered.sort_unstable();
+            return vec![ResolvedPane {
+                id: "primary".to_string(),
+                height_ratio: 1.0,
+                share_x_with_primary: true,
+                series_ids: ordered,
+                value_scale: ValueScale::Linear,
+            }];
+        }
+
+        let mut resolved = Vec::with_capacity(self.panes.len());
+        let mut assigned = HashSet::new();
+
+        for pane in &self.panes {
+            let ratio = if pane.height_ratio.is_finite() && pane.height_ratio > 0.0 {
+                pane.height_ratio
+            } else {
+                1.0
             };
             let mut pane_series = Vec::with_capacity(pane.series_ids.len());
             for series_id in &pane.series_ids {
@@ -1076,6 +2215,7 @@ impl CompositionState {
                 height_ratio: ratio,
                 share_x_with_primary: pane.share_x_with_primary,
                 series_ids: pane_series,
+                value_scale: pane.value_scale,
             });
         }
 
@@ -1092,6 +2232,7 @@ impl CompositionState {
                 height_ratio: 1.0,
                 share_x_with_primary: true,
                 series_ids: unassigned,
+                value_scale: ValueScale::Linear,
             }];
         }
 
@@ -1126,6 +2267,7 @@ impl CompositionPane {
         };
 
         let share = value.share_x_with_primary != 0;
+        let value_scale = value_scale_from_ffi(value.value_scale, value.symlog_linear_threshold)?;
 
         let series_ids = if value.series_id_count == 0 {
             Vec::new()
@@ -1143,6 +2285,7 @@ impl CompositionPane {
             height_ratio: ratio,
             share_x_with_primary: share,
             series_ids,
+            value_scale,
         })
     }
 }
@@ -1152,6 +2295,8 @@ struct FrameBuffer {
     stats: FrameStats,
     version: u64,
     metadata: FrameMetadata,
+    hit_panes: Vec<HitPane>,
+    legend_hits: Vec<LegendHit>,
 }
 
 impl FrameBuffer {
@@ -1161,6 +2306,8 @@ impl FrameBuffer {
             stats: FrameStats::default(),
             version: 0,
             metadata: FrameMetadata::default(),
+            hit_panes: Vec::new(),
+            legend_hits: Vec::new(),
         }
     }
 }
@@ -1171,7 +2318,11 @@ struct PaneSnapshot {
     plot_area: PlotArea,
     value_min: f64,
     value_max: f64,
+    /// Padded range of the right axis; `None` when no series in the pane is bound to it.
+    right_range: Option<(f64, f64)>,
+    value_scale: ValueScale,
     axis_layout: Option<AxisLayout>,
+    right_axis_layout: Option<AxisLayout>,
     series_ids: Vec<u32>,
     dirty: Option<DirtyBounds>,
 }
@@ -1182,12 +2333,16 @@ struct PaneMetadataRecord {
     plot_area: PlotArea,
     value_min: f64,
     value_max: f64,
+    value_scale: ValueScale,
     dirty_time_min: f64,
     dirty_time_max: f64,
     dirty_value_min: f64,
     dirty_value_max: f64,
     value_ticks: Vec<VelloChartAxisTickMetadata>,
     value_labels: Vec<CString>,
+    right_range: Option<(f64, f64)>,
+    right_value_ticks: Vec<VelloChartAxisTickMetadata>,
+    right_value_labels: Vec<CString>,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -1197,6 +2352,8 @@ struct SeriesStyle {
     color: Option<Color>,
 }
 
+/// Time/value box touched by changed samples. Every interpolation mode stays inside
+/// the box of the samples it joins, so sample bounds alone cover the redrawn path.
 #[derive(Debug, Clone, Copy, Default)]
 struct DirtyBounds {
     time_min: f64,
@@ -1231,16 +2388,25 @@ impl DirtyBounds {
 }
 
 struct SeriesState {
-    points: Vec<SeriesPoint>,
+    points: SeriesBuffer,
+    /// Full bars of candle series; `points` mirrors their closes.
+    candles: SeriesBuffer<OhlcPoint>,
     latest_timestamp: f64,
     style: SeriesStyle,
     definition: SeriesDefinition,
     palette_slot: usize,
     scene_node: SceneNodeId,
+    /// Plot clip the scene node is composed with; `None` while the node is hidden.
+    node_clip: Option<Rect>,
     cached_paths: u32,
+    cached_source_points: u64,
+    cached_rendered_points: u64,
     label_anchor: Option<Point>,
+    visible: bool,
     needs_rebuild: bool,
     dirty: Option<DirtyBounds>,
+    /// Resolved heatmap colour domain from the last rendered frame.
+    color_domain: Option<(f64, f64)>,
 }
 
 impl fmt::Debug for SeriesState {
@@ -1253,6 +2419,8 @@ impl fmt::Debug for SeriesState {
             .field("palette_slot", &self.palette_slot)
             .field("scene_node", &self.scene_node.index())
             .field("cached_paths", &self.cached_paths)
+            .field("cached_source_points", &self.cached_source_points)
+            .field("cached_rendered_points", &self.cached_rendered_points)
             .field("label_anchor", &self.label_anchor)
             .field("needs_rebuild", &self.needs_rebuild)
             .field("dirty", &self.dirty)
@@ -1373,12 +2541,16 @@ impl FrameMetadata {
                 plot_area: pane.plot_area,
                 value_min: pane.value_min,
                 value_max: pane.value_max,
+                value_scale: pane.value_scale,
                 dirty_time_min: f64::NAN,
                 dirty_time_max: f64::NAN,
                 dirty_value_min: f64::NAN,
                 dirty_value_max: f64::NAN,
                 value_ticks: Vec::new(),
                 value_labels: Vec::new(),
+                right_range: pane.right_range,
+                right_value_ticks: Vec::new(),
+                right_value_labels: Vec::new(),
             };
 
             if let Some(bounds) = pane.dirty.as_ref() {
@@ -1402,15 +2574,31 @@ impl FrameMetadata {
                 }
             }
 
+            if let Some(layout) = pane.right_axis_layout.as_ref() {
+                for tick in &layout.value_ticks {
+                    let label = make_c_string(&tick.label);
+                    let label_len = label.as_bytes().len();
+                    let ptr = label.as_ptr();
+                    record.right_value_ticks.push(VelloChartAxisTickMetadata {
+                        position: tick.position,
+                        label: ptr,
+                        label_len,
+                    });
+                    record.right_value_labels.push(label);
+                }
+            }
+
             self.panes.push(record);
         }
 
         self.pane_structs.clear();
         for record in &self.panes {
+            let (value_scale, value_scale_threshold) = value_scale_to_ffi(record.value_scale);
             self.pane_structs.push(VelloChartPaneMetadata {
                 id: record.id.as_ptr(),
                 id_len: record.id.as_bytes().len(),
                 share_x_with_primary: if record.share_x_with_primary { 1 } else { 0 },
+                value_scale,
                 plot_left: record.plot_area.left,
                 plot_top: record.plot_area.top,
                 plot_width: record.plot_area.width,
@@ -1423,6 +2611,12 @@ impl FrameMetadata {
                 dirty_value_max: record.dirty_value_max,
                 value_ticks: record.value_ticks.as_ptr(),
                 value_tick_count: record.value_ticks.len(),
+                value_scale_threshold,
+                has_right_axis: u32::from(record.right_range.is_some()),
+                right_value_min: record.right_range.map_or(f64::NAN, |(min, _)| min),
+                right_value_max: record.right_range.map_or(f64::NAN, |(_, max)| max),
+                right_value_ticks: record.right_value_ticks.as_ptr(),
+                right_value_tick_count: record.right_value_ticks.len(),
             });
         }
 
@@ -1476,7 +2670,12 @@ impl FrameMetadata {
                     .bar_width_seconds
                     .unwrap_or(fallback_bar_width)
                     .max(window * 0.001);
-                let baseline = definition.baseline.unwrap_or(value_min);
+                let on_right_axis = state.on_right_axis();
+                let axis_min = match pane.right_range {
+                    Some((min, _)) if on_right_axis => min,
+                    _ => pane.value_min,
+                };
+                let baseline = definition.baseline.unwrap_or(axis_min);
                 let label_text = state
                     .style
                     .label
@@ -1501,6 +2700,10 @@ impl FrameMetadata {
                     band_lower_series_id: definition.band_lower_id.unwrap_or(0),
                     heatmap_bucket_index: definition.heatmap_bucket_index.unwrap_or(0),
                     heatmap_bucket_count: definition.heatmap_bucket_count.unwrap_or(0),
+                    visible: u32::from(state.visible),
+                    value_axis: u32::from(on_right_axis),
+                    color_domain_min: state.color_domain.map_or(f64::NAN, |(min, _)| min),
+                    color_domain_max: state.color_domain.map_or(f64::NAN, |(_, max)| max),
                 });
             }
         }
@@ -1541,8 +2744,10 @@ struct VelloChartCompositionPane {
     id_len: usize,
     height_ratio: f64,
     share_x_with_primary: u32,
+    value_scale: u32,
     series_ids: *const u32,
     series_id_count: usize,
+    symlog_linear_threshold: f64,
 }
 
 #[repr(C)]
@@ -1565,6 +2770,28 @@ pub struct VelloChartSeriesDefinition {
     pub band_lower_series_id: u32,
     pub heatmap_bucket_index: u32,
     pub heatmap_bucket_count: u32,
+    pub decimation_mode: u32,
+    pub value_axis: u32,
+    pub up_color: VelloChartColor,
+    pub down_color: VelloChartColor,
+    pub stack_group: u32,
+    pub stack_mode: u32,
+    pub max_gap_seconds: f64,
+    pub gap_style: u32,
+    pub interpolation: u32,
+    /// Named colour map, used when `color_stop_count` is zero.
+    pub color_map: u32,
+    pub color_stops: *const VelloChartColorStop,
+    pub color_stop_count: usize,
+    pub color_domain_min: f64,
+    pub color_domain_max: f64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct VelloChartColorStop {
+    pub offset: f32,
+    pub color: VelloChartColor,
 }
 
 #[repr(C)]
@@ -1572,6 +2799,7 @@ struct VelloChartPaneMetadata {
     id: *const c_char,
     id_len: usize,
     share_x_with_primary: u32,
+    value_scale: u32,
     plot_left: f64,
     plot_top: f64,
     plot_width: f64,
@@ -1584,6 +2812,12 @@ struct VelloChartPaneMetadata {
     dirty_value_max: f64,
     value_ticks: *const VelloChartAxisTickMetadata,
     value_tick_count: usize,
+    value_scale_threshold: f64,
+    has_right_axis: u32,
+    right_value_min: f64,
+    right_value_max: f64,
+    right_value_ticks: *const VelloChartAxisTickMetadata,
+    right_value_tick_count: usize,
 }
 
 #[repr(C)]
@@ -1602,6 +2836,11 @@ struct VelloChartSeriesMetadata {
     band_lower_series_id: u32,
     heatmap_bucket_index: u32,
     heatmap_bucket_count: u32,
+    visible: u32,
+    value_axis: u32,
+    /// Resolved heatmap colour domain; `NaN` for other series kinds.
+    color_domain_min: f64,
+    color_domain_max: f64,
 }
 
 #[repr(C)]
@@ -1717,6 +2956,233 @@ impl SeriesOverrideSpec {
     }
 }
 
+fn derived_series_from_ffi(
+    value: &VelloChartDerivedSeries,
+) -> Result<DerivedSeries, VelloChartEngineStatus> {
+    let window = value.window as usize;
+    let needs_window = matches!(value.operator, 0 | 1 | 4 | 5);
+    if needs_window && window == 0 {
+        set_last_error("Derived series window must be at least one sample");
+        return Err(VelloChartEngineStatus::InvalidArgument);
+    }
+
+    let operator = match value.operator {
+        0 => DerivedOperator::MovingAverage { window },
+        1 => DerivedOperator::ExponentialMovingAverage { period: window },
+        2 => DerivedOperator::Derivative,
+        3 => DerivedOperator::Integral,
+        4 => DerivedOperator::MinMaxEnvelope { window },
+        5 => {
+            if !value.deviations.is_finite() || value.deviations < 0.0 {
+                set_last_error("Bollinger deviations must be finite and non-negative");
+                return Err(VelloChartEngineStatus::InvalidArgument);
+            }
+            DerivedOperator::Bollinger {
+                window,
+                deviations: value.deviations,
+            }
+        }
+        _ => {
+            set_last_error("Unknown derived series operator");
+            return Err(VelloChartEngineStatus::InvalidArgument);
+        }
+    };
+
+    Ok(DerivedSeries {
+        source_series_id: value.source_series_id,
+        operator,
+        lower_series_id: operator.is_band().then_some(value.lower_series_id),
+    })
+}
+
+fn annotation_from_ffi(value: &VelloChartAnnotation) -> Result<Annotation, VelloChartEngineStatus> {
+    if !value.start.is_finite() || !value.end.is_finite() {
+        set_last_error("Annotation coordinates must be finite");
+        return Err(VelloChartEngineStatus::InvalidArgument);
+    }
+
+    let kind = match value.kind {
+        0 => AnnotationKind::ValueLine { value: value.start },
+        1 => AnnotationKind::TimeMarker {
+            timestamp_seconds: value.start,
+        },
+        2 => AnnotationKind::ValueBand {
+            min: value.start.min(value.end),
+            max: value.start.max(value.end),
+        },
+        3 => AnnotationKind::TimeBand {
+            start_seconds: value.start.min(value.end),
+            end_seconds: value.start.max(value.end),
+        },
+        _ => {
+            set_last_error("Unknown annotation kind");
+            return Err(VelloChartEngineStatus::InvalidArgument);
+        }
+    };
+
+    let mut annotation = Annotation::new(kind);
+
+    if !value.stroke_width.is_finite() || value.stroke_width <= 0.0 {
+        set_last_error("Annotation stroke width must be positive and finite");
+        return Err(VelloChartEngineStatus::InvalidArgument);
+    }
+    annotation.stroke_width = value.stroke_width;
+
+    if !value.fill_opacity.is_finite() {
+        set_last_error("Annotation fill opacity must be finite");
+        return Err(VelloChartEngineStatus::InvalidArgument);
+    }
+    annotation.fill_opacity = value.fill_opacity.clamp(0.0, 1.0);
+
+    if value.dash_len > 0 {
+        if value.dash_ptr.is_null() {
+            set_last_error("Null dash pattern pointer passed for annotation");
+            return Err(VelloChartEngineStatus::NullPointer);
+        }
+        let dashes = unsafe { slice::from_raw_parts(value.dash_ptr, value.dash_len) };
+        if dashes.iter().any(|dash| !dash.is_finite() || *dash < 0.0) {
+            set_last_error("Annotation dash lengths must be finite and non-negative");
+            return Err(VelloChartEngineStatus::InvalidArgument);
+        }
+        annotation.dash_pattern = dashes.to_vec();
+    }
+
+    if value.flags & ANNOTATION_FLAG_COLOR_SET != 0 {
+        annotation.color = Some(value.color.into());
+    }
+
+    if value.flags & ANNOTATION_FLAG_LABEL_SET != 0 {
+        annotation.label = Some(string_from_raw(value.label_ptr, value.label_len)?);
+    }
+
+    if value.flags & ANNOTATION_FLAG_PANE_SET != 0 {
+        annotation.pane_id = Some(string_from_raw(value.pane_id_ptr, value.pane_id_len)?);
+    }
+
+    Ok(annotation)
+}
+
+fn line_style_from_ffi(
+    value: &VelloChartLineStyle,
+    name: &str,
+) -> Result<LineStyle, VelloChartEngineStatus> {
+    if !value.width.is_finite() || value.width <= 0.0 {
+        set_last_error(format!("Theme {name} width must be positive and finite"));
+        return Err(VelloChartEngineStatus::InvalidArgument);
+    }
+    let mut style = LineStyle::solid(value.color.into(), value.width);
+    if value.dash_len > 0 {
+        if value.dash_ptr.is_null() {
+            set_last_error(format!("Null dash pattern pointer passed for theme {name}"));
+            return Err(VelloChartEngineStatus::NullPointer);
+        }
+        let dashes = unsafe { slice::from_raw_parts(value.dash_ptr, value.dash_len) };
+        if dashes.iter().any(|dash| !dash.is_finite() || *dash < 0.0) {
+            set_last_error(format!(
+                "Theme {name} dash lengths must be finite and non-negative"
+            ));
+            return Err(VelloChartEngineStatus::InvalidArgument);
+        }
+        style.dashes = dashes.to_vec();
+    }
+    Ok(style)
+}
+
+fn text_style_from_ffi(
+    value: &VelloChartTextStyle,
+    name: &str,
+) -> Result<TextStyle, VelloChartEngineStatus> {
+    if !value.font_size.is_finite() || value.font_size <= 0.0 {
+        set_last_error(format!(
+            "Theme {name} font size must be positive and finite"
+        ));
+        return Err(VelloChartEngineStatus::InvalidArgument);
+    }
+    let family = if value.family_len > 0 {
+        Some(string_from_raw(value.family_ptr, value.family_len)?)
+    } else {
+        None
+    };
+    Ok(TextStyle {
+        family,
+        font_size: value.font_size,
+        color: value.color.into(),
+    })
+}
+
+fn theme_from_ffi(value: &VelloChartTheme) -> Result<ChartTheme, VelloChartEngineStatus> {
+    let callout = &value.callout;
+    let lengths = [
+        callout.padding_x,
+        callout.padding_y,
+        callout.corner_radius,
+        callout.offset_x,
+        callout.gap_y,
+        callout.border_width,
+        value.major_tick_length,
+        value.minor_tick_length,
+    ];
+    if lengths
+        .iter()
+        .any(|length| !length.is_finite() || *length < 0.0)
+    {
+        set_last_error("Theme callout and tick lengths must be finite and non-negative");
+        return Err(VelloChartEngineStatus::InvalidArgument);
+    }
+    if !callout.fill_alpha.is_finite() || !callout.border_alpha.is_finite() {
+        set_last_error("Theme callout opacities must be finite");
+        return Err(VelloChartEngineStatus::InvalidArgument);
+    }
+
+    let major_grid = if value.flags & THEME_FLAG_MAJOR_GRID != 0 {
+        Some(line_style_from_ffi(&value.major_grid, "major grid")?)
+    } else {
+        None
+    };
+    let minor_grid = if value.flags & THEME_FLAG_MINOR_GRID != 0 {
+        Some(line_style_from_ffi(&value.minor_grid, "minor grid")?)
+    } else {
+        None
+    };
+    let axis = if value.flags & THEME_FLAG_AXIS != 0 {
+        Some(AxisStyle {
+            line: line_style_from_ffi(&value.axis, "axis")?,
+            major_tick_length: value.major_tick_length,
+            minor_tick_length: value.minor_tick_length,
+        })
+    } else {
+        None
+    };
+
+    Ok(ChartTheme {
+        background_top: value.background_top.into(),
+        background_bottom: value.background_bottom.into(),
+        frame_border: value.frame_border.into(),
+        plot_background: value.plot_background.into(),
+        plot_border: value.plot_border.into(),
+        major_grid,
+        minor_grid,
+        minor_divisions: value.minor_divisions,
+        axis,
+        axis_label: text_style_from_ffi(&value.axis_label, "axis label")?,
+        label: text_style_from_ffi(&value.label, "label")?,
+        callout: CalloutStyle {
+            padding_x: callout.padding_x,
+            padding_y: callout.padding_y,
+            corner_radius: callout.corner_radius,
+            offset_x: callout.offset_x,
+            gap_y: callout.gap_y,
+            fill_alpha: callout.fill_alpha.clamp(0.0, 1.0),
+            border_alpha: callout.border_alpha.clamp(0.0, 1.0),
+            border_width: callout.border_width,
+        },
+        legend_background: value.legend_background.into(),
+        tooltip_background: value.tooltip_background.into(),
+        panel_border: value.panel_border.into(),
+        crosshair: line_style_from_ffi(&value.crosshair, "crosshair")?,
+    })
+}
+
 impl SeriesDefinitionSpec {
     fn try_from_ffi(value: &VelloChartSeriesDefinition) -> Result<Self, VelloChartEngineStatus> {
         let kind = series_kind_from_u32(value.kind)?;
@@ -1790,6 +3256,120 @@ impl SeriesDefinitionSpec {
             definition.heatmap_bucket_count = Some(value.heatmap_bucket_count);
         }
 
+        if value.flags & SERIES_DEFINITION_FLAG_DECIMATION_SET != 0 {
+            match DecimationMode::from_u32(value.decimation_mode) {
+                Some(mode) => definition.decimation = Some(mode),
+                None => {
+                    set_last_error("Unknown series decimation mode");
+                    return Err(VelloChartEngineStatus::InvalidArgument);
+                }
+            }
+        }
+
+        if value.flags & SERIES_DEFINITION_FLAG_VALUE_AXIS_SET != 0 {
+            definition.value_axis = Some(value_axis_side_from_u32(value.value_axis)?);
+        }
+
+        if value.flags & SERIES_DEFINITION_FLAG_UP_COLOR_SET != 0 {
+            definition.up_color = Some(value.up_color.into());
+        }
+
+        if value.flags & SERIES_DEFINITION_FLAG_DOWN_COLOR_SET != 0 {
+            definition.down_color = Some(value.down_color.into());
+        }
+
+        if value.flags & SERIES_DEFINITION_FLAG_STACK_SET != 0 {
+            match StackMode::from_u32(value.stack_mode) {
+                Some(mode) => {
+                    definition.stack_group = Some(StackGroup {
+                        id: value.stack_group,
+                        mode,
+                    })
+                }
+                None => {
+                    set_last_error("Unknown series stack mode");
+                    return Err(VelloChartEngineStatus::InvalidArgument);
+                }
+            }
+        }
+
+        if value.flags & SERIES_DEFINITION_FLAG_MAX_GAP_SET != 0 {
+            if !value.max_gap_seconds.is_finite() || value.max_gap_seconds <= 0.0 {
+                set_last_error("Series max gap must be positive and finite");
+                return Err(VelloChartEngineStatus::InvalidArgument);
+            }
+            definition.max_gap_seconds = Some(value.max_gap_seconds);
+        }
+
+        if value.flags & SERIES_DEFINITION_FLAG_GAP_STYLE_SET != 0 {
+            match GapStyle::from_u32(value.gap_style) {
+                Some(style) => definition.gap_style = Some(style),
+                None => {
+                    set_last_error("Unknown series gap style");
+                    return Err(VelloChartEngineStatus::InvalidArgument);
+                }
+            }
+        }
+
+        if value.flags & SERIES_DEFINITION_FLAG_INTERPOLATION_SET != 0 {
+            match Interpolation::from_u32(value.interpolation) {
+                Some(mode) => definition.interpolation = Some(mode),
+                None => {
+                    set_last_error("Unknown series interpolation mode");
+                    return Err(VelloChartEngineStatus::InvalidArgument);
+                }
+            }
+        }
+
+        if value.flags & SERIES_DEFINITION_FLAG_COLOR_MAP_SET != 0 {
+            let scale = if value.color_stop_count > 0 {
+                if value.color_stops.is_null() {
+                    set_last_error("Null colour stop pointer passed to chart engine");
+                    return Err(VelloChartEngineStatus::NullPointer);
+                }
+                let stops =
+                    unsafe { slice::from_raw_parts(value.color_stops, value.color_stop_count) }
+                        .iter()
+                        .map(|stop| (stop.offset, Color::from(stop.color)))
+                        .collect();
+                ColorScale::from_stops(stops)
+            } else {
+                ColorMap::from_u32(value.color_map).map(ColorScale::named)
+            };
+            match scale {
+                Some(scale) => definition.color_scale = Some(scale),
+                None => {
+                    set_last_error(
+                        "Colour map must be a known map or at least two ordered stops within [0, 1]",
+                    );
+                    return Err(VelloChartEngineStatus::InvalidArgument);
+                }
+            }
+        }
+
+        if value.flags & SERIES_DEFINITION_FLAG_COLOR_DOMAIN_MIN_SET != 0 {
+            if !value.color_domain_min.is_finite() {
+                set_last_error("Colour domain minimum must be finite");
+                return Err(VelloChartEngineStatus::InvalidArgument);
+            }
+            definition.color_domain_min = Some(value.color_domain_min);
+        }
+
+        if value.flags & SERIES_DEFINITION_FLAG_COLOR_DOMAIN_MAX_SET != 0 {
+            if !value.color_domain_max.is_finite() {
+                set_last_error("Colour domain maximum must be finite");
+                return Err(VelloChartEngineStatus::InvalidArgument);
+            }
+            definition.color_domain_max = Some(value.color_domain_max);
+        }
+
+        if let (Some(min), Some(max)) = (definition.color_domain_min, definition.color_domain_max)
+            && min >= max
+        {
+            set_last_error("Colour domain minimum must be less than its maximum");
+            return Err(VelloChartEngineStatus::InvalidArgument);
+        }
+
         Ok(Self {
             series_id: value.series_id,
             definition,
@@ -1797,6 +3377,7 @@ impl SeriesDefinitionSpec {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum AddOutcome {
     Appended,
     Inserted,
@@ -1807,68 +3388,151 @@ enum AddOutcome {
 impl SeriesState {
     fn new(palette_slot: usize, definition: SeriesDefinition, scene_node: SceneNodeId) -> Self {
         Self {
-            points: Vec::with_capacity(1_024),
+            points: SeriesBuffer::with_capacity(1_024),
+            candles: SeriesBuffer::default(),
             latest_timestamp: 0.0,
             style: SeriesStyle::default(),
             definition,
             palette_slot,
             scene_node,
+            node_clip: None,
             cached_paths: 0,
+            cached_source_points: 0,
+            cached_rendered_points: 0,
             label_anchor: None,
+            visible: true,
             needs_rebuild: true,
             dirty: None,
+            color_domain: None,
         }
     }
 
+    /// Shows the scene node clipped to `clip`, or hides it. Placement changes dirty the
+    /// scene root, so they are only applied when something changed.
+    fn place_node(&mut self, cache: &mut SceneGraphCache, clip: Option<Rect>) {
+        if self.node_clip == clip {
+            return;
+        }
+        match clip {
+            Some(clip) => {
+                cache.set_clip(self.scene_node, &clip);
+                cache.set_opacity(self.scene_node, 1.0);
+            }
+            None => cache.set_opacity(self.scene_node, 0.0),
+        }
+        self.node_clip = clip;
+    }
+
     fn set_definition(&mut self, definition: SeriesDefinition) -> Option<DirtyBounds> {
         self.definition = definition;
+        self.color_domain = None;
         self.needs_rebuild = true;
         self.mark_full_dirty()
     }
 
     fn add(&mut self, sample: SeriesPoint, window_seconds: f64) -> AddOutcome {
-        let outcome = match self
-            .points
-            .binary_search_by(|point| point.timestamp_seconds.total_cmp(&sample.timestamp_seconds))
-        {
-            Ok(index) => {
-                if (self.points[index].value - sample.value).abs() < f64::EPSILON {
-                    return AddOutcome::Unchanged;
-                }
-
-                self.points[index] = sample;
-                AddOutcome::Updated
-            }
-            Err(index) => {
-                let outcome = if index == self.points.len() {
-                    AddOutcome::Appended
-                } else {
-                    AddOutcome::Inserted
-                };
-                self.points.insert(index, sample);
-                outcome
-            }
-        };
+        let outcome = self.points.write(sample);
+        if outcome == AddOutcome::Unchanged {
+            return outcome;
+        }
 
         self.latest_timestamp = self.latest_timestamp.max(sample.timestamp_seconds);
-        self.extend_dirty(sample.timestamp_seconds, sample.value);
+        // Gap markers have no value to bound; the scene cache still tracks their time.
+        if sample.value.is_finite() {
+            self.extend_dirty(sample.timestamp_seconds, sample.value);
+        }
         self.needs_rebuild = true;
 
         if window_seconds > 0.0 && !self.points.is_empty() {
+            self.points
+                .trim_before(self.latest_timestamp - window_seconds);
+        }
+
+        outcome
+    }
+
+    fn add_candle(&mut self, bar: OhlcPoint, window_seconds: f64) -> AddOutcome {
+        let outcome = self.candles.write(bar);
+        if outcome == AddOutcome::Unchanged {
+            return outcome;
+        }
+
+        self.points.write(SeriesPoint {
+            timestamp_seconds: bar.timestamp_seconds,
+            value: bar.close,
+        });
+        self.latest_timestamp = self.latest_timestamp.max(bar.timestamp_seconds);
+        self.extend_dirty(bar.timestamp_seconds, bar.low);
+        self.extend_dirty(bar.timestamp_seconds, bar.high);
+        self.needs_rebuild = true;
+
+        if window_seconds > 0.0 {
             let cutoff = self.latest_timestamp - window_seconds;
-            let retain_start = self
-                .points
-                .partition_point(|point| point.timestamp_seconds < cutoff);
-            if retain_start > 0 {
-                self.points.drain(..retain_start);
-            }
+            self.points.trim_before(cutoff);
+            self.candles.trim_before(cutoff);
         }
 
         outcome
     }
 
     fn span(&self) -> &[SeriesPoint] {
-        &self.points
+        self.points.as_slice()
+    }
+
+    /// Replaces every sample, returning the bounds covering both the old and the new
+    /// samples.
+    fn replace_points(
+        &mut self,
+        points: impl IntoIterator<Item = SeriesPoint>,
+        window_seconds: f64,
+    ) -> Option<DirtyBounds> {
+        let previous = self.mark_full_dirty();
+        self.points.clear();
+        for point in points {
+            self.points.write(point);
+            self.latest_timestamp = self.latest_timestamp.max(point.timestamp_seconds);
+        }
+        if window_seconds > 0.0 {
+            self.points
+                .trim_before(self.latest_timestamp - window_seconds);
+        }
+        self.needs_rebuild = true;
+
+        let mut bounds = self.mark_full_dirty();
+        if let Some(previous) = previous {
+            bounds.get_or_insert(previous).merge(&previous);
+        }
+        self.dirty = bounds;
+        bounds
+    }
+
+    /// Value range of the samples inside `[range_start, range_end]`, spanning the full
+    /// high/low of candle series. `None` when nothing is visible.
+    fn visible_bounds(
+        &self,
+        range_start: f64,
+        range_end: f64,
+        scale: ValueScale,
+    ) -> Option<(f64, f64)> {
+        if matches!(
+            self.definition.kind,
+            SeriesKind::Candlestick | SeriesKind::Ohlc
+        ) && let Some(bounds) = candle_bounds(self.candles.within(range_start, range_end), scale)
+        {
+            return Some(bounds);
+        }
+
+        let span = self.points.within(range_start, range_end);
+        (!span.is_empty()).then(|| value_bounds(span, scale))
+    }
+
+    fn on_right_axis(&self) -> bool {
+        self.definition.value_axis == Some(ValueAxisSide::Right)
+    }
+
+    /// Samples drawn for `[range_start, range_end]`, including one past each edge.
+    fn visible_span(&self, range_start: f64, range_end: f64) -> &[SeriesPoint] {
+        self.points.range(range_start, range_end)
     }
 
     fn take_dirty(&mut self) -> Option<DirtyBounds> {
@@ -1876,10 +3540,11 @@ impl SeriesState {
     }
 
     fn mark_full_dirty(&mut self) -> Option<DirtyBounds> {
-        if let (Some(first), Some(last)) = (self.points.first(), self.points.last()) {
+        let points = self.points.as_slice();
+        if let (Some(first), Some(last)) = (points.first(), points.last()) {
             let mut bounds = DirtyBounds::new(first.timestamp_seconds, first.value);
             bounds.expand(last.timestamp_seconds, last.value);
-            for point in &self.points {
+            for point in points {
                 bounds.expand(point.timestamp_seconds, point.value);
             }
             self.dirty = Some(bounds);
@@ -1898,13 +3563,215 @@ impl SeriesState {
     }
 }
 
+/// Stacks the visible line, area and bar members of each stack group in the pane, in
+/// pane order. A change to any member rebuilds the whole group, since every member
+/// above it moves with it.
+fn stack_pane(
+    pane_series: &[u32],
+    series: &mut HashMap<u32, SeriesState>,
+    range_start: f64,
+    range_end: f64,
+) -> HashMap<u32, StackedSpan> {
+    let mut groups: Vec<(StackGroup, Vec<u32>)> = Vec::new();
+    for series_id in pane_series {
+        let Some(state) = series.get(series_id) else {
+            continue;
+        };
+        let Some(group) = state.definition.stack_group else {
+            continue;
+        };
+        if !state.visible
+            || !matches!(
+                state.definition.kind,
+                SeriesKind::Line | SeriesKind::Area | SeriesKind::Bar
+            )
+        {
+            continue;
+        }
+        match groups
+            .iter_mut()
+            .find(|(existing, _)| existing.id == group.id)
+        {
+            Some((_, members)) => members.push(*series_id),
+            None => groups.push((group, vec![*series_id])),
+        }
+    }
+
+    let mut stacks = HashMap::new();
+    for (group, members) in groups {
+        if members
+            .iter()
+            .any(|id| series.get(id).is_some_and(|state| state.needs_rebuild))
+        {
+            for id in &members {
+                if let Some(state) = series.get_mut(id) {
+                    state.needs_rebuild = true;
+                }
+            }
+        }
+
+        let marked: Vec<Cow<'_, [SeriesPoint]>> = members
+            .iter()
+            .map(|id| match series.get(id) {
+                Some(state) => mark_gaps(
+                    state.visible_span(range_start, range_end),
+                    state.definition.max_gap_seconds,
+                ),
+                None => Cow::Borrowed(&[][..]),
+            })
+            .collect();
+        let spans: Vec<&[SeriesPoint]> = marked.iter().map(AsRef::as_ref).collect();
+        stacks.extend(members.into_iter().zip(stack_spans(&spans, group.mode)));
+    }
+    stacks
+}
+
+/// Bars span their slice of the stack and filled series shade it; unfilled lines trace
+/// only the stacked total.
+fn render_stack_member(
+    scene: &mut Scene,
+    stack: &StackedSpan,
+    definition: &SeriesDefinition,
+    color: Color,
+    plot_area: &PlotArea,
+    range_start: f64,
+    window: f64,
+    value_axis: &ValueAxis,
+    stroke_width: f64,
+    fill_opacity: f32,
+    bar_width_seconds: f64,
+    baseline_value: f64,
+) -> (u32, Option<Point>) {
+    match definition.kind {
+        SeriesKind::Bar => render_stacked_bars(
+            scene,
+            stack,
+            color,
+            plot_area,
+            range_start,
+            window,
+            value_axis,
+            bar_width_seconds,
+        ),
+        SeriesKind::Line if fill_opacity <= 0.0 => render_line_series(
+            scene,
+            &stack.upper,
+            color,
+            plot_area,
+            range_start,
+            window,
+            value_axis,
+            stroke_width,
+            0.0,
+            baseline_value,
+            definition.gap_style.unwrap_or_default(),
+            definition.interpolation.unwrap_or_default(),
+        ),
+        _ => render_band_series(
+            scene,
+            &stack.upper,
+            &stack.lower,
+            color,
+            plot_area,
+            range_start,
+            window,
+            value_axis,
+            stroke_width,
+            fill_opacity.max(0.05),
+            definition.gap_style.unwrap_or_default(),
+            definition.interpolation.unwrap_or_default(),
+        ),
+    }
+}
+
+fn legend_entries(
+    composition: &CompositionState,
+    series: &HashMap<u32, SeriesState>,
+    options: &EngineOptions,
+) -> Vec<LegendEntry> {
+    let band_lower_ids: HashSet<u32> = series
+        .values()
+        .filter_map(|state| state.definition.band_lower_id)
+        .collect();
+
+    composition
+        .resolve(series)
+        .into_iter()
+        .flat_map(|pane| pane.series_ids)
+        .filter(|series_id| !band_lower_ids.contains(series_id))
+        .filter_map(|series_id| {
+            let state = series.get(&series_id)?;
+            Some(LegendEntry {
+                series_id,
+                kind: state.definition.kind,
+                color: state
+                    .style
+                    .color
+                    .unwrap_or_else(|| color_for_series(options, state.palette_slot)),
+                label: state
+                    .style
+                    .label
+                    .clone()
+                    .unwrap_or_else(|| format!("Series {series_id}")),
+                visible: state.visible,
+            })
+        })
+        .collect()
+}
+
+/// Creates a hidden series node under `root`; rendering shows it clipped to its pane.
+fn create_series_node(cache: &mut SceneGraphCache, root: SceneNodeId) -> SceneNodeId {
+    let node = cache.create_node(Some(root));
+    cache.set_z_index(node, SERIES_Z);
+    cache.set_opacity(node, 0.0);
+    node
+}
+
+fn same_plot_area(a: &PlotArea, b: &PlotArea) -> bool {
+    a.left == b.left && a.top == b.top && a.width == b.width && a.height == b.height
+}
+
+fn latest_timestamp(series: &HashMap<u32, SeriesState>) -> Option<f64> {
+    series
+        .values()
+        .filter(|state| !state.points.is_empty())
+        .map(|state| state.latest_timestamp)
+        .reduce(f64::max)
+}
+
+/// Unclamped: samples outside the window land beyond the plot edges, where the plot
+/// clip hides them.
 fn project_time(timestamp: f64, range_start: f64, window: f64, plot_area: &PlotArea) -> f64 {
-    let normalized = ((timestamp - range_start) / window).clamp(0.0, 1.0);
+    let normalized = (timestamp - range_start) / window;
     plot_area.left + normalized * plot_area.width
 }
 
-fn project_value(value: f64, value_min: f64, value_range: f64, plot_area: &PlotArea) -> f64 {
-    let normalized = ((value - value_min) / value_range).clamp(0.0, 1.0);
+/// Value range of a pane together with the scale used to lay it out.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ValueAxis {
+    scale: ValueScale,
+    scaled_min: f64,
+    scaled_range: f64,
+}
+
+impl ValueAxis {
+    fn new(scale: ValueScale, value_min: f64, value_max: f64) -> Self {
+        let scaled_min = scale.transform(value_min);
+        let scaled_range = (scale.transform(value_max) - scaled_min).max(f64::EPSILON);
+        Self {
+            scale,
+            scaled_min,
+            scaled_range,
+        }
+    }
+
+    fn normalize(&self, value: f64) -> f64 {
+        (self.scale.transform(value) - self.scaled_min) / self.scaled_range
+    }
+}
+
+fn project_value(value: f64, value_axis: &ValueAxis, plot_area: &PlotArea) -> f64 {
+    let normalized = value_axis.normalize(value).clamp(0.0, 1.0);
     plot_area.top + (1.0 - normalized) * plot_area.height
 }
 
@@ -1913,11 +3780,10 @@ fn project_point(
     range_start: f64,
     window: f64,
     plot_area: &PlotArea,
-    value_min: f64,
-    value_range: f64,
+    value_axis: &ValueAxis,
 ) -> Point {
     let x = project_time(sample.timestamp_seconds, range_start, window, plot_area);
-    let y = project_value(sample.value, value_min, value_range, plot_area);
+    let y = project_value(sample.value, value_axis, plot_area);
     Point::new(x, y)
 }
 
@@ -1928,108 +3794,95 @@ fn render_line_series(
     plot_area: &PlotArea,
     range_start: f64,
     window: f64,
-    value_min: f64,
-    value_range: f64,
+    value_axis: &ValueAxis,
     stroke_width: f64,
     fill_opacity: f32,
     baseline_value: f64,
+    gap_style: GapStyle,
+    interpolation: Interpolation,
 ) -> (u32, Option<Point>) {
-    if span.is_empty() {
+    let runs = finite_runs(span);
+    if runs.is_empty() {
         return (0, None);
     }
 
-    if span.len() == 1 {
-        let point = project_point(
-            &span[0],
-            range_start,
-            window,
-            plot_area,
-            value_min,
-            value_range,
-        );
-        let radius = (stroke_width.max(MIN_STROKE_WIDTH)) * 0.5;
-        let circle = Circle::new(point, radius);
-        scene.fill(
-            Fill::NonZero,
-            Affine::IDENTITY,
-            &Brush::Solid(color),
-            None,
-            &circle,
-        );
-        return (1, Some(point));
-    }
-
-    let baseline_y = project_value(baseline_value, value_min, value_range, plot_area);
+    let points = project_span(span, range_start, window, plot_area, value_axis);
+    let stroke_width = stroke_width.max(MIN_STROKE_WIDTH);
+    let baseline_y = project_value(baseline_value, value_axis, plot_area);
     let fill_alpha = fill_opacity.clamp(0.0, 1.0);
 
     let mut path = BezPath::new();
     let mut fill_path = BezPath::new();
     let mut area_top = plot_area.bottom();
-    let mut last_point = None;
-
-    for (index, sample) in span.iter().enumerate() {
-        let point = project_point(
-            sample,
-            range_start,
-            window,
-            plot_area,
-            value_min,
-            value_range,
-        );
-        if index == 0 {
-            path.move_to(point);
-            if fill_alpha > 0.0 {
-                fill_path.move_to(Point::new(point.x, baseline_y));
-                fill_path.line_to(point);
-            }
-        } else {
-            path.line_to(point);
-            if fill_alpha > 0.0 {
-                fill_path.line_to(point);
-            }
-        }
-        area_top = area_top.min(point.y);
-        last_point = Some(point);
-    }
-
     let mut encoded = 0;
 
-    if fill_alpha > 0.0 {
-        if let Some(last) = last_point {
-            fill_path.line_to(Point::new(last.x, baseline_y));
-            fill_path.close_path();
-
-            let gradient = Gradient::new_linear(
-                Point::new(0.0, area_top.min(baseline_y)),
-                Point::new(0.0, baseline_y.max(plot_area.bottom())),
-            )
-            .with_stops([
-                ColorStop {
-                    offset: 0.0,
-                    color: DynamicColor::from_alpha_color(color.with_alpha(fill_alpha)),
-                },
-                ColorStop {
-                    offset: 1.0,
-                    color: DynamicColor::from_alpha_color(color.with_alpha(0.0)),
-                },
-            ]);
-
+    for run in &runs {
+        let run_points = &points[run.clone()];
+        if let [point] = run_points {
+            // An isolated sample has no segment to stroke, so it is drawn as a dot.
             scene.fill(
                 Fill::NonZero,
                 Affine::IDENTITY,
-                &Brush::Gradient(gradient),
+                &Brush::Solid(color),
                 None,
-                &fill_path,
+                &Circle::new(*point, stroke_width * 0.5),
             );
             encoded += 1;
+            continue;
+        }
+
+        interpolation.trace(&mut path, run_points, false);
+        if fill_alpha > 0.0 {
+            fill_path.move_to(Point::new(run_points[0].x, baseline_y));
+            interpolation.trace(&mut fill_path, run_points, true);
+            fill_path.line_to(Point::new(run_points[run_points.len() - 1].x, baseline_y));
+            fill_path.close_path();
         }
+        area_top = run_points
+            .iter()
+            .fold(area_top, |top, point| top.min(point.y));
     }
 
-    let stroke = Stroke::new(stroke_width.max(MIN_STROKE_WIDTH));
-    scene.stroke(&stroke, Affine::IDENTITY, &color, None, &path);
-    encoded += 1;
+    if !fill_path.elements().is_empty() {
+        let gradient = Gradient::new_linear(
+            Point::new(0.0, area_top.min(baseline_y)),
+            Point::new(0.0, baseline_y.max(plot_area.bottom())),
+        )
+        .with_stops([
+            ColorStop {
+                offset: 0.0,
+                color: DynamicColor::from_alpha_color(color.with_alpha(fill_alpha)),
+            },
+            ColorStop {
+                offset: 1.0,
+                color: DynamicColor::from_alpha_color(color.with_alpha(0.0)),
+            },
+        ]);
 
-    (encoded, last_point)
+        scene.fill(
+            Fill::NonZero,
+            Affine::IDENTITY,
+            &Brush::Gradient(gradient),
+            None,
+            &fill_path,
+        );
+        encoded += 1;
+    }
+
+    if !path.elements().is_empty() {
+        let stroke = Stroke::new(stroke_width);
+        scene.stroke(&stroke, Affine::IDENTITY, &color, None, &path);
+        encoded += 1;
+    }
+
+    if gap_style == GapStyle::Dashed {
+        encoded += draw_gap_bridges(scene, &points, &runs, color, stroke_width);
+    }
+
+    (
+        encoded,
+        last_visible_point(span, &points, &runs, range_start, window),
+    )
 }
 
 fn render_area_series(
@@ -2039,65 +3892,89 @@ fn render_area_series(
     plot_area: &PlotArea,
     range_start: f64,
     window: f64,
-    value_min: f64,
-    value_range: f64,
+    value_axis: &ValueAxis,
     stroke_width: f64,
     fill_opacity: f32,
     baseline_value: f64,
+    gap_style: GapStyle,
+    interpolation: Interpolation,
 ) -> (u32, Option<Point>) {
-    if span.is_empty() {
+    let runs = finite_runs(span);
+    if runs.is_empty() {
         return (0, None);
     }
 
-    let baseline_y = project_value(baseline_value, value_min, value_range, plot_area);
+    let points = project_span(span, range_start, window, plot_area, value_axis);
+    let baseline_y = project_value(baseline_value, value_axis, plot_area);
     let fill_alpha = fill_opacity.clamp(0.0, 1.0);
     let mut fill_path = BezPath::new();
     let mut outline = BezPath::new();
-    let mut last_point = None;
-
-    for (index, sample) in span.iter().enumerate() {
-        let point = project_point(
-            sample,
-            range_start,
-            window,
-            plot_area,
-            value_min,
-            value_range,
-        );
-        if index == 0 {
-            fill_path.move_to(Point::new(point.x, baseline_y));
-            fill_path.line_to(point);
-            outline.move_to(point);
-        } else {
-            fill_path.line_to(point);
-            outline.line_to(point);
-        }
-        last_point = Some(point);
-    }
-
-    let mut encoded = 0;
 
-    if let Some(last) = last_point {
+    // Each run closes its own fill back to the baseline.
+    for run in &runs {
+        let run_points = &points[run.clone()];
+        let (first, last) = (run_points[0], run_points[run_points.len() - 1]);
+        fill_path.move_to(Point::new(first.x, baseline_y));
+        interpolation.trace(&mut fill_path, run_points, true);
+        interpolation.trace(&mut outline, run_points, false);
         fill_path.line_to(Point::new(last.x, baseline_y));
         fill_path.close_path();
+    }
 
-        scene.fill(
-            Fill::NonZero,
-            Affine::IDENTITY,
-            &Brush::Solid(color.with_alpha(fill_alpha)),
-            None,
-            &fill_path,
-        );
+    scene.fill(
+        Fill::NonZero,
+        Affine::IDENTITY,
+        &Brush::Solid(color.with_alpha(fill_alpha)),
+        None,
+        &fill_path,
+    );
+    let mut encoded = 1;
+
+    if stroke_width > 0.0 {
+        let stroke_width = stroke_width.max(MIN_STROKE_WIDTH);
+        let stroke = Stroke::new(stroke_width);
+        scene.stroke(&stroke, Affine::IDENTITY, &color, None, &outline);
         encoded += 1;
 
-        if stroke_width > 0.0 {
-            let stroke = Stroke::new(stroke_width.max(MIN_STROKE_WIDTH));
-            scene.stroke(&stroke, Affine::IDENTITY, &color, None, &outline);
-            encoded += 1;
+        if gap_style == GapStyle::Dashed {
+            encoded += draw_gap_bridges(scene, &points, &runs, color, stroke_width);
         }
     }
 
-    (encoded, last_point)
+    (
+        encoded,
+        last_visible_point(span, &points, &runs, range_start, window),
+    )
+}
+
+fn project_span(
+    span: &[SeriesPoint],
+    range_start: f64,
+    window: f64,
+    plot_area: &PlotArea,
+    value_axis: &ValueAxis,
+) -> Vec<Point> {
+    span.iter()
+        .map(|sample| project_point(sample, range_start, window, plot_area, value_axis))
+        .collect()
+}
+
+/// Label anchor of a connected series: its newest drawn sample inside the window, so
+/// the overhang past the right edge never carries the label.
+fn last_visible_point(
+    span: &[SeriesPoint],
+    points: &[Point],
+    runs: &[Range<usize>],
+    range_start: f64,
+    window: f64,
+) -> Option<Point> {
+    runs.iter()
+        .rev()
+        .flat_map(|run| run.clone().rev())
+        .find(|&index| {
+            (0.0..=1.0).contains(&((span[index].timestamp_seconds - range_start) / window))
+        })
+        .map(|index| points[index])
 }
 
 fn render_scatter_series(
@@ -2107,8 +3984,7 @@ fn render_scatter_series(
     plot_area: &PlotArea,
     range_start: f64,
     window: f64,
-    value_min: f64,
-    value_range: f64,
+    value_axis: &ValueAxis,
     marker_size: f64,
 ) -> (u32, Option<Point>) {
     if span.is_empty() {
@@ -2119,14 +3995,7 @@ fn render_scatter_series(
     let mut last_point = None;
 
     for sample in span {
-        let point = project_point(
-            sample,
-            range_start,
-            window,
-            plot_area,
-            value_min,
-            value_range,
-        );
+        let point = project_point(sample, range_start, window, plot_area, value_axis);
         let circle = Circle::new(point, radius);
         scene.fill(
             Fill::NonZero,
@@ -2148,8 +4017,7 @@ fn render_bar_series(
     plot_area: &PlotArea,
     range_start: f64,
     window: f64,
-    value_min: f64,
-    value_range: f64,
+    value_axis: &ValueAxis,
     bar_width_seconds: f64,
     baseline_value: f64,
 ) -> (u32, Option<Point>) {
@@ -2157,21 +4025,14 @@ fn render_bar_series(
         return (0, None);
     }
 
-    let baseline_y = project_value(baseline_value, value_min, value_range, plot_area);
+    let baseline_y = project_value(baseline_value, value_axis, plot_area);
     let normalized_width = (bar_width_seconds / window).clamp(0.0, 1.0);
     let half_width = (normalized_width * plot_area.width).max(1.0) * 0.5;
     let mut last_point = None;
     let mut encoded = 0;
 
     for sample in span {
-        let point = project_point(
-            sample,
-            range_start,
-            window,
-            plot_area,
-            value_min,
-            value_range,
-        );
+        let point = project_point(sample, range_start, window, plot_area, value_axis);
         let x0 = (point.x - half_width).max(plot_area.left);
         let x1 = (point.x + half_width).min(plot_area.right());
         let (top, bottom) = if point.y < baseline_y {
@@ -2202,116 +4063,101 @@ fn render_band_series(
     plot_area: &PlotArea,
     range_start: f64,
     window: f64,
-    value_min: f64,
-    value_range: f64,
+    value_axis: &ValueAxis,
     stroke_width: f64,
     fill_opacity: f32,
+    gap_style: GapStyle,
+    interpolation: Interpolation,
 ) -> (u32, Option<Point>) {
     let count = upper_span.len().min(lower_span.len());
-    if count == 0 {
+    let upper_span = &upper_span[..count];
+    let lower_span = &lower_span[..count];
+    // A gap on either edge breaks the band.
+    let runs: Vec<_> = gaps::runs(count, |index| {
+        upper_span[index].value.is_finite() && lower_span[index].value.is_finite()
+    })
+    .into_iter()
+    .filter(|run| run.len() >= 2)
+    .collect();
+    if runs.is_empty() {
         return (0, None);
     }
 
-    let mut upper_points = Vec::with_capacity(count);
-    let mut lower_points = Vec::with_capacity(count);
+    let upper_points = project_span(upper_span, range_start, window, plot_area, value_axis);
+    let lower_points = project_span(lower_span, range_start, window, plot_area, value_axis);
+    let fill_alpha = if fill_opacity > 0.0 {
+        fill_opacity.clamp(0.0, 1.0)
+    } else {
+        0.25
+    };
 
-    for index in 0..count {
-        let upper = project_point(
-            &upper_span[index],
-            range_start,
-            window,
-            plot_area,
-            value_min,
-            value_range,
-        );
-        let lower = project_point(
-            &lower_span[index],
-            range_start,
-            window,
-            plot_area,
-            value_min,
-            value_range,
-        );
-        upper_points.push(upper);
-        lower_points.push(lower);
+    let mut fill_path = BezPath::new();
+    let mut upper_path = BezPath::new();
+    let mut lower_path = BezPath::new();
+    for run in &runs {
+        let upper = &upper_points[run.clone()];
+        let lower = &lower_points[run.clone()];
+
+        interpolation.trace(&mut fill_path, upper, false);
+        interpolation.trace_reversed(&mut fill_path, lower);
+        fill_path.close_path();
+
+        interpolation.trace(&mut upper_path, upper, false);
+        interpolation.trace(&mut lower_path, lower, false);
     }
 
     let mut encoded = 0;
-    let anchor = upper_points.last().copied();
+    if fill_alpha > 0.0 {
+        scene.fill(
+            Fill::NonZero,
+            Affine::IDENTITY,
+            &Brush::Solid(color.with_alpha(fill_alpha)),
+            None,
+            &fill_path,
+        );
+        encoded += 1;
+    }
 
-    if count >= 2 {
-        let fill_alpha = if fill_opacity > 0.0 {
-            fill_opacity.clamp(0.0, 1.0)
-        } else {
-            0.25
-        };
-
-        if fill_alpha > 0.0 {
-            let mut fill_path = BezPath::new();
-            fill_path.move_to(upper_points[0]);
-            for point in upper_points.iter().skip(1) {
-                fill_path.line_to(*point);
-            }
-            for point in lower_points.iter().rev() {
-                fill_path.line_to(*point);
-            }
-            fill_path.close_path();
-
-            scene.fill(
-                Fill::NonZero,
-                Affine::IDENTITY,
-                &Brush::Solid(color.with_alpha(fill_alpha)),
-                None,
-                &fill_path,
-            );
-            encoded += 1;
-        }
+    scene.stroke(
+        &Stroke::new(stroke_width),
+        Affine::IDENTITY,
+        &color,
+        None,
+        &upper_path,
+    );
+    encoded += 1;
 
-        let mut upper_path = BezPath::new();
-        upper_path.move_to(upper_points[0]);
-        for point in upper_points.iter().skip(1) {
-            upper_path.line_to(*point);
-        }
-        scene.stroke(
-            &Stroke::new(stroke_width),
-            Affine::IDENTITY,
-            &color,
-            None,
-            &upper_path,
-        );
-        encoded += 1;
+    let lower_alpha = (fill_alpha * 0.6 + 0.2).clamp(0.0, 1.0);
+    let lower_color = color.with_alpha(lower_alpha);
+    let lower_width = (stroke_width * 0.8).max(MIN_STROKE_WIDTH);
+    scene.stroke(
+        &Stroke::new(lower_width),
+        Affine::IDENTITY,
+        &lower_color,
+        None,
+        &lower_path,
+    );
+    encoded += 1;
 
-        let mut lower_path = BezPath::new();
-        lower_path.move_to(lower_points[0]);
-        for point in lower_points.iter().skip(1) {
-            lower_path.line_to(*point);
-        }
-        let lower_alpha = (fill_alpha * 0.6 + 0.2).clamp(0.0, 1.0);
-        let lower_color = color.with_alpha(lower_alpha);
-        scene.stroke(
-            &Stroke::new((stroke_width * 0.8).max(MIN_STROKE_WIDTH)),
-            Affine::IDENTITY,
-            &lower_color,
-            None,
-            &lower_path,
-        );
-        encoded += 1;
+    if gap_style == GapStyle::Dashed {
+        encoded += draw_gap_bridges(scene, &upper_points, &runs, color, stroke_width);
+        encoded += draw_gap_bridges(scene, &lower_points, &runs, lower_color, lower_width);
     }
 
-    (encoded, anchor)
+    let last = last_visible_point(upper_span, &upper_points, &runs, range_start, window);
+    (encoded, last)
 }
 
 fn render_heatmap_series(
     scene: &mut Scene,
     span: &[SeriesPoint],
-    color: Color,
+    scale: &ColorScale,
+    domain: (f64, f64),
     plot_area: &PlotArea,
     range_start: f64,
     window: f64,
     bucket_index: u32,
     bucket_count: u32,
-    value_min: f64,
-    value_range: f64,
 ) -> (u32, Option<Point>) {
     if span.is_empty() {
         return (0, None);
@@ -2362,14 +4208,16 @@ fn render_heatmap_series(
             continue;
         }
 
-        let normalized = ((sample.value - value_min) / value_range).clamp(0.0, 1.0);
-        let alpha = (0.2 + normalized * 0.7).clamp(0.05, 1.0) as f32;
+        if !sample.value.is_finite() {
+            continue;
+        }
+        let color = scale.sample(color_scale::normalize(sample.value, domain));
 
         let rect = Rect::new(x0, bucket_top, x1, bucket_bottom);
         scene.fill(
             Fill::NonZero,
             Affine::IDENTITY,
-            &Brush::Solid(color.with_alpha(alpha)),
+            &Brush::Solid(color),
             None,
             &rect,
         );
@@ -2379,7 +4227,39 @@ fn render_heatmap_series(
     (encoded, None)
 }
 
-fn draw_background(scene: &mut Scene, width: f64, height: f64, plot: &PlotArea) -> u32 {
+fn heatmap_scale(definition: &SeriesDefinition, series_color: Color) -> ColorScale {
+    definition
+        .color_scale
+        .clone()
+        .unwrap_or_else(|| ColorScale::single_hue(series_color))
+}
+
+/// Scale and domain of the first visible heatmap series in a pane, shown on its colour bar.
+fn pane_color_bar(
+    pane_series: &[u32],
+    series: &HashMap<u32, SeriesState>,
+    options: &EngineOptions,
+) -> Option<(ColorScale, (f64, f64))> {
+    pane_series.iter().find_map(|series_id| {
+        let state = series.get(series_id)?;
+        if !state.visible || state.definition.kind != SeriesKind::Heatmap {
+            return None;
+        }
+        let color = state
+            .style
+            .color
+            .unwrap_or_else(|| color_for_series(options, state.palette_slot));
+        Some((heatmap_scale(&state.definition, color), state.color_domain?))
+    })
+}
+
+fn draw_background(
+    scene: &mut Scene,
+    width: f64,
+    height: f64,
+    plot: &PlotArea,
+    theme: &ChartTheme,
+) -> u32 {
     if width <= 0.0 || height <= 0.0 {
         return 0;
     }
@@ -2391,11 +4271,11 @@ fn draw_background(scene: &mut Scene, width: f64, height: f64, plot: &PlotArea)
         Gradient::new_linear(Point::new(0.0, 0.0), Point::new(0.0, height)).with_stops([
             ColorStop {
                 offset: 0.0,
-                color: DynamicColor::from_alpha_color(Color::from_rgba8(0x10, 0x18, 0x28, 0xF0)),
+                color: DynamicColor::from_alpha_color(theme.background_top),
             },
             ColorStop {
                 offset: 1.0,
-                color: DynamicColor::from_alpha_color(Color::from_rgba8(0x0B, 0x11, 0x1C, 0xE6)),
+                color: DynamicColor::from_alpha_color(theme.background_bottom),
             },
         ]);
     scene.fill(
@@ -2408,11 +4288,10 @@ fn draw_background(scene: &mut Scene, width: f64, height: f64, plot: &PlotArea)
     encoded += 1;
 
     let border_rect = Rect::new(0.5, 0.5, width - 0.5, height - 0.5);
-    let border_color = Color::from_rgba8(0x2A, 0x34, 0x4A, 0xFF);
     scene.stroke(
         &Stroke::new(1.0),
         Affine::IDENTITY,
-        &border_color,
+        theme.frame_border,
         None,
         &border_rect,
     );
@@ -2425,21 +4304,19 @@ fn draw_background(scene: &mut Scene, width: f64, height: f64, plot: &PlotArea)
         plot.bottom().min(height - 0.5),
     );
 
-    let plot_fill = Color::from_rgba8(0x12, 0x1B, 0x2A, 0xC8);
     scene.fill(
         Fill::NonZero,
         Affine::IDENTITY,
-        &Brush::Solid(plot_fill),
+        &Brush::Solid(theme.plot_background),
         None,
         &plot_rect,
     );
     encoded += 1;
 
-    let plot_border = Color::from_rgba8(0x24, 0x30, 0x45, 0xFF);
     scene.stroke(
         &Stroke::new(1.0),
         Affine::IDENTITY,
-        &plot_border,
+        theme.plot_border,
         None,
         &plot_rect,
     );
@@ -2456,22 +4333,24 @@ fn draw_series_label(
     chart_width: f64,
     chart_height: f64,
     plot: &PlotArea,
+    theme: &ChartTheme,
 ) -> u32 {
-    let layout = match layout_label(label, LABEL_FONT_SIZE) {
+    let callout = &theme.callout;
+    let layout = match theme.label.layout(label) {
         Some(layout) => layout,
         None => return 0,
     };
 
     let text_width = f64::from(layout.width);
     let text_height = f64::from(layout.height);
-    let total_width = text_width + 2.0 * LABEL_HORIZONTAL_PADDING;
-    let total_height = text_height + 2.0 * LABEL_VERTICAL_PADDING;
+    let total_width = text_width + 2.0 * callout.padding_x;
+    let total_height = text_height + 2.0 * callout.padding_y;
 
     if total_width <= 0.0 || total_height <= 0.0 {
         return 0;
     }
 
-    let mut left = anchor.x + LABEL_HORIZONTAL_OFFSET;
+    let mut left = anchor.x + callout.offset_x;
     let min_left = plot.left.max(0.0);
     let mut max_left = (chart_width - total_width).max(min_left);
     if max_left < min_left {
@@ -2488,7 +4367,7 @@ fn draw_series_label(
         left = (plot_right_limit - total_width).max(min_left);
     }
 
-    let mut top = anchor.y - LABEL_VERTICAL_GAP - total_height;
+    let mut top = anchor.y - callout.gap_y - total_height;
     let min_top = (plot.top - total_height).max(0.0);
     let mut max_top = (plot.bottom() - total_height).min(chart_height - total_height);
     if max_top < min_top {
@@ -2508,8 +4387,8 @@ fn draw_series_label(
         return 0;
     }
 
-    let background = RoundedRect::new(left, top, right, bottom, LABEL_CORNER_RADIUS);
-    let background_color = color.with_alpha(0.28);
+    let background = RoundedRect::new(left, top, right, bottom, callout.corner_radius);
+    let background_color = color.with_alpha(callout.fill_alpha);
     scene.fill(
         Fill::NonZero,
         Affine::IDENTITY,
@@ -2518,38 +4397,24 @@ fn draw_series_label(
         &background,
     );
 
-    let border_color = color.with_alpha(0.6);
+    let border_color = color.with_alpha(callout.border_alpha);
     scene.stroke(
-        &Stroke::new(1.0),
+        &Stroke::new(callout.border_width),
         Affine::IDENTITY,
         &border_color,
         None,
         &background,
     );
 
-    let baseline_x = (left + LABEL_HORIZONTAL_PADDING).min(chart_width);
-    let baseline_y = (top + LABEL_VERTICAL_PADDING + f64::from(layout.ascent)).min(chart_height);
-
-    scene
-        .draw_glyphs(label_font())
-        .font_size(LABEL_FONT_SIZE)
-        .transform(Affine::translate((baseline_x, baseline_y)))
-        .brush(Brush::Solid(label_text_color()))
-        .draw(Fill::NonZero, layout.glyphs.into_iter());
-
-    2
-}
-
-fn axis_color() -> Color {
-    Color::from_rgba8(0x45, 0x53, 0x72, 0xFF)
-}
-
-fn grid_color() -> Color {
-    Color::from_rgba8(0x23, 0x2E, 0x42, 0xFF)
-}
+    let baseline_x = (left + callout.padding_x).min(chart_width);
+    let baseline_y = (top + callout.padding_y + f64::from(layout.ascent)).min(chart_height);
 
-fn axis_label_color() -> Color {
-    Color::from_rgba8(0xA8, 0xB7, 0xD6, 0xFF)
+    1 + layout.draw(
+        scene,
+        theme.label.font_size,
+        Affine::translate((baseline_x, baseline_y)),
+        &Brush::Solid(theme.label.color),
+    )
 }
 
 fn make_c_string(label: &str) -> CString {
@@ -2571,138 +4436,329 @@ fn chart_color_from_peniko(color: Color) -> VelloChartColor {
     }
 }
 
-fn draw_axes_and_grid(scene: &mut Scene, plot: &PlotArea, layout: &AxisLayout) -> u32 {
+/// Draws the grid and, when the theme styles them, the axes with their ticks and labels.
+/// Time labels and ticks are only drawn for the pane along the bottom of the plot.
+fn draw_axes_and_grid(
+    scene: &mut Scene,
+    plot: &PlotArea,
+    layout: &AxisLayout,
+    right_layout: Option<&AxisLayout>,
+    theme: &ChartTheme,
+    time_axis: bool,
+) -> u32 {
     let mut encoded = 0;
-    let axis_stroke = Stroke::new(AXIS_STROKE_WIDTH);
-    let axis_color = axis_color();
-    let grid_stroke = Stroke::new(GRID_STROKE_WIDTH);
-    let grid_color = grid_color();
-    let label_color = axis_label_color();
+    let value_y = |position: f64| plot.bottom() - position * plot.height;
+    let time_x = |position: f64| plot.left + position * plot.width;
+    let major_values: Vec<f64> = layout
+        .value_ticks
+        .iter()
+        .map(|tick| tick.position)
+        .collect();
+    let major_times: Vec<f64> = layout.time_ticks.iter().map(|tick| tick.position).collect();
+    let minor_values = minor_positions(&layout.value_ticks, theme.minor_divisions);
+    let minor_times = minor_positions(&layout.time_ticks, theme.minor_divisions);
+
+    // Minor lines go first so major lines cover them where they meet.
+    for (style, values, times) in [
+        (&theme.minor_grid, &minor_values, &minor_times),
+        (&theme.major_grid, &major_values, &major_times),
+    ] {
+        let Some(style) = style else {
+            continue;
+        };
+        let stroke = style.stroke();
+        for &position in values {
+            let y = value_y(position);
+            if (y - plot.bottom()).abs() < 0.5 || (y - plot.top).abs() < 0.5 {
+                continue;
+            }
+            scene.stroke(
+                &stroke,
+                Affine::IDENTITY,
+                style.color,
+                None,
+                &Line::new((plot.left, y), (plot.right(), y)),
+            );
+            encoded += 1;
+        }
+        for &position in times {
+            let x = time_x(position);
+            if (x - plot.left).abs() < 0.5 || (x - plot.right()).abs() < 0.5 {
+                continue;
+            }
+            scene.stroke(
+                &stroke,
+                Affine::IDENTITY,
+                style.color,
+                None,
+                &Line::new((x, plot.top), (x, plot.bottom())),
+            );
+            encoded += 1;
+        }
+    }
 
-    scene.stroke(
-        &axis_stroke,
-        Affine::IDENTITY,
-        &axis_color,
-        None,
-        &Line::new((plot.left, plot.top), (plot.left, plot.bottom())),
-    );
-    encoded += 1;
+    let Some(axis) = &theme.axis else {
+        return encoded;
+    };
+    let axis_stroke = axis.line.stroke();
+    let axis_color = axis.line.color;
 
     scene.stroke(
         &axis_stroke,
         Affine::IDENTITY,
-        &axis_color,
+        axis_color,
         None,
-        &Line::new((plot.left, plot.bottom()), (plot.right(), plot.bottom())),
+        &Line::new((plot.left, plot.top), (plot.left, plot.bottom())),
     );
     encoded += 1;
 
-    for tick in &layout.value_ticks {
-        let y = plot.bottom() - tick.position * plot.height;
-        if (y - plot.bottom()).abs() < 0.5 || (y - plot.top).abs() < 0.5 {
-            continue;
-        }
+    if time_axis {
         scene.stroke(
-            &grid_stroke,
+            &axis_stroke,
             Affine::IDENTITY,
-            &grid_color,
+            axis_color,
             None,
-            &Line::new((plot.left, y), (plot.right(), y)),
+            &Line::new((plot.left, plot.bottom()), (plot.right(), plot.bottom())),
         );
         encoded += 1;
     }
 
-    for tick in &layout.time_ticks {
-        let x = plot.left + tick.position * plot.width;
-        if (x - plot.left).abs() < 0.5 || (x - plot.right()).abs() < 0.5 {
+    let tick_stroke = Stroke::new(axis.line.width);
+    for (values, times, length) in [
+        (&major_values, &major_times, axis.major_tick_length),
+        (&minor_values, &minor_times, axis.minor_tick_length),
+    ] {
+        if length <= 0.0 {
             continue;
         }
-        scene.stroke(
-            &grid_stroke,
-            Affine::IDENTITY,
-            &grid_color,
-            None,
-            &Line::new((x, plot.top), (x, plot.bottom())),
-        );
-        encoded += 1;
+        for &position in values {
+            let y = value_y(position);
+            scene.stroke(
+                &tick_stroke,
+                Affine::IDENTITY,
+                axis_color,
+                None,
+                &Line::new((plot.left - length, y), (plot.left, y)),
+            );
+            encoded += 1;
+        }
+        if !time_axis {
+            continue;
+        }
+        for &position in times {
+            let x = time_x(position);
+            scene.stroke(
+                &tick_stroke,
+                Affine::IDENTITY,
+                axis_color,
+                None,
+                &Line::new((x, plot.bottom()), (x, plot.bottom() + length)),
+            );
+            encoded += 1;
+        }
     }
 
+    let text = &theme.axis_label;
     for tick in &layout.value_ticks {
-        let y = plot.bottom() - tick.position * plot.height;
-        if let Some(layout) = layout_label(&tick.label, AXIS_FONT_SIZE) {
-            let LabelLayout {
-                glyphs,
-                width,
-                height,
-                ..
-            } = layout;
-            let width = f64::from(width);
-            let height = f64::from(height);
-            let mut baseline_x = (plot.left - AXIS_LABEL_LEFT_MARGIN - width).max(0.0);
-            let min_baseline_x = 0.0;
-            baseline_x = baseline_x.max(min_baseline_x);
+        let y = value_y(tick.position);
+        if let Some(layout) = text.layout(&tick.label) {
+            let width = f64::from(layout.width);
+            let height = f64::from(layout.height);
+            let baseline_x = (plot.left - AXIS_LABEL_LEFT_MARGIN - width).max(0.0);
             let baseline_y = (y + height / 2.0).clamp(plot.top - height, plot.bottom() + height);
-            scene
-                .draw_glyphs(label_font())
-                .font_size(AXIS_FONT_SIZE)
-                .transform(Affine::translate((baseline_x, baseline_y)))
-                .brush(Brush::Solid(label_color))
-                .draw(Fill::NonZero, glyphs.into_iter());
-            encoded += 1;
+            encoded += layout.draw(
+                scene,
+                text.font_size,
+                Affine::translate((baseline_x, baseline_y)),
+                &Brush::Solid(text.color),
+            );
         }
     }
 
+    if let Some(right_layout) = right_layout {
+        encoded += draw_right_axis(scene, plot, right_layout, axis, theme);
+    }
+
+    if !time_axis {
+        return encoded;
+    }
+
     for tick in &layout.time_ticks {
-        let x = plot.left + tick.position * plot.width;
-        if let Some(layout) = layout_label(&tick.label, AXIS_FONT_SIZE) {
-            let LabelLayout {
-                glyphs,
-                width,
-                height,
-                ..
-            } = layout;
-            let width = f64::from(width);
-            let height = f64::from(height);
+        let x = time_x(tick.position);
+        // Two-level labels carry the date on a second line.
+        for (line_index, line) in tick.label.lines().enumerate() {
+            let Some(layout) = text.layout(line) else {
+                continue;
+            };
+            let width = f64::from(layout.width);
+            let height = f64::from(layout.height);
             let min_x = plot.left;
             let max_x = (plot.right() - width).max(min_x);
-            let mut baseline_x = x - width / 2.0;
-            baseline_x = baseline_x.clamp(min_x, max_x);
-            let baseline_y = plot.bottom() + AXIS_LABEL_BOTTOM_MARGIN + height * 0.25;
-            scene
-                .draw_glyphs(label_font())
-                .font_size(AXIS_FONT_SIZE)
-                .transform(Affine::translate((baseline_x, baseline_y)))
-                .brush(Brush::Solid(label_color))
-                .draw(Fill::NonZero, glyphs.into_iter());
-            encoded += 1;
+            let baseline_x = (x - width / 2.0).clamp(min_x, max_x);
+            let baseline_y = plot.bottom()
+                + AXIS_LABEL_BOTTOM_MARGIN
+                + height * 0.25
+                + line_index as f64 * AXIS_LABEL_LINE_HEIGHT;
+            encoded += layout.draw(
+                scene,
+                text.font_size,
+                Affine::translate((baseline_x, baseline_y)),
+                &Brush::Solid(text.color),
+            );
         }
     }
 
     encoded
 }
 
-fn label_text_color() -> Color {
-    Color::from_rgba8(0xF5, 0xF9, 0xFF, 0xFF)
+/// Right value axis of panes with right-bound series: the edge line, outward ticks and
+/// labels in the gutter `reserve_right_axis` keeps free.
+fn draw_right_axis(
+    scene: &mut Scene,
+    plot: &PlotArea,
+    layout: &AxisLayout,
+    axis: &AxisStyle,
+    theme: &ChartTheme,
+) -> u32 {
+    let value_y = |position: f64| plot.bottom() - position * plot.height;
+    let axis_color = axis.line.color;
+    scene.stroke(
+        &axis.line.stroke(),
+        Affine::IDENTITY,
+        axis_color,
+        None,
+        &Line::new((plot.right(), plot.top), (plot.right(), plot.bottom())),
+    );
+    let mut encoded = 1;
+
+    let major_values: Vec<f64> = layout
+        .value_ticks
+        .iter()
+        .map(|tick| tick.position)
+        .collect();
+    let minor_values = minor_positions(&layout.value_ticks, theme.minor_divisions);
+    let tick_stroke = Stroke::new(axis.line.width);
+    for (values, length) in [
+        (&major_values, axis.major_tick_length),
+        (&minor_values, axis.minor_tick_length),
+    ] {
+        if length <= 0.0 {
+            continue;
+        }
+        for &position in values {
+            let y = value_y(position);
+            scene.stroke(
+                &tick_stroke,
+                Affine::IDENTITY,
+                axis_color,
+                None,
+                &Line::new((plot.right(), y), (plot.right() + length, y)),
+            );
+            encoded += 1;
+        }
+    }
+
+    let text = &theme.axis_label;
+    for tick in &layout.value_ticks {
+        let y = value_y(tick.position);
+        if let Some(layout) = text.layout(&tick.label) {
+            let height = f64::from(layout.height);
+            let baseline_x = plot.right() + AXIS_LABEL_LEFT_MARGIN;
+            let baseline_y = (y + height / 2.0).clamp(plot.top - height, plot.bottom() + height);
+            encoded += layout.draw(
+                scene,
+                text.font_size,
+                Affine::translate((baseline_x, baseline_y)),
+                &Brush::Solid(text.color),
+            );
+        }
+    }
+
+    encoded
 }
 
-fn value_bounds(points: &[SeriesPoint]) -> (f64, f64) {
+fn value_bounds(points: &[SeriesPoint], scale: ValueScale) -> (f64, f64) {
     let mut min = f64::INFINITY;
     let mut max = f64::NEG_INFINITY;
 
     for point in points {
+        if !scale.accepts(point.value) {
+            continue;
+        }
         min = min.min(point.value);
         max = max.max(point.value);
     }
 
     if !min.is_finite() || !max.is_finite() {
-        (0.0, 1.0)
+        match scale {
+            ValueScale::Log10 => (1.0, 10.0),
+            _ => (0.0, 1.0),
+        }
     } else if (max - min).abs() < f64::EPSILON {
-        (min - 1.0, max + 1.0)
+        match scale {
+            ValueScale::Log10 => (min * 0.5, max * 2.0),
+            _ => (min - 1.0, max + 1.0),
+        }
     } else {
         (min, max)
     }
 }
 
+/// Leaves room below the plot for the date line of two-level time labels.
+fn reserve_date_row(plot_area: &mut PlotArea) {
+    plot_area.height =
+        (plot_area.height - AXIS_LABEL_LINE_HEIGHT).max(MIN_PLOT_DIMENSION.min(plot_area.height));
+}
+
+/// Takes room for heatmap colour bars from the right of the plot, past any right axis
+/// labels, and returns the left edge of the bars.
+fn reserve_color_bar(plot_area: &mut PlotArea, has_right_axis: bool) -> f64 {
+    plot_area.width =
+        (plot_area.width - COLOR_BAR_SPACE).max(MIN_PLOT_DIMENSION.min(plot_area.width));
+    let gutter = if has_right_axis {
+        plot_area.left
+    } else {
+        AXIS_LABEL_LEFT_MARGIN
+    };
+    plot_area.right() + gutter
+}
+
+/// Widens the right inset to match the left one so right-axis labels have room.
+fn reserve_right_axis(plot_area: &mut PlotArea, chart_width: f64) {
+    let right_inset = chart_width - plot_area.right();
+    let extra = (plot_area.left - right_inset).max(0.0);
+    plot_area.width = (plot_area.width - extra).max(MIN_PLOT_DIMENSION.min(plot_area.width));
+}
+
+/// Applies the vertical padding ratio in the scale's own space so that logarithmic panes
+/// are padded by the same fraction of their height as linear ones.
+fn pad_value_range(min: f64, max: f64, padding_ratio: f64, scale: ValueScale) -> (f64, f64) {
+    let (min, max) = if min.is_finite() && max.is_finite() && scale.accepts(min) {
+        (min, max)
+    } else {
+        match scale {
+            ValueScale::Log10 => (1.0, 10.0),
+            _ => (0.0, 1.0),
+        }
+    };
+
+    let mut low = scale.transform(min);
+    let mut high = scale.transform(max);
+    let mut padding = (high - low).abs() * padding_ratio;
+    if !padding.is_finite() {
+        padding = 0.0;
+    }
+    if padding > 0.0 {
+        low -= padding;
+        high += padding;
+    }
+    if (high - low).abs() < f64::EPSILON {
+        high = low + 1.0;
+    }
+
+    (scale.inverse(low), scale.inverse(high))
+}
+
 fn unix_millis() -> u128 {
     SystemTime::now()
         .duration_since(SystemTime::UNIX_EPOCH)
@@ -2863,81 +4919,313 @@ pub struct VelloChartEngineHandle {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
-pub struct VelloChartEngineOptions {
-    pub visible_duration_seconds: f64,
-    pub vertical_padding_ratio: f64,
-    pub stroke_width: f64,
-    pub show_axes: u32,
-    pub palette_len: usize,
-    pub palette_ptr: *const VelloChartColor,
+pub struct VelloChartLegendOptions {
+    pub placement: u32,
+    pub floating_pane_index: u32,
+    pub floating_corner: u32,
 }
 
 #[repr(C)]
 #[derive(Clone, Copy)]
-pub struct VelloChartColor {
-    pub r: u8,
-    pub g: u8,
-    pub b: u8,
-    pub a: u8,
+pub struct VelloChartAxisLabelOptions {
+    pub utc_offset_minutes: i32,
+    pub flags: u32,
+    /// UTF-8 time pattern; empty keeps the automatic format.
+    pub time_format: *const c_char,
+    pub time_format_len: usize,
+    /// UTF-8 value pattern; empty keeps the automatic format.
+    pub value_format: *const c_char,
+    pub value_format_len: usize,
 }
 
-impl From<VelloChartColor> for Color {
-    fn from(value: VelloChartColor) -> Self {
-        Color::from_rgba8(value.r, value.g, value.b, value.a)
-    }
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct VelloChartLineStyle {
+    pub color: VelloChartColor,
+    pub width: f64,
+    pub dash_ptr: *const f64,
+    pub dash_len: usize,
 }
 
+/// Label text style; a zero `family_len` draws with the embedded label font only.
 #[repr(C)]
 #[derive(Clone, Copy)]
-pub struct VelloChartSeriesOverride {
+pub struct VelloChartTextStyle {
+    pub family_ptr: *const c_char,
+    pub family_len: usize,
+    pub font_size: f32,
+    pub color: VelloChartColor,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct VelloChartCalloutStyle {
+    pub padding_x: f64,
+    pub padding_y: f64,
+    pub corner_radius: f64,
+    pub offset_x: f64,
+    pub gap_y: f64,
+    pub fill_alpha: f32,
+    pub border_alpha: f32,
+    pub border_width: f64,
+}
+
+/// Chart theme; `major_grid`, `minor_grid` and `axis` are only read when the matching
+/// `THEME_FLAG_*` bit is set, otherwise the host draws them.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct VelloChartTheme {
+    pub flags: u32,
+    pub background_top: VelloChartColor,
+    pub background_bottom: VelloChartColor,
+    pub frame_border: VelloChartColor,
+    pub plot_background: VelloChartColor,
+    pub plot_border: VelloChartColor,
+    pub major_grid: VelloChartLineStyle,
+    pub minor_grid: VelloChartLineStyle,
+    pub minor_divisions: u32,
+    pub axis: VelloChartLineStyle,
+    pub major_tick_length: f64,
+    pub minor_tick_length: f64,
+    pub axis_label: VelloChartTextStyle,
+    pub label: VelloChartTextStyle,
+    pub callout: VelloChartCalloutStyle,
+    pub legend_background: VelloChartColor,
+    pub tooltip_background: VelloChartColor,
+    pub panel_border: VelloChartColor,
+    pub crosshair: VelloChartLineStyle,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+pub struct VelloChartLegendHit {
+    pub hit: u32,
     pub series_id: u32,
+}
+
+/// Annotation descriptor; `start` carries the value or timestamp of lines and markers,
+/// `start`/`end` bound bands.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct VelloChartAnnotation {
+    pub annotation_id: u32,
+    pub kind: u32,
     pub flags: u32,
+    pub start: f64,
+    pub end: f64,
+    pub color: VelloChartColor,
+    pub stroke_width: f64,
+    pub fill_opacity: f32,
+    pub dash_ptr: *const f64,
+    pub dash_len: usize,
     pub label_ptr: *const c_char,
     pub label_len: usize,
-    pub stroke_width: f64,
-    pub color: VelloChartColor,
+    pub pane_id_ptr: *const c_char,
+    pub pane_id_len: usize,
 }
 
-impl From<VelloChartEngineOptions> for EngineOptions {
-    fn from(value: VelloChartEngineOptions) -> Self {
-        let defaults = EngineOptions::default();
-
-        let visible_duration_seconds = if value.visible_duration_seconds.is_finite() {
-            value
-                .visible_duration_seconds
-                .max(MIN_VISIBLE_DURATION_SECS)
-        } else {
-            defaults.visible_duration_seconds
-        };
+/// Derived series descriptor. `operator` is 0 = moving average, 1 = exponential moving
+/// average, 2 = derivative, 3 = integral, 4 = min/max envelope, 5 = Bollinger bands.
+/// `window` is the sample count (the period for the exponential average) and is ignored
+/// by the derivative and integral; `deviations` only applies to Bollinger bands and
+/// `lower_series_id` only to the two band operators.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct VelloChartDerivedSeries {
+    pub series_id: u32,
+    pub source_series_id: u32,
+    pub operator: u32,
+    pub window: u32,
+    pub deviations: f64,
+    pub lower_series_id: u32,
+}
 
-        let vertical_padding_ratio = if value.vertical_padding_ratio.is_finite() {
-            value.vertical_padding_ratio.max(0.0)
-        } else {
-            defaults.vertical_padding_ratio
-        };
+/// Export settings. `format` is 0 = PNG, 1 = SVG; `background` is used when `flags`
+/// has bit 0 set.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct VelloChartExportOptions {
+    pub format: u32,
+    pub flags: u32,
+    pub scale: f64,
+    pub background: VelloChartColor,
+}
 
-        let stroke_width = if value.stroke_width.is_finite() && value.stroke_width > 0.0 {
-            value.stroke_width
-        } else {
-            defaults.stroke_width
-        };
+/// Encoded export or snapshot owned by the engine library until destroyed.
+pub struct VelloChartExportBuffer {
+    bytes: Vec<u8>,
+}
 
-        let mut palette = palette_from_raw(value.palette_ptr, value.palette_len);
-        if palette.is_empty() {
-            palette = defaults.palette.clone();
-        }
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+pub struct VelloChartViewport {
+    pub range_start: f64,
+    pub range_end: f64,
+    pub follow_live: u32,
+}
 
+impl From<ViewportRange> for VelloChartViewport {
+    fn from(value: ViewportRange) -> Self {
         Self {
-            visible_duration_seconds,
-            vertical_padding_ratio,
-            stroke_width,
-            palette,
-            show_axes: value.show_axes != 0,
+            range_start: value.range_start,
+            range_end: value.range_end,
+            follow_live: u32::from(value.follow_live),
         }
     }
 }
 
-fn palette_from_raw(ptr: *const VelloChartColor, len: usize) -> Vec<Color> {
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+pub struct VelloChartHitTestResult {
+    pub hit: u32,
+    pub series_id: u32,
+    pub pane_index: u32,
+    pub series_kind: u32,
+    pub timestamp_seconds: f64,
+    pub value: f64,
+    pub x: f64,
+    pub y: f64,
+    pub distance: f64,
+}
+
+impl From<&HitTestResult> for VelloChartHitTestResult {
+    fn from(result: &HitTestResult) -> Self {
+        Self {
+            hit: 1,
+            series_id: result.series_id,
+            pane_index: result.pane_index.min(u32::MAX as usize) as u32,
+            series_kind: series_kind_to_u32(result.kind),
+            timestamp_seconds: result.timestamp_seconds,
+            value: result.value,
+            x: result.x,
+            y: result.y,
+            distance: result.distance,
+        }
+    }
+}
+
+#[repr(C)]
+pub struct VelloChartDataBusHandle {
+    inner: DataBus,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct VelloChartDataPoint {
+    pub timestamp_ns: u64,
+    pub value: f64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+pub struct VelloChartDataBusStats {
+    pub capacity: usize,
+    pub queued: usize,
+    pub pushed: u64,
+    pub dropped: u64,
+    pub dropped_points: u64,
+    pub drained: u64,
+}
+
+impl From<DataBusStats> for VelloChartDataBusStats {
+    fn from(stats: DataBusStats) -> Self {
+        Self {
+            capacity: stats.capacity,
+            queued: stats.queued,
+            pushed: stats.pushed,
+            dropped: stats.dropped,
+            dropped_points: stats.dropped_points,
+            drained: stats.drained,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct VelloChartEngineOptions {
+    pub visible_duration_seconds: f64,
+    pub retention_duration_seconds: f64,
+    pub vertical_padding_ratio: f64,
+    pub stroke_width: f64,
+    pub show_axes: u32,
+    pub palette_len: usize,
+    pub palette_ptr: *const VelloChartColor,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct VelloChartColor {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl From<VelloChartColor> for Color {
+    fn from(value: VelloChartColor) -> Self {
+        Color::from_rgba8(value.r, value.g, value.b, value.a)
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct VelloChartSeriesOverride {
+    pub series_id: u32,
+    pub flags: u32,
+    pub label_ptr: *const c_char,
+    pub label_len: usize,
+    pub stroke_width: f64,
+    pub color: VelloChartColor,
+}
+
+impl From<VelloChartEngineOptions> for EngineOptions {
+    fn from(value: VelloChartEngineOptions) -> Self {
+        let defaults = EngineOptions::default();
+
+        let visible_duration_seconds = if value.visible_duration_seconds.is_finite() {
+            value
+                .visible_duration_seconds
+                .max(MIN_VISIBLE_DURATION_SECS)
+        } else {
+            defaults.visible_duration_seconds
+        };
+
+        let retention_duration_seconds = (value.retention_duration_seconds.is_finite()
+            && value.retention_duration_seconds > 0.0)
+            .then_some(value.retention_duration_seconds);
+
+        let vertical_padding_ratio = if value.vertical_padding_ratio.is_finite() {
+            value.vertical_padding_ratio.max(0.0)
+        } else {
+            defaults.vertical_padding_ratio
+        };
+
+        let stroke_width = if value.stroke_width.is_finite() && value.stroke_width > 0.0 {
+            value.stroke_width
+        } else {
+            defaults.stroke_width
+        };
+
+        let mut palette = palette_from_raw(value.palette_ptr, value.palette_len);
+        if palette.is_empty() {
+            palette = defaults.palette.clone();
+        }
+
+        Self {
+            visible_duration_seconds,
+            retention_duration_seconds,
+            vertical_padding_ratio,
+            stroke_width,
+            palette,
+            show_axes: value.show_axes != 0,
+            legend: defaults.legend,
+            axis_labels: defaults.axis_labels,
+            theme: defaults.theme,
+        }
+    }
+}
+
+fn palette_from_raw(ptr: *const VelloChartColor, len: usize) -> Vec<Color> {
     if ptr.is_null() || len == 0 {
         return Vec::new();
     }
@@ -2985,6 +5273,33 @@ pub struct VelloChartSamplePoint {
     pub value: f64,
 }
 
+/// Bar for candlestick and OHLC series; a `NaN` volume means none was reported.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct VelloChartOhlcSample {
+    pub series_id: u32,
+    pub timestamp_seconds: f64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: f64,
+}
+
+impl From<&VelloChartOhlcSample> for OhlcSample {
+    fn from(value: &VelloChartOhlcSample) -> Self {
+        Self {
+            series_id: value.series_id,
+            timestamp_seconds: value.timestamp_seconds,
+            open: value.open,
+            high: value.high,
+            low: value.low,
+            close: value.close,
+            volume: value.volume.is_finite().then_some(value.volume),
+        }
+    }
+}
+
 #[repr(C)]
 #[derive(Default, Clone, Copy)]
 pub struct VelloChartFrameStats {
@@ -2993,6 +5308,8 @@ pub struct VelloChartFrameStats {
     pub queue_latency_ms: f32,
     pub encoded_paths: u32,
     pub timestamp_ms: i64,
+    pub source_points: u64,
+    pub rendered_points: u64,
 }
 
 impl From<&FrameStats> for VelloChartFrameStats {
@@ -3003,6 +5320,8 @@ impl From<&FrameStats> for VelloChartFrameStats {
             queue_latency_ms: stats.queue_latency_ms,
             encoded_paths: stats.encoded_paths,
             timestamp_ms: stats.timestamp.min(i64::MAX as u128) as i64,
+            source_points: stats.source_points,
+            rendered_points: stats.rendered_points,
         }
     }
 }
@@ -3013,6 +5332,7 @@ pub enum VelloChartEngineStatus {
     Success = 0,
     NullPointer = 1,
     InvalidArgument = 2,
+    QueueFull = 3,
     Unknown = 255,
 }
 
@@ -3088,6 +5408,43 @@ pub unsafe extern "C" fn vello_chart_engine_publish_samples(
     VelloChartEngineStatus::Success
 }
 
+/// # Safety
+/// `handle` must be a live engine handle and `samples` must reference `sample_count` bars.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn vello_chart_engine_publish_ohlc_samples(
+    handle: *mut VelloChartEngineHandle,
+    samples: *const VelloChartOhlcSample,
+    sample_count: usize,
+) -> VelloChartEngineStatus {
+    clear_last_error();
+    let engine = match unsafe { handle.as_mut() } {
+        Some(engine) => engine,
+        None => {
+            set_last_error("Null engine handle passed to publish_ohlc_samples");
+            return VelloChartEngineStatus::NullPointer;
+        }
+    };
+
+    let Ok(sample_slice) = slice_from_raw(samples, sample_count) else {
+        return VelloChartEngineStatus::NullPointer;
+    };
+
+    if sample_slice.iter().any(|sample| {
+        !(sample.timestamp_seconds.is_finite()
+            && sample.open.is_finite()
+            && sample.high.is_finite()
+            && sample.low.is_finite()
+            && sample.close.is_finite())
+    }) {
+        set_last_error("OHLC samples must have finite timestamps and prices");
+        return VelloChartEngineStatus::InvalidArgument;
+    }
+
+    let bars: Vec<OhlcSample> = sample_slice.iter().map(OhlcSample::from).collect();
+    engine.inner.publish_ohlc_samples(&bars);
+    VelloChartEngineStatus::Success
+}
+
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn vello_chart_engine_set_palette(
     handle: *mut VelloChartEngineHandle,
@@ -3304,19 +5661,970 @@ pub unsafe extern "C" fn vello_chart_engine_last_frame_metadata(
     VelloChartEngineStatus::Success
 }
 
+/// # Safety
+/// `handle` must be a live engine handle.
 #[unsafe(no_mangle)]
-pub extern "C" fn vello_chart_engine_set_trace_callback(
-    callback: Option<TraceCallback>,
+pub unsafe extern "C" fn vello_chart_engine_set_cursor(
+    handle: *mut VelloChartEngineHandle,
+    x: f64,
+    y: f64,
 ) -> VelloChartEngineStatus {
-    ensure_tracing_layer();
+    clear_last_error();
+    let engine = match unsafe { handle.as_mut() } {
+        Some(engine) => engine,
+        None => {
+            set_last_error("Null engine handle passed to set_cursor");
+            return VelloChartEngineStatus::NullPointer;
+        }
+    };
 
-    let storage = trace_callback_storage();
-    if let Ok(mut guard) = storage.write() {
-        *guard = callback;
-    } else {
-        set_last_error("Failed to register trace callback");
-        return VelloChartEngineStatus::Unknown;
+    if !x.is_finite() || !y.is_finite() {
+        set_last_error("Cursor position must be finite");
+        return VelloChartEngineStatus::InvalidArgument;
+    }
+
+    engine.inner.set_cursor(x, y);
+    VelloChartEngineStatus::Success
+}
+
+/// # Safety
+/// `handle` must be a live engine handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn vello_chart_engine_clear_cursor(
+    handle: *mut VelloChartEngineHandle,
+) -> VelloChartEngineStatus {
+    clear_last_error();
+    let engine = match unsafe { handle.as_mut() } {
+        Some(engine) => engine,
+        None => {
+            set_last_error("Null engine handle passed to clear_cursor");
+            return VelloChartEngineStatus::NullPointer;
+        }
+    };
+
+    engine.inner.clear_cursor();
+    VelloChartEngineStatus::Success
+}
+
+/// # Safety
+/// `handle` must be a live engine handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn vello_chart_engine_set_legend(
+    handle: *mut VelloChartEngineHandle,
+    options: VelloChartLegendOptions,
+) -> VelloChartEngineStatus {
+    clear_last_error();
+    let engine = match unsafe { handle.as_mut() } {
+        Some(engine) => engine,
+        None => {
+            set_last_error("Null engine handle passed to set_legend");
+            return VelloChartEngineStatus::NullPointer;
+        }
+    };
+
+    let Some(placement) = LegendPlacement::from_u32(options.placement) else {
+        set_last_error("Unknown legend placement");
+        return VelloChartEngineStatus::InvalidArgument;
+    };
+    let Some(floating_corner) = LegendCorner::from_u32(options.floating_corner) else {
+        set_last_error("Unknown legend corner");
+        return VelloChartEngineStatus::InvalidArgument;
+    };
+
+    engine.inner.set_legend(LegendOptions {
+        placement,
+        floating_pane: options.floating_pane_index as usize,
+        floating_corner,
+    });
+    VelloChartEngineStatus::Success
+}
+
+/// # Safety
+/// `handle` must be a live engine handle and the format pointers must reference
+/// `*_len` readable bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn vello_chart_engine_set_axis_labels(
+    handle: *mut VelloChartEngineHandle,
+    options: VelloChartAxisLabelOptions,
+) -> VelloChartEngineStatus {
+    clear_last_error();
+    let engine = match unsafe { handle.as_mut() } {
+        Some(engine) => engine,
+        None => {
+            set_last_error("Null engine handle passed to set_axis_labels");
+            return VelloChartEngineStatus::NullPointer;
+        }
+    };
+
+    let utc_offset_seconds = i64::from(options.utc_offset_minutes) * 60;
+    if utc_offset_seconds.abs() > i64::from(MAX_UTC_OFFSET_SECONDS) {
+        set_last_error("UTC offset must be within 18 hours");
+        return VelloChartEngineStatus::InvalidArgument;
+    }
+    let time_format = match string_from_raw(options.time_format, options.time_format_len) {
+        Ok(pattern) if pattern.is_empty() => None,
+        Ok(pattern) => match TimeFormat::parse(&pattern) {
+            Ok(format) => Some(format),
+            Err(error) => {
+                set_last_error(error.to_string());
+                return VelloChartEngineStatus::InvalidArgument;
+            }
+        },
+        Err(status) => return status,
+    };
+    let value_format = match string_from_raw(options.value_format, options.value_format_len) {
+        Ok(pattern) if pattern.is_empty() => None,
+        Ok(pattern) => match ValueFormat::parse(&pattern) {
+            Ok(format) => Some(format),
+            Err(error) => {
+                set_last_error(error.to_string());
+                return VelloChartEngineStatus::InvalidArgument;
+            }
+        },
+        Err(status) => return status,
+    };
+
+    engine.inner.set_axis_labels(AxisLabelOptions {
+        utc_offset_seconds: utc_offset_seconds as i32,
+        time_format,
+        value_format,
+        date_on_day_change: options.flags & AXIS_LABEL_FLAG_DATE_ON_DAY_CHANGE != 0,
+    });
+    VelloChartEngineStatus::Success
+}
+
+/// Restyles the chart chrome without rebuilding cached series scenes.
+///
+/// # Safety
+/// `handle` must be a live engine handle, `theme` must point to a valid theme and every
+/// dash pointer must reference `dash_len` readable values.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn vello_chart_engine_set_theme(
+    handle: *mut VelloChartEngineHandle,
+    theme: *const VelloChartTheme,
+) -> VelloChartEngineStatus {
+    clear_last_error();
+    let engine = match unsafe { handle.as_mut() } {
+        Some(engine) => engine,
+        None => {
+            set_last_error("Null engine handle passed to set_theme");
+            return VelloChartEngineStatus::NullPointer;
+        }
+    };
+    let descriptor = match unsafe { theme.as_ref() } {
+        Some(descriptor) => descriptor,
+        None => {
+            set_last_error("Null theme pointer passed to set_theme");
+            return VelloChartEngineStatus::NullPointer;
+        }
+    };
+
+    match theme_from_ffi(descriptor) {
+        Ok(theme) => {
+            engine.inner.set_theme(theme);
+            VelloChartEngineStatus::Success
+        }
+        Err(status) => status,
     }
+}
+
+/// Writes the series whose legend entry contains `(x, y)` in the last rendered frame.
+///
+/// # Safety
+/// `handle` must be a live engine handle and `out_hit` must be writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn vello_chart_engine_legend_hit_test(
+    handle: *mut VelloChartEngineHandle,
+    x: f64,
+    y: f64,
+    out_hit: *mut VelloChartLegendHit,
+) -> VelloChartEngineStatus {
+    clear_last_error();
+    let engine = match unsafe { handle.as_ref() } {
+        Some(engine) => engine,
+        None => {
+            set_last_error("Null engine handle passed to legend_hit_test");
+            return VelloChartEngineStatus::NullPointer;
+        }
+    };
+    let out = match unsafe { out_hit.as_mut() } {
+        Some(out) => out,
+        None => {
+            set_last_error("Null result pointer passed to legend_hit_test");
+            return VelloChartEngineStatus::NullPointer;
+        }
+    };
+
+    *out = match engine.inner.legend_hit_test(x, y) {
+        Some(series_id) => VelloChartLegendHit { hit: 1, series_id },
+        None => VelloChartLegendHit::default(),
+    };
+    VelloChartEngineStatus::Success
+}
+
+/// # Safety
+/// `handle` must be a live engine handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn vello_chart_engine_set_series_visibility(
+    handle: *mut VelloChartEngineHandle,
+    series_id: u32,
+    visible: u32,
+) -> VelloChartEngineStatus {
+    clear_last_error();
+    let engine = match unsafe { handle.as_mut() } {
+        Some(engine) => engine,
+        None => {
+            set_last_error("Null engine handle passed to set_series_visibility");
+            return VelloChartEngineStatus::NullPointer;
+        }
+    };
+
+    engine.inner.set_series_visible(series_id, visible != 0);
+    VelloChartEngineStatus::Success
+}
+
+/// # Safety
+/// `handle` must be a live engine handle and `out_visible` must be writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn vello_chart_engine_get_series_visibility(
+    handle: *mut VelloChartEngineHandle,
+    series_id: u32,
+    out_visible: *mut u32,
+) -> VelloChartEngineStatus {
+    clear_last_error();
+    let engine = match unsafe { handle.as_ref() } {
+        Some(engine) => engine,
+        None => {
+            set_last_error("Null engine handle passed to get_series_visibility");
+            return VelloChartEngineStatus::NullPointer;
+        }
+    };
+    let out = match unsafe { out_visible.as_mut() } {
+        Some(out) => out,
+        None => {
+            set_last_error("Null visibility pointer");
+            return VelloChartEngineStatus::NullPointer;
+        }
+    };
+
+    *out = u32::from(engine.inner.is_series_visible(series_id));
+    VelloChartEngineStatus::Success
+}
+
+/// Adds the annotation described by `annotation`; fails when its id is already in use.
+///
+/// # Safety
+/// `handle` must be a live engine handle and `annotation` must point to a valid descriptor
+/// whose dash, label and pane pointers reference their stated lengths.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn vello_chart_engine_add_annotation(
+    handle: *mut VelloChartEngineHandle,
+    annotation: *const VelloChartAnnotation,
+) -> VelloChartEngineStatus {
+    clear_last_error();
+    let engine = match unsafe { handle.as_mut() } {
+        Some(engine) => engine,
+        None => {
+            set_last_error("Null engine handle passed to add_annotation");
+            return VelloChartEngineStatus::NullPointer;
+        }
+    };
+    let descriptor = match unsafe { annotation.as_ref() } {
+        Some(descriptor) => descriptor,
+        None => {
+            set_last_error("Null annotation pointer passed to add_annotation");
+            return VelloChartEngineStatus::NullPointer;
+        }
+    };
+
+    let parsed = match annotation_from_ffi(descriptor) {
+        Ok(parsed) => parsed,
+        Err(status) => return status,
+    };
+
+    if !engine
+        .inner
+        .add_annotation(descriptor.annotation_id, parsed)
+    {
+        set_last_error("Annotation id is already in use");
+        return VelloChartEngineStatus::InvalidArgument;
+    }
+    VelloChartEngineStatus::Success
+}
+
+/// Replaces an existing annotation; fails when no annotation has the descriptor's id.
+///
+/// # Safety
+/// Same requirements as [`vello_chart_engine_add_annotation`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn vello_chart_engine_update_annotation(
+    handle: *mut VelloChartEngineHandle,
+    annotation: *const VelloChartAnnotation,
+) -> VelloChartEngineStatus {
+    clear_last_error();
+    let engine = match unsafe { handle.as_mut() } {
+        Some(engine) => engine,
+        None => {
+            set_last_error("Null engine handle passed to update_annotation");
+            return VelloChartEngineStatus::NullPointer;
+        }
+    };
+    let descriptor = match unsafe { annotation.as_ref() } {
+        Some(descriptor) => descriptor,
+        None => {
+            set_last_error("Null annotation pointer passed to update_annotation");
+            return VelloChartEngineStatus::NullPointer;
+        }
+    };
+
+    let parsed = match annotation_from_ffi(descriptor) {
+        Ok(parsed) => parsed,
+        Err(status) => return status,
+    };
+
+    if !engine
+        .inner
+        .update_annotation(descriptor.annotation_id, parsed)
+    {
+        set_last_error("Unknown annotation id passed to update_annotation");
+        return VelloChartEngineStatus::InvalidArgument;
+    }
+    VelloChartEngineStatus::Success
+}
+
+/// # Safety
+/// `handle` must be a live engine handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn vello_chart_engine_remove_annotation(
+    handle: *mut VelloChartEngineHandle,
+    annotation_id: u32,
+) -> VelloChartEngineStatus {
+    clear_last_error();
+    let engine = match unsafe { handle.as_mut() } {
+        Some(engine) => engine,
+        None => {
+            set_last_error("Null engine handle passed to remove_annotation");
+            return VelloChartEngineStatus::NullPointer;
+        }
+    };
+
+    if !engine.inner.remove_annotation(annotation_id) {
+        set_last_error("Unknown annotation id passed to remove_annotation");
+        return VelloChartEngineStatus::InvalidArgument;
+    }
+    VelloChartEngineStatus::Success
+}
+
+/// # Safety
+/// `handle` must be a live engine handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn vello_chart_engine_clear_annotations(
+    handle: *mut VelloChartEngineHandle,
+) -> VelloChartEngineStatus {
+    clear_last_error();
+    let engine = match unsafe { handle.as_mut() } {
+        Some(engine) => engine,
+        None => {
+            set_last_error("Null engine handle passed to clear_annotations");
+            return VelloChartEngineStatus::NullPointer;
+        }
+    };
+
+    engine.inner.clear_annotations();
+    VelloChartEngineStatus::Success
+}
+
+/// Computes a series from another one; fails when the ids clash with existing derived
+/// series.
+///
+/// # Safety
+/// `handle` must be a live engine handle and `derived` must point to a valid descriptor.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn vello_chart_engine_add_derived_series(
+    handle: *mut VelloChartEngineHandle,
+    derived: *const VelloChartDerivedSeries,
+) -> VelloChartEngineStatus {
+    clear_last_error();
+    let engine = match unsafe { handle.as_mut() } {
+        Some(engine) => engine,
+        None => {
+            set_last_error("Null engine handle passed to add_derived_series");
+            return VelloChartEngineStatus::NullPointer;
+        }
+    };
+    let descriptor = match unsafe { derived.as_ref() } {
+        Some(descriptor) => descriptor,
+        None => {
+            set_last_error("Null derived series pointer passed to add_derived_series");
+            return VelloChartEngineStatus::NullPointer;
+        }
+    };
+
+    let parsed = match derived_series_from_ffi(descriptor) {
+        Ok(parsed) => parsed,
+        Err(status) => return status,
+    };
+
+    if !engine
+        .inner
+        .add_derived_series(descriptor.series_id, parsed)
+    {
+        set_last_error("Derived series ids conflict with an existing derived series or source");
+        return VelloChartEngineStatus::InvalidArgument;
+    }
+    VelloChartEngineStatus::Success
+}
+
+/// # Safety
+/// `handle` must be a live engine handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn vello_chart_engine_remove_derived_series(
+    handle: *mut VelloChartEngineHandle,
+    series_id: u32,
+) -> VelloChartEngineStatus {
+    clear_last_error();
+    let engine = match unsafe { handle.as_mut() } {
+        Some(engine) => engine,
+        None => {
+            set_last_error("Null engine handle passed to remove_derived_series");
+            return VelloChartEngineStatus::NullPointer;
+        }
+    };
+
+    if !engine.inner.remove_derived_series(series_id) {
+        set_last_error("Unknown series id passed to remove_derived_series");
+        return VelloChartEngineStatus::InvalidArgument;
+    }
+    VelloChartEngineStatus::Success
+}
+
+/// Renders a frame and encodes it as PNG or SVG without a GPU. On success `out_buffer`
+/// receives a buffer to release with `vello_chart_engine_export_buffer_destroy`.
+///
+/// # Safety
+/// `handle` must be a live engine handle, `options` must point to valid options and
+/// `out_buffer` must be writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn vello_chart_engine_export(
+    handle: *mut VelloChartEngineHandle,
+    width: u32,
+    height: u32,
+    options: *const VelloChartExportOptions,
+    out_buffer: *mut *mut VelloChartExportBuffer,
+) -> VelloChartEngineStatus {
+    clear_last_error();
+    let engine = match unsafe { handle.as_mut() } {
+        Some(engine) => engine,
+        None => {
+            set_last_error("Null engine handle passed to export");
+            return VelloChartEngineStatus::NullPointer;
+        }
+    };
+    let options = match unsafe { options.as_ref() } {
+        Some(options) => *options,
+        None => {
+            set_last_error("Null export options passed to export");
+            return VelloChartEngineStatus::NullPointer;
+        }
+    };
+    let out = match unsafe { out_buffer.as_mut() } {
+        Some(out) => out,
+        None => {
+            set_last_error("Null buffer pointer passed to export");
+            return VelloChartEngineStatus::NullPointer;
+        }
+    };
+    *out = std::ptr::null_mut();
+
+    let Some(format) = ExportFormat::from_u32(options.format) else {
+        set_last_error("Unknown export format");
+        return VelloChartEngineStatus::InvalidArgument;
+    };
+    let export_options = ExportOptions {
+        scale: options.scale,
+        background: (options.flags & EXPORT_FLAG_BACKGROUND != 0)
+            .then(|| options.background.into()),
+    };
+
+    match engine
+        .inner
+        .export_frame(width, height, format, &export_options)
+    {
+        Ok(bytes) => {
+            *out = Box::into_raw(Box::new(VelloChartExportBuffer { bytes }));
+            VelloChartEngineStatus::Success
+        }
+        Err(error @ ExportError::Png(_)) => {
+            set_last_error(error.to_string());
+            VelloChartEngineStatus::Unknown
+        }
+        Err(error) => {
+            set_last_error(error.to_string());
+            VelloChartEngineStatus::InvalidArgument
+        }
+    }
+}
+
+/// # Safety
+/// `buffer` must be a live export buffer and `out_data` and `out_length` must be
+/// writable. The data stays valid until the buffer is destroyed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn vello_chart_engine_export_buffer_data(
+    buffer: *const VelloChartExportBuffer,
+    out_data: *mut *const u8,
+    out_length: *mut usize,
+) -> VelloChartEngineStatus {
+    clear_last_error();
+    let buffer = match unsafe { buffer.as_ref() } {
+        Some(buffer) => buffer,
+        None => {
+            set_last_error("Null export buffer passed to export_buffer_data");
+            return VelloChartEngineStatus::NullPointer;
+        }
+    };
+    let (Some(data), Some(length)) = (unsafe { out_data.as_mut() }, unsafe { out_length.as_mut() })
+    else {
+        set_last_error("Null output pointer passed to export_buffer_data");
+        return VelloChartEngineStatus::NullPointer;
+    };
+
+    *data = buffer.bytes.as_ptr();
+    *length = buffer.bytes.len();
+    VelloChartEngineStatus::Success
+}
+
+/// # Safety
+/// `buffer` must be null or a pointer returned by `vello_chart_engine_export` or
+/// `vello_chart_engine_snapshot` that has not been destroyed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn vello_chart_engine_export_buffer_destroy(
+    buffer: *mut VelloChartExportBuffer,
+) {
+    if !buffer.is_null() {
+        unsafe { drop(Box::from_raw(buffer)) };
+    }
+}
+
+/// # Safety
+/// `handle` must be a live engine handle and `out_buffer` must be writable. The
+/// returned buffer is read and released with the export buffer functions.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn vello_chart_engine_snapshot(
+    handle: *mut VelloChartEngineHandle,
+    out_buffer: *mut *mut VelloChartExportBuffer,
+) -> VelloChartEngineStatus {
+    clear_last_error();
+    let engine = match unsafe { handle.as_ref() } {
+        Some(engine) => engine,
+        None => {
+            set_last_error("Null engine handle passed to snapshot");
+            return VelloChartEngineStatus::NullPointer;
+        }
+    };
+    let out = match unsafe { out_buffer.as_mut() } {
+        Some(out) => out,
+        None => {
+            set_last_error("Null buffer pointer passed to snapshot");
+            return VelloChartEngineStatus::NullPointer;
+        }
+    };
+
+    let bytes = engine.inner.snapshot();
+    *out = Box::into_raw(Box::new(VelloChartExportBuffer { bytes }));
+    VelloChartEngineStatus::Success
+}
+
+/// # Safety
+/// `handle` must be a live engine handle and `data` must point to `length` readable
+/// bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn vello_chart_engine_restore_snapshot(
+    handle: *mut VelloChartEngineHandle,
+    data: *const u8,
+    length: usize,
+) -> VelloChartEngineStatus {
+    clear_last_error();
+    let engine = match unsafe { handle.as_mut() } {
+        Some(engine) => engine,
+        None => {
+            set_last_error("Null engine handle passed to restore_snapshot");
+            return VelloChartEngineStatus::NullPointer;
+        }
+    };
+    if data.is_null() {
+        set_last_error("Null snapshot data passed to restore_snapshot");
+        return VelloChartEngineStatus::NullPointer;
+    }
+
+    let bytes = unsafe { slice::from_raw_parts(data, length) };
+    match engine.inner.restore_snapshot(bytes) {
+        Ok(()) => VelloChartEngineStatus::Success,
+        Err(error) => {
+            set_last_error(error.to_string());
+            VelloChartEngineStatus::InvalidArgument
+        }
+    }
+}
+
+/// # Safety
+/// `handle` must be a live engine handle and `out_viewport` must be writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn vello_chart_engine_get_viewport(
+    handle: *mut VelloChartEngineHandle,
+    out_viewport: *mut VelloChartViewport,
+) -> VelloChartEngineStatus {
+    clear_last_error();
+    let engine = match unsafe { handle.as_ref() } {
+        Some(engine) => engine,
+        None => {
+            set_last_error("Null engine handle passed to get_viewport");
+            return VelloChartEngineStatus::NullPointer;
+        }
+    };
+    let out_viewport = match unsafe { out_viewport.as_mut() } {
+        Some(out_viewport) => out_viewport,
+        None => {
+            set_last_error("Null viewport pointer");
+            return VelloChartEngineStatus::NullPointer;
+        }
+    };
+
+    *out_viewport = engine.inner.viewport().into();
+    VelloChartEngineStatus::Success
+}
+
+/// # Safety
+/// `handle` must be a live engine handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn vello_chart_engine_set_visible_range(
+    handle: *mut VelloChartEngineHandle,
+    range_start: f64,
+    range_end: f64,
+) -> VelloChartEngineStatus {
+    clear_last_error();
+    let engine = match unsafe { handle.as_mut() } {
+        Some(engine) => engine,
+        None => {
+            set_last_error("Null engine handle passed to set_visible_range");
+            return VelloChartEngineStatus::NullPointer;
+        }
+    };
+
+    if !range_start.is_finite() || !range_end.is_finite() || range_end <= range_start {
+        set_last_error("Visible range must be finite with range_end > range_start");
+        return VelloChartEngineStatus::InvalidArgument;
+    }
+
+    engine.inner.set_visible_range(range_start, range_end);
+    VelloChartEngineStatus::Success
+}
+
+/// # Safety
+/// `handle` must be a live engine handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn vello_chart_engine_set_follow_live(
+    handle: *mut VelloChartEngineHandle,
+    follow_live: u32,
+) -> VelloChartEngineStatus {
+    clear_last_error();
+    let engine = match unsafe { handle.as_mut() } {
+        Some(engine) => engine,
+        None => {
+            set_last_error("Null engine handle passed to set_follow_live");
+            return VelloChartEngineStatus::NullPointer;
+        }
+    };
+
+    engine.inner.set_follow_live(follow_live != 0);
+    VelloChartEngineStatus::Success
+}
+
+/// # Safety
+/// `handle` must be a live engine handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn vello_chart_engine_zoom(
+    handle: *mut VelloChartEngineHandle,
+    factor: f64,
+    anchor_seconds: f64,
+) -> VelloChartEngineStatus {
+    clear_last_error();
+    let engine = match unsafe { handle.as_mut() } {
+        Some(engine) => engine,
+        None => {
+            set_last_error("Null engine handle passed to zoom");
+            return VelloChartEngineStatus::NullPointer;
+        }
+    };
+
+    if !factor.is_finite() || factor <= 0.0 || !anchor_seconds.is_finite() {
+        set_last_error("Zoom factor must be positive and the anchor finite");
+        return VelloChartEngineStatus::InvalidArgument;
+    }
+
+    engine.inner.zoom(factor, anchor_seconds);
+    VelloChartEngineStatus::Success
+}
+
+/// # Safety
+/// `handle` must be a live engine handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn vello_chart_engine_pan(
+    handle: *mut VelloChartEngineHandle,
+    delta_seconds: f64,
+) -> VelloChartEngineStatus {
+    clear_last_error();
+    let engine = match unsafe { handle.as_mut() } {
+        Some(engine) => engine,
+        None => {
+            set_last_error("Null engine handle passed to pan");
+            return VelloChartEngineStatus::NullPointer;
+        }
+    };
+
+    if !delta_seconds.is_finite() {
+        set_last_error("Pan delta must be finite");
+        return VelloChartEngineStatus::InvalidArgument;
+    }
+
+    engine.inner.pan(delta_seconds);
+    VelloChartEngineStatus::Success
+}
+
+/// Sets how much history is retained; zero or negative keeps only the visible duration.
+///
+/// # Safety
+/// `handle` must be a live engine handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn vello_chart_engine_set_retention_duration(
+    handle: *mut VelloChartEngineHandle,
+    retention_seconds: f64,
+) -> VelloChartEngineStatus {
+    clear_last_error();
+    let engine = match unsafe { handle.as_mut() } {
+        Some(engine) => engine,
+        None => {
+            set_last_error("Null engine handle passed to set_retention_duration");
+            return VelloChartEngineStatus::NullPointer;
+        }
+    };
+
+    if retention_seconds.is_nan() {
+        set_last_error("Retention duration must not be NaN");
+        return VelloChartEngineStatus::InvalidArgument;
+    }
+
+    engine
+        .inner
+        .set_retention_duration((retention_seconds > 0.0).then_some(retention_seconds));
+    VelloChartEngineStatus::Success
+}
+
+/// Writes the sample nearest to `(x, y)` in the last rendered frame to `out_result`;
+/// `hit` is zero when nothing lies within `radius` pixels.
+///
+/// # Safety
+/// `handle` must be a live engine handle and `out_result` must be writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn vello_chart_engine_hit_test(
+    handle: *mut VelloChartEngineHandle,
+    x: f64,
+    y: f64,
+    radius: f64,
+    out_result: *mut VelloChartHitTestResult,
+) -> VelloChartEngineStatus {
+    clear_last_error();
+    let engine = match unsafe { handle.as_ref() } {
+        Some(engine) => engine,
+        None => {
+            set_last_error("Null engine handle passed to hit_test");
+            return VelloChartEngineStatus::NullPointer;
+        }
+    };
+    let out = match unsafe { out_result.as_mut() } {
+        Some(out) => out,
+        None => {
+            set_last_error("Null result pointer passed to hit_test");
+            return VelloChartEngineStatus::NullPointer;
+        }
+    };
+
+    if !x.is_finite() || !y.is_finite() || !radius.is_finite() || radius < 0.0 {
+        set_last_error("Hit test position and radius must be finite, with a non-negative radius");
+        return VelloChartEngineStatus::InvalidArgument;
+    }
+
+    *out = engine
+        .inner
+        .hit_test(x, y, radius)
+        .map(|result| (&result).into())
+        .unwrap_or_default();
+    VelloChartEngineStatus::Success
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn vello_chart_engine_set_trace_callback(
+    callback: Option<TraceCallback>,
+) -> VelloChartEngineStatus {
+    ensure_tracing_layer();
+
+    let storage = trace_callback_storage();
+    if let Ok(mut guard) = storage.write() {
+        *guard = callback;
+    } else {
+        set_last_error("Failed to register trace callback");
+        return VelloChartEngineStatus::Unknown;
+    }
+
+    VelloChartEngineStatus::Success
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn vello_chart_engine_data_bus_create(
+    capacity: usize,
+) -> *mut VelloChartDataBusHandle {
+    clear_last_error();
+    if capacity == 0 {
+        set_last_error("Data bus capacity must be greater than zero");
+        return std::ptr::null_mut();
+    }
+
+    Box::into_raw(Box::new(VelloChartDataBusHandle {
+        inner: DataBus::new(capacity),
+    }))
+}
+
+/// # Safety
+/// `handle` must be null or a pointer returned by `vello_chart_engine_data_bus_create`
+/// that has not been destroyed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn vello_chart_engine_data_bus_destroy(handle: *mut VelloChartDataBusHandle) {
+    if !handle.is_null() {
+        unsafe { drop(Box::from_raw(handle)) };
+    }
+}
+
+/// Queues a batch of points for one series. Safe to call from any thread without
+/// synchronising with the engine the bus is attached to.
+///
+/// # Safety
+/// `handle` must be a live data bus handle and `points` must be valid for
+/// `point_count` elements.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn vello_chart_engine_data_bus_push(
+    handle: *mut VelloChartDataBusHandle,
+    series_id: u32,
+    points: *const VelloChartDataPoint,
+    point_count: usize,
+) -> VelloChartEngineStatus {
+    clear_last_error();
+    let bus = match unsafe { handle.as_ref() } {
+        Some(bus) => bus,
+        None => {
+            set_last_error("Null data bus handle passed to push");
+            return VelloChartEngineStatus::NullPointer;
+        }
+    };
+
+    let Ok(point_slice) = slice_from_raw(points, point_count) else {
+        return VelloChartEngineStatus::NullPointer;
+    };
+
+    if point_slice.is_empty() {
+        return VelloChartEngineStatus::Success;
+    }
+
+    let sample = SeriesSample {
+        series_id,
+        points: point_slice
+            .iter()
+            .map(|point| DataPoint {
+                timestamp_ns: point.timestamp_ns,
+                value: point.value,
+            })
+            .collect(),
+    };
+
+    match bus.inner.push(sample) {
+        Ok(()) => VelloChartEngineStatus::Success,
+        Err(_) => {
+            set_last_error("Data bus is full; batch dropped");
+            VelloChartEngineStatus::QueueFull
+        }
+    }
+}
+
+/// # Safety
+/// `handle` must be a live data bus handle and `out_stats` must be writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn vello_chart_engine_data_bus_stats(
+    handle: *mut VelloChartDataBusHandle,
+    out_stats: *mut VelloChartDataBusStats,
+) -> VelloChartEngineStatus {
+    clear_last_error();
+    let bus = match unsafe { handle.as_ref() } {
+        Some(bus) => bus,
+        None => {
+            set_last_error("Null data bus handle passed to stats");
+            return VelloChartEngineStatus::NullPointer;
+        }
+    };
+    let out_stats = match unsafe { out_stats.as_mut() } {
+        Some(out_stats) => out_stats,
+        None => {
+            set_last_error("Null stats pointer passed to data bus stats");
+            return VelloChartEngineStatus::NullPointer;
+        }
+    };
+
+    *out_stats = bus.inner.stats().into();
+    VelloChartEngineStatus::Success
+}
+
+/// Attaches `bus` to the engine; the engine keeps its own reference, so the bus
+/// handle may be destroyed independently.
+///
+/// # Safety
+/// `handle` and `bus` must be live handles created by this library.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn vello_chart_engine_attach_data_bus(
+    handle: *mut VelloChartEngineHandle,
+    bus: *mut VelloChartDataBusHandle,
+) -> VelloChartEngineStatus {
+    clear_last_error();
+    let engine = match unsafe { handle.as_mut() } {
+        Some(engine) => engine,
+        None => {
+            set_last_error("Null engine handle passed to attach_data_bus");
+            return VelloChartEngineStatus::NullPointer;
+        }
+    };
+    let bus = match unsafe { bus.as_ref() } {
+        Some(bus) => bus,
+        None => {
+            set_last_error("Null data bus handle passed to attach_data_bus");
+            return VelloChartEngineStatus::NullPointer;
+        }
+    };
+
+    engine.inner.attach_data_bus(bus.inner.clone());
+    VelloChartEngineStatus::Success
+}
+
+/// # Safety
+/// `handle` must be a live engine handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn vello_chart_engine_detach_data_bus(
+    handle: *mut VelloChartEngineHandle,
+) -> VelloChartEngineStatus {
+    clear_last_error();
+    let engine = match unsafe { handle.as_mut() } {
+        Some(engine) => engine,
+        None => {
+            set_last_error("Null engine handle passed to detach_data_bus");
+            return VelloChartEngineStatus::NullPointer;
+        }
+    };
 
+    engine.inner.detach_data_bus();
     VelloChartEngineStatus::Success
 }
@@ -0,0 +1,289 @@
+//! Pointer overlay drawn above the series: crosshair, snapped markers and a value callout.
+
+use vello::{
+    Scene,
+    kurbo::{Affine, Circle, Line, Point, Rect, RoundedRect, Stroke},
+    peniko::{Brush, Color, Fill},
+};
+use vello_composition::{AxisLabelOptions, LabelLayout, PlotArea, TimeFormat, ValueScale};
+
+use crate::hit_test::HitPane;
+use crate::{ChartTheme, SeriesKind, SeriesPoint, ValueAxis};
+
+const MARKER_RADIUS: f64 = 4.0;
+const SWATCH_SIZE: f64 = 8.0;
+const SWATCH_GAP: f64 = 6.0;
+const ROW_GAP: f64 = 2.0;
+
+struct TooltipRow {
+    swatch: Option<Color>,
+    layout: LabelLayout,
+}
+
+/// Encodes the overlay for a pointer at `cursor`, returning the number of paths drawn.
+///
+/// Nothing is drawn while the pointer is outside the plot area.
+pub(crate) fn draw_cursor_overlay<'a>(
+    scene: &mut Scene,
+    cursor: Point,
+    panes: &[HitPane],
+    lookup: impl Fn(u32) -> Option<&'a [SeriesPoint]>,
+    label_of: impl Fn(u32) -> Option<&'a str>,
+    chart_width: f64,
+    chart_height: f64,
+    plot: &PlotArea,
+    theme: &ChartTheme,
+    axis_labels: &AxisLabelOptions,
+) -> u32 {
+    let Some(first_pane) = panes.first() else {
+        return 0;
+    };
+    if cursor.x < plot.left
+        || cursor.x > plot.right()
+        || cursor.y < plot.top
+        || cursor.y > plot.bottom()
+    {
+        return 0;
+    }
+
+    let mut encoded = 0;
+    let crosshair = theme.crosshair.stroke();
+    let crosshair_color = theme.crosshair.color;
+    scene.stroke(
+        &crosshair,
+        Affine::IDENTITY,
+        crosshair_color,
+        None,
+        &Line::new((cursor.x, plot.top), (cursor.x, plot.bottom())),
+    );
+    encoded += 1;
+
+    if let Some(pane) = panes
+        .iter()
+        .find(|pane| cursor.y >= pane.plot_area.top && cursor.y <= pane.plot_area.bottom())
+    {
+        let area = &pane.plot_area;
+        scene.stroke(
+            &crosshair,
+            Affine::IDENTITY,
+            crosshair_color,
+            None,
+            &Line::new((area.left, cursor.y), (area.right(), cursor.y)),
+        );
+        encoded += 1;
+    }
+
+    // Every pane shares the time axis, so one timestamp snaps all of them.
+    let cursor_time = first_pane.time_at(cursor.x);
+    let mut rows = Vec::new();
+    if let Some(layout) = theme
+        .label
+        .layout(&format_cursor_time(cursor_time, axis_labels))
+    {
+        rows.push(TooltipRow {
+            swatch: None,
+            layout,
+        });
+    }
+
+    for pane in panes {
+        for series in &pane.series {
+            let Some(sample) = series
+                .drawn_span(&lookup)
+                .and_then(|span| nearest_visible(span, cursor_time, pane.range_start, pane.window))
+            else {
+                continue;
+            };
+
+            if series.kind != SeriesKind::Heatmap {
+                let marker = Circle::new(pane.project(series, sample), MARKER_RADIUS);
+                scene.fill(
+                    Fill::NonZero,
+                    Affine::IDENTITY,
+                    &Brush::Solid(series.color),
+                    None,
+                    &marker,
+                );
+                scene.stroke(
+                    &Stroke::new(1.5),
+                    Affine::IDENTITY,
+                    theme.label.color,
+                    None,
+                    &marker,
+                );
+                encoded += 2;
+            }
+
+            let text = match label_of(series.series_id) {
+                Some(label) => format!(
+                    "{label}: {}",
+                    format_cursor_value(sample.value, &series.value_axis)
+                ),
+                None => format!(
+                    "Series {}: {}",
+                    series.series_id,
+                    format_cursor_value(sample.value, &series.value_axis)
+                ),
+            };
+            if let Some(layout) = theme.label.layout(&text) {
+                rows.push(TooltipRow {
+                    swatch: Some(series.color),
+                    layout,
+                });
+            }
+        }
+    }
+
+    encoded + draw_tooltip(scene, cursor, &rows, chart_width, chart_height, theme)
+}
+
+fn draw_tooltip(
+    scene: &mut Scene,
+    cursor: Point,
+    rows: &[TooltipRow],
+    chart_width: f64,
+    chart_height: f64,
+    theme: &ChartTheme,
+) -> u32 {
+    if rows.is_empty() {
+        return 0;
+    }
+    let callout = &theme.callout;
+
+    let row_width = |row: &TooltipRow| {
+        let swatch = if row.swatch.is_some() {
+            SWATCH_SIZE + SWATCH_GAP
+        } else {
+            0.0
+        };
+        swatch + f64::from(row.layout.width)
+    };
+    let content_width = rows.iter().map(row_width).fold(0.0, f64::max);
+    let content_height = rows
+        .iter()
+        .map(|row| f64::from(row.layout.height))
+        .sum::<f64>()
+        + ROW_GAP * (rows.len() - 1) as f64;
+    let width = content_width + 2.0 * callout.padding_x;
+    let height = content_height + 2.0 * callout.padding_y;
+
+    // Prefer below-right of the pointer and flip to the other side near the chart edges.
+    let mut left = cursor.x + callout.offset_x;
+    if left + width > chart_width {
+        left = cursor.x - callout.offset_x - width;
+    }
+    let mut top = cursor.y + callout.gap_y;
+    if top + height > chart_height {
+        top = cursor.y - callout.gap_y - height;
+    }
+    let left = left.clamp(0.0, (chart_width - width).max(0.0));
+    let top = top.clamp(0.0, (chart_height - height).max(0.0));
+
+    let background = RoundedRect::new(left, top, left + width, top + height, callout.corner_radius);
+    scene.fill(
+        Fill::NonZero,
+        Affine::IDENTITY,
+        &Brush::Solid(theme.tooltip_background),
+        None,
+        &background,
+    );
+    scene.stroke(
+        &Stroke::new(1.0),
+        Affine::IDENTITY,
+        theme.panel_border,
+        None,
+        &background,
+    );
+    let mut encoded = 2;
+
+    let mut row_top = top + callout.padding_y;
+    for row in rows {
+        let row_height = f64::from(row.layout.height);
+        let mut text_left = left + callout.padding_x;
+        if let Some(color) = row.swatch {
+            let swatch_top = row_top + (row_height - SWATCH_SIZE) * 0.5;
+            scene.fill(
+                Fill::NonZero,
+                Affine::IDENTITY,
+                &Brush::Solid(color),
+                None,
+                &Rect::new(
+                    text_left,
+                    swatch_top,
+                    text_left + SWATCH_SIZE,
+                    swatch_top + SWATCH_SIZE,
+                ),
+            );
+            text_left += SWATCH_SIZE + SWATCH_GAP;
+            encoded += 1;
+        }
+
+        let baseline_y = row_top + f64::from(row.layout.ascent);
+        encoded += row.layout.draw(
+            scene,
+            theme.label.font_size,
+            Affine::translate((text_left, baseline_y)),
+            &Brush::Solid(theme.label.color),
+        );
+
+        row_top += row_height + ROW_GAP;
+    }
+
+    encoded
+}
+
+fn nearest_visible(
+    span: &[SeriesPoint],
+    timestamp: f64,
+    range_start: f64,
+    window: f64,
+) -> Option<&SeriesPoint> {
+    let index = span.partition_point(|point| point.timestamp_seconds < timestamp);
+    let before = index.checked_sub(1).map(|index| &span[index]);
+    let after = span.get(index);
+    let nearest = match (before, after) {
+        (Some(before), Some(after)) => {
+            if timestamp - before.timestamp_seconds <= after.timestamp_seconds - timestamp {
+                before
+            } else {
+                after
+            }
+        }
+        (Some(point), None) | (None, Some(point)) => point,
+        (None, None) => return None,
+    };
+
+    // A gap under the cursor shows no value for the series.
+    let visible = nearest.value.is_finite()
+        && nearest.timestamp_seconds >= range_start
+        && nearest.timestamp_seconds <= range_start + window;
+    visible.then_some(nearest)
+}
+
+/// Formats the cursor time in the time axis' zone and pattern; without a pattern it
+/// shows the time of day to the millisecond.
+fn format_cursor_time(timestamp: f64, axis_labels: &AxisLabelOptions) -> String {
+    let fallback = TimeFormat::parse("%H:%M:%S.%f").expect("built-in time formats are valid");
+    axis_labels.format_time(timestamp, &fallback)
+}
+
+/// Formats `value` with enough decimals to resolve roughly a hundredth of the pane's range.
+fn format_cursor_value(value: f64, axis: &ValueAxis) -> String {
+    if !value.is_finite() {
+        return "NaN".to_string();
+    }
+
+    let min = axis.scale.inverse(axis.scaled_min);
+    let max = axis.scale.inverse(axis.scaled_min + axis.scaled_range);
+    let mut resolution = (max - min).abs() / 100.0;
+    if !resolution.is_finite() || resolution <= 0.0 || !matches!(axis.scale, ValueScale::Linear) {
+        resolution = value.abs() / 100.0;
+    }
+
+    let decimals = if resolution > 0.0 && resolution < 1.0 {
+        ((-resolution.log10()).ceil() as usize).min(6)
+    } else {
+        0
+    };
+    format!("{value:.decimals$}")
+}
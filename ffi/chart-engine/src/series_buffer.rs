@@ -0,0 +1,248 @@
+//! Sliding-window storage for series samples.
+//!
+//! Samples live in a single contiguous allocation so renderers can keep borrowing
+//! plain sample slices. Trimming the window only advances a head offset; the expired
+//! prefix is reclaimed lazily once it outgrows the live samples, which keeps both
+//! appends and trims amortized O(1).
+
+use crate::{AddOutcome, SeriesPoint};
+
+/// Expired samples tolerated before the live window is shifted back to the front.
+const MIN_COMPACT_HEAD: usize = 256;
+
+/// Timestamped sample that can be kept in a [`SeriesBuffer`].
+pub(crate) trait TimedSample: Copy {
+    fn timestamp_seconds(&self) -> f64;
+
+    /// Whether writing `other` over `self` would leave the sample unchanged.
+    fn same_values(&self, other: &Self) -> bool;
+}
+
+impl TimedSample for SeriesPoint {
+    fn timestamp_seconds(&self) -> f64 {
+        self.timestamp_seconds
+    }
+
+    fn same_values(&self, other: &Self) -> bool {
+        (self.value - other.value).abs() < f64::EPSILON
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct SeriesBuffer<T = SeriesPoint> {
+    storage: Vec<T>,
+    head: usize,
+}
+
+impl<T> Default for SeriesBuffer<T> {
+    fn default() -> Self {
+        Self {
+            storage: Vec::new(),
+            head: 0,
+        }
+    }
+}
+
+impl<T: TimedSample> SeriesBuffer<T> {
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        Self {
+            storage: Vec::with_capacity(capacity),
+            head: 0,
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.storage.len() - self.head
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub(crate) fn as_slice(&self) -> &[T] {
+        &self.storage[self.head..]
+    }
+
+    /// Live samples needed to draw `[range_start, range_end]`: those inside the range
+    /// plus the nearest sample beyond each edge, so segments crossing the edges reach
+    /// them. Callers clip the overhang to the plot area.
+    pub(crate) fn range(&self, range_start: f64, range_end: f64) -> &[T] {
+        let live = self.as_slice();
+        let (start, end) = Self::bounds(live, range_start, range_end);
+        &live[start.saturating_sub(1)..(end + 1).min(live.len()).max(start)]
+    }
+
+    /// Live samples with timestamps inside `[range_start, range_end]`.
+    pub(crate) fn within(&self, range_start: f64, range_end: f64) -> &[T] {
+        let live = self.as_slice();
+        let (start, end) = Self::bounds(live, range_start, range_end);
+        &live[start..end.max(start)]
+    }
+
+    fn bounds(live: &[T], range_start: f64, range_end: f64) -> (usize, usize) {
+        let start = live.partition_point(|point| point.timestamp_seconds() < range_start);
+        let end = live.partition_point(|point| point.timestamp_seconds() <= range_end);
+        (start, end)
+    }
+
+    /// Writes `sample`, replacing any existing sample with the same timestamp.
+    pub(crate) fn write(&mut self, sample: T) -> AddOutcome {
+        // Fully trimmed storage is cleared, so `last` is always the newest live sample.
+        let is_tail = self
+            .storage
+            .last()
+            .is_none_or(|last| last.timestamp_seconds() < sample.timestamp_seconds());
+        if is_tail {
+            self.storage.push(sample);
+            return AddOutcome::Appended;
+        }
+
+        let live = &self.storage[self.head..];
+        match live.binary_search_by(|point| {
+            point
+                .timestamp_seconds()
+                .total_cmp(&sample.timestamp_seconds())
+        }) {
+            Ok(index) => {
+                let slot = &mut self.storage[self.head + index];
+                if slot.same_values(&sample) {
+                    return AddOutcome::Unchanged;
+                }
+                *slot = sample;
+                AddOutcome::Updated
+            }
+            Err(0) if self.head > 0 => {
+                // Reuse the slot freed by the last trim instead of shifting the window.
+                self.head -= 1;
+                self.storage[self.head] = sample;
+                AddOutcome::Inserted
+            }
+            Err(index) => {
+                self.storage.insert(self.head + index, sample);
+                AddOutcome::Inserted
+            }
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.storage.clear();
+        self.head = 0;
+    }
+
+    /// Drops samples older than `cutoff`, returning how many were removed.
+    pub(crate) fn trim_before(&mut self, cutoff: f64) -> usize {
+        let removed = self
+            .as_slice()
+            .partition_point(|point| point.timestamp_seconds() < cutoff);
+        if removed == 0 {
+            return 0;
+        }
+
+        self.head += removed;
+        if self.head == self.storage.len() {
+            self.storage.clear();
+            self.head = 0;
+        } else if self.head >= MIN_COMPACT_HEAD && self.head >= self.len() {
+            self.compact();
+        }
+
+        removed
+    }
+
+    fn compact(&mut self) {
+        let live = self.len();
+        self.storage.copy_within(self.head.., 0);
+        self.storage.truncate(live);
+        self.head = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(timestamp_seconds: f64, value: f64) -> SeriesPoint {
+        SeriesPoint {
+            timestamp_seconds,
+            value,
+        }
+    }
+
+    fn timestamps(buffer: &SeriesBuffer) -> Vec<f64> {
+        buffer
+            .as_slice()
+            .iter()
+            .map(|p| p.timestamp_seconds)
+            .collect()
+    }
+
+    #[test]
+    fn writes_classify_like_sorted_insert() {
+        let mut buffer = SeriesBuffer::with_capacity(8);
+        assert_eq!(buffer.write(point(1.0, 1.0)), AddOutcome::Appended);
+        assert_eq!(buffer.write(point(3.0, 3.0)), AddOutcome::Appended);
+        assert_eq!(buffer.write(point(2.0, 2.0)), AddOutcome::Inserted);
+        assert_eq!(buffer.write(point(2.0, 2.0)), AddOutcome::Unchanged);
+        assert_eq!(buffer.write(point(2.0, 5.0)), AddOutcome::Updated);
+        assert_eq!(buffer.write(point(0.5, 0.0)), AddOutcome::Inserted);
+        assert_eq!(timestamps(&buffer), vec![0.5, 1.0, 2.0, 3.0]);
+        assert_eq!(buffer.as_slice()[2].value, 5.0);
+    }
+
+    #[test]
+    fn trimming_keeps_window_contiguous_across_compactions() {
+        let mut buffer = SeriesBuffer::with_capacity(64);
+        let window = 100.0;
+        for index in 0..10_000 {
+            let timestamp = index as f64;
+            buffer.write(point(timestamp, timestamp));
+            buffer.trim_before(timestamp - window);
+            assert!(buffer.len() <= window as usize + 1);
+        }
+
+        let live = timestamps(&buffer);
+        assert_eq!(live.first().copied(), Some(9_899.0));
+        assert_eq!(live.last().copied(), Some(9_999.0));
+        assert!(live.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(buffer.storage.len() <= 2 * (window as usize + 1) + MIN_COMPACT_HEAD);
+    }
+
+    #[test]
+    fn range_reaches_one_sample_past_each_edge() {
+        let mut buffer = SeriesBuffer::with_capacity(8);
+        for index in 0..8 {
+            buffer.write(point(index as f64, 0.0));
+        }
+        let stamps = |span: &[SeriesPoint]| -> Vec<f64> {
+            span.iter().map(|p| p.timestamp_seconds).collect()
+        };
+
+        assert_eq!(stamps(buffer.range(2.5, 4.5)), vec![2.0, 3.0, 4.0, 5.0]);
+        assert_eq!(stamps(buffer.within(2.5, 4.5)), vec![3.0, 4.0]);
+        assert_eq!(stamps(buffer.range(3.0, 4.0)), vec![2.0, 3.0, 4.0, 5.0]);
+        assert_eq!(stamps(buffer.range(3.2, 3.8)), vec![3.0, 4.0]);
+        assert!(buffer.within(3.2, 3.8).is_empty());
+        assert_eq!(stamps(buffer.range(-5.0, 0.5)), vec![0.0, 1.0]);
+        assert_eq!(stamps(buffer.range(6.5, 20.0)), vec![6.0, 7.0]);
+        assert_eq!(stamps(buffer.range(10.0, 20.0)), vec![7.0]);
+        assert!(
+            SeriesBuffer::<SeriesPoint>::default()
+                .range(0.0, 1.0)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn late_samples_reuse_trimmed_slots() {
+        let mut buffer = SeriesBuffer::with_capacity(8);
+        for index in 0..8 {
+            buffer.write(point(index as f64, 0.0));
+        }
+        assert_eq!(buffer.trim_before(4.0), 4);
+        assert_eq!(buffer.write(point(3.5, 1.0)), AddOutcome::Inserted);
+        assert_eq!(timestamps(&buffer), vec![3.5, 4.0, 5.0, 6.0, 7.0]);
+        assert_eq!(buffer.trim_before(100.0), 5);
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.write(point(1.0, 0.0)), AddOutcome::Appended);
+    }
+}
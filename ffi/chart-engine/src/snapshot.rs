@@ -0,0 +1,601 @@
+//! Binary snapshots of engine state.
+//!
+//! A snapshot is the `VCES` magic, a little-endian `u16` format version and the state
+//! below encoded with postcard. The schema mirrors the engine types instead of
+//! serialising them directly, so internal refactors don't change the format; bump
+//! [`SNAPSHOT_VERSION`] whenever the schema itself changes so older snapshots are
+//! rejected instead of misread.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use vello::peniko::Color;
+use vello_composition::ValueScale;
+
+use crate::candles::OhlcPoint;
+use crate::color_scale::ColorScale;
+use crate::decimation::DecimationMode;
+use crate::gaps::GapStyle;
+use crate::interpolation::Interpolation;
+use crate::stacking::{StackGroup, StackMode};
+use crate::viewport::ViewportRange;
+use crate::{
+    CompositionPane, SeriesDefinition, SeriesKind, SeriesPoint, SeriesStyle, ValueAxisSide,
+};
+
+const SNAPSHOT_MAGIC: [u8; 4] = *b"VCES";
+const HEADER_LEN: usize = SNAPSHOT_MAGIC.len() + 2;
+
+/// Format version written to, and required of, snapshot headers.
+pub const SNAPSHOT_VERSION: u16 = 1;
+
+#[derive(Debug)]
+pub enum SnapshotError {
+    /// The bytes don't start with a snapshot header.
+    NotASnapshot,
+    /// The snapshot was written with a different format version.
+    UnsupportedVersion { found: u16 },
+    /// The payload is truncated or malformed.
+    Decode(postcard::Error),
+    /// The payload decoded but holds a value the engine rejects.
+    InvalidValue(&'static str),
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotASnapshot => f.write_str("data is not a chart engine snapshot"),
+            Self::UnsupportedVersion { found } => write!(
+                f,
+                "snapshot format version {found} is not supported (expected {SNAPSHOT_VERSION})"
+            ),
+            Self::Decode(error) => write!(f, "snapshot payload is malformed: {error}"),
+            Self::InvalidValue(what) => write!(f, "snapshot holds an invalid {what}"),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Decode(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Everything a snapshot restores.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct EngineSnapshot {
+    pub(crate) palette: Vec<SnapshotColor>,
+    pub(crate) next_palette_slot: u64,
+    pub(crate) definitions: Vec<(u32, SnapshotDefinition)>,
+    pub(crate) series: Vec<SnapshotSeries>,
+    pub(crate) panes: Vec<SnapshotPane>,
+    pub(crate) viewport: SnapshotViewport,
+}
+
+pub(crate) fn encode(snapshot: &EngineSnapshot) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(HEADER_LEN);
+    bytes.extend_from_slice(&SNAPSHOT_MAGIC);
+    bytes.extend_from_slice(&SNAPSHOT_VERSION.to_le_bytes());
+    // Serialising into a growable buffer only fails for types serde can't represent.
+    postcard::to_extend(snapshot, bytes).expect("snapshot schema is serialisable")
+}
+
+pub(crate) fn decode(bytes: &[u8]) -> Result<EngineSnapshot, SnapshotError> {
+    let Some((header, payload)) = bytes.split_at_checked(HEADER_LEN) else {
+        return Err(SnapshotError::NotASnapshot);
+    };
+    if header[..SNAPSHOT_MAGIC.len()] != SNAPSHOT_MAGIC {
+        return Err(SnapshotError::NotASnapshot);
+    }
+    let found = u16::from_le_bytes([header[4], header[5]]);
+    if found != SNAPSHOT_VERSION {
+        return Err(SnapshotError::UnsupportedVersion { found });
+    }
+    postcard::from_bytes(payload).map_err(SnapshotError::Decode)
+}
+
+/// Colour as straight-alpha sRGB components, so colours round-trip exactly.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub(crate) struct SnapshotColor([f32; 4]);
+
+impl From<Color> for SnapshotColor {
+    fn from(color: Color) -> Self {
+        Self(color.components)
+    }
+}
+
+impl From<SnapshotColor> for Color {
+    fn from(color: SnapshotColor) -> Self {
+        Color::new(color.0)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct SnapshotDefinition {
+    kind: SnapshotSeriesKind,
+    baseline: Option<f64>,
+    fill_opacity: Option<f32>,
+    stroke_width: Option<f64>,
+    marker_size: Option<f64>,
+    bar_width_seconds: Option<f64>,
+    band_lower_id: Option<u32>,
+    heatmap_bucket_index: Option<u32>,
+    heatmap_bucket_count: Option<u32>,
+    decimation: Option<SnapshotDecimation>,
+    value_axis: Option<SnapshotValueAxis>,
+    up_color: Option<SnapshotColor>,
+    down_color: Option<SnapshotColor>,
+    stack_group: Option<SnapshotStackGroup>,
+    max_gap_seconds: Option<f64>,
+    gap_style: Option<SnapshotGapStyle>,
+    interpolation: Option<SnapshotInterpolation>,
+    color_scale: Option<Vec<(f32, SnapshotColor)>>,
+    color_domain_min: Option<f64>,
+    color_domain_max: Option<f64>,
+}
+
+impl From<&SeriesDefinition> for SnapshotDefinition {
+    fn from(definition: &SeriesDefinition) -> Self {
+        Self {
+            kind: definition.kind.into(),
+            baseline: definition.baseline,
+            fill_opacity: definition.fill_opacity,
+            stroke_width: definition.stroke_width,
+            marker_size: definition.marker_size,
+            bar_width_seconds: definition.bar_width_seconds,
+            band_lower_id: definition.band_lower_id,
+            heatmap_bucket_index: definition.heatmap_bucket_index,
+            heatmap_bucket_count: definition.heatmap_bucket_count,
+            decimation: definition.decimation.map(Into::into),
+            value_axis: definition.value_axis.map(Into::into),
+            up_color: definition.up_color.map(Into::into),
+            down_color: definition.down_color.map(Into::into),
+            stack_group: definition.stack_group.map(Into::into),
+            max_gap_seconds: definition.max_gap_seconds,
+            gap_style: definition.gap_style.map(Into::into),
+            interpolation: definition.interpolation.map(Into::into),
+            color_scale: definition.color_scale.as_ref().map(|scale| {
+                scale
+                    .stops()
+                    .iter()
+                    .map(|(offset, color)| (*offset, (*color).into()))
+                    .collect()
+            }),
+            color_domain_min: definition.color_domain_min,
+            color_domain_max: definition.color_domain_max,
+        }
+    }
+}
+
+impl TryFrom<SnapshotDefinition> for SeriesDefinition {
+    type Error = SnapshotError;
+
+    fn try_from(definition: SnapshotDefinition) -> Result<Self, Self::Error> {
+        let color_scale = match definition.color_scale {
+            Some(stops) => Some(
+                ColorScale::from_stops(
+                    stops
+                        .into_iter()
+                        .map(|(offset, color)| (offset, color.into()))
+                        .collect(),
+                )
+                .ok_or(SnapshotError::InvalidValue("colour scale"))?,
+            ),
+            None => None,
+        };
+        Ok(Self {
+            kind: definition.kind.into(),
+            baseline: definition.baseline,
+            fill_opacity: definition.fill_opacity,
+            stroke_width: definition.stroke_width,
+            marker_size: definition.marker_size,
+            bar_width_seconds: definition.bar_width_seconds,
+            band_lower_id: definition.band_lower_id,
+            heatmap_bucket_index: definition.heatmap_bucket_index,
+            heatmap_bucket_count: definition.heatmap_bucket_count,
+            decimation: definition.decimation.map(Into::into),
+            value_axis: definition.value_axis.map(Into::into),
+            up_color: definition.up_color.map(Into::into),
+            down_color: definition.down_color.map(Into::into),
+            stack_group: definition.stack_group.map(Into::into),
+            max_gap_seconds: definition.max_gap_seconds,
+            gap_style: definition.gap_style.map(Into::into),
+            interpolation: definition.interpolation.map(Into::into),
+            color_scale,
+            color_domain_min: definition.color_domain_min,
+            color_domain_max: definition.color_domain_max,
+        })
+    }
+}
+
+/// Samples, overrides and visibility of one series.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct SnapshotSeries {
+    pub(crate) series_id: u32,
+    pub(crate) palette_slot: u64,
+    pub(crate) visible: bool,
+    pub(crate) label: Option<String>,
+    pub(crate) stroke_width: Option<f64>,
+    pub(crate) color: Option<SnapshotColor>,
+    /// `(timestamp_seconds, value)` pairs; `NaN` values are gaps.
+    pub(crate) points: Vec<(f64, f64)>,
+    /// `(timestamp_seconds, open, high, low, close, volume)` bars of candle series.
+    pub(crate) candles: Vec<(f64, f64, f64, f64, f64, f64)>,
+}
+
+impl SnapshotSeries {
+    pub(crate) fn style(&self) -> SeriesStyle {
+        SeriesStyle {
+            label: self.label.clone(),
+            stroke_width: self.stroke_width,
+            color: self.color.map(Into::into),
+        }
+    }
+
+    pub(crate) fn points(&self) -> impl Iterator<Item = SeriesPoint> + '_ {
+        self.points
+            .iter()
+            .map(|(timestamp_seconds, value)| SeriesPoint {
+                timestamp_seconds: *timestamp_seconds,
+                value: *value,
+            })
+    }
+
+    pub(crate) fn candles(&self) -> impl Iterator<Item = OhlcPoint> + '_ {
+        self.candles.iter().map(
+            |(timestamp_seconds, open, high, low, close, volume)| OhlcPoint {
+                timestamp_seconds: *timestamp_seconds,
+                open: *open,
+                high: *high,
+                low: *low,
+                close: *close,
+                volume: *volume,
+            },
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub(crate) enum SnapshotSeriesKind {
+    Line,
+    Area,
+    Scatter,
+    Bar,
+    Band,
+    Heatmap,
+    Candlestick,
+    Ohlc,
+}
+
+impl From<SeriesKind> for SnapshotSeriesKind {
+    fn from(kind: SeriesKind) -> Self {
+        match kind {
+            SeriesKind::Line => Self::Line,
+            SeriesKind::Area => Self::Area,
+            SeriesKind::Scatter => Self::Scatter,
+            SeriesKind::Bar => Self::Bar,
+            SeriesKind::Band => Self::Band,
+            SeriesKind::Heatmap => Self::Heatmap,
+            SeriesKind::Candlestick => Self::Candlestick,
+            SeriesKind::Ohlc => Self::Ohlc,
+        }
+    }
+}
+
+impl From<SnapshotSeriesKind> for SeriesKind {
+    fn from(kind: SnapshotSeriesKind) -> Self {
+        match kind {
+            SnapshotSeriesKind::Line => Self::Line,
+            SnapshotSeriesKind::Area => Self::Area,
+            SnapshotSeriesKind::Scatter => Self::Scatter,
+            SnapshotSeriesKind::Bar => Self::Bar,
+            SnapshotSeriesKind::Band => Self::Band,
+            SnapshotSeriesKind::Heatmap => Self::Heatmap,
+            SnapshotSeriesKind::Candlestick => Self::Candlestick,
+            SnapshotSeriesKind::Ohlc => Self::Ohlc,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub(crate) enum SnapshotDecimation {
+    None,
+    MinMax,
+    Lttb,
+}
+
+impl From<DecimationMode> for SnapshotDecimation {
+    fn from(mode: DecimationMode) -> Self {
+        match mode {
+            DecimationMode::None => Self::None,
+            DecimationMode::MinMax => Self::MinMax,
+            DecimationMode::Lttb => Self::Lttb,
+        }
+    }
+}
+
+impl From<SnapshotDecimation> for DecimationMode {
+    fn from(mode: SnapshotDecimation) -> Self {
+        match mode {
+            SnapshotDecimation::None => Self::None,
+            SnapshotDecimation::MinMax => Self::MinMax,
+            SnapshotDecimation::Lttb => Self::Lttb,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub(crate) enum SnapshotValueAxis {
+    Left,
+    Right,
+}
+
+impl From<ValueAxisSide> for SnapshotValueAxis {
+    fn from(side: ValueAxisSide) -> Self {
+        match side {
+            ValueAxisSide::Left => Self::Left,
+            ValueAxisSide::Right => Self::Right,
+        }
+    }
+}
+
+impl From<SnapshotValueAxis> for ValueAxisSide {
+    fn from(side: SnapshotValueAxis) -> Self {
+        match side {
+            SnapshotValueAxis::Left => Self::Left,
+            SnapshotValueAxis::Right => Self::Right,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub(crate) enum SnapshotStackMode {
+    Normal,
+    Percent,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub(crate) struct SnapshotStackGroup {
+    id: u32,
+    mode: SnapshotStackMode,
+}
+
+impl From<StackGroup> for SnapshotStackGroup {
+    fn from(group: StackGroup) -> Self {
+        Self {
+            id: group.id,
+            mode: match group.mode {
+                StackMode::Normal => SnapshotStackMode::Normal,
+                StackMode::Percent => SnapshotStackMode::Percent,
+            },
+        }
+    }
+}
+
+impl From<SnapshotStackGroup> for StackGroup {
+    fn from(group: SnapshotStackGroup) -> Self {
+        Self {
+            id: group.id,
+            mode: match group.mode {
+                SnapshotStackMode::Normal => StackMode::Normal,
+                SnapshotStackMode::Percent => StackMode::Percent,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub(crate) enum SnapshotGapStyle {
+    Break,
+    Dashed,
+}
+
+impl From<GapStyle> for SnapshotGapStyle {
+    fn from(style: GapStyle) -> Self {
+        match style {
+            GapStyle::Break => Self::Break,
+            GapStyle::Dashed => Self::Dashed,
+        }
+    }
+}
+
+impl From<SnapshotGapStyle> for GapStyle {
+    fn from(style: SnapshotGapStyle) -> Self {
+        match style {
+            SnapshotGapStyle::Break => Self::Break,
+            SnapshotGapStyle::Dashed => Self::Dashed,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub(crate) enum SnapshotInterpolation {
+    Linear,
+    StepBefore,
+    StepAfter,
+    StepMiddle,
+    Monotone,
+}
+
+impl From<Interpolation> for SnapshotInterpolation {
+    fn from(interpolation: Interpolation) -> Self {
+        match interpolation {
+            Interpolation::Linear => Self::Linear,
+            Interpolation::StepBefore => Self::StepBefore,
+            Interpolation::StepAfter => Self::StepAfter,
+            Interpolation::StepMiddle => Self::StepMiddle,
+            Interpolation::Monotone => Self::Monotone,
+        }
+    }
+}
+
+impl From<SnapshotInterpolation> for Interpolation {
+    fn from(interpolation: SnapshotInterpolation) -> Self {
+        match interpolation {
+            SnapshotInterpolation::Linear => Self::Linear,
+            SnapshotInterpolation::StepBefore => Self::StepBefore,
+            SnapshotInterpolation::StepAfter => Self::StepAfter,
+            SnapshotInterpolation::StepMiddle => Self::StepMiddle,
+            SnapshotInterpolation::Monotone => Self::Monotone,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) enum SnapshotValueScale {
+    Linear,
+    Log10,
+    SymLog { linear_threshold: f64 },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct SnapshotPane {
+    id: String,
+    height_ratio: f64,
+    share_x_with_primary: bool,
+    series_ids: Vec<u32>,
+    value_scale: SnapshotValueScale,
+}
+
+impl From<&CompositionPane> for SnapshotPane {
+    fn from(pane: &CompositionPane) -> Self {
+        Self {
+            id: pane.id.clone(),
+            height_ratio: pane.height_ratio,
+            share_x_with_primary: pane.share_x_with_primary,
+            series_ids: pane.series_ids.clone(),
+            value_scale: match pane.value_scale {
+                ValueScale::Linear => SnapshotValueScale::Linear,
+                ValueScale::Log10 => SnapshotValueScale::Log10,
+                ValueScale::SymLog { linear_threshold } => {
+                    SnapshotValueScale::SymLog { linear_threshold }
+                }
+            },
+        }
+    }
+}
+
+impl From<SnapshotPane> for CompositionPane {
+    fn from(pane: SnapshotPane) -> Self {
+        Self {
+            id: pane.id,
+            height_ratio: pane.height_ratio,
+            share_x_with_primary: pane.share_x_with_primary,
+            series_ids: pane.series_ids,
+            value_scale: match pane.value_scale {
+                SnapshotValueScale::Linear => ValueScale::Linear,
+                SnapshotValueScale::Log10 => ValueScale::Log10,
+                SnapshotValueScale::SymLog { linear_threshold } => {
+                    ValueScale::symlog(linear_threshold)
+                }
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub(crate) struct SnapshotViewport {
+    follow_live: bool,
+    range_start: f64,
+    range_end: f64,
+}
+
+impl From<ViewportRange> for SnapshotViewport {
+    fn from(range: ViewportRange) -> Self {
+        Self {
+            follow_live: range.follow_live,
+            range_start: range.range_start,
+            range_end: range.range_end,
+        }
+    }
+}
+
+impl TryFrom<SnapshotViewport> for ViewportRange {
+    type Error = SnapshotError;
+
+    fn try_from(viewport: SnapshotViewport) -> Result<Self, Self::Error> {
+        if !viewport.range_start.is_finite() || !viewport.range_end.is_finite() {
+            return Err(SnapshotError::InvalidValue("viewport range"));
+        }
+        Ok(Self {
+            range_start: viewport.range_start,
+            range_end: viewport.range_end,
+            follow_live: viewport.follow_live,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty_snapshot() -> EngineSnapshot {
+        EngineSnapshot {
+            palette: vec![Color::from_rgba8(10, 20, 30, 255).into()],
+            next_palette_slot: 1,
+            definitions: Vec::new(),
+            series: Vec::new(),
+            panes: Vec::new(),
+            viewport: SnapshotViewport {
+                follow_live: true,
+                range_start: 0.0,
+                range_end: 0.0,
+            },
+        }
+    }
+
+    #[test]
+    fn header_guards_the_payload() {
+        let bytes = encode(&empty_snapshot());
+        assert_eq!(&bytes[..4], b"VCES");
+        assert!(decode(&bytes).is_ok());
+
+        assert!(matches!(decode(b"VCE"), Err(SnapshotError::NotASnapshot)));
+        assert!(matches!(
+            decode(b"PNG\0\x01\0"),
+            Err(SnapshotError::NotASnapshot)
+        ));
+
+        let mut future = bytes.clone();
+        future[4..6].copy_from_slice(&(SNAPSHOT_VERSION + 1).to_le_bytes());
+        assert!(matches!(
+            decode(&future),
+            Err(SnapshotError::UnsupportedVersion { found }) if found == SNAPSHOT_VERSION + 1
+        ));
+
+        let truncated = &bytes[..bytes.len() - 1];
+        assert!(matches!(decode(truncated), Err(SnapshotError::Decode(_))));
+    }
+
+    #[test]
+    fn colour_scales_are_validated_on_restore() {
+        let mut definition = SnapshotDefinition::from(&SeriesDefinition::default());
+        definition.color_scale = Some(vec![(0.0, Color::BLACK.into())]);
+        assert!(matches!(
+            SeriesDefinition::try_from(definition),
+            Err(SnapshotError::InvalidValue(_))
+        ));
+    }
+
+    #[test]
+    fn non_finite_viewport_ranges_are_rejected() {
+        for (range_start, range_end) in [
+            (f64::NAN, 10.0),
+            (0.0, f64::INFINITY),
+            (f64::NEG_INFINITY, 0.0),
+        ] {
+            let viewport = SnapshotViewport {
+                follow_live: false,
+                range_start,
+                range_end,
+            };
+            assert!(matches!(
+                ViewportRange::try_from(viewport),
+                Err(SnapshotError::InvalidValue(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,222 @@
+//! Stack groups accumulate line, area and bar series per timestamp.
+//!
+//! Each member keeps its own timestamps; the contribution of earlier members at those
+//! timestamps is linearly interpolated, and a member contributes nothing outside the
+//! time span it covers. Members stack in paint order.
+
+use vello::{
+    Scene,
+    kurbo::{Affine, Point, Rect},
+    peniko::{Brush, Color, Fill},
+};
+use vello_composition::PlotArea;
+
+use crate::{SeriesPoint, ValueAxis, project_point, project_value};
+
+/// Upper bound of percent-normalized stacks.
+const PERCENT_TOTAL: f64 = 100.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum StackMode {
+    /// Values are summed as-is.
+    #[default]
+    Normal,
+    /// Every timestamp is rescaled so the group totals 100.
+    Percent,
+}
+
+impl StackMode {
+    pub(crate) fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::Normal),
+            1 => Some(Self::Percent),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct StackGroup {
+    pub(crate) id: u32,
+    pub(crate) mode: StackMode,
+}
+
+/// Stacked geometry of one member: `lower` is the accumulated total of the members
+/// below it and `upper` adds the member's own value, both at the member's timestamps.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct StackedSpan {
+    pub(crate) upper: Vec<SeriesPoint>,
+    pub(crate) lower: Vec<SeriesPoint>,
+}
+
+impl StackedSpan {
+    /// Value range of the stacked samples inside `[range_start, range_end]`.
+    pub(crate) fn bounds(&self, range_start: f64, range_end: f64) -> Option<(f64, f64)> {
+        let mut min = f64::INFINITY;
+        let mut max = f64::NEG_INFINITY;
+        for point in self.upper.iter().chain(&self.lower) {
+            let visible = (range_start..=range_end).contains(&point.timestamp_seconds);
+            if visible && point.value.is_finite() {
+                min = min.min(point.value);
+                max = max.max(point.value);
+            }
+        }
+        (min.is_finite() && max.is_finite()).then_some((min, max))
+    }
+}
+
+/// Stacks `members`, bottom first, returning one span per member in the same order.
+pub(crate) fn stack_spans(members: &[&[SeriesPoint]], mode: StackMode) -> Vec<StackedSpan> {
+    members
+        .iter()
+        .enumerate()
+        .map(|(index, span)| {
+            let mut stacked = StackedSpan {
+                upper: Vec::with_capacity(span.len()),
+                lower: Vec::with_capacity(span.len()),
+            };
+            for point in span.iter() {
+                let timestamp = point.timestamp_seconds;
+                if !point.value.is_finite() {
+                    // Gaps stay gaps in the stack; members above skip over them.
+                    stacked.lower.push(*point);
+                    stacked.upper.push(*point);
+                    continue;
+                }
+                let below: f64 = members[..index]
+                    .iter()
+                    .map(|other| value_at(other, timestamp))
+                    .sum();
+                let mut lower = below;
+                let mut upper = below + point.value;
+                if mode == StackMode::Percent {
+                    let total: f64 = upper
+                        + members[index + 1..]
+                            .iter()
+                            .map(|other| value_at(other, timestamp))
+                            .sum::<f64>();
+                    let scale = if total.abs() > f64::EPSILON {
+                        PERCENT_TOTAL / total
+                    } else {
+                        0.0
+                    };
+                    lower *= scale;
+                    upper *= scale;
+                }
+                stacked.lower.push(SeriesPoint {
+                    timestamp_seconds: timestamp,
+                    value: lower,
+                });
+                stacked.upper.push(SeriesPoint {
+                    timestamp_seconds: timestamp,
+                    value: upper,
+                });
+            }
+            stacked
+        })
+        .collect()
+}
+
+/// Linearly interpolated value of `span` at `timestamp`; zero outside the span and
+/// inside its gaps.
+fn value_at(span: &[SeriesPoint], timestamp: f64) -> f64 {
+    let index = span.partition_point(|point| point.timestamp_seconds < timestamp);
+    let value = match (
+        index.checked_sub(1).map(|prev| &span[prev]),
+        span.get(index),
+    ) {
+        (_, Some(next)) if next.timestamp_seconds == timestamp => next.value,
+        (Some(prev), Some(next)) => {
+            let t = (timestamp - prev.timestamp_seconds)
+                / (next.timestamp_seconds - prev.timestamp_seconds);
+            prev.value + (next.value - prev.value) * t
+        }
+        _ => 0.0,
+    };
+    if value.is_finite() { value } else { 0.0 }
+}
+
+/// Draws bars spanning each member's slice of the stack.
+pub(crate) fn render_stacked_bars(
+    scene: &mut Scene,
+    stack: &StackedSpan,
+    color: Color,
+    plot_area: &PlotArea,
+    range_start: f64,
+    window: f64,
+    value_axis: &ValueAxis,
+    bar_width_seconds: f64,
+) -> (u32, Option<Point>) {
+    let normalized_width = (bar_width_seconds / window).clamp(0.0, 1.0);
+    let half_width = (normalized_width * plot_area.width).max(1.0) * 0.5;
+    let mut last_point = None;
+    let mut encoded = 0;
+
+    for (upper, lower) in stack.upper.iter().zip(&stack.lower) {
+        // Stacks carry the overhang sample for connected members; bars skip it.
+        let offset = (upper.timestamp_seconds - range_start) / window;
+        if !upper.value.is_finite() || !(0.0..=1.0).contains(&offset) {
+            continue;
+        }
+        let point = project_point(upper, range_start, window, plot_area, value_axis);
+        let base_y = project_value(lower.value, value_axis, plot_area);
+        let x0 = (point.x - half_width).max(plot_area.left);
+        let x1 = (point.x + half_width).min(plot_area.right());
+        let top = point.y.min(base_y);
+        let rect = Rect::new(x0, top, x1, point.y.max(base_y));
+        scene.fill(
+            Fill::NonZero,
+            Affine::IDENTITY,
+            &Brush::Solid(color),
+            None,
+            &rect,
+        );
+        encoded += 1;
+        last_point = Some(Point::new((x0 + x1) * 0.5, top));
+    }
+
+    (encoded, last_point)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::points;
+
+    fn values(span: &[SeriesPoint]) -> Vec<f64> {
+        span.iter().map(|point| point.value).collect()
+    }
+
+    #[test]
+    fn members_accumulate_in_order() {
+        let bottom = points(&[(0.0, 1.0), (1.0, 2.0)]);
+        let top = points(&[(0.0, 3.0), (1.0, 4.0)]);
+        let stacks = stack_spans(&[&bottom, &top], StackMode::Normal);
+        assert_eq!(values(&stacks[0].lower), vec![0.0, 0.0]);
+        assert_eq!(values(&stacks[0].upper), vec![1.0, 2.0]);
+        assert_eq!(values(&stacks[1].lower), vec![1.0, 2.0]);
+        assert_eq!(values(&stacks[1].upper), vec![4.0, 6.0]);
+        assert_eq!(stacks[1].bounds(0.0, 1.0), Some((1.0, 6.0)));
+        assert_eq!(stacks[1].bounds(0.5, 1.0), Some((2.0, 6.0)));
+    }
+
+    #[test]
+    fn misaligned_timestamps_interpolate_and_stop_at_span_edges() {
+        let bottom = points(&[(0.0, 0.0), (2.0, 4.0)]);
+        let top = points(&[(1.0, 1.0), (3.0, 1.0)]);
+        let stacks = stack_spans(&[&bottom, &top], StackMode::Normal);
+        // The bottom member is halfway between its samples at 1s and has ended by 3s.
+        assert_eq!(values(&stacks[1].lower), vec![2.0, 0.0]);
+        assert_eq!(values(&stacks[1].upper), vec![3.0, 1.0]);
+    }
+
+    #[test]
+    fn percent_mode_normalizes_each_timestamp() {
+        let a = points(&[(0.0, 1.0), (1.0, 0.0)]);
+        let b = points(&[(0.0, 3.0), (1.0, 0.0)]);
+        let stacks = stack_spans(&[&a, &b], StackMode::Percent);
+        assert_eq!(values(&stacks[0].upper), vec![25.0, 0.0]);
+        assert_eq!(values(&stacks[1].lower), vec![25.0, 0.0]);
+        assert_eq!(values(&stacks[1].upper), vec![100.0, 0.0]);
+    }
+}
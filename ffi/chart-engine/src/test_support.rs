@@ -0,0 +1,14 @@
+//! Fixtures shared by the unit tests of several modules.
+
+use crate::SeriesPoint;
+
+/// Samples from `(timestamp_seconds, value)` pairs.
+pub(crate) fn points(values: &[(f64, f64)]) -> Vec<SeriesPoint> {
+    values
+        .iter()
+        .map(|&(timestamp_seconds, value)| SeriesPoint {
+            timestamp_seconds,
+            value,
+        })
+        .collect()
+}
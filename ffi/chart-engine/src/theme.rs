@@ -0,0 +1,295 @@
+//! Chart chrome styling: backgrounds, grid and axis strokes, label text and callouts.
+//!
+//! The default theme reproduces the engine's built-in dark look. Grid lines and axes
+//! are left to the host unless the theme enables them, since most hosts draw their own
+//! axes over the engine frame.
+
+use vello::{kurbo::Stroke, peniko::Color};
+use vello_composition::{AxisTick, LabelLayout, LabelStyle, layout_label, layout_styled_label};
+
+/// Stroke colour, width and optional dash pattern for chrome lines.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineStyle {
+    pub color: Color,
+    pub width: f64,
+    /// Alternating dash and gap lengths; empty draws a solid line.
+    pub dashes: Vec<f64>,
+}
+
+impl LineStyle {
+    pub fn solid(color: Color, width: f64) -> Self {
+        Self {
+            color,
+            width,
+            dashes: Vec::new(),
+        }
+    }
+
+    pub(crate) fn stroke(&self) -> Stroke {
+        let stroke = Stroke::new(self.width);
+        if self.dashes.is_empty() {
+            stroke
+        } else {
+            stroke.with_dashes(0.0, self.dashes.iter().copied())
+        }
+    }
+}
+
+/// Family, size and colour of label text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextStyle {
+    /// Family tried before the embedded label font; `None` uses the embedded font only.
+    pub family: Option<String>,
+    pub font_size: f32,
+    pub color: Color,
+}
+
+impl TextStyle {
+    pub(crate) fn layout(&self, text: &str) -> Option<LabelLayout> {
+        match &self.family {
+            None => layout_label(text, self.font_size),
+            Some(family) => layout_styled_label(
+                text,
+                &LabelStyle {
+                    family: Some(family.clone()),
+                    ..LabelStyle::new(self.font_size)
+                },
+            ),
+        }
+    }
+}
+
+/// Axis lines along the left and bottom plot edges, with outward tick marks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AxisStyle {
+    pub line: LineStyle,
+    /// Length of the marks at labelled ticks; zero draws none.
+    pub major_tick_length: f64,
+    /// Length of the marks at minor grid positions; zero draws none.
+    pub minor_tick_length: f64,
+}
+
+/// Geometry of the rounded callouts used for series labels and the pointer tooltip.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CalloutStyle {
+    pub padding_x: f64,
+    pub padding_y: f64,
+    pub corner_radius: f64,
+    /// Horizontal distance from the anchor to the callout.
+    pub offset_x: f64,
+    /// Vertical distance from the anchor to the callout.
+    pub gap_y: f64,
+    /// Opacity of the series colour filling a series label.
+    pub fill_alpha: f32,
+    /// Opacity of the series colour outlining a series label.
+    pub border_alpha: f32,
+    pub border_width: f64,
+}
+
+/// Colours, strokes and text styles for everything the engine draws around the series.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartTheme {
+    /// Frame gradient, top to bottom.
+    pub background_top: Color,
+    pub background_bottom: Color,
+    pub frame_border: Color,
+    pub plot_background: Color,
+    pub plot_border: Color,
+    /// Grid lines at labelled ticks; `None` leaves them to the host.
+    pub major_grid: Option<LineStyle>,
+    /// Grid lines between labelled ticks; `None` draws none.
+    pub minor_grid: Option<LineStyle>,
+    /// Intervals each major step is split into by minor lines and ticks.
+    pub minor_divisions: u32,
+    /// Axis lines and tick labels; `None` leaves them to the host.
+    pub axis: Option<AxisStyle>,
+    pub axis_label: TextStyle,
+    /// Series labels, legend entries, tooltip rows and annotation labels.
+    pub label: TextStyle,
+    pub callout: CalloutStyle,
+    /// Fill behind a floating legend.
+    pub legend_background: Color,
+    /// Fill behind the pointer tooltip.
+    pub tooltip_background: Color,
+    /// Outline of the floating legend and the tooltip.
+    pub panel_border: Color,
+    pub crosshair: LineStyle,
+}
+
+impl Default for ChartTheme {
+    fn default() -> Self {
+        let axis_label_color = Color::from_rgba8(0xA8, 0xB7, 0xD6, 0xFF);
+        Self {
+            background_top: Color::from_rgba8(0x10, 0x18, 0x28, 0xF0),
+            background_bottom: Color::from_rgba8(0x0B, 0x11, 0x1C, 0xE6),
+            frame_border: Color::from_rgba8(0x2A, 0x34, 0x4A, 0xFF),
+            plot_background: Color::from_rgba8(0x12, 0x1B, 0x2A, 0xC8),
+            plot_border: Color::from_rgba8(0x24, 0x30, 0x45, 0xFF),
+            major_grid: None,
+            minor_grid: None,
+            minor_divisions: 0,
+            axis: None,
+            axis_label: TextStyle {
+                family: None,
+                font_size: 12.0,
+                color: axis_label_color,
+            },
+            label: TextStyle {
+                family: None,
+                font_size: 14.0,
+                color: Color::from_rgba8(0xF5, 0xF9, 0xFF, 0xFF),
+            },
+            callout: CalloutStyle {
+                padding_x: 6.0,
+                padding_y: 4.0,
+                corner_radius: 6.0,
+                offset_x: 12.0,
+                gap_y: 8.0,
+                fill_alpha: 0.28,
+                border_alpha: 0.6,
+                border_width: 1.0,
+            },
+            legend_background: Color::from_rgba8(0x10, 0x18, 0x28, 0xCC),
+            tooltip_background: Color::from_rgba8(0x10, 0x18, 0x28, 0xE6),
+            panel_border: Color::from_rgba8(0x45, 0x53, 0x72, 0xFF),
+            crosshair: LineStyle {
+                color: axis_label_color.with_alpha(0.7),
+                width: 1.0,
+                dashes: vec![4.0, 3.0],
+            },
+        }
+    }
+}
+
+impl ChartTheme {
+    /// Enables the engine's own grid and axes in the built-in colours, for hosts that
+    /// do not draw axes themselves.
+    pub fn with_engine_axes(self) -> Self {
+        Self {
+            major_grid: Some(LineStyle::solid(
+                Color::from_rgba8(0x23, 0x2E, 0x42, 0xFF),
+                1.0,
+            )),
+            axis: Some(AxisStyle {
+                line: LineStyle::solid(Color::from_rgba8(0x45, 0x53, 0x72, 0xFF), 1.0),
+                major_tick_length: 4.0,
+                minor_tick_length: 0.0,
+            }),
+            ..self
+        }
+    }
+}
+
+/// Positions in `0..=1` splitting each step between adjacent ticks into `divisions` parts.
+pub(crate) fn minor_positions(ticks: &[AxisTick], divisions: u32) -> Vec<f64> {
+    if divisions < 2 {
+        return Vec::new();
+    }
+    let mut positions = Vec::with_capacity(ticks.len() * divisions as usize);
+    for pair in ticks.windows(2) {
+        let (start, end) = (pair[0].position, pair[1].position);
+        let step = (end - start) / f64::from(divisions);
+        positions.extend((1..divisions).map(|index| start + step * f64::from(index)));
+    }
+    positions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tick(position: f64) -> AxisTick {
+        AxisTick {
+            position,
+            label: String::new(),
+        }
+    }
+
+    #[test]
+    fn minor_positions_split_every_major_step() {
+        let ticks = [tick(0.0), tick(0.5), tick(1.0)];
+        assert_eq!(
+            minor_positions(&ticks, 4),
+            [0.125, 0.25, 0.375, 0.625, 0.75, 0.875]
+        );
+        assert!(minor_positions(&ticks, 1).is_empty());
+        assert!(minor_positions(&ticks[..1], 4).is_empty());
+    }
+
+    #[test]
+    fn dashed_styles_produce_dashed_strokes() {
+        assert!(
+            LineStyle::solid(Color::WHITE, 1.0)
+                .stroke()
+                .dash_pattern
+                .is_empty()
+        );
+        let dashed = ChartTheme::default().crosshair.stroke();
+        assert_eq!(dashed.dash_pattern.as_slice(), [4.0, 3.0]);
+    }
+
+    #[test]
+    fn right_axis_layouts_draw_an_outer_edge_with_ticks_and_labels() {
+        use vello::Scene;
+        use vello_composition::{
+            AxisLabelOptions, PlotArea, ValueScale, compute_labeled_axis_layout,
+        };
+
+        let theme = ChartTheme {
+            axis: Some(AxisStyle {
+                line: LineStyle::solid(Color::WHITE, 1.0),
+                major_tick_length: 4.0,
+                minor_tick_length: 0.0,
+            }),
+            ..ChartTheme::default()
+        };
+        let plot = PlotArea {
+            left: 40.0,
+            top: 10.0,
+            width: 200.0,
+            height: 120.0,
+        };
+        let layout = compute_labeled_axis_layout(
+            0.0,
+            60.0,
+            0.0,
+            100.0,
+            6,
+            ValueScale::Linear,
+            &AxisLabelOptions::default(),
+        );
+        let mut scene = Scene::new();
+        let left_only = crate::draw_axes_and_grid(&mut scene, &plot, &layout, None, &theme, false);
+        let both =
+            crate::draw_axes_and_grid(&mut scene, &plot, &layout, Some(&layout), &theme, false);
+
+        // The edge line, then a tick mark and a label run per labelled value.
+        let ticks = layout.value_ticks.len() as u32;
+        assert!(ticks > 0);
+        assert_eq!(both - left_only, 1 + 2 * ticks);
+    }
+
+    #[test]
+    fn text_families_are_tried_before_the_label_font() {
+        vello_composition::register_font(
+            "Theme Test Flex",
+            include_bytes!(
+                "../../../samples/AvaloniaVelloSkiaSharpSample/Assets/fonts/RobotoFlex-Subset.ttf"
+            )
+            .to_vec(),
+            0,
+        )
+        .expect("flex font");
+        let label_font = vello_composition::label_font().data.id();
+        let uses_label_font = |style: &TextStyle| {
+            style.layout("2024").expect("layout").runs[0].font.data.id() == label_font
+        };
+
+        let mut style = ChartTheme::default().label;
+        assert!(uses_label_font(&style));
+        style.family = Some("Theme Test Flex".to_string());
+        assert!(!uses_label_font(&style));
+        style.family = Some("No Such Family".to_string());
+        assert!(uses_label_font(&style));
+    }
+}
@@ -0,0 +1,157 @@
+//! Visible time range tracking for live and historical browsing.
+//!
+//! While following live data the range trails the newest sample by the configured
+//! visible duration. Any explicit range, zoom or pan freezes the range in place until
+//! follow mode is re-enabled.
+
+use crate::MIN_VISIBLE_DURATION_SECS;
+
+/// Time range resolved for the current frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ViewportRange {
+    pub range_start: f64,
+    pub range_end: f64,
+    pub follow_live: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Viewport {
+    follow_live: bool,
+    range_start: f64,
+    range_end: f64,
+}
+
+impl Default for Viewport {
+    fn default() -> Self {
+        Self {
+            follow_live: true,
+            range_start: 0.0,
+            range_end: 0.0,
+        }
+    }
+}
+
+impl Viewport {
+    /// Viewport holding `range` as stored, without re-applying the minimum duration.
+    pub(crate) fn from_stored(range: ViewportRange) -> Self {
+        Self {
+            follow_live: range.follow_live,
+            range_start: range.range_start,
+            range_end: range.range_end,
+        }
+    }
+
+    /// The stored range and follow flag; the range is stale while following live data.
+    pub(crate) fn stored(&self) -> ViewportRange {
+        ViewportRange {
+            range_start: self.range_start,
+            range_end: self.range_end,
+            follow_live: self.follow_live,
+        }
+    }
+
+    pub(crate) fn follow_live(&self) -> bool {
+        self.follow_live
+    }
+
+    /// Resolves the range to draw, given the newest sample and the live window length.
+    pub(crate) fn resolve(&self, latest: f64, live_window: f64) -> ViewportRange {
+        if self.follow_live {
+            let window = live_window.max(MIN_VISIBLE_DURATION_SECS);
+            ViewportRange {
+                range_start: latest - window,
+                range_end: latest,
+                follow_live: true,
+            }
+        } else {
+            ViewportRange {
+                range_start: self.range_start,
+                range_end: self.range_end,
+                follow_live: false,
+            }
+        }
+    }
+
+    pub(crate) fn set_follow_live(&mut self, follow_live: bool, latest: f64, live_window: f64) {
+        if !follow_live && self.follow_live {
+            // Freeze where the live range currently sits so the view doesn't jump.
+            let current = self.resolve(latest, live_window);
+            self.range_start = current.range_start;
+            self.range_end = current.range_end;
+        }
+        self.follow_live = follow_live;
+    }
+
+    /// Pins the view to `[range_start, range_end]` and leaves follow mode.
+    pub(crate) fn set_range(&mut self, range_start: f64, range_end: f64) {
+        self.follow_live = false;
+        self.range_start = range_start;
+        self.range_end = range_end.max(range_start + MIN_VISIBLE_DURATION_SECS);
+    }
+
+    /// Scales the visible duration by `factor` while keeping `anchor` at the same
+    /// relative position; factors below one zoom in.
+    pub(crate) fn zoom(&mut self, factor: f64, anchor: f64, latest: f64, live_window: f64) {
+        let current = self.resolve(latest, live_window);
+        let start = anchor - (anchor - current.range_start) * factor;
+        let end = anchor + (current.range_end - anchor) * factor;
+        if end - start < MIN_VISIBLE_DURATION_SECS {
+            let center = (start + end) * 0.5;
+            let half = MIN_VISIBLE_DURATION_SECS * 0.5;
+            self.set_range(center - half, center + half);
+        } else {
+            self.set_range(start, end);
+        }
+    }
+
+    /// Shifts the visible range by `delta_seconds`; negative values move back in time.
+    pub(crate) fn pan(&mut self, delta_seconds: f64, latest: f64, live_window: f64) {
+        let current = self.resolve(latest, live_window);
+        self.set_range(
+            current.range_start + delta_seconds,
+            current.range_end + delta_seconds,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn live_range_trails_latest_until_frozen() {
+        let mut viewport = Viewport::default();
+        let range = viewport.resolve(100.0, 10.0);
+        assert_eq!((range.range_start, range.range_end), (90.0, 100.0));
+
+        viewport.set_follow_live(false, 100.0, 10.0);
+        let range = viewport.resolve(250.0, 10.0);
+        assert!(!range.follow_live);
+        assert_eq!((range.range_start, range.range_end), (90.0, 100.0));
+
+        viewport.set_follow_live(true, 250.0, 10.0);
+        assert_eq!(viewport.resolve(250.0, 10.0).range_start, 240.0);
+    }
+
+    #[test]
+    fn zoom_keeps_anchor_fixed() {
+        let mut viewport = Viewport::default();
+        viewport.set_range(0.0, 100.0);
+        viewport.zoom(0.5, 25.0, 0.0, 0.0);
+        let range = viewport.resolve(0.0, 0.0);
+        assert_eq!((range.range_start, range.range_end), (12.5, 62.5));
+
+        viewport.zoom(2.0, 25.0, 0.0, 0.0);
+        let range = viewport.resolve(0.0, 0.0);
+        assert_eq!((range.range_start, range.range_end), (0.0, 100.0));
+    }
+
+    #[test]
+    fn pan_from_live_freezes_history() {
+        let mut viewport = Viewport::default();
+        viewport.pan(-30.0, 100.0, 20.0);
+        assert!(!viewport.follow_live());
+        let range = viewport.resolve(500.0, 20.0);
+        assert_eq!((range.range_start, range.range_end), (50.0, 70.0));
+    }
+}
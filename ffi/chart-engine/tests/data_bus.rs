@@ -0,0 +1,51 @@
+//! Producer-side ingestion through an attached data bus.
+
+use vello_chart_data::{DataBus, DataPoint, SeriesSample};
+use vello_chart_engine::{ChartEngine, EngineOptions};
+
+fn batch(series_id: u32, start: u64, count: u64) -> SeriesSample {
+    SeriesSample {
+        series_id,
+        points: (start..start + count)
+            .map(|index| DataPoint {
+                timestamp_ns: index * 1_000_000_000,
+                value: index as f64,
+            })
+            .collect(),
+    }
+}
+
+#[test]
+fn render_frame_drains_an_attached_bus() {
+    let mut engine = ChartEngine::new(EngineOptions::default());
+    let bus = DataBus::new(8);
+    engine.attach_data_bus(bus.clone());
+
+    bus.push(batch(0, 0, 10)).expect("push first batch");
+    bus.push(batch(1, 0, 5)).expect("push second batch");
+    assert_eq!(bus.stats().queued, 2);
+
+    let stats = engine.render_frame(320, 200).stats;
+    assert_eq!(stats.source_points, 15);
+    assert_eq!(bus.stats().queued, 0);
+    assert_eq!(bus.stats().drained, 2);
+    assert_eq!(engine.viewport().range_end, 9.0);
+
+    bus.push(batch(0, 10, 5)).expect("push third batch");
+    let stats = engine.render_frame(320, 200).stats;
+    assert_eq!(stats.source_points, 20);
+    assert_eq!(engine.viewport().range_end, 14.0);
+}
+
+#[test]
+fn detached_bus_is_no_longer_drained() {
+    let mut engine = ChartEngine::new(EngineOptions::default());
+    let bus = DataBus::new(8);
+    engine.attach_data_bus(bus.clone());
+    assert!(engine.detach_data_bus().is_some());
+
+    bus.push(batch(0, 0, 10)).expect("push batch");
+    let stats = engine.render_frame(320, 200).stats;
+    assert_eq!(stats.source_points, 0);
+    assert_eq!(bus.stats().queued, 1);
+}
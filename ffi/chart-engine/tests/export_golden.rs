@@ -0,0 +1,217 @@
+//! Golden-image tests for headless export.
+//!
+//! References live in `tests/golden` and a missing reference fails the test. Set
+//! `VELLO_CHART_BLESS=1` to write them after an intentional rendering change, then
+//! review and commit the new files.
+
+use std::path::PathBuf;
+
+use vello::peniko::Color;
+use vello_chart_engine::{
+    Annotation, AnnotationKind, ChartEngine, ChartSample, EngineOptions, ExportFormat,
+    ExportOptions, LegendOptions, LegendPlacement,
+};
+
+const WIDTH: u32 = 320;
+const HEIGHT: u32 = 200;
+
+/// Largest per-channel difference tolerated between a rendered and a reference pixel.
+const CHANNEL_TOLERANCE: u8 = 3;
+/// Share of pixels allowed to exceed the channel tolerance, e.g. anti-aliased edges.
+const MISMATCH_TOLERANCE: f64 = 0.001;
+
+fn sample_engine() -> ChartEngine {
+    let mut engine = ChartEngine::new(EngineOptions {
+        show_axes: false,
+        ..EngineOptions::default()
+    });
+    let samples: Vec<ChartSample> = (0..120)
+        .flat_map(|index| {
+            let t = f64::from(index);
+            [
+                ChartSample {
+                    series_id: 0,
+                    timestamp_seconds: t,
+                    value: (t / 12.0).sin() * 40.0 + 50.0,
+                },
+                ChartSample {
+                    series_id: 1,
+                    timestamp_seconds: t,
+                    value: (t / 20.0).cos() * 25.0 + 40.0,
+                },
+            ]
+        })
+        .collect();
+    engine.publish_samples(&samples);
+    engine.set_visible_range(0.0, 119.0);
+
+    let mut limit = Annotation::new(AnnotationKind::ValueLine { value: 80.0 });
+    limit.label = Some("Limit".to_string());
+    limit.dash_pattern = vec![4.0, 2.0];
+    engine.add_annotation(1, limit);
+    engine.add_annotation(
+        2,
+        Annotation::new(AnnotationKind::TimeBand {
+            start_seconds: 30.0,
+            end_seconds: 45.0,
+        }),
+    );
+    engine.set_legend(LegendOptions {
+        placement: LegendPlacement::Top,
+        ..LegendOptions::default()
+    });
+    engine
+}
+
+fn white_background(scale: f64) -> ExportOptions {
+    ExportOptions {
+        scale,
+        background: Some(Color::WHITE),
+    }
+}
+
+fn golden_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("golden")
+        .join(name)
+}
+
+/// Returns the reference bytes, or `None` after rewriting the reference when blessing.
+fn reference(name: &str, actual: &[u8]) -> Option<Vec<u8>> {
+    let path = golden_path(name);
+    let bless = std::env::var_os("VELLO_CHART_BLESS").is_some_and(|value| value != "0");
+    if bless {
+        std::fs::create_dir_all(path.parent().expect("golden directory"))
+            .expect("create golden directory");
+        std::fs::write(&path, actual).expect("write golden reference");
+        return None;
+    }
+    match std::fs::read(&path) {
+        Ok(expected) => Some(expected),
+        Err(error) => panic!(
+            "{}: cannot read the golden reference ({error}); run with VELLO_CHART_BLESS=1 to create it",
+            path.display()
+        ),
+    }
+}
+
+fn decode_png(bytes: &[u8]) -> (u32, u32, Vec<u8>) {
+    let mut reader = png::Decoder::new(bytes).read_info().expect("png header");
+    let mut pixels = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut pixels).expect("png frame");
+    assert_eq!(info.color_type, png::ColorType::Rgba);
+    pixels.truncate(info.buffer_size());
+    (info.width, info.height, pixels)
+}
+
+fn assert_png_matches(name: &str, actual: &[u8]) {
+    let Some(expected) = reference(name, actual) else {
+        return;
+    };
+    let (width, height, actual_pixels) = decode_png(actual);
+    let (expected_width, expected_height, expected_pixels) = decode_png(&expected);
+    assert_eq!(
+        (width, height),
+        (expected_width, expected_height),
+        "{name}: size differs from the reference"
+    );
+
+    let mismatched = actual_pixels
+        .chunks_exact(4)
+        .zip(expected_pixels.chunks_exact(4))
+        .filter(|(actual, expected)| {
+            actual
+                .iter()
+                .zip(expected.iter())
+                .any(|(a, e)| a.abs_diff(*e) > CHANNEL_TOLERANCE)
+        })
+        .count();
+    let share = mismatched as f64 / f64::from(width * height);
+    assert!(
+        share <= MISMATCH_TOLERANCE,
+        "{name}: {mismatched} pixels differ from the reference ({:.3}%)",
+        share * 100.0
+    );
+}
+
+#[test]
+fn png_export_matches_reference() {
+    let bytes = sample_engine()
+        .export_frame(WIDTH, HEIGHT, ExportFormat::Png, &white_background(1.0))
+        .expect("export png");
+    assert_png_matches("line_chart.png", &bytes);
+
+    let (width, height, _) = decode_png(&bytes);
+    assert_eq!((width, height), (WIDTH, HEIGHT));
+}
+
+#[test]
+fn scaled_png_export_matches_reference() {
+    let bytes = sample_engine()
+        .export_frame(WIDTH, HEIGHT, ExportFormat::Png, &white_background(2.0))
+        .expect("export png");
+    assert_png_matches("line_chart@2x.png", &bytes);
+
+    let (width, height, _) = decode_png(&bytes);
+    assert_eq!((width, height), (WIDTH * 2, HEIGHT * 2));
+}
+
+/// Lines cross both plot edges towards the samples just outside the view.
+#[test]
+fn panned_png_export_matches_reference() {
+    let mut engine = sample_engine();
+    engine.set_visible_range(30.5, 90.5);
+    let bytes = engine
+        .export_frame(WIDTH, HEIGHT, ExportFormat::Png, &white_background(1.0))
+        .expect("export png");
+    assert_png_matches("line_chart_panned.png", &bytes);
+}
+
+#[test]
+fn svg_export_matches_reference() {
+    let bytes = sample_engine()
+        .export_frame(WIDTH, HEIGHT, ExportFormat::Svg, &white_background(1.0))
+        .expect("export svg");
+    let svg = String::from_utf8(bytes).expect("svg is utf-8");
+    assert!(svg.starts_with("<svg "));
+    assert!(svg.contains(r#"viewBox="0 0 320 200""#));
+    assert!(svg.contains(r##"<rect width="320" height="200" fill="#ffffff"/>"##));
+
+    if let Some(expected) = reference("line_chart.svg", svg.as_bytes()) {
+        assert_eq!(
+            svg,
+            String::from_utf8(expected).expect("reference is utf-8"),
+            "line_chart.svg differs from the reference"
+        );
+    }
+}
+
+#[test]
+fn cursor_overlay_is_not_exported() {
+    let options = white_background(1.0);
+    let mut engine = sample_engine();
+    let plain = engine
+        .export_frame(WIDTH, HEIGHT, ExportFormat::Svg, &options)
+        .expect("export svg");
+    let plain_paths = engine.render_frame(WIDTH, HEIGHT).stats.encoded_paths;
+
+    engine.set_cursor(160.0, 100.0);
+    let with_cursor = engine
+        .export_frame(WIDTH, HEIGHT, ExportFormat::Svg, &options)
+        .expect("export svg");
+    assert_eq!(plain, with_cursor);
+
+    // The interactive frame still draws the cursor after an export.
+    let cursor_paths = engine.render_frame(WIDTH, HEIGHT).stats.encoded_paths;
+    assert!(cursor_paths > plain_paths);
+}
+
+#[test]
+fn svg_export_without_background_is_transparent() {
+    let bytes = sample_engine()
+        .export_frame(WIDTH, HEIGHT, ExportFormat::Svg, &ExportOptions::default())
+        .expect("export svg");
+    let svg = String::from_utf8(bytes).expect("svg is utf-8");
+    assert!(!svg.contains("<rect"));
+}
@@ -0,0 +1,171 @@
+//! Rendering with the viewport panned into the middle of the data, so samples lie past
+//! both plot edges. Series kinds are only configurable through the C ABI, so the engine
+//! is driven through it here.
+
+use std::ptr;
+
+use vello_chart_engine::{
+    VelloChartColor, VelloChartEngineHandle, VelloChartEngineOptions, VelloChartEngineStatus,
+    VelloChartExportBuffer, VelloChartExportOptions, VelloChartOhlcSample, VelloChartSamplePoint,
+    VelloChartSeriesDefinition, vello_chart_engine_create, vello_chart_engine_destroy,
+    vello_chart_engine_export, vello_chart_engine_export_buffer_data,
+    vello_chart_engine_export_buffer_destroy, vello_chart_engine_publish_ohlc_samples,
+    vello_chart_engine_publish_samples, vello_chart_engine_set_series_definitions,
+    vello_chart_engine_set_visible_range,
+};
+
+const WIDTH: u32 = 320;
+const HEIGHT: u32 = 200;
+
+const SCATTER: u32 = 2;
+const BAR: u32 = 3;
+const HEATMAP: u32 = 5;
+const CANDLESTICK: u32 = 6;
+
+const VISIBLE_START: f64 = 15.0;
+const VISIBLE_END: f64 = 55.0;
+
+const BLACK: VelloChartColor = VelloChartColor {
+    r: 0,
+    g: 0,
+    b: 0,
+    a: 0xFF,
+};
+
+fn definition(series_id: u32, kind: u32) -> VelloChartSeriesDefinition {
+    VelloChartSeriesDefinition {
+        series_id,
+        kind,
+        flags: 0,
+        reserved: 0,
+        baseline: 0.0,
+        fill_opacity: 0.0,
+        stroke_width: 0.0,
+        marker_size: 0.0,
+        bar_width_seconds: 0.0,
+        band_lower_series_id: 0,
+        heatmap_bucket_index: 0,
+        heatmap_bucket_count: 0,
+        decimation_mode: 0,
+        value_axis: 0,
+        up_color: BLACK,
+        down_color: BLACK,
+        stack_group: 0,
+        stack_mode: 0,
+        max_gap_seconds: 0.0,
+        gap_style: 0,
+        interpolation: 0,
+        color_map: 0,
+        color_stops: ptr::null(),
+        color_stop_count: 0,
+        color_domain_min: 0.0,
+        color_domain_max: 0.0,
+    }
+}
+
+/// Value at `t`; samples outside the view are far off the visible value range, so one
+/// drawn at a plot edge would stand out.
+fn value_at(t: f64) -> f64 {
+    if (VISIBLE_START..=VISIBLE_END).contains(&t) {
+        40.0 + t
+    } else {
+        500.0
+    }
+}
+
+/// Exports a frame of scatter, bar, heatmap and candlestick series over the view,
+/// optionally with samples every ten seconds over `0..=100` rather than only the
+/// visible ones. Bar and candle widths are left to the engine, so they must not
+/// depend on retained samples outside the view either.
+fn export_view(with_offscreen_samples: bool) -> Vec<u8> {
+    let timestamps: Vec<f64> = (0..=10)
+        .map(|index| f64::from(index) * 10.0)
+        .filter(|t| with_offscreen_samples || (VISIBLE_START..=VISIBLE_END).contains(t))
+        .collect();
+    let samples: Vec<VelloChartSamplePoint> = [0, 1, 2]
+        .into_iter()
+        .flat_map(|series_id| {
+            timestamps.iter().map(move |&t| VelloChartSamplePoint {
+                series_id,
+                timestamp_seconds: t,
+                value: value_at(t),
+            })
+        })
+        .collect();
+    let bars: Vec<VelloChartOhlcSample> = timestamps
+        .iter()
+        .map(|&t| VelloChartOhlcSample {
+            series_id: 3,
+            timestamp_seconds: t,
+            open: value_at(t) - 5.0,
+            high: value_at(t) + 10.0,
+            low: value_at(t) - 10.0,
+            close: value_at(t) + 5.0,
+            volume: f64::NAN,
+        })
+        .collect();
+    let definitions = [
+        definition(0, SCATTER),
+        definition(1, BAR),
+        definition(2, HEATMAP),
+        definition(3, CANDLESTICK),
+    ];
+
+    let options = VelloChartEngineOptions {
+        visible_duration_seconds: VISIBLE_END - VISIBLE_START,
+        retention_duration_seconds: 1000.0,
+        vertical_padding_ratio: 0.1,
+        stroke_width: 1.5,
+        show_axes: 0,
+        palette_len: 0,
+        palette_ptr: ptr::null(),
+    };
+    let export_options = VelloChartExportOptions {
+        format: 0,
+        flags: 1,
+        scale: 1.0,
+        background: VelloChartColor {
+            r: 0xFF,
+            g: 0xFF,
+            b: 0xFF,
+            a: 0xFF,
+        },
+    };
+
+    let handle: *mut VelloChartEngineHandle = vello_chart_engine_create(options);
+    unsafe {
+        let status = vello_chart_engine_set_series_definitions(
+            handle,
+            definitions.as_ptr(),
+            definitions.len(),
+        );
+        assert_eq!(status, VelloChartEngineStatus::Success);
+        let status = vello_chart_engine_publish_samples(handle, samples.as_ptr(), samples.len());
+        assert_eq!(status, VelloChartEngineStatus::Success);
+        let status = vello_chart_engine_publish_ohlc_samples(handle, bars.as_ptr(), bars.len());
+        assert_eq!(status, VelloChartEngineStatus::Success);
+        let status = vello_chart_engine_set_visible_range(handle, VISIBLE_START, VISIBLE_END);
+        assert_eq!(status, VelloChartEngineStatus::Success);
+
+        let mut buffer: *mut VelloChartExportBuffer = ptr::null_mut();
+        let status = vello_chart_engine_export(handle, WIDTH, HEIGHT, &export_options, &mut buffer);
+        assert_eq!(status, VelloChartEngineStatus::Success);
+        let mut data = ptr::null();
+        let mut length = 0;
+        let status = vello_chart_engine_export_buffer_data(buffer, &mut data, &mut length);
+        assert_eq!(status, VelloChartEngineStatus::Success);
+        let bytes = std::slice::from_raw_parts(data, length).to_vec();
+
+        vello_chart_engine_export_buffer_destroy(buffer);
+        vello_chart_engine_destroy(handle);
+        bytes
+    }
+}
+
+#[test]
+fn samples_past_the_edges_leave_unconnected_kinds_untouched() {
+    assert!(
+        export_view(true) == export_view(false),
+        "samples outside the view changed the scatter, bar, heatmap or candlestick frame"
+    );
+}
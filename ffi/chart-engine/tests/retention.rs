@@ -0,0 +1,41 @@
+//! Retention changes on an engine that already holds data.
+
+use vello_chart_engine::{ChartEngine, EngineOptions, OhlcSample};
+
+/// Retention never drops below the visible duration, so keep that short.
+fn engine(retention_seconds: f64) -> ChartEngine {
+    ChartEngine::new(EngineOptions {
+        visible_duration_seconds: 10.0,
+        retention_duration_seconds: Some(retention_seconds),
+        ..EngineOptions::default()
+    })
+}
+
+fn bars(timestamps: impl Iterator<Item = u32>) -> Vec<OhlcSample> {
+    timestamps
+        .map(|index| {
+            let t = f64::from(index);
+            OhlcSample {
+                series_id: 0,
+                timestamp_seconds: t,
+                open: t,
+                high: t + 2.0,
+                low: t - 2.0,
+                close: t + 1.0,
+                volume: None,
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn shrinking_retention_trims_candles_with_their_closes() {
+    let mut shrunk = engine(1000.0);
+    shrunk.publish_ohlc_samples(&bars(0..=100));
+    shrunk.set_retention_duration(Some(10.0));
+
+    let mut fresh = engine(10.0);
+    fresh.publish_ohlc_samples(&bars(90..=100));
+
+    assert_eq!(shrunk.snapshot(), fresh.snapshot());
+}
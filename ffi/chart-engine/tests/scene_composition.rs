@@ -0,0 +1,34 @@
+//! Frames composed from the engine's retained scene graph.
+
+use vello_chart_engine::{ChartEngine, ChartSample, EngineOptions, ExportFormat, ExportOptions};
+
+fn engine() -> ChartEngine {
+    let mut engine = ChartEngine::new(EngineOptions::default());
+    let samples: Vec<ChartSample> = (0..40)
+        .map(|index| ChartSample {
+            series_id: index % 2,
+            timestamp_seconds: f64::from(index / 2),
+            value: f64::from(index % 7),
+        })
+        .collect();
+    engine.publish_samples(&samples);
+    engine
+}
+
+fn export(engine: &mut ChartEngine) -> Vec<u8> {
+    engine
+        .export_frame(240, 160, ExportFormat::Png, &ExportOptions::default())
+        .expect("export")
+}
+
+#[test]
+fn series_hidden_after_a_frame_leave_the_composed_scene() {
+    let mut shown_first = engine();
+    export(&mut shown_first);
+    shown_first.set_series_visible(1, false);
+
+    let mut never_shown = engine();
+    never_shown.set_series_visible(1, false);
+
+    assert!(export(&mut shown_first) == export(&mut never_shown));
+}
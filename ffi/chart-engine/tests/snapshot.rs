@@ -0,0 +1,84 @@
+//! Snapshot round trips through the public engine API.
+
+use vello_chart_engine::{ChartEngine, ChartSample, EngineOptions, SnapshotError};
+
+fn populated_engine() -> ChartEngine {
+    let mut engine = ChartEngine::new(EngineOptions::default());
+    let samples: Vec<ChartSample> = (0..50)
+        .map(|index| ChartSample {
+            series_id: index % 2,
+            timestamp_seconds: f64::from(index),
+            value: if index == 20 {
+                f64::NAN
+            } else {
+                f64::from(index).sqrt()
+            },
+        })
+        .collect();
+    engine.publish_samples(&samples);
+    engine.set_series_visible(1, false);
+    engine.set_visible_range(10.0, 40.0);
+    engine
+}
+
+#[test]
+fn restored_engine_snapshots_identically() {
+    let original = populated_engine();
+    let bytes = original.snapshot();
+
+    let mut restored = ChartEngine::new(EngineOptions::default());
+    restored.restore_snapshot(&bytes).expect("restore snapshot");
+
+    assert_eq!(restored.snapshot(), bytes);
+    assert!(!restored.is_series_visible(1));
+    let viewport = restored.viewport();
+    assert_eq!(
+        (
+            viewport.range_start,
+            viewport.range_end,
+            viewport.follow_live
+        ),
+        (10.0, 40.0, false)
+    );
+}
+
+#[test]
+fn rejected_snapshot_leaves_the_engine_untouched() {
+    let mut engine = populated_engine();
+    let before = engine.snapshot();
+
+    let mut newer = before.clone();
+    newer[4] = newer[4].wrapping_add(1);
+    assert!(matches!(
+        engine.restore_snapshot(&newer),
+        Err(SnapshotError::UnsupportedVersion { .. })
+    ));
+    assert!(matches!(
+        engine.restore_snapshot(&before[..before.len() / 2]),
+        Err(SnapshotError::Decode(_))
+    ));
+    assert!(matches!(
+        engine.restore_snapshot(b"not a snapshot"),
+        Err(SnapshotError::NotASnapshot)
+    ));
+
+    // The viewport closes the payload, so the last eight bytes are its range end.
+    let mut unbounded = before.clone();
+    let end = unbounded.len();
+    unbounded[end - 8..].copy_from_slice(&f64::NAN.to_le_bytes());
+    assert!(matches!(
+        engine.restore_snapshot(&unbounded),
+        Err(SnapshotError::InvalidValue(_))
+    ));
+
+    assert_eq!(engine.snapshot(), before);
+}
+
+#[test]
+fn hiding_an_unknown_series_adds_nothing() {
+    let mut engine = populated_engine();
+    let before = engine.snapshot();
+    engine.set_series_visible(99, false);
+    assert_eq!(engine.snapshot(), before);
+    assert!(engine.is_series_visible(99));
+}
@@ -0,0 +1,525 @@
+//! Calendar-aware time ticks and user-supplied axis label patterns.
+//!
+//! Sub-day steps are aligned to multiples of the step in local time; longer steps land
+//! on local midnights, Mondays, and the first day of months and years. The local zone
+//! is a fixed UTC offset, so there is no daylight-saving adjustment.
+
+use std::fmt;
+
+use time::{Date, Month, OffsetDateTime, UtcOffset};
+
+use crate::layout::AxisTick;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+const AVERAGE_MONTH_SECONDS: f64 = 30.436_875 * SECONDS_PER_DAY as f64;
+/// 1970-01-01 was a Thursday; the first Monday after the epoch is day 4.
+const FIRST_MONDAY_EPOCH_DAY: i64 = 4;
+
+/// Largest UTC offset accepted by [`AxisLabelOptions::utc_offset_seconds`].
+pub const MAX_UTC_OFFSET_SECONDS: i32 = 18 * 3_600;
+
+const SUB_DAY_STEPS: &[f64] = &[
+    0.5, 1.0, 2.0, 5.0, 10.0, 15.0, 30.0, 60.0, 120.0, 300.0, 600.0, 900.0, 1800.0, 3600.0, 7200.0,
+    14_400.0, 28_800.0, 43_200.0,
+];
+const MONTH_STEPS: &[i32] = &[1, 3, 6, 12, 24, 60, 120, 240, 600, 1_200];
+
+/// Formatting applied to axis tick labels.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AxisLabelOptions {
+    /// Offset of the local zone from UTC. Ticks align to local boundaries and labels
+    /// show local time.
+    pub utc_offset_seconds: i32,
+    /// Pattern for time labels; `None` picks a format from the tick step.
+    pub time_format: Option<TimeFormat>,
+    /// Pattern for value labels; `None` picks decimals from the tick step.
+    pub value_format: Option<ValueFormat>,
+    /// Adds the date as a second label line on the first tick of each local day when
+    /// ticks are less than a day apart.
+    pub date_on_day_change: bool,
+}
+
+impl AxisLabelOptions {
+    fn offset(&self) -> UtcOffset {
+        let seconds = self
+            .utc_offset_seconds
+            .clamp(-MAX_UTC_OFFSET_SECONDS, MAX_UTC_OFFSET_SECONDS);
+        UtcOffset::from_whole_seconds(seconds).unwrap_or(UtcOffset::UTC)
+    }
+
+    /// Formats a timestamp shown outside the axis, such as a cursor callout, in the
+    /// local zone with `time_format`, or with `fallback` when no pattern is set.
+    pub fn format_time(&self, timestamp: f64, fallback: &TimeFormat) -> String {
+        self.time_format
+            .as_ref()
+            .unwrap_or(fallback)
+            .format(timestamp, self.offset())
+    }
+}
+
+/// Error returned when a label pattern cannot be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LabelFormatError(String);
+
+impl fmt::Display for LabelFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for LabelFormatError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TimeField {
+    Literal(String),
+    Year,
+    Month,
+    MonthName,
+    Day,
+    Weekday,
+    Hour,
+    Minute,
+    Second,
+    Millisecond,
+}
+
+/// Time label pattern using a subset of `strftime` specifiers:
+/// `%Y` year, `%m` month, `%b` month name, `%d` day, `%a` weekday, `%H` hour,
+/// `%M` minute, `%S` second, `%f` milliseconds and `%%` for a literal percent sign.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimeFormat {
+    fields: Vec<TimeField>,
+}
+
+impl TimeFormat {
+    pub fn parse(pattern: &str) -> Result<Self, LabelFormatError> {
+        let mut fields = Vec::new();
+        let mut literal = String::new();
+        let mut chars = pattern.chars();
+        while let Some(ch) = chars.next() {
+            if ch != '%' {
+                literal.push(ch);
+                continue;
+            }
+            let field = match chars.next() {
+                Some('%') => {
+                    literal.push('%');
+                    continue;
+                }
+                Some('Y') => TimeField::Year,
+                Some('m') => TimeField::Month,
+                Some('b') => TimeField::MonthName,
+                Some('d') => TimeField::Day,
+                Some('a') => TimeField::Weekday,
+                Some('H') => TimeField::Hour,
+                Some('M') => TimeField::Minute,
+                Some('S') => TimeField::Second,
+                Some('f') => TimeField::Millisecond,
+                Some(other) => {
+                    return Err(LabelFormatError(format!(
+                        "Unknown time format specifier '%{other}'"
+                    )));
+                }
+                None => {
+                    return Err(LabelFormatError(
+                        "Time format ends with an incomplete '%' specifier".to_string(),
+                    ));
+                }
+            };
+            if !literal.is_empty() {
+                fields.push(TimeField::Literal(std::mem::take(&mut literal)));
+            }
+            fields.push(field);
+        }
+        if !literal.is_empty() {
+            fields.push(TimeField::Literal(literal));
+        }
+        Ok(Self { fields })
+    }
+
+    fn format(&self, timestamp: f64, offset: UtcOffset) -> String {
+        let Some(local) = local_date_time(timestamp, offset) else {
+            return format!("{timestamp:.0}");
+        };
+        let millis = ((timestamp - timestamp.floor()) * 1_000.0)
+            .round()
+            .clamp(0.0, 999.0) as u32;
+
+        let mut label = String::new();
+        for field in &self.fields {
+            match field {
+                TimeField::Literal(text) => label.push_str(text),
+                TimeField::Year => label.push_str(&format!("{:04}", local.year())),
+                TimeField::Month => label.push_str(&format!("{:02}", u8::from(local.month()))),
+                TimeField::MonthName => label.push_str(month_abbreviation(local.month())),
+                TimeField::Day => label.push_str(&format!("{:02}", local.day())),
+                TimeField::Weekday => {
+                    let name = local.weekday().to_string();
+                    label.push_str(&name[..3]);
+                }
+                TimeField::Hour => label.push_str(&format!("{:02}", local.hour())),
+                TimeField::Minute => label.push_str(&format!("{:02}", local.minute())),
+                TimeField::Second => label.push_str(&format!("{:02}", local.second())),
+                TimeField::Millisecond => label.push_str(&format!("{millis:03}")),
+            }
+        }
+        label
+    }
+}
+
+/// Value label pattern with a single placeholder: `{}` keeps the automatic decimals
+/// and `{:.N}` prints `N` decimals. `{{` and `}}` produce literal braces.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValueFormat {
+    prefix: String,
+    suffix: String,
+    decimals: Option<usize>,
+}
+
+impl ValueFormat {
+    pub fn parse(pattern: &str) -> Result<Self, LabelFormatError> {
+        let mut prefix = String::new();
+        let mut suffix = String::new();
+        let mut placeholder = None;
+        let mut chars = pattern.chars().peekable();
+        while let Some(ch) = chars.next() {
+            let target = if placeholder.is_some() {
+                &mut suffix
+            } else {
+                &mut prefix
+            };
+            match ch {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    target.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    target.push('}');
+                }
+                '{' => {
+                    if placeholder.is_some() {
+                        return Err(LabelFormatError(
+                            "Value format must contain a single placeholder".to_string(),
+                        ));
+                    }
+                    let mut spec = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(ch) => spec.push(ch),
+                            None => {
+                                return Err(LabelFormatError(
+                                    "Unterminated placeholder in value format".to_string(),
+                                ));
+                            }
+                        }
+                    }
+                    placeholder = Some(parse_decimals(&spec)?);
+                }
+                '}' => {
+                    return Err(LabelFormatError(
+                        "Unmatched '}' in value format".to_string(),
+                    ));
+                }
+                _ => target.push(ch),
+            }
+        }
+
+        match placeholder {
+            Some(decimals) => Ok(Self {
+                prefix,
+                suffix,
+                decimals,
+            }),
+            None => Err(LabelFormatError(
+                "Value format must contain a '{}' placeholder".to_string(),
+            )),
+        }
+    }
+
+    /// Formats `value`, calling `automatic` when the placeholder has no precision.
+    pub(crate) fn format(&self, value: f64, automatic: impl FnOnce() -> String) -> String {
+        let number = match self.decimals {
+            Some(decimals) if value.is_finite() => format!("{value:.decimals$}"),
+            _ => automatic(),
+        };
+        format!("{}{number}{}", self.prefix, self.suffix)
+    }
+}
+
+fn parse_decimals(spec: &str) -> Result<Option<usize>, LabelFormatError> {
+    if spec.is_empty() {
+        return Ok(None);
+    }
+    spec.strip_prefix(":.")
+        .and_then(|digits| digits.parse::<usize>().ok())
+        .filter(|decimals| *decimals <= 17)
+        .map(Some)
+        .ok_or_else(|| LabelFormatError(format!("Unsupported value placeholder '{{{spec}}}'")))
+}
+
+/// Spacing between consecutive time ticks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TimeStep {
+    Seconds(f64),
+    Days(i64),
+    Week,
+    Months(i32),
+}
+
+impl TimeStep {
+    fn pick(target: f64) -> Self {
+        if let Some(step) = SUB_DAY_STEPS.iter().find(|step| **step >= target) {
+            return Self::Seconds(*step);
+        }
+        let candidates = [Self::Days(1), Self::Days(2), Self::Week]
+            .into_iter()
+            .chain(MONTH_STEPS.iter().map(|months| Self::Months(*months)));
+        let mut last = Self::Days(1);
+        for step in candidates {
+            if step.approximate_seconds() >= target {
+                return step;
+            }
+            last = step;
+        }
+        last
+    }
+
+    fn approximate_seconds(self) -> f64 {
+        match self {
+            Self::Seconds(seconds) => seconds,
+            Self::Days(days) => (days * SECONDS_PER_DAY) as f64,
+            Self::Week => (7 * SECONDS_PER_DAY) as f64,
+            Self::Months(months) => f64::from(months) * AVERAGE_MONTH_SECONDS,
+        }
+    }
+
+    /// Tick timestamps in `[range_start, range_end]`, aligned in local time.
+    fn ticks(self, range_start: f64, range_end: f64, offset: UtcOffset) -> Vec<f64> {
+        let shift = f64::from(offset.whole_seconds());
+        let mut ticks = Vec::new();
+        match self {
+            Self::Seconds(step) => {
+                let mut value = ((range_start + shift) / step).ceil() * step - shift;
+                while value <= range_end + step * 0.5 {
+                    ticks.push(value);
+                    value += step;
+                }
+            }
+            Self::Days(_) | Self::Week => {
+                let (stride, anchor) = match self {
+                    Self::Days(days) => (days, 0),
+                    _ => (7, FIRST_MONDAY_EPOCH_DAY),
+                };
+                let first_day = ((range_start + shift) / SECONDS_PER_DAY as f64).ceil() as i64;
+                let mut day = first_day + (anchor - first_day).rem_euclid(stride);
+                loop {
+                    let value = (day * SECONDS_PER_DAY) as f64 - shift;
+                    if value > range_end {
+                        break;
+                    }
+                    ticks.push(value);
+                    day += stride;
+                }
+            }
+            Self::Months(stride) => {
+                let Some(start) = local_date_time(range_start, offset) else {
+                    return ticks;
+                };
+                let mut index = start.year() * 12 + i32::from(u8::from(start.month())) - 1;
+                index += (-index).rem_euclid(stride);
+                while let Some(value) = month_start(index, offset) {
+                    if value > range_end {
+                        break;
+                    }
+                    if value >= range_start {
+                        ticks.push(value);
+                    }
+                    index += stride;
+                }
+            }
+        }
+        ticks
+    }
+
+    fn default_format(self) -> TimeFormat {
+        let pattern = match self {
+            Self::Seconds(step) if step < 1.0 => "%H:%M:%S.%f",
+            Self::Seconds(step) if step < 60.0 => "%H:%M:%S",
+            Self::Seconds(_) => "%H:%M",
+            Self::Days(_) | Self::Week => "%b %d",
+            Self::Months(months) if months < 12 => "%b %Y",
+            Self::Months(_) => "%Y",
+        };
+        TimeFormat::parse(pattern).expect("built-in time formats are valid")
+    }
+}
+
+pub(crate) fn compute_time_ticks(
+    range_start: f64,
+    range_end: f64,
+    max_ticks: usize,
+    options: &AxisLabelOptions,
+) -> Vec<AxisTick> {
+    if !range_start.is_finite() || !range_end.is_finite() || range_end <= range_start {
+        return Vec::new();
+    }
+
+    let total = range_end - range_start;
+    let offset = options.offset();
+    let step = TimeStep::pick(total / (max_ticks as f64));
+    let default_format;
+    let format = match &options.time_format {
+        Some(format) => format,
+        None => {
+            default_format = step.default_format();
+            &default_format
+        }
+    };
+    let date_format = (options.date_on_day_change && matches!(step, TimeStep::Seconds(_)))
+        .then(|| TimeFormat::parse("%b %d").expect("built-in time formats are valid"));
+
+    let mut timestamps = step.ticks(range_start, range_end, offset);
+    if timestamps.is_empty() {
+        timestamps = vec![range_start, range_end];
+    }
+
+    let mut previous_day = None;
+    timestamps
+        .into_iter()
+        .map(|timestamp| {
+            let mut label = format.format(timestamp, offset);
+            if let Some(date_format) = &date_format {
+                let day = local_date_time(timestamp, offset).map(|local| local.date());
+                if day != previous_day {
+                    label.push('\n');
+                    label.push_str(&date_format.format(timestamp, offset));
+                    previous_day = day;
+                }
+            }
+            AxisTick {
+                position: ((timestamp - range_start) / total).clamp(0.0, 1.0),
+                label,
+            }
+        })
+        .collect()
+}
+
+fn local_date_time(timestamp: f64, offset: UtcOffset) -> Option<OffsetDateTime> {
+    if !timestamp.is_finite() {
+        return None;
+    }
+    OffsetDateTime::from_unix_timestamp(timestamp.floor() as i64)
+        .ok()?
+        .checked_to_offset(offset)
+}
+
+/// Unix timestamp of local midnight on the first day of month `index` (`year * 12 + month0`).
+fn month_start(index: i32, offset: UtcOffset) -> Option<f64> {
+    let year = index.div_euclid(12);
+    let month = Month::try_from(index.rem_euclid(12) as u8 + 1).ok()?;
+    let date = Date::from_calendar_date(year, month, 1).ok()?;
+    Some(date.midnight().assume_offset(offset).unix_timestamp() as f64)
+}
+
+fn month_abbreviation(month: Month) -> &'static str {
+    match month {
+        Month::January => "Jan",
+        Month::February => "Feb",
+        Month::March => "Mar",
+        Month::April => "Apr",
+        Month::May => "May",
+        Month::June => "Jun",
+        Month::July => "Jul",
+        Month::August => "Aug",
+        Month::September => "Sep",
+        Month::October => "Oct",
+        Month::November => "Nov",
+        Month::December => "Dec",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(ticks: &[AxisTick]) -> Vec<&str> {
+        ticks.iter().map(|tick| tick.label.as_str()).collect()
+    }
+
+    /// 2024-01-01T00:00:00Z, a Monday.
+    const JAN_1_2024: f64 = 1_704_067_200.0;
+    const DAY: f64 = SECONDS_PER_DAY as f64;
+
+    #[test]
+    fn long_ranges_step_by_calendar_units() {
+        let options = AxisLabelOptions::default();
+        let weeks = compute_time_ticks(JAN_1_2024 + DAY, JAN_1_2024 + 40.0 * DAY, 6, &options);
+        assert_eq!(
+            labels(&weeks),
+            ["Jan 08", "Jan 15", "Jan 22", "Jan 29", "Feb 05"]
+        );
+
+        let months = compute_time_ticks(JAN_1_2024 + DAY, JAN_1_2024 + 400.0 * DAY, 6, &options);
+        assert_eq!(
+            labels(&months),
+            ["Apr 2024", "Jul 2024", "Oct 2024", "Jan 2025"]
+        );
+    }
+
+    #[test]
+    fn ticks_align_to_local_boundaries() {
+        let options = AxisLabelOptions {
+            utc_offset_seconds: 5 * 3_600 + 1_800,
+            date_on_day_change: true,
+            ..AxisLabelOptions::default()
+        };
+        // 20:00 to 04:00 local time on 2024-01-01/02 at UTC+05:30.
+        let start = JAN_1_2024 + 14.5 * 3_600.0;
+        let ticks = compute_time_ticks(start, start + 8.0 * 3_600.0, 4, &options);
+        assert_eq!(
+            labels(&ticks),
+            ["20:00\nJan 01", "22:00", "00:00\nJan 02", "02:00", "04:00"]
+        );
+    }
+
+    #[test]
+    fn single_timestamps_use_the_zone_and_pattern() {
+        let fallback = TimeFormat::parse("%H:%M:%S.%f").unwrap();
+        let mut options = AxisLabelOptions {
+            utc_offset_seconds: 2 * 3_600,
+            ..AxisLabelOptions::default()
+        };
+        assert_eq!(
+            options.format_time(JAN_1_2024 + 0.25, &fallback),
+            "02:00:00.250"
+        );
+
+        options.time_format = Some(TimeFormat::parse("%d %H:%M").unwrap());
+        assert_eq!(
+            options.format_time(JAN_1_2024 + 0.25, &fallback),
+            "01 02:00"
+        );
+    }
+
+    #[test]
+    fn patterns_format_labels() {
+        let time = TimeFormat::parse("%a %Y-%m-%d %H:%M:%S.%f %%").unwrap();
+        assert_eq!(
+            time.format(JAN_1_2024 + 3_723.25, UtcOffset::UTC),
+            "Mon 2024-01-01 01:02:03.250 %"
+        );
+        assert!(TimeFormat::parse("%Q").is_err());
+        assert!(TimeFormat::parse("%").is_err());
+
+        let value = ValueFormat::parse("{{{:.2}}} ms").unwrap();
+        assert_eq!(value.format(1.5, || unreachable!()), "{1.50} ms");
+        let automatic = ValueFormat::parse("$ {}").unwrap();
+        assert_eq!(automatic.format(3.0, || "3".to_string()), "$ 3");
+        assert!(ValueFormat::parse("no placeholder").is_err());
+        assert!(ValueFormat::parse("{} {}").is_err());
+        assert!(ValueFormat::parse("{:x}").is_err());
+        assert!(ValueFormat::parse("{:.2").is_err());
+    }
+}
@@ -0,0 +1,262 @@
+//! Font registry for composition labels.
+//!
+//! Hosts register font blobs under a family name and choose the default family list.
+//! Label shaping walks the fallback chain built from those families, ending with the
+//! embedded label font, and picks a face per cluster. Faces of one family are ordered by
+//! how closely their weight, width and slant match the requested style.
+
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+use harfrust::{FontRef as ShapingFontRef, ShaperData};
+use once_cell::sync::Lazy;
+use skrifa::attribute::{Attributes, Style};
+use skrifa::raw::{FileRef, FontRef};
+use skrifa::{MetadataProvider, Tag};
+use vello::peniko::{Blob, FontData};
+
+use crate::text::{FontSlant, LabelStyle, label_font};
+
+/// Family name of the embedded label font, always the last entry of a fallback chain.
+pub const BUILTIN_FONT_FAMILY: &str = "Roboto";
+
+static FONT_REGISTRY: Lazy<RwLock<FontRegistry>> = Lazy::new(|| {
+    RwLock::new(FontRegistry {
+        fonts: vec![Arc::new(
+            RegisteredFont::new(FontId(0), BUILTIN_FONT_FAMILY, label_font().clone())
+                .expect("embedded label font parses"),
+        )],
+        default_families: Vec::new(),
+    })
+});
+
+/// Bumped whenever the registry changes so shapers can drop stale layouts.
+static FONT_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FontId(pub u32);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FontRegistrationError {
+    EmptyFamily,
+    /// The blob is not a font, or `index` is past the end of a collection.
+    InvalidFont,
+}
+
+impl fmt::Display for FontRegistrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyFamily => f.write_str("font family name is empty"),
+            Self::InvalidFont => f.write_str("font data could not be parsed"),
+        }
+    }
+}
+
+impl std::error::Error for FontRegistrationError {}
+
+/// A registered face with the shaping tables parsed once.
+pub(crate) struct RegisteredFont {
+    pub(crate) id: FontId,
+    family: String,
+    pub(crate) font: FontData,
+    pub(crate) shaper_data: ShaperData,
+    attributes: Attributes,
+}
+
+impl RegisteredFont {
+    fn new(id: FontId, family: &str, font: FontData) -> Option<Self> {
+        let shaping = ShapingFontRef::from_index(font.data.as_ref(), font.index).ok()?;
+        let shaper_data = ShaperData::new(&shaping);
+        let attributes = to_font_ref(&font)?.attributes();
+        Some(Self {
+            id,
+            family: family.to_owned(),
+            font,
+            shaper_data,
+            attributes,
+        })
+    }
+
+    pub(crate) fn font_ref(&self) -> Option<FontRef<'_>> {
+        to_font_ref(&self.font)
+    }
+
+    pub(crate) fn shaping_font_ref(&self) -> Option<ShapingFontRef<'_>> {
+        ShapingFontRef::from_index(self.font.data.as_ref(), self.font.index).ok()
+    }
+
+    /// How far the face is from `style`, zero being an exact match. A slant mismatch
+    /// outweighs any width difference, which outweighs any weight difference; variable
+    /// axes match every value in their range.
+    fn style_distance(&self, style: &LabelStyle) -> f32 {
+        let Some(font) = self.font_ref() else {
+            return f32::INFINITY;
+        };
+        let axes = font.axes();
+        let axis = |tag: &[u8; 4]| axes.get_by_tag(Tag::new(tag));
+        let distance = |tag: &[u8; 4], value: f32, fixed: f32| match axis(tag) {
+            Some(axis) => (axis.min_value() - value)
+                .max(value - axis.max_value())
+                .max(0.0),
+            None => (fixed - value).abs(),
+        };
+
+        let slant_matches = match style.slant {
+            FontSlant::Normal => matches!(self.attributes.style, Style::Normal),
+            FontSlant::Italic | FontSlant::Oblique => {
+                !matches!(self.attributes.style, Style::Normal)
+                    || axis(b"ital").is_some_and(|axis| axis.max_value() > 0.0)
+                    || axis(b"slnt").is_some_and(|axis| axis.min_value() < 0.0)
+            }
+        };
+        let slant = if slant_matches { 0.0 } else { 1.0e6 };
+        let width = distance(b"wdth", style.width, self.attributes.stretch.percentage());
+        let weight = distance(b"wght", style.weight, self.attributes.weight.value());
+        slant + width * 1.0e3 + weight
+    }
+}
+
+struct FontRegistry {
+    /// Registration order; the embedded label font is always first.
+    fonts: Vec<Arc<RegisteredFont>>,
+    default_families: Vec<String>,
+}
+
+impl FontRegistry {
+    fn chain(&self, families: &[String], style: &LabelStyle) -> Vec<Arc<RegisteredFont>> {
+        let mut chain: Vec<Arc<RegisteredFont>> = Vec::new();
+        for family in style.family.iter().chain(families) {
+            let start = chain.len();
+            for font in &self.fonts {
+                if font.family.eq_ignore_ascii_case(family)
+                    && !chain.iter().any(|existing| existing.id == font.id)
+                {
+                    chain.push(font.clone());
+                }
+            }
+            // The closest face leads; the rest of the family still covers what it lacks.
+            chain[start..]
+                .sort_by(|a, b| a.style_distance(style).total_cmp(&b.style_distance(style)));
+        }
+        if !chain.iter().any(|font| font.id == FontId(0)) {
+            chain.push(self.fonts[0].clone());
+        }
+        chain
+    }
+}
+
+/// Registers the face at `index` of `data` under `family`.
+pub fn register_font(
+    family: &str,
+    data: Vec<u8>,
+    index: u32,
+) -> Result<FontId, FontRegistrationError> {
+    let family = family.trim();
+    if family.is_empty() {
+        return Err(FontRegistrationError::EmptyFamily);
+    }
+
+    let font = FontData::new(Blob::new(Arc::new(data)), index);
+    let mut registry = FONT_REGISTRY.write().expect("font registry poisoned");
+    let id = FontId(registry.fonts.len() as u32);
+    let registered =
+        RegisteredFont::new(id, family, font).ok_or(FontRegistrationError::InvalidFont)?;
+    registry.fonts.push(Arc::new(registered));
+    FONT_GENERATION.fetch_add(1, Ordering::Release);
+    Ok(id)
+}
+
+/// Sets the families tried, in order, before the embedded label font.
+pub fn set_default_font_families<S: AsRef<str>>(families: &[S]) {
+    let mut registry = FONT_REGISTRY.write().expect("font registry poisoned");
+    registry.default_families = families
+        .iter()
+        .map(|family| family.as_ref().trim().to_owned())
+        .filter(|family| !family.is_empty())
+        .collect();
+    FONT_GENERATION.fetch_add(1, Ordering::Release);
+}
+
+pub fn default_font_families() -> Vec<String> {
+    FONT_REGISTRY
+        .read()
+        .expect("font registry poisoned")
+        .default_families
+        .clone()
+}
+
+pub(crate) fn font_generation() -> u64 {
+    FONT_GENERATION.load(Ordering::Acquire)
+}
+
+/// Faces of the style's family, then of `families` (or the default families), in
+/// preference order, then the embedded label font.
+pub(crate) fn fallback_chain(
+    families: Option<&[String]>,
+    style: &LabelStyle,
+) -> Vec<Arc<RegisteredFont>> {
+    let registry = FONT_REGISTRY.read().expect("font registry poisoned");
+    registry.chain(families.unwrap_or(&registry.default_families), style)
+}
+
+pub(crate) fn to_font_ref(font: &FontData) -> Option<FontRef<'_>> {
+    let file_ref = FileRef::new(font.data.as_ref()).ok()?;
+    match file_ref {
+        FileRef::Font(font) => Some(font),
+        FileRef::Collection(collection) => collection.get(font.index).ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registration_rejects_bad_input() {
+        assert_eq!(
+            register_font("  ", vec![0; 4], 0),
+            Err(FontRegistrationError::EmptyFamily)
+        );
+        assert_eq!(
+            register_font("Broken", b"not a font".to_vec(), 0),
+            Err(FontRegistrationError::InvalidFont)
+        );
+    }
+
+    #[test]
+    fn chains_end_with_the_embedded_font() {
+        let chain = fallback_chain(Some(&["Missing".to_string()]), &LabelStyle::default());
+        assert_eq!(chain.len(), 1);
+        assert_eq!(chain[0].id, FontId(0));
+    }
+
+    #[test]
+    fn closest_face_of_a_family_leads() {
+        let flex = include_bytes!(
+            "../../../samples/AvaloniaVelloSkiaSharpSample/Assets/fonts/RobotoFlex-Subset.ttf"
+        );
+        let families = ["Test Pair".to_string()];
+        let regular = register_font("Test Pair", label_font().data.as_ref().to_vec(), 0)
+            .expect("static face");
+        let variable = register_font("Test Pair", flex.to_vec(), 0).expect("variable face");
+        let ids = |style: &LabelStyle| -> Vec<FontId> {
+            fallback_chain(Some(&families), style)
+                .iter()
+                .map(|font| font.id)
+                .collect()
+        };
+
+        assert_eq!(ids(&LabelStyle::default()), [regular, variable, FontId(0)]);
+        let bold = LabelStyle {
+            weight: 700.0,
+            ..LabelStyle::default()
+        };
+        assert_eq!(ids(&bold), [variable, regular, FontId(0)]);
+        let italic = LabelStyle {
+            slant: FontSlant::Italic,
+            ..LabelStyle::default()
+        };
+        assert_eq!(ids(&italic)[0], variable);
+    }
+}
@@ -1,11 +1,14 @@
 use std::ffi::c_uchar;
 use std::{ptr, slice, str};
 
+use vello::kurbo::{Affine, Rect, RoundedRect};
+
 use crate::animation::{
     DirtyIntent, EasingFunction, EasingTrackDescriptor, RepeatMode, SpringTrackDescriptor,
     TimelineGroupConfig, TimelineSample, TimelineSystem,
 };
 use crate::constraints::{LayoutConstraints, LayoutSize, ScalarConstraint};
+use crate::fonts::{register_font, set_default_font_families};
 use crate::layout::{self, PlotArea};
 use crate::linear_layout::{self, LinearLayoutItem};
 use crate::materials::{
@@ -19,8 +22,9 @@ use crate::panels::{
     StackLayoutOptions, WrapLayoutChild, WrapLayoutLine, WrapLayoutOptions, WrapLayoutResult,
     solve_dock_layout, solve_grid_layout, solve_stack_layout, solve_wrap_layout,
 };
+use crate::paragraph::{ParagraphOptions, TextWrap, layout_paragraph};
 use crate::scene_cache::{DirtyRegion, SceneGraphCache, SceneNodeId};
-use crate::text;
+use crate::text::{self, FontSlant, FontVariation, LabelStyle};
 use crate::virtualization::{
     ColumnSlice, ColumnStrip, ColumnViewportMetrics, FrozenKind, HybridVirtualizer, RowAction,
     RowPlanEntry, RowViewportMetrics, VirtualNodeId, VirtualizerTelemetry,
@@ -465,6 +469,25 @@ pub struct CompositionDirtyRegion {
     pub max_y: f64,
 }
 
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct CompositionAffine {
+    pub m11: f64,
+    pub m12: f64,
+    pub m21: f64,
+    pub m22: f64,
+    pub dx: f64,
+    pub dy: f64,
+}
+
+impl From<CompositionAffine> for Affine {
+    fn from(value: CompositionAffine) -> Self {
+        Affine::new([
+            value.m11, value.m12, value.m21, value.m22, value.dx, value.dy,
+        ])
+    }
+}
+
 #[repr(u32)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CompositionTimelineDirtyKind {
@@ -1227,6 +1250,361 @@ pub unsafe extern "C" fn vello_composition_measure_label(
     }
 }
 
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompositionFontSlant {
+    Normal = 0,
+    Italic = 1,
+    Oblique = 2,
+}
+
+impl From<CompositionFontSlant> for FontSlant {
+    fn from(value: CompositionFontSlant) -> Self {
+        match value {
+            CompositionFontSlant::Normal => FontSlant::Normal,
+            CompositionFontSlant::Italic => FontSlant::Italic,
+            CompositionFontSlant::Oblique => FontSlant::Oblique,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct CompositionFontVariation {
+    /// Axis tag packed big-endian, e.g. `u32::from_be_bytes(*b"wght")`.
+    pub tag: u32,
+    pub value: f32,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct CompositionLabelStyle {
+    /// Optional family tried before the default families; null or empty for none.
+    pub family_ptr: *const c_uchar,
+    pub family_len: usize,
+    pub font_size: f32,
+    pub weight: f32,
+    pub width: f32,
+    pub slant: CompositionFontSlant,
+    pub variations: *const CompositionFontVariation,
+    pub variation_count: usize,
+}
+
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn vello_composition_measure_label_styled(
+    text_ptr: *const c_uchar,
+    text_len: usize,
+    style: *const CompositionLabelStyle,
+    out_metrics: *mut CompositionLabelMetrics,
+) -> bool {
+    if style.is_null() || out_metrics.is_null() {
+        return false;
+    }
+
+    let text = if text_ptr.is_null() || text_len == 0 {
+        ""
+    } else {
+        let bytes = unsafe { slice::from_raw_parts(text_ptr, text_len) };
+        match str::from_utf8(bytes) {
+            Ok(value) => value,
+            Err(_) => return false,
+        }
+    };
+
+    let style = unsafe { &*style };
+    let family = if style.family_ptr.is_null() || style.family_len == 0 {
+        None
+    } else {
+        let bytes = unsafe { slice::from_raw_parts(style.family_ptr, style.family_len) };
+        match str::from_utf8(bytes) {
+            Ok(value) => Some(value.to_owned()),
+            Err(_) => return false,
+        }
+    };
+    let variations = if style.variation_count == 0 {
+        &[][..]
+    } else if style.variations.is_null() {
+        return false;
+    } else {
+        unsafe { slice::from_raw_parts(style.variations, style.variation_count) }
+    };
+
+    let label_style = LabelStyle {
+        family,
+        font_size: style.font_size,
+        weight: style.weight,
+        width: style.width,
+        slant: style.slant.into(),
+        variations: variations
+            .iter()
+            .map(|variation| FontVariation::new(&variation.tag.to_be_bytes(), variation.value))
+            .collect(),
+    };
+    match text::layout_styled_label(text, &label_style) {
+        Some(layout) => {
+            let metrics = CompositionLabelMetrics {
+                width: layout.width,
+                height: layout.height,
+                ascent: layout.ascent,
+            };
+            unsafe {
+                ptr::write(out_metrics, metrics);
+            }
+            true
+        }
+        None => false,
+    }
+}
+
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompositionTextWrap {
+    NoWrap = 0,
+    Word = 1,
+    Character = 2,
+}
+
+impl From<CompositionTextWrap> for TextWrap {
+    fn from(value: CompositionTextWrap) -> Self {
+        match value {
+            CompositionTextWrap::NoWrap => TextWrap::NoWrap,
+            CompositionTextWrap::Word => TextWrap::Word,
+            CompositionTextWrap::Character => TextWrap::Character,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct CompositionParagraphOptions {
+    pub font_size: f32,
+    /// Non-positive or non-finite values leave lines unbounded.
+    pub max_width: f32,
+    /// Non-positive or non-finite values align lines to the text itself.
+    pub max_height: f32,
+    pub line_height: f32,
+    /// Zero keeps every line.
+    pub max_lines: u32,
+    pub wrap: CompositionTextWrap,
+    pub ellipsis: i32,
+    pub horizontal_alignment: CompositionLayoutAlignment,
+    pub vertical_alignment: CompositionLayoutAlignment,
+}
+
+impl From<CompositionParagraphOptions> for ParagraphOptions {
+    fn from(value: CompositionParagraphOptions) -> Self {
+        let bounded = |extent: f32| {
+            if extent.is_finite() && extent > 0.0 {
+                extent
+            } else {
+                f32::INFINITY
+            }
+        };
+        ParagraphOptions {
+            font_size: value.font_size,
+            max_width: bounded(value.max_width),
+            max_height: bounded(value.max_height),
+            wrap: value.wrap.into(),
+            line_height: value.line_height,
+            max_lines: (value.max_lines > 0).then_some(value.max_lines as usize),
+            ellipsis: value.ellipsis != 0,
+            horizontal_alignment: value.horizontal_alignment.into(),
+            vertical_alignment: value.vertical_alignment.into(),
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CompositionParagraphMetrics {
+    pub width: f32,
+    pub height: f32,
+    pub line_advance: f32,
+    pub line_count: u32,
+    pub glyph_count: u32,
+    pub truncated: i32,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CompositionParagraphLine {
+    /// UTF-8 byte offset of the line in the source text.
+    pub text_start: u32,
+    pub text_length: u32,
+    pub x: f32,
+    pub baseline: f32,
+    pub width: f32,
+    pub glyph_start: u32,
+    pub glyph_count: u32,
+    pub ellipsized: i32,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CompositionGlyph {
+    pub font_id: u32,
+    pub glyph_id: u32,
+    pub x: f32,
+    pub y: f32,
+}
+
+/// Lays out a paragraph and copies as many lines and glyphs as the output buffers hold.
+/// The metrics always report the full counts, so hosts can size buffers by calling once
+/// with null outputs.
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn vello_composition_layout_paragraph(
+    text_ptr: *const c_uchar,
+    text_len: usize,
+    options: *const CompositionParagraphOptions,
+    out_metrics: *mut CompositionParagraphMetrics,
+    out_lines: *mut CompositionParagraphLine,
+    line_len: usize,
+    out_glyphs: *mut CompositionGlyph,
+    glyph_len: usize,
+) -> bool {
+    if options.is_null() || out_metrics.is_null() || text_ptr.is_null() || text_len == 0 {
+        return false;
+    }
+
+    let bytes = unsafe { slice::from_raw_parts(text_ptr, text_len) };
+    let Ok(text) = str::from_utf8(bytes) else {
+        return false;
+    };
+    let options = ParagraphOptions::from(unsafe { *options });
+    let Some(layout) = layout_paragraph(text, &options) else {
+        return false;
+    };
+
+    let lines = if out_lines.is_null() || line_len == 0 {
+        &mut [][..]
+    } else {
+        unsafe { slice::from_raw_parts_mut(out_lines, line_len) }
+    };
+    let glyphs = if out_glyphs.is_null() || glyph_len == 0 {
+        &mut [][..]
+    } else {
+        unsafe { slice::from_raw_parts_mut(out_glyphs, glyph_len) }
+    };
+
+    let mut glyph_index = 0usize;
+    for (index, line) in layout.lines.iter().enumerate() {
+        let glyph_start = glyph_index;
+        for run in &line.runs {
+            for glyph in &run.glyphs {
+                if let Some(dst) = glyphs.get_mut(glyph_index) {
+                    *dst = CompositionGlyph {
+                        font_id: run.font_id.0,
+                        glyph_id: glyph.id,
+                        x: glyph.x,
+                        y: glyph.y,
+                    };
+                }
+                glyph_index += 1;
+            }
+        }
+        if let Some(dst) = lines.get_mut(index) {
+            *dst = CompositionParagraphLine {
+                text_start: line.range.start as u32,
+                text_length: line.range.len() as u32,
+                x: line.x,
+                baseline: line.baseline,
+                width: line.width,
+                glyph_start: glyph_start as u32,
+                glyph_count: (glyph_index - glyph_start) as u32,
+                ellipsized: i32::from(line.ellipsized),
+            };
+        }
+    }
+
+    unsafe {
+        ptr::write(
+            out_metrics,
+            CompositionParagraphMetrics {
+                width: layout.width,
+                height: layout.height,
+                line_advance: layout.line_advance,
+                line_count: layout.lines.len() as u32,
+                glyph_count: glyph_index as u32,
+                truncated: i32::from(layout.truncated),
+            },
+        );
+    }
+    true
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct CompositionFontFamily {
+    pub name_ptr: *const c_uchar,
+    pub name_len: usize,
+}
+
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn vello_composition_font_register(
+    family_ptr: *const c_uchar,
+    family_len: usize,
+    data_ptr: *const c_uchar,
+    data_len: usize,
+    index: u32,
+    out_font_id: *mut u32,
+) -> bool {
+    if family_ptr.is_null() || data_ptr.is_null() || data_len == 0 {
+        return false;
+    }
+
+    let family = match str::from_utf8(unsafe { slice::from_raw_parts(family_ptr, family_len) }) {
+        Ok(value) => value,
+        Err(_) => return false,
+    };
+    let data = unsafe { slice::from_raw_parts(data_ptr, data_len) }.to_vec();
+
+    match register_font(family, data, index) {
+        Ok(id) => {
+            if !out_font_id.is_null() {
+                unsafe {
+                    ptr::write(out_font_id, id.0);
+                }
+            }
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn vello_composition_font_set_default_families(
+    families_ptr: *const CompositionFontFamily,
+    family_count: usize,
+) -> bool {
+    if families_ptr.is_null() && family_count > 0 {
+        return false;
+    }
+
+    let families = if family_count == 0 {
+        &[][..]
+    } else {
+        unsafe { slice::from_raw_parts(families_ptr, family_count) }
+    };
+    let mut names = Vec::with_capacity(families.len());
+    for family in families {
+        if family.name_ptr.is_null() {
+            return false;
+        }
+        let bytes = unsafe { slice::from_raw_parts(family.name_ptr, family.name_len) };
+        match str::from_utf8(bytes) {
+            Ok(name) => names.push(name),
+            Err(_) => return false,
+        }
+    }
+
+    set_default_font_families(&names);
+    true
+}
+
 #[unsafe(no_mangle)]
 #[allow(clippy::missing_safety_doc)]
 pub unsafe extern "C" fn vello_composition_solve_linear_layout(
@@ -1388,6 +1766,121 @@ pub unsafe extern "C" fn vello_composition_scene_cache_clear(
     cache.clear(SceneNodeId(node_id as usize));
 }
 
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn vello_composition_scene_cache_set_transform(
+    cache: *mut SceneGraphCache,
+    node_id: u32,
+    transform: CompositionAffine,
+) -> bool {
+    if cache.is_null() {
+        return false;
+    }
+    let cache = unsafe { &mut *cache };
+    cache.set_transform(SceneNodeId(node_id as usize), transform.into());
+    true
+}
+
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn vello_composition_scene_cache_set_clip_rect(
+    cache: *mut SceneGraphCache,
+    node_id: u32,
+    left: f64,
+    top: f64,
+    width: f64,
+    height: f64,
+    corner_radius: f64,
+) -> bool {
+    if cache.is_null() || !(width >= 0.0 && height >= 0.0) {
+        return false;
+    }
+    let cache = unsafe { &mut *cache };
+    let rect = Rect::from_origin_size((left, top), (width, height));
+    let node = SceneNodeId(node_id as usize);
+    if corner_radius > 0.0 {
+        cache.set_clip(node, &RoundedRect::from_rect(rect, corner_radius));
+    } else {
+        cache.set_clip(node, &rect);
+    }
+    true
+}
+
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn vello_composition_scene_cache_clear_clip(
+    cache: *mut SceneGraphCache,
+    node_id: u32,
+) {
+    if cache.is_null() {
+        return;
+    }
+    let cache = unsafe { &mut *cache };
+    cache.clear_clip(SceneNodeId(node_id as usize));
+}
+
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn vello_composition_scene_cache_set_bounds(
+    cache: *mut SceneGraphCache,
+    node_id: u32,
+    left: f64,
+    top: f64,
+    width: f64,
+    height: f64,
+) -> bool {
+    if cache.is_null() || !(width >= 0.0 && height >= 0.0) {
+        return false;
+    }
+    let cache = unsafe { &mut *cache };
+    let rect = Rect::from_origin_size((left, top), (width, height));
+    cache.set_bounds(SceneNodeId(node_id as usize), Some(rect));
+    true
+}
+
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn vello_composition_scene_cache_clear_bounds(
+    cache: *mut SceneGraphCache,
+    node_id: u32,
+) {
+    if cache.is_null() {
+        return;
+    }
+    let cache = unsafe { &mut *cache };
+    cache.set_bounds(SceneNodeId(node_id as usize), None);
+}
+
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn vello_composition_scene_cache_set_opacity(
+    cache: *mut SceneGraphCache,
+    node_id: u32,
+    opacity: f32,
+) -> bool {
+    if cache.is_null() {
+        return false;
+    }
+    let cache = unsafe { &mut *cache };
+    cache.set_opacity(SceneNodeId(node_id as usize), opacity);
+    true
+}
+
+#[unsafe(no_mangle)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn vello_composition_scene_cache_set_z_index(
+    cache: *mut SceneGraphCache,
+    node_id: u32,
+    z_index: i32,
+) -> bool {
+    if cache.is_null() {
+        return false;
+    }
+    let cache = unsafe { &mut *cache };
+    cache.set_z_index(SceneNodeId(node_id as usize), z_index);
+    true
+}
+
 #[unsafe(no_mangle)]
 #[allow(clippy::missing_safety_doc)]
 pub unsafe extern "C" fn vello_composition_timeline_system_create() -> *mut TimelineSystem {
@@ -1,4 +1,4 @@
-use time::OffsetDateTime;
+use crate::axis_labels::{self, AxisLabelOptions, ValueFormat};
 
 #[derive(Debug, Clone, Copy, Default)]
 pub struct PlotArea {
@@ -42,6 +42,74 @@ impl AxisLayout {
     }
 }
 
+/// Mapping applied to values before they are placed on a value axis.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ValueScale {
+    #[default]
+    Linear,
+    Log10,
+    /// Logarithmic beyond `linear_threshold`, linear through zero inside it.
+    SymLog {
+        linear_threshold: f64,
+    },
+}
+
+impl ValueScale {
+    pub const DEFAULT_SYMLOG_THRESHOLD: f64 = 1.0;
+
+    pub fn symlog(linear_threshold: f64) -> Self {
+        let linear_threshold = if linear_threshold.is_finite() && linear_threshold > 0.0 {
+            linear_threshold
+        } else {
+            Self::DEFAULT_SYMLOG_THRESHOLD
+        };
+        Self::SymLog { linear_threshold }
+    }
+
+    /// Returns `true` when the scale can represent `value`.
+    #[inline]
+    pub fn accepts(self, value: f64) -> bool {
+        match self {
+            Self::Log10 => value.is_finite() && value > 0.0,
+            _ => value.is_finite(),
+        }
+    }
+
+    /// Maps a data value into the scale's linear space.
+    pub fn transform(self, value: f64) -> f64 {
+        match self {
+            Self::Linear => value,
+            Self::Log10 => value.max(f64::MIN_POSITIVE).log10(),
+            Self::SymLog { linear_threshold } => {
+                value.signum() * (value.abs() / linear_threshold).ln_1p() / std::f64::consts::LN_10
+            }
+        }
+    }
+
+    /// Maps a value from the scale's linear space back into data space.
+    pub fn inverse(self, scaled: f64) -> f64 {
+        match self {
+            Self::Linear => scaled,
+            Self::Log10 => 10f64.powf(scaled),
+            Self::SymLog { linear_threshold } => {
+                scaled.signum()
+                    * linear_threshold
+                    * (scaled.abs() * std::f64::consts::LN_10).exp_m1()
+            }
+        }
+    }
+
+    /// Normalised position of `value` between `min_value` and `max_value`. Not clamped.
+    pub fn normalize(self, value: f64, min_value: f64, max_value: f64) -> f64 {
+        let start = self.transform(min_value);
+        let span = self.transform(max_value) - start;
+        if span.abs() < f64::EPSILON {
+            return 0.0;
+        }
+        (self.transform(value) - start) / span
+    }
+}
+
 pub const MIN_PLOT_DIMENSION: f64 = 32.0;
 
 const PLOT_LEFT_MARGIN_RATIO: f64 = 0.12;
@@ -137,125 +205,257 @@ pub fn compute_axis_layout(
     min_value: f64,
     max_value: f64,
     max_ticks: usize,
+) -> AxisLayout {
+    compute_scaled_axis_layout(
+        range_start,
+        range_end,
+        min_value,
+        max_value,
+        max_ticks,
+        ValueScale::Linear,
+    )
+}
+
+pub fn compute_scaled_axis_layout(
+    range_start: f64,
+    range_end: f64,
+    min_value: f64,
+    max_value: f64,
+    max_ticks: usize,
+    scale: ValueScale,
+) -> AxisLayout {
+    compute_labeled_axis_layout(
+        range_start,
+        range_end,
+        min_value,
+        max_value,
+        max_ticks,
+        scale,
+        &AxisLabelOptions::default(),
+    )
+}
+
+/// Like [`compute_scaled_axis_layout`], with calendar alignment and label formats
+/// taken from `options`.
+pub fn compute_labeled_axis_layout(
+    range_start: f64,
+    range_end: f64,
+    min_value: f64,
+    max_value: f64,
+    max_ticks: usize,
+    scale: ValueScale,
+    options: &AxisLabelOptions,
 ) -> AxisLayout {
     AxisLayout {
-        time_ticks: compute_time_ticks(range_start, range_end, max_ticks.max(2)),
-        value_ticks: compute_value_ticks(min_value, max_value, max_ticks.max(2)),
+        time_ticks: axis_labels::compute_time_ticks(
+            range_start,
+            range_end,
+            max_ticks.max(2),
+            options,
+        ),
+        value_ticks: compute_value_ticks(
+            min_value,
+            max_value,
+            max_ticks.max(2),
+            scale,
+            options.value_format.as_ref(),
+        ),
     }
 }
 
-fn compute_time_ticks(range_start: f64, range_end: f64, max_ticks: usize) -> Vec<AxisTick> {
-    let mut ticks = Vec::new();
-    if !range_start.is_finite() || !range_end.is_finite() || range_end <= range_start {
-        return ticks;
+fn compute_value_ticks(
+    min_value: f64,
+    max_value: f64,
+    max_ticks: usize,
+    scale: ValueScale,
+    format: Option<&ValueFormat>,
+) -> Vec<AxisTick> {
+    if !min_value.is_finite() || !max_value.is_finite() || max_value <= min_value {
+        return Vec::new();
     }
 
-    let total = range_end - range_start;
-    let approx_step = total / (max_ticks as f64);
-    let step = pick_time_step(approx_step);
-    if step <= 0.0 {
-        return ticks;
+    match scale {
+        ValueScale::Linear => {
+            compute_linear_value_ticks(min_value, max_value, max_ticks, scale, format)
+        }
+        ValueScale::Log10 => compute_log_value_ticks(min_value, max_value, max_ticks, format),
+        ValueScale::SymLog { linear_threshold } => {
+            compute_symlog_value_ticks(min_value, max_value, max_ticks, linear_threshold, format)
+        }
     }
+}
+
+fn compute_linear_value_ticks(
+    min_value: f64,
+    max_value: f64,
+    max_ticks: usize,
+    scale: ValueScale,
+    format: Option<&ValueFormat>,
+) -> Vec<AxisTick> {
+    let mut ticks = Vec::new();
 
-    let first_tick = (range_start / step).ceil() * step;
-    let mut value = first_tick;
-    while value <= range_end + step * 0.5 {
-        let position = ((value - range_start) / total).clamp(0.0, 1.0);
-        ticks.push(AxisTick {
-            position,
-            label: format_time_label(value, step),
-        });
-        value += step;
+    let range = nice_number(max_value - min_value, false);
+    let step = nice_number(range / ((max_ticks - 1) as f64), true);
+    if step <= 0.0 {
+        return ticks;
     }
 
-    if ticks.is_empty() {
-        ticks.push(AxisTick {
-            position: 0.0,
-            label: format_time_label(range_start, step),
-        });
-        ticks.push(AxisTick {
-            position: 1.0,
-            label: format_time_label(range_end, step),
-        });
+    let mut tick = (min_value / step).floor() * step;
+    let tick_max = (max_value / step).ceil() * step;
+    while tick <= tick_max + step * 0.5 {
+        // Non-linear scales only keep ticks that land inside the range; outside it the
+        // clamped position would stack labels on the plot edges.
+        let in_range = tick >= min_value - step * 1e-9 && tick <= max_value + step * 1e-9;
+        if scale == ValueScale::Linear || (in_range && scale.accepts(tick)) {
+            let position = scale.normalize(tick, min_value, max_value).clamp(0.0, 1.0);
+            ticks.push(AxisTick {
+                position,
+                label: value_label(tick, format, || format_value_label(tick, step)),
+            });
+        }
+        tick += step;
     }
 
     ticks
 }
 
-fn pick_time_step(target: f64) -> f64 {
-    const STEPS: &[f64] = &[
-        0.5, 1.0, 2.0, 5.0, 10.0, 15.0, 30.0, 60.0, 120.0, 300.0, 600.0, 900.0, 1800.0, 3600.0,
-        7200.0, 14_400.0, 28_800.0, 43_200.0, 86_400.0,
-    ];
+fn compute_log_value_ticks(
+    min_value: f64,
+    max_value: f64,
+    max_ticks: usize,
+    format: Option<&ValueFormat>,
+) -> Vec<AxisTick> {
+    let scale = ValueScale::Log10;
+    if min_value <= 0.0 {
+        return Vec::new();
+    }
 
-    for step in STEPS {
-        if *step >= target {
-            return *step;
+    let low = min_value.log10();
+    let high = max_value.log10();
+    let first_decade = low.floor() as i32;
+    let last_decade = high.ceil() as i32;
+    let decades = (last_decade - first_decade).max(1);
+
+    // Inside a single decade there are no decade ticks to show, so fall back to a
+    // linear step and place the results on the logarithmic axis.
+    if high - low < 1.0 {
+        return compute_linear_value_ticks(min_value, max_value, max_ticks, scale, format);
+    }
+
+    let stride = ((decades as f64) / ((max_ticks - 1) as f64))
+        .ceil()
+        .max(1.0) as i32;
+    let multipliers: &[f64] = if stride == 1 && decades * 3 <= max_ticks as i32 {
+        &[1.0, 2.0, 5.0]
+    } else {
+        &[1.0]
+    };
+
+    let mut ticks = Vec::new();
+    let mut exponent = first_decade;
+    while exponent <= last_decade {
+        let decade = 10f64.powi(exponent);
+        for multiplier in multipliers {
+            let value = decade * multiplier;
+            let position = scale.normalize(value, min_value, max_value);
+            if (-1e-9..=1.0 + 1e-9).contains(&position) {
+                ticks.push(AxisTick {
+                    position: position.clamp(0.0, 1.0),
+                    label: value_label(value, format, || format_log_value_label(value)),
+                });
+            }
         }
+        exponent += stride;
     }
 
-    *STEPS.last().unwrap_or(&60.0)
+    ticks
 }
 
-fn format_time_label(timestamp: f64, step: f64) -> String {
-    if !timestamp.is_finite() {
-        return "NaN".to_string();
+fn compute_symlog_value_ticks(
+    min_value: f64,
+    max_value: f64,
+    max_ticks: usize,
+    linear_threshold: f64,
+    format: Option<&ValueFormat>,
+) -> Vec<AxisTick> {
+    let scale = ValueScale::symlog(linear_threshold);
+    let threshold = match scale {
+        ValueScale::SymLog { linear_threshold } => linear_threshold,
+        _ => ValueScale::DEFAULT_SYMLOG_THRESHOLD,
+    };
+
+    let magnitude = min_value.abs().max(max_value.abs());
+    if magnitude <= threshold {
+        return compute_linear_value_ticks(min_value, max_value, max_ticks, scale, format);
     }
 
-    let secs = timestamp.floor();
-    let subseconds = ((timestamp - secs) * 1_000.0).round() as i64;
-    let secs_i64 = secs as i64;
-
-    if let Ok(dt) = OffsetDateTime::from_unix_timestamp(secs_i64) {
-        let time = dt.time();
-        if step < 1.0 {
-            let millis = subseconds.clamp(0, 999);
-            format!(
-                "{:02}:{:02}:{:02}.{:03}",
-                time.hour(),
-                time.minute(),
-                time.second(),
-                millis
-            )
-        } else if step < 60.0 {
-            format!(
-                "{:02}:{:02}:{:02}",
-                time.hour(),
-                time.minute(),
-                time.second()
-            )
-        } else {
-            format!("{:02}:{:02}", time.hour(), time.minute())
-        }
+    let first_decade = threshold.log10().ceil() as i32;
+    let last_decade = magnitude.log10().ceil() as i32;
+    let decades = (last_decade - first_decade + 1).max(1);
+    let sides = if min_value < 0.0 && max_value > 0.0 {
+        2
     } else {
-        format!("{timestamp:.0}")
+        1
+    };
+    let budget = (max_ticks.saturating_sub(1) / sides).max(1) as i32;
+    let stride = ((decades as f64) / (budget as f64)).ceil().max(1.0) as i32;
+
+    let mut values = Vec::new();
+    if min_value <= 0.0 && max_value >= 0.0 {
+        values.push(0.0);
     }
+    let mut exponent = first_decade;
+    while exponent <= last_decade {
+        let decade = 10f64.powi(exponent);
+        values.push(decade);
+        values.push(-decade);
+        exponent += stride;
+    }
+    values.sort_by(f64::total_cmp);
+
+    values
+        .into_iter()
+        .filter_map(|value| {
+            let position = scale.normalize(value, min_value, max_value);
+            (-1e-9..=1.0 + 1e-9).contains(&position).then(|| AxisTick {
+                position: position.clamp(0.0, 1.0),
+                label: value_label(value, format, || format_log_value_label(value)),
+            })
+        })
+        .collect()
 }
 
-fn compute_value_ticks(min_value: f64, max_value: f64, max_ticks: usize) -> Vec<AxisTick> {
-    let mut ticks = Vec::new();
-    if !min_value.is_finite() || !max_value.is_finite() || max_value <= min_value {
-        return ticks;
+fn value_label(
+    value: f64,
+    format: Option<&ValueFormat>,
+    automatic: impl FnOnce() -> String,
+) -> String {
+    match format {
+        Some(format) => format.format(value, automatic),
+        None => automatic(),
     }
+}
 
-    let range = nice_number(max_value - min_value, false);
-    let step = nice_number(range / ((max_ticks - 1) as f64), true);
-    if step <= 0.0 {
-        return ticks;
+fn format_log_value_label(value: f64) -> String {
+    if !value.is_finite() {
+        return "NaN".to_string();
     }
-
-    let mut tick = (min_value / step).floor() * step;
-    let tick_max = (max_value / step).ceil() * step;
-    while tick <= tick_max + step * 0.5 {
-        let position = ((tick - min_value) / (max_value - min_value)).clamp(0.0, 1.0);
-        ticks.push(AxisTick {
-            position,
-            label: format_value_label(tick, step),
-        });
-        tick += step;
+    if value == 0.0 {
+        return "0".to_string();
     }
 
-    ticks
+    let exponent = value.abs().log10().floor() as i32;
+    if (-3..=4).contains(&exponent) {
+        let decimals = if exponent < 0 {
+            (-exponent) as usize
+        } else {
+            0
+        };
+        format!("{value:.decimals$}")
+    } else {
+        let mantissa = value / 10f64.powi(exponent);
+        format!("{mantissa:.0}e{exponent}")
+    }
 }
 
 fn nice_number(range: f64, round: bool) -> f64 {
@@ -365,4 +565,38 @@ mod tests {
             "value ticks must be monotonically increasing"
         );
     }
+
+    #[test]
+    fn log_scale_places_decade_ticks_evenly() {
+        let layout = compute_scaled_axis_layout(0.0, 60.0, 1.0, 1_000_000.0, 6, ValueScale::Log10);
+        let labels: Vec<_> = layout
+            .value_ticks
+            .iter()
+            .map(|tick| tick.label.as_str())
+            .collect();
+        assert_eq!(labels, ["1", "100", "10000", "1e6"]);
+        for (index, tick) in layout.value_ticks.iter().enumerate() {
+            assert_close(tick.position, index as f64 / 3.0);
+        }
+    }
+
+    #[test]
+    fn symlog_scale_is_symmetric_and_invertible() {
+        let scale = ValueScale::symlog(10.0);
+        for value in [-12_500.0, -3.0, 0.0, 0.25, 10.0, 1e6] {
+            assert_close(scale.inverse(scale.transform(value)), value);
+        }
+        assert_close(scale.transform(-500.0), -scale.transform(500.0));
+
+        let ticks = compute_value_ticks(-1_000.0, 1_000.0, 8, scale, None);
+        assert!(
+            ticks.iter().any(|tick| tick.label == "0"),
+            "symlog ticks must include zero"
+        );
+        let zero = ticks
+            .iter()
+            .find(|tick| tick.label == "0")
+            .expect("zero tick");
+        assert_close(zero.position, 0.5);
+    }
 }
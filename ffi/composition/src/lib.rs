@@ -3,12 +3,15 @@
 #![allow(clippy::too_many_arguments)]
 
 mod animation;
+mod axis_labels;
 mod constraints;
+mod fonts;
 mod interop;
 mod layout;
 mod linear_layout;
 mod materials;
 mod panels;
+mod paragraph;
 mod scene_cache;
 mod text;
 mod virtualization;
@@ -19,18 +22,34 @@ pub use animation::{
     SpringTrackDescriptor, TimelineGroupConfig, TimelineGroupId, TimelineSample, TimelineSystem,
     TimelineTrackId,
 };
+pub use axis_labels::{
+    AxisLabelOptions, LabelFormatError, MAX_UTC_OFFSET_SECONDS, TimeFormat, ValueFormat,
+};
 pub use constraints::{LayoutConstraints, LayoutSize, ScalarConstraint};
+pub use fonts::{
+    BUILTIN_FONT_FAMILY, FontId, FontRegistrationError, default_font_families, register_font,
+    set_default_font_families,
+};
 pub use interop::{
-    CompositionDirtyRegion, CompositionLabelMetrics, CompositionLinearLayoutItem,
-    CompositionLinearLayoutSlot, CompositionPlotArea, vello_composition_compute_plot_area,
-    vello_composition_measure_label, vello_composition_scene_cache_clear,
-    vello_composition_scene_cache_create, vello_composition_scene_cache_create_node,
-    vello_composition_scene_cache_destroy, vello_composition_scene_cache_dispose_node,
-    vello_composition_scene_cache_mark_dirty, vello_composition_scene_cache_mark_dirty_bounds,
+    CompositionAffine, CompositionDirtyRegion, CompositionFontFamily, CompositionFontSlant,
+    CompositionFontVariation, CompositionGlyph, CompositionLabelMetrics, CompositionLabelStyle,
+    CompositionLinearLayoutItem, CompositionLinearLayoutSlot, CompositionParagraphLine,
+    CompositionParagraphMetrics, CompositionParagraphOptions, CompositionPlotArea,
+    CompositionTextWrap, vello_composition_compute_plot_area, vello_composition_font_register,
+    vello_composition_font_set_default_families, vello_composition_layout_paragraph,
+    vello_composition_measure_label, vello_composition_measure_label_styled,
+    vello_composition_scene_cache_clear, vello_composition_scene_cache_clear_bounds,
+    vello_composition_scene_cache_clear_clip, vello_composition_scene_cache_create,
+    vello_composition_scene_cache_create_node, vello_composition_scene_cache_destroy,
+    vello_composition_scene_cache_dispose_node, vello_composition_scene_cache_mark_dirty,
+    vello_composition_scene_cache_mark_dirty_bounds, vello_composition_scene_cache_set_bounds,
+    vello_composition_scene_cache_set_clip_rect, vello_composition_scene_cache_set_opacity,
+    vello_composition_scene_cache_set_transform, vello_composition_scene_cache_set_z_index,
     vello_composition_scene_cache_take_dirty, vello_composition_solve_linear_layout,
 };
 pub use layout::{
-    AxisLayout, AxisTick, MIN_PLOT_DIMENSION, PlotArea, compute_axis_layout, compute_plot_area,
+    AxisLayout, AxisTick, MIN_PLOT_DIMENSION, PlotArea, ValueScale, compute_axis_layout,
+    compute_labeled_axis_layout, compute_plot_area, compute_scaled_axis_layout,
 };
 pub use linear_layout::{LinearLayoutItem, LinearLayoutSlot, solve_linear_layout};
 pub use materials::{
@@ -44,8 +63,12 @@ pub use panels::{
     StackLayoutChild, StackLayoutOptions, WrapLayoutChild, WrapLayoutLine, WrapLayoutOptions,
     WrapLayoutResult, solve_dock_layout, solve_grid_layout, solve_stack_layout, solve_wrap_layout,
 };
+pub use paragraph::{ParagraphLayout, ParagraphLine, ParagraphOptions, TextWrap, layout_paragraph};
 pub use scene_cache::{DirtyRegion, SceneGraphCache, SceneNodeId};
-pub use text::{LabelLayout, TextShaper, label_font, layout_label};
+pub use text::{
+    FontSlant, FontVariation, GlyphRun, LabelLayout, LabelStyle, TextShaper, label_font,
+    layout_label, layout_styled_label,
+};
 pub use virtualization::{
     ColumnSlice, ColumnStrip, ColumnViewportMetrics, FrozenColumns, FrozenKind, HybridVirtualizer,
     RowAction, RowPlanEntry, RowViewportMetrics, VirtualNodeId, VirtualizerTelemetry,
@@ -53,40 +76,49 @@ pub use virtualization::{
 
 pub mod ffi {
     pub use crate::interop::{
-        CompositionColumnSlice, CompositionColumnViewportMetrics, CompositionDirtyRegion,
-        CompositionDockLayoutChild, CompositionDockLayoutOptions, CompositionDockSide,
-        CompositionFrozenKind, CompositionGridLayoutChild, CompositionGridLayoutOptions,
-        CompositionGridTrack, CompositionGridTrackKind, CompositionLabelMetrics,
-        CompositionLayoutAlignment, CompositionLayoutConstraints, CompositionLayoutOrientation,
-        CompositionLayoutRect, CompositionLayoutThickness, CompositionLinearLayoutItem,
-        CompositionLinearLayoutSlot, CompositionPlotArea, CompositionRowAction,
-        CompositionRowPlanEntry, CompositionRowViewportMetrics, CompositionRowWindow,
-        CompositionScalarConstraint, CompositionStackLayoutChild, CompositionStackLayoutOptions,
+        CompositionAffine, CompositionColumnSlice, CompositionColumnViewportMetrics,
+        CompositionDirtyRegion, CompositionDockLayoutChild, CompositionDockLayoutOptions,
+        CompositionDockSide, CompositionFontFamily, CompositionFontSlant, CompositionFontVariation,
+        CompositionFrozenKind, CompositionGlyph, CompositionGridLayoutChild,
+        CompositionGridLayoutOptions, CompositionGridTrack, CompositionGridTrackKind,
+        CompositionLabelMetrics, CompositionLabelStyle, CompositionLayoutAlignment,
+        CompositionLayoutConstraints, CompositionLayoutOrientation, CompositionLayoutRect,
+        CompositionLayoutThickness, CompositionLinearLayoutItem, CompositionLinearLayoutSlot,
+        CompositionParagraphLine, CompositionParagraphMetrics, CompositionParagraphOptions,
+        CompositionPlotArea, CompositionRowAction, CompositionRowPlanEntry,
+        CompositionRowViewportMetrics, CompositionRowWindow, CompositionScalarConstraint,
+        CompositionStackLayoutChild, CompositionStackLayoutOptions, CompositionTextWrap,
         CompositionTimelineDirtyBinding, CompositionTimelineDirtyKind, CompositionTimelineEasing,
         CompositionTimelineEasingTrackDesc, CompositionTimelineGroupConfig,
         CompositionTimelineRepeat, CompositionTimelineSample, CompositionTimelineSpringTrackDesc,
         CompositionVirtualColumnStrip, CompositionVirtualRowMetric,
         CompositionVirtualizerTelemetry, CompositionWrapLayoutChild, CompositionWrapLayoutLine,
         CompositionWrapLayoutOptions, vello_composition_compute_plot_area,
-        vello_composition_dock_layout, vello_composition_grid_layout,
-        vello_composition_measure_label, vello_composition_scene_cache_clear,
+        vello_composition_dock_layout, vello_composition_font_register,
+        vello_composition_font_set_default_families, vello_composition_grid_layout,
+        vello_composition_layout_paragraph, vello_composition_measure_label,
+        vello_composition_measure_label_styled, vello_composition_scene_cache_clear,
+        vello_composition_scene_cache_clear_bounds, vello_composition_scene_cache_clear_clip,
         vello_composition_scene_cache_create, vello_composition_scene_cache_create_node,
         vello_composition_scene_cache_destroy, vello_composition_scene_cache_dispose_node,
         vello_composition_scene_cache_mark_dirty, vello_composition_scene_cache_mark_dirty_bounds,
-        vello_composition_scene_cache_take_dirty, vello_composition_solve_linear_layout,
-        vello_composition_stack_layout, vello_composition_timeline_add_easing_track,
-        vello_composition_timeline_add_spring_track, vello_composition_timeline_group_create,
-        vello_composition_timeline_group_destroy, vello_composition_timeline_group_pause,
-        vello_composition_timeline_group_play, vello_composition_timeline_group_set_speed,
-        vello_composition_timeline_system_create, vello_composition_timeline_system_destroy,
-        vello_composition_timeline_tick, vello_composition_timeline_track_remove,
-        vello_composition_timeline_track_reset, vello_composition_timeline_track_set_spring_target,
-        vello_composition_virtualizer_clear, vello_composition_virtualizer_column_slice,
-        vello_composition_virtualizer_copy_plan, vello_composition_virtualizer_copy_recycle,
-        vello_composition_virtualizer_create, vello_composition_virtualizer_destroy,
-        vello_composition_virtualizer_plan, vello_composition_virtualizer_set_columns,
-        vello_composition_virtualizer_set_rows, vello_composition_virtualizer_telemetry,
-        vello_composition_virtualizer_window, vello_composition_wrap_layout,
+        vello_composition_scene_cache_set_bounds, vello_composition_scene_cache_set_clip_rect,
+        vello_composition_scene_cache_set_opacity, vello_composition_scene_cache_set_transform,
+        vello_composition_scene_cache_set_z_index, vello_composition_scene_cache_take_dirty,
+        vello_composition_solve_linear_layout, vello_composition_stack_layout,
+        vello_composition_timeline_add_easing_track, vello_composition_timeline_add_spring_track,
+        vello_composition_timeline_group_create, vello_composition_timeline_group_destroy,
+        vello_composition_timeline_group_pause, vello_composition_timeline_group_play,
+        vello_composition_timeline_group_set_speed, vello_composition_timeline_system_create,
+        vello_composition_timeline_system_destroy, vello_composition_timeline_tick,
+        vello_composition_timeline_track_remove, vello_composition_timeline_track_reset,
+        vello_composition_timeline_track_set_spring_target, vello_composition_virtualizer_clear,
+        vello_composition_virtualizer_column_slice, vello_composition_virtualizer_copy_plan,
+        vello_composition_virtualizer_copy_recycle, vello_composition_virtualizer_create,
+        vello_composition_virtualizer_destroy, vello_composition_virtualizer_plan,
+        vello_composition_virtualizer_set_columns, vello_composition_virtualizer_set_rows,
+        vello_composition_virtualizer_telemetry, vello_composition_virtualizer_window,
+        vello_composition_wrap_layout,
     };
     pub use crate::interop::{
         vello_composition_material_register, vello_composition_material_resolve_color,
@@ -0,0 +1,522 @@
+//! Paragraph layout: wraps label text to a width, limits the line count with a trailing
+//! ellipsis and aligns the lines inside a box. Each line is shaped like a label.
+
+use std::ops::Range;
+
+use unicode_linebreak::{BreakOpportunity, linebreaks};
+use vello::Scene;
+use vello::kurbo::Affine;
+use vello::peniko::{Brush, Fill};
+
+use crate::panels::LayoutAlignment;
+use crate::text::{
+    GlyphRun, LabelLayout, TextShaper, ZERO_WIDTH_JOINER, extends_cluster, with_default_shaper,
+};
+
+const ELLIPSIS: char = '\u{2026}';
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextWrap {
+    /// Lines only break at line terminators.
+    #[default]
+    NoWrap,
+    /// Lines break between words; words wider than a line break between characters.
+    Word,
+    /// Lines break between any two clusters.
+    Character,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParagraphOptions {
+    pub font_size: f32,
+    /// Width lines wrap and trim to; infinite leaves lines unbounded.
+    pub max_width: f32,
+    /// Height of the box lines are vertically aligned in; infinite aligns to the text.
+    pub max_height: f32,
+    pub wrap: TextWrap,
+    /// Distance between baselines; zero or non-finite uses the primary font's natural
+    /// line height.
+    pub line_height: f32,
+    /// Lines kept before the rest of the text is dropped; `None` keeps every line.
+    pub max_lines: Option<usize>,
+    /// Ends the last kept line, and lines wider than `max_width`, with an ellipsis.
+    pub ellipsis: bool,
+    pub horizontal_alignment: LayoutAlignment,
+    pub vertical_alignment: LayoutAlignment,
+}
+
+impl Default for ParagraphOptions {
+    fn default() -> Self {
+        Self {
+            font_size: 14.0,
+            max_width: f32::INFINITY,
+            max_height: f32::INFINITY,
+            wrap: TextWrap::NoWrap,
+            line_height: 0.0,
+            max_lines: None,
+            ellipsis: false,
+            horizontal_alignment: LayoutAlignment::Start,
+            vertical_alignment: LayoutAlignment::Start,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ParagraphLine {
+    /// Byte range of the source text shown on the line, excluding any ellipsis.
+    pub range: Range<usize>,
+    /// Glyphs positioned relative to the top-left corner of the paragraph box.
+    pub runs: Vec<GlyphRun>,
+    /// Left edge of the line after alignment.
+    pub x: f32,
+    pub baseline: f32,
+    pub width: f32,
+    pub ellipsized: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct ParagraphLayout {
+    pub lines: Vec<ParagraphLine>,
+    /// Width of the widest line.
+    pub width: f32,
+    /// Line advance times the line count.
+    pub height: f32,
+    pub line_advance: f32,
+    /// Whether lines were dropped or cut short with an ellipsis.
+    pub truncated: bool,
+}
+
+impl ParagraphLayout {
+    pub fn glyph_count(&self) -> usize {
+        self.lines
+            .iter()
+            .flat_map(|line| &line.runs)
+            .map(|run| run.glyphs.len())
+            .sum()
+    }
+
+    /// Draws every line with the paragraph box's top-left corner at `transform`,
+    /// returning the number of glyph runs encoded.
+    pub fn draw(&self, scene: &mut Scene, font_size: f32, transform: Affine, brush: &Brush) -> u32 {
+        let mut encoded = 0;
+        for run in self.lines.iter().flat_map(|line| &line.runs) {
+            scene
+                .draw_glyphs(&run.font)
+                .font_size(font_size)
+                .transform(transform)
+                .brush(brush)
+                .normalized_coords(&run.normalized_coords)
+                .draw(Fill::NonZero, run.glyphs.iter().copied());
+            encoded += 1;
+        }
+        encoded
+    }
+}
+
+impl TextShaper {
+    pub fn shape_paragraph(
+        &mut self,
+        text: &str,
+        options: &ParagraphOptions,
+    ) -> Option<ParagraphLayout> {
+        if text.trim().is_empty() {
+            return None;
+        }
+
+        let font_size = options.font_size;
+        let (ascent, natural_height) = self.line_metrics(font_size)?;
+        let advance = if options.line_height.is_finite() && options.line_height > 0.0 {
+            options.line_height
+        } else {
+            natural_height
+        };
+        let max_width = (options.max_width.is_finite() && options.max_width >= 0.0)
+            .then_some(options.max_width);
+
+        let mut breaker = LineBreaker {
+            shaper: self,
+            text,
+            font_size,
+            max_width,
+        };
+        let mut ranges = breaker.break_lines(options.wrap, options.max_lines);
+        let dropped = options
+            .max_lines
+            .is_some_and(|max_lines| ranges.len() > max_lines);
+        if let Some(max_lines) = options.max_lines {
+            ranges.truncate(max_lines);
+        }
+
+        let mut truncated = dropped;
+        let mut shaped: Vec<(Range<usize>, Option<LabelLayout>, bool)> =
+            Vec::with_capacity(ranges.len());
+        let last = ranges.len().saturating_sub(1);
+        for (index, range) in ranges.into_iter().enumerate() {
+            let overflows = max_width.is_some_and(|width| breaker.measure(range.clone()) > width);
+            if options.ellipsis && ((dropped && index == last) || overflows) {
+                let (range, shown) = breaker.ellipsize(range);
+                let layout = breaker.shaper.shape(&shown, font_size);
+                shaped.push((range, layout, true));
+                truncated = true;
+            } else {
+                let layout = breaker.shaper.shape(&text[range.clone()], font_size);
+                shaped.push((range, layout, false));
+            }
+        }
+
+        let width = shaped
+            .iter()
+            .filter_map(|(_, layout, _)| layout.as_ref())
+            .fold(0f32, |widest, layout| widest.max(layout.width));
+        let height = advance * shaped.len() as f32;
+        let box_width = max_width.unwrap_or(width);
+        let top = if options.max_height.is_finite() {
+            (options.max_height - height) * alignment_factor(options.vertical_alignment)
+        } else {
+            0.0
+        };
+
+        let lines = shaped
+            .into_iter()
+            .enumerate()
+            .map(|(index, (range, layout, ellipsized))| {
+                let line_width = layout.as_ref().map_or(0.0, |layout| layout.width);
+                let x = (box_width - line_width) * alignment_factor(options.horizontal_alignment);
+                let baseline =
+                    top + advance * index as f32 + (advance - natural_height) * 0.5 + ascent;
+                let runs = layout
+                    .map(|layout| {
+                        layout
+                            .runs
+                            .into_iter()
+                            .map(|mut run| {
+                                for glyph in &mut run.glyphs {
+                                    glyph.x += x;
+                                    glyph.y += baseline;
+                                }
+                                run
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                ParagraphLine {
+                    range,
+                    runs,
+                    x,
+                    baseline,
+                    width: line_width,
+                    ellipsized,
+                }
+            })
+            .collect();
+
+        Some(ParagraphLayout {
+            lines,
+            width,
+            height,
+            line_advance: advance,
+            truncated,
+        })
+    }
+}
+
+pub fn layout_paragraph(text: &str, options: &ParagraphOptions) -> Option<ParagraphLayout> {
+    with_default_shaper(|shaper| shaper.shape_paragraph(text, options))
+}
+
+struct LineBreaker<'a> {
+    shaper: &'a mut TextShaper,
+    text: &'a str,
+    font_size: f32,
+    max_width: Option<f32>,
+}
+
+impl LineBreaker<'_> {
+    fn measure(&mut self, range: Range<usize>) -> f32 {
+        let text = self.text;
+        self.measure_str(&text[range])
+    }
+
+    fn measure_str(&mut self, text: &str) -> f32 {
+        self.shaper.measure(text, self.font_size)
+    }
+
+    /// Line ranges in logical order, stopping one line past `max_lines` so callers can
+    /// tell whether text was dropped.
+    fn break_lines(&mut self, wrap: TextWrap, max_lines: Option<usize>) -> Vec<Range<usize>> {
+        let limit = max_lines.map(|max_lines| max_lines.saturating_add(1));
+        let mut lines = Vec::new();
+        let mut allowed = Vec::new();
+        let mut start = 0;
+        for (index, opportunity) in linebreaks(self.text) {
+            if opportunity == BreakOpportunity::Allowed {
+                allowed.push(index);
+                continue;
+            }
+
+            let end = start
+                + self.text[start..index]
+                    .trim_end_matches(is_line_terminator)
+                    .len();
+            match (wrap, self.max_width) {
+                (TextWrap::Word, Some(width)) => {
+                    allowed.retain(|&offset| offset < end);
+                    self.wrap(start..end, &allowed, true, width, &mut lines);
+                }
+                (TextWrap::Character, Some(width)) => {
+                    let clusters = cluster_boundaries(self.text, start..end);
+                    self.wrap(start..end, &clusters, false, width, &mut lines);
+                }
+                _ => lines.push(start..end),
+            }
+            allowed.clear();
+            start = index;
+            if limit.is_some_and(|limit| lines.len() >= limit) {
+                break;
+            }
+        }
+
+        if let Some(limit) = limit {
+            lines.truncate(limit);
+        }
+        lines
+    }
+
+    /// Greedily fills lines of `range`, ending each at the last break in `breaks` that
+    /// fits. With `split_words`, a word wider than the line breaks between clusters;
+    /// otherwise a break that does not fit is taken anyway so every line makes progress.
+    fn wrap(
+        &mut self,
+        range: Range<usize>,
+        breaks: &[usize],
+        split_words: bool,
+        width: f32,
+        lines: &mut Vec<Range<usize>>,
+    ) {
+        if range.is_empty() {
+            lines.push(range);
+            return;
+        }
+
+        let mut start = range.start;
+        while start < range.end {
+            if self.measure(start..range.end) <= width {
+                lines.push(trim_end(self.text, start..range.end));
+                return;
+            }
+
+            let first = breaks.partition_point(|&offset| offset <= start);
+            let candidates = &breaks[first..];
+            let mut end = self.last_fitting(start, candidates, width);
+            if end.is_none() && split_words {
+                let word_end = candidates.first().copied().unwrap_or(range.end);
+                let clusters = cluster_boundaries(self.text, start..word_end);
+                end = self
+                    .last_fitting(start, &clusters, width)
+                    .or_else(|| clusters.first().copied())
+                    .or(Some(word_end));
+            }
+            let end = end
+                .or_else(|| candidates.first().copied())
+                .unwrap_or(range.end);
+
+            lines.push(trim_end(self.text, start..end));
+            start = end + self.text[end..range.end].len()
+                - self.text[end..range.end].trim_start().len();
+        }
+    }
+
+    fn last_fitting(&mut self, start: usize, candidates: &[usize], width: f32) -> Option<usize> {
+        let count = candidates.partition_point(|&end| self.measure(start..end) <= width);
+        count.checked_sub(1).map(|index| candidates[index])
+    }
+
+    /// Shortens `range` so it fits the line width with an ellipsis appended, returning
+    /// the kept range and the text to shape.
+    fn ellipsize(&mut self, range: Range<usize>) -> (Range<usize>, String) {
+        let mut candidates = cluster_boundaries(self.text, range.clone());
+        candidates.push(range.end);
+        let end = match self.max_width {
+            Some(width) => {
+                let text = self.text;
+                let count = candidates.partition_point(|&end| {
+                    self.measure_str(&with_ellipsis(&text[range.start..end])) <= width
+                });
+                count
+                    .checked_sub(1)
+                    .map_or(range.start, |index| candidates[index])
+            }
+            None => range.end,
+        };
+        let range = trim_end(self.text, range.start..end);
+        let shown = with_ellipsis(&self.text[range.clone()]);
+        (range, shown)
+    }
+}
+
+/// Offsets strictly inside `range` where a line may break without splitting a cluster.
+fn cluster_boundaries(text: &str, range: Range<usize>) -> Vec<usize> {
+    let mut boundaries = Vec::new();
+    let mut previous = None;
+    for (offset, ch) in text[range.clone()].char_indices() {
+        if offset > 0 && previous != Some(ZERO_WIDTH_JOINER) && !extends_cluster(ch) {
+            boundaries.push(range.start + offset);
+        }
+        previous = Some(ch);
+    }
+    boundaries
+}
+
+fn trim_end(text: &str, range: Range<usize>) -> Range<usize> {
+    range.start..range.start + text[range].trim_end().len()
+}
+
+fn with_ellipsis(text: &str) -> String {
+    let mut shown = text.trim_end().to_owned();
+    shown.push(ELLIPSIS);
+    shown
+}
+
+fn is_line_terminator(ch: char) -> bool {
+    matches!(
+        ch,
+        '\n' | '\r' | '\u{0b}' | '\u{0c}' | '\u{85}' | '\u{2028}' | '\u{2029}'
+    )
+}
+
+fn alignment_factor(alignment: LayoutAlignment) -> f32 {
+    match alignment {
+        LayoutAlignment::Start | LayoutAlignment::Stretch => 0.0,
+        LayoutAlignment::Center => 0.5,
+        LayoutAlignment::End => 1.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text::layout_label;
+
+    fn width_of(text: &str) -> f32 {
+        layout_label(text, 14.0).map_or(0.0, |layout| layout.width)
+    }
+
+    fn line_texts<'a>(text: &'a str, layout: &ParagraphLayout) -> Vec<&'a str> {
+        layout
+            .lines
+            .iter()
+            .map(|line| &text[line.range.clone()])
+            .collect()
+    }
+
+    #[test]
+    fn words_wrap_at_the_last_fitting_break() {
+        let text = "alpha beta gamma delta";
+        let options = ParagraphOptions {
+            max_width: width_of("alpha beta gamma") - 1.0,
+            wrap: TextWrap::Word,
+            ..ParagraphOptions::default()
+        };
+        let layout = layout_paragraph(text, &options).expect("layout");
+        assert_eq!(line_texts(text, &layout), ["alpha beta", "gamma delta"]);
+        assert!(!layout.truncated);
+        assert!(
+            layout
+                .lines
+                .iter()
+                .all(|line| line.width <= options.max_width)
+        );
+        assert_eq!(
+            layout.lines[1].baseline - layout.lines[0].baseline,
+            layout.line_advance
+        );
+    }
+
+    #[test]
+    fn long_words_break_between_characters() {
+        let text = "abcdefghijkl";
+        let options = ParagraphOptions {
+            max_width: width_of("abcd") + 0.5,
+            wrap: TextWrap::Word,
+            ..ParagraphOptions::default()
+        };
+        let layout = layout_paragraph(text, &options).expect("layout");
+        assert!(layout.lines.len() >= 3);
+        assert_eq!(line_texts(text, &layout).concat(), text);
+        assert!(
+            layout
+                .lines
+                .iter()
+                .all(|line| line.width <= options.max_width)
+        );
+    }
+
+    #[test]
+    fn max_lines_end_with_an_ellipsis() {
+        let text = "one two three four five six seven eight nine";
+        let options = ParagraphOptions {
+            max_width: width_of("one two three"),
+            wrap: TextWrap::Word,
+            max_lines: Some(2),
+            ellipsis: true,
+            ..ParagraphOptions::default()
+        };
+        let layout = layout_paragraph(text, &options).expect("layout");
+        assert_eq!(layout.lines.len(), 2);
+        assert!(layout.truncated);
+        let last = &layout.lines[1];
+        assert!(last.ellipsized);
+        assert!(last.width <= options.max_width);
+
+        let ellipsis = layout_label("\u{2026}", 14.0).expect("ellipsis");
+        let last_glyph = last.runs.last().and_then(|run| run.glyphs.last());
+        assert_eq!(
+            last_glyph.map(|glyph| glyph.id),
+            Some(ellipsis.runs[0].glyphs[0].id)
+        );
+    }
+
+    #[test]
+    fn overflowing_unwrapped_lines_are_trimmed() {
+        let options = ParagraphOptions {
+            max_width: 40.0,
+            ellipsis: true,
+            ..ParagraphOptions::default()
+        };
+        let layout = layout_paragraph("A rather long caption", &options).expect("layout");
+        assert_eq!(layout.lines.len(), 1);
+        assert!(layout.lines[0].ellipsized);
+        assert!(layout.lines[0].width <= 40.0);
+    }
+
+    #[test]
+    fn hard_breaks_keep_blank_lines_and_alignment_offsets_lines() {
+        let text = "a\n\nbb";
+        let options = ParagraphOptions {
+            max_width: 100.0,
+            max_height: 100.0,
+            line_height: 24.0,
+            horizontal_alignment: LayoutAlignment::Center,
+            vertical_alignment: LayoutAlignment::End,
+            ..ParagraphOptions::default()
+        };
+        let layout = layout_paragraph(text, &options).expect("layout");
+        assert_eq!(line_texts(text, &layout), ["a", "", "bb"]);
+        assert!(layout.lines[1].runs.is_empty());
+
+        let metrics = layout_label("a", 14.0).expect("a");
+        let advance = layout.line_advance;
+        assert_eq!(advance, 24.0);
+        assert!((layout.height - advance * 3.0).abs() < 1e-4);
+
+        let top = 100.0 - layout.height;
+        let first_baseline = top + (advance - metrics.height) * 0.5 + metrics.ascent;
+        assert!((layout.lines[0].baseline - first_baseline).abs() < 1e-4);
+
+        let last = &layout.lines[2];
+        assert!((last.x - (100.0 - last.width) * 0.5).abs() < 1e-4);
+        let first_glyph = last.runs[0].glyphs[0];
+        assert!((first_glyph.x - last.x).abs() < 1e-4);
+        assert!((first_glyph.y - last.baseline).abs() < 1e-4);
+    }
+}
@@ -1,6 +1,14 @@
 use std::collections::HashSet;
 
 use vello::Scene;
+use vello::kurbo::{Affine, BezPath, Rect, Shape};
+use vello::peniko::BlendMode;
+
+/// Tolerance used to flatten clip shapes into paths.
+const CLIP_TOLERANCE: f64 = 0.1;
+
+/// Half extent of the clip used for layers that only need opacity or blending.
+const UNCLIPPED_LAYER_EXTENT: f64 = 1.0e7;
 
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct DirtyRegion {
@@ -11,6 +19,14 @@ pub struct DirtyRegion {
 }
 
 impl DirtyRegion {
+    /// Region covering the whole plane.
+    pub const UNBOUNDED: DirtyRegion = DirtyRegion {
+        min_x: f64::NEG_INFINITY,
+        max_x: f64::INFINITY,
+        min_y: f64::NEG_INFINITY,
+        max_y: f64::INFINITY,
+    };
+
     pub fn new(x: f64, y: f64) -> Self {
         let x = sanitise_dimension(x);
         let y = sanitise_dimension(y);
@@ -41,6 +57,35 @@ impl DirtyRegion {
     pub fn is_empty(&self) -> bool {
         !(self.min_x <= self.max_x && self.min_y <= self.max_y)
     }
+
+    /// The overlap of both regions, or `None` when they are disjoint.
+    pub fn intersect(&self, other: &DirtyRegion) -> Option<DirtyRegion> {
+        let region = DirtyRegion {
+            min_x: self.min_x.max(other.min_x),
+            max_x: self.max_x.min(other.max_x),
+            min_y: self.min_y.max(other.min_y),
+            max_y: self.max_y.min(other.max_y),
+        };
+        (!region.is_empty()).then_some(region)
+    }
+
+    /// Bounding box of the region mapped through `transform`. Unbounded regions already
+    /// cover the whole surface and are returned unchanged.
+    pub fn transformed(&self, transform: Affine) -> DirtyRegion {
+        let rect = Rect::new(self.min_x, self.min_y, self.max_x, self.max_y);
+        if transform == Affine::IDENTITY || !rect.is_finite() {
+            return *self;
+        }
+        DirtyRegion::from(transform.transform_rect_bbox(rect))
+    }
+}
+
+impl From<Rect> for DirtyRegion {
+    fn from(rect: Rect) -> Self {
+        let mut region = DirtyRegion::new(rect.x0, rect.y0);
+        region.expand(rect.x1, rect.y1);
+        region
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -58,6 +103,19 @@ struct SceneNode {
     children: Vec<SceneNodeId>,
     dirty: Option<DirtyRegion>,
     scene: Scene,
+    /// Maps the node's space into its parent's.
+    transform: Affine,
+    /// Clip in the node's own space.
+    clip: Option<BezPath>,
+    /// Extent the subtree draws in, in the node's own space; unknown when `None`.
+    bounds: Option<Rect>,
+    opacity: f32,
+    blend: BlendMode,
+    /// Children draw in ascending z-index, ties in creation order.
+    z_index: i32,
+    /// The node's scene followed by its composed children, in the node's space.
+    composed: Scene,
+    composed_valid: bool,
 }
 
 impl SceneNode {
@@ -67,8 +125,79 @@ impl SceneNode {
             children: Vec::new(),
             dirty: None,
             scene: Scene::new(),
+            transform: Affine::IDENTITY,
+            clip: None,
+            bounds: None,
+            opacity: 1.0,
+            blend: BlendMode::default(),
+            z_index: 0,
+            composed: Scene::new(),
+            composed_valid: false,
         }
     }
+
+    fn reset(&mut self, parent: Option<SceneNodeId>) {
+        self.parent = parent;
+        self.children.clear();
+        self.dirty = None;
+        self.scene.reset();
+        self.transform = Affine::IDENTITY;
+        self.clip = None;
+        self.bounds = None;
+        self.opacity = 1.0;
+        self.blend = BlendMode::default();
+        self.z_index = 0;
+        self.composed.reset();
+        self.composed_valid = false;
+    }
+
+    fn needs_layer(&self) -> bool {
+        self.clip.is_some() || self.opacity < 1.0 || self.blend != BlendMode::default()
+    }
+
+    /// Appends the composed subtree to `target` in the parent's space.
+    fn draw_into(&self, target: &mut Scene) {
+        if self.opacity <= 0.0 && self.blend == BlendMode::default() {
+            return;
+        }
+        if !self.needs_layer() {
+            target.append(&self.composed, Some(self.transform));
+            return;
+        }
+
+        match &self.clip {
+            // A plain clip needs no blending layer.
+            Some(clip) if self.opacity >= 1.0 && self.blend == BlendMode::default() => {
+                target.push_clip_layer(self.transform, clip);
+            }
+            Some(clip) => target.push_layer(self.blend, self.opacity, self.transform, clip),
+            None => {
+                let extent = UNCLIPPED_LAYER_EXTENT;
+                let bounds = Rect::new(-extent, -extent, extent, extent);
+                target.push_layer(self.blend, self.opacity, self.transform, &bounds);
+            }
+        }
+        target.append(&self.composed, Some(self.transform));
+        target.pop_layer();
+    }
+
+    /// Maps a region of this node into its parent's space, dropping what the clip hides.
+    fn region_in_parent(&self, region: DirtyRegion) -> Option<DirtyRegion> {
+        let region = match &self.clip {
+            Some(clip) => region.intersect(&DirtyRegion::from(clip.bounding_box()))?,
+            None => region,
+        };
+        Some(region.transformed(self.transform))
+    }
+
+    /// Area the subtree covers in its parent: the bounds, or everything when they are
+    /// unknown, cut by the clip.
+    fn extent_in_parent(&self) -> Option<DirtyRegion> {
+        let extent = self
+            .bounds
+            .map_or(DirtyRegion::UNBOUNDED, DirtyRegion::from);
+        self.region_in_parent(extent)
+    }
 }
 
 pub struct SceneGraphCache {
@@ -92,11 +221,7 @@ impl SceneGraphCache {
         if let Some(index) = self.reusable_nodes.iter().copied().next() {
             self.reusable_nodes.remove(&index);
             let node_id = SceneNodeId(index);
-            let node = &mut self.nodes[index];
-            node.parent = parent;
-            node.children.clear();
-            node.dirty = None;
-            node.scene.reset();
+            self.nodes[index].reset(parent);
             if let Some(parent_id) = parent {
                 self.attach_child(parent_id, node_id);
             }
@@ -112,24 +237,39 @@ impl SceneGraphCache {
         node_id
     }
 
+    /// Releases the node and its descendants for reuse, marking the area they covered
+    /// dirty on the parent. Disposing a released node does nothing.
     pub fn dispose_node(&mut self, node: SceneNodeId) {
-        let parent_id = self.nodes.get(node.0).and_then(|node| node.parent);
-        if let Some(parent_id) = parent_id {
+        let Some(entry) = self.nodes.get(node.0) else {
+            return;
+        };
+        if self.reusable_nodes.contains(&node.0) {
+            return;
+        }
+
+        if let Some(parent_id) = entry.parent {
+            if let Some(extent) = entry.extent_in_parent() {
+                self.mark_region(parent_id, extent);
+            }
+            self.invalidate(parent_id);
             if let Some(parent) = self.nodes.get_mut(parent_id.0) {
                 parent.children.retain(|child| child.0 != node.0);
             }
         }
 
-        if let Some(entry) = self.nodes.get_mut(node.0) {
-            entry.parent = None;
-            entry.children.clear();
-            entry.dirty = None;
-            entry.scene.reset();
-            self.reusable_nodes.insert(node.0);
+        let mut pending = vec![node];
+        while let Some(id) = pending.pop() {
+            let entry = &mut self.nodes[id.0];
+            pending.append(&mut entry.children);
+            entry.reset(None);
+            self.reusable_nodes.insert(id.0);
         }
     }
 
+    /// The node's scene for re-encoding; the node's composed output is rebuilt by the
+    /// next [`compose`](Self::compose).
     pub fn scene_mut(&mut self, node: SceneNodeId) -> Option<&mut Scene> {
+        self.invalidate(node);
         self.nodes.get_mut(node.0).map(|node| &mut node.scene)
     }
 
@@ -138,7 +278,7 @@ impl SceneGraphCache {
     }
 
     pub fn scene_mut_by_index(&mut self, index: usize) -> Option<&mut Scene> {
-        self.nodes.get_mut(index).map(|node| &mut node.scene)
+        self.scene_mut(SceneNodeId(index))
     }
 
     pub fn scene_by_index(&self, index: usize) -> Option<&Scene> {
@@ -184,6 +324,17 @@ impl SceneGraphCache {
         }
     }
 
+    fn mark_region(&mut self, node: SceneNodeId, region: DirtyRegion) {
+        if let Some(entry) = self.nodes.get_mut(node.0) {
+            match &mut entry.dirty {
+                Some(dirty) => dirty.merge(&region),
+                None => entry.dirty = Some(region),
+            }
+        }
+    }
+
+    /// Takes the dirty regions of the node and its descendants, merged in the node's space.
+    /// Descendant regions are clipped and transformed on the way up.
     pub fn take_dirty_recursive(&mut self, node: SceneNodeId) -> Option<DirtyRegion> {
         let mut accumulation = None;
         if let Some(entry) = self.nodes.get_mut(node.0) {
@@ -194,7 +345,10 @@ impl SceneGraphCache {
             let children = entry.children.clone();
 
             for child in children {
-                if let Some(child_region) = self.take_dirty_recursive(child) {
+                let child_region = self
+                    .take_dirty_recursive(child)
+                    .and_then(|region| self.nodes[child.0].region_in_parent(region));
+                if let Some(child_region) = child_region {
                     accumulation = Some(match accumulation {
                         Some(mut region) => {
                             region.merge(&child_region);
@@ -214,7 +368,145 @@ impl SceneGraphCache {
         }
     }
 
+    pub fn transform(&self, node: SceneNodeId) -> Option<Affine> {
+        self.nodes.get(node.0).map(|node| node.transform)
+    }
+
+    /// Sets the transform from the node's space into its parent's.
+    pub fn set_transform(&mut self, node: SceneNodeId, transform: Affine) {
+        self.update_placement(node, |entry| entry.transform = transform);
+    }
+
+    /// Clips the node and its descendants to `clip`, given in the node's space.
+    pub fn set_clip(&mut self, node: SceneNodeId, clip: &impl Shape) {
+        let path = clip.to_path(CLIP_TOLERANCE);
+        self.update_placement(node, |entry| entry.clip = Some(path));
+    }
+
+    pub fn clear_clip(&mut self, node: SceneNodeId) {
+        self.update_placement(node, |entry| entry.clip = None);
+    }
+
+    /// Declares the extent the node's subtree draws in, in the node's space. Placement
+    /// changes dirty this area of the parent before and after the change; nodes without
+    /// bounds dirty all of it, up to their clip.
+    pub fn set_bounds(&mut self, node: SceneNodeId, bounds: Option<Rect>) {
+        if let Some(entry) = self.nodes.get_mut(node.0) {
+            entry.bounds = bounds;
+        }
+    }
+
+    pub fn opacity(&self, node: SceneNodeId) -> Option<f32> {
+        self.nodes.get(node.0).map(|node| node.opacity)
+    }
+
+    /// Sets the opacity the subtree is composited with, clamped to `[0, 1]`.
+    pub fn set_opacity(&mut self, node: SceneNodeId, opacity: f32) {
+        let opacity = if opacity.is_nan() {
+            1.0
+        } else {
+            opacity.clamp(0.0, 1.0)
+        };
+        self.update_placement(node, |entry| entry.opacity = opacity);
+    }
+
+    /// Sets how the subtree blends with what its parent drew before it.
+    pub fn set_blend_mode(&mut self, node: SceneNodeId, blend: impl Into<BlendMode>) {
+        let blend = blend.into();
+        self.update_placement(node, |entry| entry.blend = blend);
+    }
+
+    pub fn z_index(&self, node: SceneNodeId) -> Option<i32> {
+        self.nodes.get(node.0).map(|node| node.z_index)
+    }
+
+    /// Orders the node among its siblings; higher values draw later, on top.
+    pub fn set_z_index(&mut self, node: SceneNodeId, z_index: i32) {
+        self.update_placement(node, |entry| entry.z_index = z_index);
+    }
+
+    /// Composes the subtree under `root` into one scene: each node's scene, then its
+    /// children by z-index with their transforms, clips and layers applied. Subtrees
+    /// whose scenes and properties are unchanged since the last call reuse their
+    /// composed output. Node properties place a node within its parent, so the root's
+    /// own are not applied; the result is in the root's space, like the regions from
+    /// [`take_dirty_recursive`](Self::take_dirty_recursive).
+    pub fn compose(&mut self, root: SceneNodeId) -> Scene {
+        self.ensure_composed(root);
+        self.nodes
+            .get(root.0)
+            .map(|node| node.composed.clone())
+            .unwrap_or_default()
+    }
+
+    fn ensure_composed(&mut self, node: SceneNodeId) {
+        let Some(entry) = self.nodes.get(node.0) else {
+            return;
+        };
+        if entry.composed_valid {
+            return;
+        }
+
+        let children = self.draw_order(node);
+        for &child in &children {
+            self.ensure_composed(child);
+        }
+
+        let mut composed = std::mem::take(&mut self.nodes[node.0].composed);
+        composed.reset();
+        composed.append(&self.nodes[node.0].scene, None);
+        for child in children {
+            self.nodes[child.0].draw_into(&mut composed);
+        }
+        let entry = &mut self.nodes[node.0];
+        entry.composed = composed;
+        entry.composed_valid = true;
+    }
+
+    /// Children of `node` sorted by z-index; the sort is stable, so ties keep creation order.
+    fn draw_order(&self, node: SceneNodeId) -> Vec<SceneNodeId> {
+        let mut children = self.nodes[node.0].children.clone();
+        children.sort_by_key(|child| self.nodes[child.0].z_index);
+        children
+    }
+
+    /// Applies a change to how the node is placed in its parent, which only stales the
+    /// parent's composed output and dirties the area the node covers there before and
+    /// after the change.
+    fn update_placement(&mut self, node: SceneNodeId, update: impl FnOnce(&mut SceneNode)) {
+        let Some(entry) = self.nodes.get_mut(node.0) else {
+            return;
+        };
+        let before = entry.extent_in_parent();
+        update(entry);
+        let after = entry.extent_in_parent();
+        let Some(parent) = entry.parent else {
+            return;
+        };
+        for extent in [before, after].into_iter().flatten() {
+            self.mark_region(parent, extent);
+        }
+        self.invalidate(parent);
+    }
+
+    /// Marks the composed output of `node` and its ancestors stale. Ancestors of a stale
+    /// node are always stale, so the walk stops at the first one found.
+    fn invalidate(&mut self, node: SceneNodeId) {
+        let mut current = Some(node);
+        while let Some(id) = current {
+            let Some(entry) = self.nodes.get_mut(id.0) else {
+                break;
+            };
+            if !entry.composed_valid {
+                break;
+            }
+            entry.composed_valid = false;
+            current = entry.parent;
+        }
+    }
+
     fn attach_child(&mut self, parent_id: SceneNodeId, child_id: SceneNodeId) {
+        self.invalidate(parent_id);
         if let Some(parent) = self.nodes.get_mut(parent_id.0) {
             if !parent.children.iter().any(|child| child.0 == child_id.0) {
                 parent.children.push(child_id);
@@ -275,4 +567,133 @@ mod tests {
         assert!((region.min_y + 6.0).abs() < 1e-6);
         assert!((region.max_y - 9.0).abs() < 1e-6);
     }
+
+    #[test]
+    fn dirty_regions_follow_transforms_and_clips() {
+        let mut cache = SceneGraphCache::new();
+        let root = cache.create_node(None);
+        let child = cache.create_node(Some(root));
+        cache.set_transform(child, Affine::translate((10.0, 20.0)) * Affine::scale(2.0));
+        cache.clear(root);
+
+        cache.mark_dirty_bounds(child, 0.0, 5.0, 0.0, 5.0);
+        let region = cache.take_dirty_recursive(root).expect("dirty region");
+        assert_eq!(
+            (region.min_x, region.max_x, region.min_y, region.max_y),
+            (10.0, 20.0, 20.0, 30.0)
+        );
+
+        cache.set_clip(child, &Rect::new(0.0, 0.0, 2.0, 2.0));
+        cache.clear(root);
+        cache.mark_dirty_bounds(child, 0.0, 5.0, 0.0, 5.0);
+        let region = cache.take_dirty_recursive(root).expect("clipped region");
+        assert_eq!(
+            (region.min_x, region.max_x, region.min_y, region.max_y),
+            (10.0, 14.0, 20.0, 24.0)
+        );
+
+        cache.mark_dirty_bounds(child, 3.0, 5.0, 3.0, 5.0);
+        assert!(cache.take_dirty_recursive(root).is_none());
+    }
+
+    #[test]
+    fn placement_changes_dirty_the_old_and_new_extent() {
+        let mut cache = SceneGraphCache::new();
+        let root = cache.create_node(None);
+        let child = cache.create_node(Some(root));
+        let extent = |cache: &mut SceneGraphCache| {
+            cache
+                .take_dirty_recursive(root)
+                .map(|region| (region.min_x, region.max_x, region.min_y, region.max_y))
+        };
+
+        cache.set_bounds(child, Some(Rect::new(0.0, 0.0, 10.0, 10.0)));
+        cache.set_transform(child, Affine::translate((100.0, 0.0)));
+        assert_eq!(extent(&mut cache), Some((0.0, 110.0, 0.0, 10.0)));
+
+        cache.set_z_index(child, 1);
+        assert_eq!(extent(&mut cache), Some((100.0, 110.0, 0.0, 10.0)));
+
+        cache.set_clip(child, &Rect::new(0.0, 0.0, 5.0, 20.0));
+        cache.set_opacity(child, 0.5);
+        assert_eq!(extent(&mut cache), Some((100.0, 110.0, 0.0, 10.0)));
+
+        cache.set_bounds(child, None);
+        cache.clear_clip(child);
+        assert_eq!(
+            extent(&mut cache),
+            Some((
+                f64::NEG_INFINITY,
+                f64::INFINITY,
+                f64::NEG_INFINITY,
+                f64::INFINITY
+            ))
+        );
+    }
+
+    #[test]
+    fn dispose_releases_the_whole_subtree() {
+        let mut cache = SceneGraphCache::new();
+        let root = cache.create_node(None);
+        let branch = cache.create_node(Some(root));
+        let leaf = cache.create_node(Some(branch));
+        cache.set_bounds(branch, Some(Rect::new(0.0, 0.0, 4.0, 4.0)));
+
+        cache.dispose_node(branch);
+        let region = cache.take_dirty_recursive(root).expect("disposed extent");
+        assert_eq!(
+            (region.min_x, region.max_x, region.min_y, region.max_y),
+            (0.0, 4.0, 0.0, 4.0)
+        );
+        assert!(cache.nodes[root.0].children.is_empty());
+
+        cache.dispose_node(leaf);
+        let reused = [cache.create_node(Some(root)), cache.create_node(Some(root))];
+        assert_eq!(cache.capacity(), 3);
+        assert!(reused.contains(&branch) && reused.contains(&leaf));
+        assert_eq!(cache.nodes[root.0].children, reused);
+    }
+
+    #[test]
+    fn compose_reuses_clean_subtrees() {
+        let mut cache = SceneGraphCache::new();
+        let root = cache.create_node(None);
+        let left = cache.create_node(Some(root));
+        let right = cache.create_node(Some(root));
+        let leaf = cache.create_node(Some(left));
+        let valid = |cache: &SceneGraphCache| -> Vec<bool> {
+            [root, left, right, leaf]
+                .iter()
+                .map(|node| cache.nodes[node.0].composed_valid)
+                .collect()
+        };
+
+        cache.compose(root);
+        assert_eq!(valid(&cache), [true, true, true, true]);
+
+        cache.scene_mut(leaf).expect("leaf scene");
+        assert_eq!(valid(&cache), [false, false, true, false]);
+        cache.compose(root);
+
+        cache.set_opacity(leaf, 0.5);
+        assert_eq!(valid(&cache), [false, false, true, true]);
+        cache.compose(root);
+
+        cache.dispose_node(right);
+        assert_eq!(valid(&cache)[..2], [false, true]);
+    }
+
+    #[test]
+    fn children_draw_in_z_order() {
+        let mut cache = SceneGraphCache::new();
+        let root = cache.create_node(None);
+        let first = cache.create_node(Some(root));
+        let second = cache.create_node(Some(root));
+        let third = cache.create_node(Some(root));
+        assert_eq!(cache.draw_order(root), [first, second, third]);
+
+        cache.set_z_index(first, 2);
+        cache.set_z_index(third, -1);
+        assert_eq!(cache.draw_order(root), [third, second, first]);
+    }
 }
@@ -1,14 +1,29 @@
+//! Label shaping: bidi reordering, script itemization, per-cluster font fallback and
+//! OpenType shaping of short, single-line strings, with a per-shaper layout cache.
+//! A [`LabelStyle`] picks the face and the variable font instance the label is shaped,
+//! measured and drawn with.
+
+use std::borrow::Cow;
+use std::ops::Range;
 use std::sync::{Arc, Mutex};
 
+use harfrust::{Direction, NormalizedCoord, Script, ShaperInstance, Tag, UnicodeBuffer};
 use hashbrown::HashMap;
 use once_cell::sync::Lazy;
-use skrifa::raw::{FileRef, FontRef};
+use skrifa::charmap::Charmap;
+use skrifa::raw::FontRef;
+use skrifa::setting::VariationSetting;
 use skrifa::{
     MetadataProvider,
     instance::{Location, Size},
 };
-use vello::Glyph;
-use vello::peniko::{Blob, FontData};
+use unicode_bidi::ParagraphBidiInfo;
+use unicode_script::{Script as UnicodeScriptKind, UnicodeScript};
+use vello::kurbo::Affine;
+use vello::peniko::{Blob, Brush, Fill, FontData};
+use vello::{Glyph, Scene};
+
+use crate::fonts::{FontId, RegisteredFont, fallback_chain, font_generation};
 
 const LABEL_FONT_BYTES: &[u8] =
     include_bytes!("../../../extern/vello/examples/assets/roboto/Roboto-Regular.ttf");
@@ -16,57 +31,209 @@ const LABEL_FONT_BYTES: &[u8] =
 static LABEL_FONT: Lazy<FontData> =
     Lazy::new(|| FontData::new(Blob::new(Arc::new(LABEL_FONT_BYTES)), 0));
 
-static DEFAULT_TEXT_SHAPER: Lazy<Mutex<TextShaper>> =
-    Lazy::new(|| Mutex::new(TextShaper::new(label_font())));
+/// Slant angle, in degrees, requested from `slnt` axes for italic and oblique styles.
+const OBLIQUE_ANGLE: f32 = 14.0;
+
+static DEFAULT_TEXT_SHAPER: Lazy<Mutex<TextShaper>> = Lazy::new(|| Mutex::new(TextShaper::new()));
 
+/// Glyphs of a label drawn with one font, positioned relative to the label origin.
 #[derive(Debug, Clone)]
-pub struct LabelLayout {
+pub struct GlyphRun {
+    /// Registry identifier of `font`; the embedded label font is `FontId(0)`.
+    pub font_id: FontId,
+    pub font: FontData,
+    /// Variation coordinates of the instance the run was shaped with, as F2Dot14 bits in
+    /// axis order; empty for static fonts.
+    pub normalized_coords: Vec<i16>,
     pub glyphs: Vec<Glyph>,
+}
+
+#[derive(Debug, Clone)]
+pub struct LabelLayout {
+    /// Runs in visual order; adjacent clusters using the same font share a run.
+    pub runs: Vec<GlyphRun>,
     pub width: f32,
     pub height: f32,
     pub ascent: f32,
 }
 
+impl LabelLayout {
+    pub fn glyph_count(&self) -> usize {
+        self.runs.iter().map(|run| run.glyphs.len()).sum()
+    }
+
+    /// Draws every run with its baseline origin at `transform`, returning the number of
+    /// glyph runs encoded.
+    pub fn draw(&self, scene: &mut Scene, font_size: f32, transform: Affine, brush: &Brush) -> u32 {
+        for run in &self.runs {
+            scene
+                .draw_glyphs(&run.font)
+                .font_size(font_size)
+                .transform(transform)
+                .brush(brush)
+                .normalized_coords(&run.normalized_coords)
+                .draw(Fill::NonZero, run.glyphs.iter().copied());
+        }
+        self.runs.len() as u32
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum FontSlant {
+    #[default]
+    Normal,
+    /// Prefers italic faces and the `ital` axis, then the `slnt` axis.
+    Italic,
+    /// Prefers the `slnt` axis, then italic faces and the `ital` axis.
+    Oblique,
+}
+
+/// A variation axis value in the axis' user units, such as `wght` 650.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FontVariation {
+    pub tag: [u8; 4],
+    pub value: f32,
+}
+
+impl FontVariation {
+    pub fn new(tag: &[u8; 4], value: f32) -> Self {
+        Self { tag: *tag, value }
+    }
+}
+
+/// Face selection and variable font instance of a label.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LabelStyle {
+    /// Family tried before the shaper's families.
+    pub family: Option<String>,
+    pub font_size: f32,
+    /// CSS weight, 1 to 1000; drives the `wght` axis.
+    pub weight: f32,
+    /// Width as a percentage of normal; drives the `wdth` axis.
+    pub width: f32,
+    pub slant: FontSlant,
+    /// Axis values applied after weight, width and slant, overriding them.
+    pub variations: Vec<FontVariation>,
+}
+
+impl LabelStyle {
+    pub fn new(font_size: f32) -> Self {
+        Self {
+            family: None,
+            font_size,
+            weight: 400.0,
+            width: 100.0,
+            slant: FontSlant::Normal,
+            variations: Vec::new(),
+        }
+    }
+}
+
+impl Default for LabelStyle {
+    fn default() -> Self {
+        Self::new(14.0)
+    }
+}
+
+/// The parts of a style that pick faces.
+#[derive(Clone, Hash, Eq, PartialEq)]
+struct FaceKey {
+    family: Option<String>,
+    weight_bits: u32,
+    width_bits: u32,
+    slant: FontSlant,
+}
+
+impl FaceKey {
+    fn new(style: &LabelStyle) -> Self {
+        Self {
+            family: style.family.clone(),
+            weight_bits: style.weight.to_bits(),
+            width_bits: style.width.to_bits(),
+            slant: style.slant,
+        }
+    }
+}
+
 #[derive(Hash, Eq, PartialEq)]
 struct TextCacheKey {
+    face: FaceKey,
     font_size_bits: u32,
+    variations: Vec<([u8; 4], u32)>,
     text: String,
 }
 
 impl TextCacheKey {
-    fn new(text: &str, font_size: f32) -> Self {
+    fn new(text: &str, style: &LabelStyle) -> Self {
         Self {
-            font_size_bits: font_size.to_bits(),
+            face: FaceKey::new(style),
+            font_size_bits: style.font_size.to_bits(),
+            variations: style
+                .variations
+                .iter()
+                .map(|variation| (variation.tag, variation.value.to_bits()))
+                .collect(),
             text: text.to_owned(),
         }
     }
 }
 
 pub struct TextShaper {
-    font: &'static FontData,
+    /// Preferred families; `None` follows the registry's default families.
+    families: Option<Vec<String>>,
+    chains: HashMap<FaceKey, Vec<Arc<RegisteredFont>>>,
+    generation: u64,
     cache: HashMap<TextCacheKey, LabelLayout>,
 }
 
+impl Default for TextShaper {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl TextShaper {
-    pub fn new(font: &'static FontData) -> Self {
+    /// Shapes with the registry's default families, then the embedded label font.
+    pub fn new() -> Self {
+        Self::from_families(None)
+    }
+
+    /// Shapes with `families` in preference order, then the embedded label font.
+    pub fn with_families<S: AsRef<str>>(families: &[S]) -> Self {
+        Self::from_families(Some(
+            families
+                .iter()
+                .map(|family| family.as_ref().to_owned())
+                .collect(),
+        ))
+    }
+
+    fn from_families(families: Option<Vec<String>>) -> Self {
         Self {
-            font,
+            families,
+            chains: HashMap::new(),
+            generation: font_generation(),
             cache: HashMap::with_capacity(64),
         }
     }
 
     pub fn shape(&mut self, text: &str, font_size: f32) -> Option<LabelLayout> {
+        self.shape_styled(text, &LabelStyle::new(font_size))
+    }
+
+    pub fn shape_styled(&mut self, text: &str, style: &LabelStyle) -> Option<LabelLayout> {
         let trimmed = text.trim();
         if trimmed.is_empty() {
             return None;
         }
 
-        let key = TextCacheKey::new(trimmed, font_size);
+        self.sync_fonts();
+        let key = TextCacheKey::new(trimmed, style);
         if let Some(layout) = self.cache.get(&key) {
             return Some(layout.clone());
         }
 
-        let layout = layout_text(self.font, trimmed, font_size)?;
+        let layout = layout_text(self.chain(style), trimmed, style)?;
         self.cache.insert(key, layout.clone());
         Some(layout)
     }
@@ -74,6 +241,50 @@ impl TextShaper {
     pub fn clear(&mut self) {
         self.cache.clear();
     }
+
+    /// Width of `text` as [`Self::shape`] lays it out, without caching the layout.
+    /// Line breaking probes many substrings that are never drawn.
+    pub(crate) fn measure(&mut self, text: &str, font_size: f32) -> f32 {
+        let trimmed = text.trim();
+        if trimmed.is_empty() {
+            return 0.0;
+        }
+
+        self.sync_fonts();
+        let style = LabelStyle::new(font_size);
+        if let Some(layout) = self.cache.get(&TextCacheKey::new(trimmed, &style)) {
+            return layout.width;
+        }
+        layout_text(self.chain(&style), trimmed, &style).map_or(0.0, |layout| layout.width)
+    }
+
+    /// Ascent and natural line height of the primary font at `font_size`.
+    pub(crate) fn line_metrics(&mut self, font_size: f32) -> Option<(f32, f32)> {
+        self.sync_fonts();
+        let style = LabelStyle::new(font_size);
+        let font = self.chain(&style).first()?.font_ref()?;
+        let location = font.axes().location(variation_settings(&font, &style));
+        let metrics = font.metrics(Size::new(font_size), &location);
+        Some((metrics.ascent, metrics.ascent - metrics.descent))
+    }
+
+    fn chain(&mut self, style: &LabelStyle) -> &[Arc<RegisteredFont>] {
+        let families = self.families.as_deref();
+        self.chains
+            .entry(FaceKey::new(style))
+            .or_insert_with(|| fallback_chain(families, style))
+    }
+
+    /// Drops fallback chains and cached layouts after fonts were registered or the
+    /// default families changed.
+    fn sync_fonts(&mut self) {
+        let generation = font_generation();
+        if generation != self.generation {
+            self.chains.clear();
+            self.generation = generation;
+            self.cache.clear();
+        }
+    }
 }
 
 pub fn label_font() -> &'static FontData {
@@ -81,63 +292,309 @@ pub fn label_font() -> &'static FontData {
 }
 
 pub fn layout_label(text: &str, font_size: f32) -> Option<LabelLayout> {
+    with_default_shaper(|shaper| shaper.shape(text, font_size))
+}
+
+pub fn layout_styled_label(text: &str, style: &LabelStyle) -> Option<LabelLayout> {
+    with_default_shaper(|shaper| shaper.shape_styled(text, style))
+}
+
+pub(crate) fn with_default_shaper<R>(f: impl FnOnce(&mut TextShaper) -> R) -> R {
     let mut shaper = DEFAULT_TEXT_SHAPER
         .lock()
         .expect("text shaper mutex poisoned");
-    shaper.shape(text, font_size)
+    f(&mut shaper)
+}
+
+/// A chain face positioned at a style's variation coordinates.
+struct FaceInstance {
+    location: Location,
+    shaper: ShaperInstance,
+}
+
+impl FaceInstance {
+    fn new(font: &FontRef<'_>, shaping_font: &harfrust::FontRef<'_>, style: &LabelStyle) -> Self {
+        let location = font.axes().location(variation_settings(font, style));
+        // harfrust and skrifa build on different read-fonts releases, so coordinates
+        // cross over as raw bits.
+        let coords = location
+            .coords()
+            .iter()
+            .map(|coord| NormalizedCoord::from_bits(coord.to_bits()));
+        Self {
+            shaper: ShaperInstance::from_coords(shaping_font, coords),
+            location,
+        }
+    }
+}
+
+/// User-space axis settings for `style`, with optical size following the font size;
+/// axes the face lacks are ignored when the settings are normalized.
+fn variation_settings(font: &FontRef<'_>, style: &LabelStyle) -> Vec<VariationSetting> {
+    let has_axis = |tag: &[u8; 4]| font.axes().get_by_tag(skrifa::Tag::new(tag)).is_some();
+    let mut settings: Vec<VariationSetting> = vec![
+        ("opsz", style.font_size).into(),
+        ("wght", style.weight).into(),
+        ("wdth", style.width).into(),
+    ];
+    match style.slant {
+        FontSlant::Normal => {}
+        FontSlant::Italic if has_axis(b"ital") => settings.push(("ital", 1.0).into()),
+        _ if has_axis(b"slnt") => settings.push(("slnt", -OBLIQUE_ANGLE).into()),
+        _ => settings.push(("ital", 1.0).into()),
+    }
+    settings.extend(
+        style.variations.iter().map(|variation| {
+            VariationSetting::new(skrifa::Tag::new(&variation.tag), variation.value)
+        }),
+    );
+    settings
 }
 
-fn layout_text(font: &'static FontData, text: &str, font_size: f32) -> Option<LabelLayout> {
-    let font_ref = to_font_ref(font)?;
+fn layout_text(
+    chain: &[Arc<RegisteredFont>],
+    text: &str,
+    style: &LabelStyle,
+) -> Option<LabelLayout> {
+    let font_size = style.font_size;
+    let font_refs = chain
+        .iter()
+        .map(|font| font.font_ref())
+        .collect::<Option<Vec<_>>>()?;
+    let charmaps: Vec<Charmap<'_>> = font_refs.iter().map(|font| font.charmap()).collect();
     let size = Size::new(font_size);
-    let axes = font_ref.axes();
-    let location = Location::new(axes.len());
-    let metrics = font_ref.metrics(size, &location);
-    let glyph_metrics = font_ref.glyph_metrics(size, &location);
-    let charmap = font_ref.charmap();
 
+    let text = strip_controls(text);
     let mut pen_x = 0f32;
-    let mut glyphs = Vec::with_capacity(text.len());
-    for ch in text.chars() {
-        if ch.is_control() && ch != ' ' {
-            continue;
-        }
-        let glyph_id = charmap.map(ch).unwrap_or_default();
-        let advance = glyph_metrics.advance_width(glyph_id).unwrap_or_default();
-        glyphs.push(Glyph {
-            id: glyph_id.to_u32(),
-            x: pen_x,
-            y: 0.0,
+    let mut ascent = 0f32;
+    let mut descent = 0f32;
+    let mut instances: Vec<Option<FaceInstance>> = chain.iter().map(|_| None).collect();
+    let mut runs: Vec<(usize, Vec<Glyph>)> = Vec::new();
+    for run in itemize(&text, &charmaps) {
+        let registered = &chain[run.font];
+        let shaping_font = registered.shaping_font_ref()?;
+        let instance = match &mut instances[run.font] {
+            Some(instance) => instance,
+            slot => {
+                let font = &font_refs[run.font];
+                let instance = FaceInstance::new(font, &shaping_font, style);
+                let metrics = font.metrics(size, &instance.location);
+                ascent = ascent.max(metrics.ascent);
+                descent = descent.min(metrics.descent);
+                slot.insert(instance)
+            }
+        };
+        let shaper = registered
+            .shaper_data
+            .shaper(&shaping_font)
+            .instance(Some(&instance.shaper))
+            .point_size(Some(font_size))
+            .build();
+        let units_per_em = shaper.units_per_em() as f32;
+        let scale = if units_per_em > 0.0 {
+            font_size / units_per_em
+        } else {
+            1.0
+        };
+
+        let mut buffer = UnicodeBuffer::new();
+        buffer.push_str(&text[run.range]);
+        buffer.set_direction(if run.rtl {
+            Direction::RightToLeft
+        } else {
+            Direction::LeftToRight
         });
-        pen_x += advance;
+        if let Some(script) = run.script {
+            buffer.set_script(script);
+        }
+        buffer.guess_segment_properties();
+
+        // Shaped RTL runs already come back in visual order.
+        let shaped = shaper.shape(buffer, &[]);
+        let glyphs = match runs.last_mut() {
+            Some((font, glyphs)) if *font == run.font => glyphs,
+            _ => {
+                runs.push((run.font, Vec::new()));
+                &mut runs.last_mut().expect("run pushed").1
+            }
+        };
+        for (info, position) in shaped.glyph_infos().iter().zip(shaped.glyph_positions()) {
+            glyphs.push(Glyph {
+                id: info.glyph_id,
+                x: pen_x + position.x_offset as f32 * scale,
+                y: -(position.y_offset as f32) * scale,
+            });
+            pen_x += position.x_advance as f32 * scale;
+        }
     }
 
-    if glyphs.is_empty() {
+    runs.retain(|(_, glyphs)| !glyphs.is_empty());
+    if runs.is_empty() {
         return None;
     }
 
-    let ascent = metrics.ascent;
-    let height = ascent - metrics.descent;
-
     Some(LabelLayout {
-        glyphs,
+        runs: runs
+            .into_iter()
+            .map(|(font, glyphs)| GlyphRun {
+                font_id: chain[font].id,
+                font: chain[font].font.clone(),
+                normalized_coords: instances[font].as_ref().map_or_else(Vec::new, |instance| {
+                    instance
+                        .location
+                        .coords()
+                        .iter()
+                        .map(|coord| coord.to_bits())
+                        .collect()
+                }),
+                glyphs,
+            })
+            .collect(),
         width: pen_x,
-        height,
+        height: ascent - descent,
         ascent,
     })
 }
 
-fn to_font_ref(font: &FontData) -> Option<FontRef<'_>> {
-    let file_ref = FileRef::new(font.data.as_ref()).ok()?;
-    match file_ref {
-        FileRef::Font(font) => Some(font),
-        FileRef::Collection(collection) => collection.get(font.index).ok(),
+/// A span of the label with one direction, script and font, shaped as a unit.
+#[derive(Debug, PartialEq)]
+struct TextRun {
+    range: Range<usize>,
+    rtl: bool,
+    script: Option<Script>,
+    /// Index into the fallback chain.
+    font: usize,
+}
+
+/// Splits `text` into runs in visual order: bidi level runs first, then script changes
+/// within each level run, then font changes within each script run.
+fn itemize(text: &str, fonts: &[Charmap<'_>]) -> Vec<TextRun> {
+    let bidi = ParagraphBidiInfo::new(text, None);
+    let (levels, level_runs) = bidi.visual_runs(0..text.len());
+    let mut runs = Vec::new();
+    for level_run in level_runs {
+        let rtl = levels[level_run.start].is_rtl();
+        let first = runs.len();
+        split_scripts(text, level_run, rtl, fonts, &mut runs);
+        if rtl {
+            runs[first..].reverse();
+        }
+    }
+    runs
+}
+
+/// Pushes the script runs of `range` in logical order. Common and inherited characters
+/// such as spaces, digits and combining marks stay with the run they follow.
+fn split_scripts(
+    text: &str,
+    range: Range<usize>,
+    rtl: bool,
+    fonts: &[Charmap<'_>],
+    runs: &mut Vec<TextRun>,
+) {
+    let mut start = range.start;
+    let mut current: Option<UnicodeScriptKind> = None;
+    for (offset, ch) in text[range.clone()].char_indices() {
+        let script = ch.script();
+        if matches!(
+            script,
+            UnicodeScriptKind::Common | UnicodeScriptKind::Inherited | UnicodeScriptKind::Unknown
+        ) {
+            continue;
+        }
+        match current {
+            None => current = Some(script),
+            Some(previous) if previous != script => {
+                let end = range.start + offset;
+                split_fonts(text, start..end, rtl, shaping_script(previous), fonts, runs);
+                start = end;
+                current = Some(script);
+            }
+            Some(_) => {}
+        }
+    }
+    if start < range.end {
+        let script = current.and_then(shaping_script);
+        split_fonts(text, start..range.end, rtl, script, fonts, runs);
+    }
+}
+
+/// Pushes the font runs of `range` in logical order. Each cluster takes the first font
+/// in the chain that maps its base character; characters that extend a cluster (marks,
+/// joiners, variation selectors, emoji modifiers and tags) keep the cluster's font.
+/// Characters no font maps stay with the current font, or the primary one at the start.
+fn split_fonts(
+    text: &str,
+    range: Range<usize>,
+    rtl: bool,
+    script: Option<Script>,
+    fonts: &[Charmap<'_>],
+    runs: &mut Vec<TextRun>,
+) {
+    let mut start = range.start;
+    let mut current: Option<usize> = None;
+    let mut after_joiner = false;
+    for (offset, ch) in text[range.clone()].char_indices() {
+        let extends = after_joiner || extends_cluster(ch);
+        after_joiner = ch == ZERO_WIDTH_JOINER;
+        let font = match current {
+            Some(font) if extends => font,
+            _ => fonts
+                .iter()
+                .position(|charmap| charmap.map(ch).is_some())
+                .or(current)
+                .unwrap_or(0),
+        };
+        match current {
+            None => current = Some(font),
+            Some(previous) if previous != font => {
+                let end = range.start + offset;
+                runs.push(TextRun {
+                    range: start..end,
+                    rtl,
+                    script,
+                    font: previous,
+                });
+                start = end;
+                current = Some(font);
+            }
+            Some(_) => {}
+        }
+    }
+    if start < range.end {
+        runs.push(TextRun {
+            range: start..range.end,
+            rtl,
+            script,
+            font: current.unwrap_or(0),
+        });
+    }
+}
+
+pub(crate) const ZERO_WIDTH_JOINER: char = '\u{200d}';
+
+pub(crate) fn extends_cluster(ch: char) -> bool {
+    ch.script() == UnicodeScriptKind::Inherited
+        || matches!(ch, '\u{1f3fb}'..='\u{1f3ff}' | '\u{e0020}'..='\u{e007f}')
+}
+
+fn shaping_script(script: UnicodeScriptKind) -> Option<Script> {
+    let name: [u8; 4] = script.short_name().as_bytes().try_into().ok()?;
+    Script::from_iso15924_tag(Tag::new(&name))
+}
+
+fn strip_controls(text: &str) -> Cow<'_, str> {
+    if text.chars().any(char::is_control) {
+        Cow::Owned(text.chars().filter(|ch| !ch.is_control()).collect())
+    } else {
+        Cow::Borrowed(text)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::fonts::register_font;
 
     #[test]
     fn layout_label_returns_metrics_for_ascii_text() {
@@ -145,16 +602,194 @@ mod tests {
         assert!((layout.width - 45.04883).abs() < 1e-5);
         assert!((layout.height - 16.40625).abs() < 1e-5);
         assert!((layout.ascent - 12.988281).abs() < 1e-5);
-        assert_eq!(layout.glyphs.len(), 7);
+        assert_eq!(layout.runs.len(), 1);
+        assert_eq!(layout.glyph_count(), 7);
     }
 
     #[test]
     fn text_shaper_caches_repeated_requests() {
-        let mut shaper = TextShaper::new(label_font());
+        let mut shaper = TextShaper::new();
         let first = shaper.shape("Volume", 12.0).expect("first layout");
         let second = shaper.shape("Volume", 12.0).expect("cached layout");
         assert_eq!(first.width, second.width);
         assert_eq!(first.height, second.height);
         assert_eq!(first.ascent, second.ascent);
     }
+
+    #[test]
+    fn wrapping_paragraphs_caches_only_the_shaped_lines() {
+        let mut shaper = TextShaper::new();
+        let options = crate::ParagraphOptions {
+            max_width: 80.0,
+            wrap: crate::TextWrap::Word,
+            ..crate::ParagraphOptions::default()
+        };
+        let layout = shaper
+            .shape_paragraph("the quick brown fox jumps over the lazy dog", &options)
+            .expect("paragraph");
+        assert!(layout.lines.len() > 1);
+        // Font registration in parallel tests can clear the cache, never grow it.
+        assert!(shaper.cache.len() <= layout.lines.len());
+    }
+
+    #[test]
+    fn kerning_tightens_pairs() {
+        let pair = layout_label("AV", 20.0).expect("pair");
+        let a = layout_label("A", 20.0).expect("A");
+        let v = layout_label("V", 20.0).expect("V");
+        assert!(pair.width < a.width + v.width);
+    }
+
+    #[test]
+    fn combining_marks_compose_with_their_base() {
+        let decomposed = layout_label("e\u{301}", 14.0).expect("decomposed");
+        let precomposed = layout_label("\u{e9}", 14.0).expect("precomposed");
+        assert_eq!(decomposed.glyph_count(), 1);
+        assert_eq!(
+            decomposed.runs[0].glyphs[0].id,
+            precomposed.runs[0].glyphs[0].id
+        );
+    }
+
+    #[test]
+    fn right_to_left_runs_are_placed_in_visual_order() {
+        let text = "Tag \u{5d0}\u{5d1}\u{5d2} ok";
+        let spans: Vec<(&str, bool)> = itemize(text, &[])
+            .into_iter()
+            .map(|run| (&text[run.range], run.rtl))
+            .collect();
+        assert_eq!(
+            spans,
+            [
+                ("Tag ", false),
+                ("\u{5d0}\u{5d1}\u{5d2}", true),
+                (" ok", false)
+            ]
+        );
+
+        let text = "\u{5d0} \u{5d1}";
+        assert!(itemize(text, &[]).iter().all(|run| run.rtl));
+    }
+
+    #[test]
+    fn script_changes_split_runs() {
+        let text = "Load \u{939}\u{93f}\u{902}\u{926}\u{940} 2";
+        let runs = itemize(text, &[]);
+        assert_eq!(runs.len(), 2);
+        assert_eq!(&text[runs[0].range.clone()], "Load ");
+        assert_eq!(runs[0].script, Script::from_iso15924_tag(Tag::new(b"Latn")));
+        assert_eq!(runs[1].script, Script::from_iso15924_tag(Tag::new(b"Deva")));
+    }
+
+    const EMOJI_FONT_BYTES: &[u8] = include_bytes!(
+        "../../../samples/AvaloniaVelloSkiaSharpSample/Assets/fonts/NotoColorEmoji-Subset.ttf"
+    );
+
+    fn emoji_shaper() -> TextShaper {
+        register_font("Test Emoji", EMOJI_FONT_BYTES.to_vec(), 0).expect("emoji font");
+        TextShaper::with_families(&["Test Emoji"])
+    }
+
+    fn is_label_font(run: &GlyphRun) -> bool {
+        run.font.data.id() == label_font().data.id()
+    }
+
+    #[test]
+    fn missing_characters_fall_back_per_cluster() {
+        let mut shaper = emoji_shaper();
+        let layout = shaper.shape("Go \u{1f389} now", 14.0).expect("layout");
+        let fonts: Vec<bool> = layout.runs.iter().map(is_label_font).collect();
+        assert_eq!(fonts, [true, false, true]);
+        assert_eq!(layout.runs[1].glyphs.len(), 1);
+        assert!(layout.runs[1].glyphs[0].x > layout.runs[0].glyphs[2].x);
+
+        let plain = layout_label("Go now", 14.0).expect("plain");
+        assert!(layout.height >= plain.height);
+    }
+
+    #[test]
+    fn joined_emoji_stay_in_one_font() {
+        let mut shaper = emoji_shaper();
+        let layout = shaper
+            .shape("\u{1f389}\u{200d}\u{1f389}\u{fe0f}", 14.0)
+            .expect("layout");
+        assert_eq!(layout.runs.len(), 1);
+        assert!(!is_label_font(&layout.runs[0]));
+    }
+
+    const FLEX_FONT_BYTES: &[u8] = include_bytes!(
+        "../../../samples/AvaloniaVelloSkiaSharpSample/Assets/fonts/RobotoFlex-Subset.ttf"
+    );
+
+    fn flex_style(configure: impl FnOnce(&mut LabelStyle)) -> LabelStyle {
+        let mut style = LabelStyle::new(14.0);
+        style.family = Some("Test Flex".to_string());
+        configure(&mut style);
+        style
+    }
+
+    #[test]
+    fn styles_resolve_variable_instances() {
+        register_font("Test Flex", FLEX_FONT_BYTES.to_vec(), 0).expect("flex font");
+        let mut shaper = TextShaper::new();
+        let regular = shaper
+            .shape_styled("2024", &flex_style(|_| {}))
+            .expect("regular");
+        let bold = shaper
+            .shape_styled("2024", &flex_style(|style| style.weight = 800.0))
+            .expect("bold");
+        let condensed = shaper
+            .shape_styled("2024", &flex_style(|style| style.width = 50.0))
+            .expect("condensed");
+        let italic = shaper
+            .shape_styled("2024", &flex_style(|style| style.slant = FontSlant::Italic))
+            .expect("italic");
+
+        assert!(!is_label_font(&regular.runs[0]));
+        let coords: Vec<&[i16]> = [&regular, &bold, &condensed, &italic]
+            .iter()
+            .map(|layout| layout.runs[0].normalized_coords.as_slice())
+            .collect();
+        assert!(coords.iter().all(|coords| coords.len() == 13));
+        assert_ne!(coords[0], coords[1]);
+        assert_ne!(coords[0], coords[3]);
+        assert!(condensed.width < regular.width);
+    }
+
+    #[test]
+    fn explicit_variations_override_the_style() {
+        register_font("Test Flex", FLEX_FONT_BYTES.to_vec(), 0).expect("flex font");
+        let mut shaper = TextShaper::new();
+        let bold = shaper
+            .shape_styled("42", &flex_style(|style| style.weight = 800.0))
+            .expect("bold");
+        let overridden = shaper
+            .shape_styled(
+                "42",
+                &flex_style(|style| {
+                    style.variations.push(FontVariation::new(b"wght", 800.0));
+                }),
+            )
+            .expect("overridden");
+        assert_eq!(
+            bold.runs[0].normalized_coords,
+            overridden.runs[0].normalized_coords
+        );
+
+        let static_font = shaper
+            .shape_styled("42", &LabelStyle::new(14.0))
+            .expect("static");
+        assert!(static_font.runs[0].normalized_coords.is_empty());
+    }
+
+    #[test]
+    fn registering_fonts_refreshes_cached_layouts() {
+        let mut shaper = TextShaper::with_families(&["Late Emoji"]);
+        let before = shaper.shape("\u{1f389}", 14.0).expect("before");
+        assert!(is_label_font(&before.runs[0]));
+
+        register_font("Late Emoji", EMOJI_FONT_BYTES.to_vec(), 0).expect("emoji font");
+        let after = shaper.shape("\u{1f389}", 14.0).expect("after");
+        assert!(!is_label_font(&after.runs[0]));
+    }
 }
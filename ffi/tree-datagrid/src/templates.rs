@@ -11,7 +11,9 @@ use hashbrown::HashMap;
 use vello::Scene;
 use vello::kurbo::{Affine, Rect};
 use vello::peniko::{Brush, Color, Fill};
-use vello_composition::{SceneGraphCache, label_font, layout_label};
+use vello_composition::{
+    LayoutAlignment, ParagraphOptions, SceneGraphCache, TextWrap, layout_paragraph,
+};
 
 const DEFAULT_TEXT_FONT_SIZE: f32 = 13.0;
 const DEFAULT_TEXT_HORIZONTAL_PADDING: f64 = 8.0;
@@ -147,6 +149,12 @@ struct TextTemplateBuilder {
     foreground: Option<Color>,
     background: Option<Color>,
     font_size: Option<f32>,
+    wrap: Option<TextWrap>,
+    ellipsis: bool,
+    max_lines: Option<usize>,
+    line_height: Option<f32>,
+    text_alignment: Option<LayoutAlignment>,
+    vertical_alignment: Option<LayoutAlignment>,
 }
 
 impl TextTemplateBuilder {
@@ -186,6 +194,42 @@ impl TextTemplateBuilder {
                     }
                 }
             }
+            "TextWrapping" => {
+                if let Some(wrap) = parse_text_wrap(raw) {
+                    self.wrap = Some(wrap);
+                }
+            }
+            "TextTrimming" => {
+                if let Some(value) = raw {
+                    self.ellipsis = !value.trim().eq_ignore_ascii_case("None");
+                }
+            }
+            "MaxLines" => {
+                if let Some(lines) = parse_f32_value(instruction, raw)
+                    && lines.is_finite()
+                    && lines >= 0.0
+                {
+                    self.max_lines = (lines >= 1.0).then_some(lines as usize);
+                }
+            }
+            "LineHeight" => {
+                if let Some(height) = parse_f32_value(instruction, raw)
+                    && height.is_finite()
+                    && height > 0.0
+                {
+                    self.line_height = Some(height);
+                }
+            }
+            "TextAlignment" => {
+                if let Some(alignment) = parse_alignment(raw) {
+                    self.text_alignment = Some(alignment);
+                }
+            }
+            "VerticalAlignment" | "VerticalContentAlignment" => {
+                if let Some(alignment) = parse_alignment(raw) {
+                    self.vertical_alignment = Some(alignment);
+                }
+            }
             _ => {}
         }
     }
@@ -235,7 +279,16 @@ impl TextTemplateBuilder {
             binding_path: self.binding_path,
             foreground,
             background,
-            font_size,
+            paragraph: ParagraphOptions {
+                font_size,
+                wrap: self.wrap.unwrap_or_default(),
+                line_height: self.line_height.unwrap_or(0.0),
+                max_lines: self.max_lines,
+                ellipsis: self.ellipsis,
+                horizontal_alignment: self.text_alignment.unwrap_or(LayoutAlignment::Start),
+                vertical_alignment: self.vertical_alignment.unwrap_or(LayoutAlignment::Center),
+                ..ParagraphOptions::default()
+            },
             kind: self.kind,
         })
     }
@@ -249,7 +302,8 @@ struct TextTemplate {
     binding_path: Option<String>,
     foreground: Option<Color>,
     background: Option<Color>,
-    font_size: f32,
+    /// Font, wrapping and alignment; the box size is filled in per cell.
+    paragraph: ParagraphOptions,
     kind: VelloTdgTemplateNodeKind,
 }
 
@@ -636,33 +690,34 @@ impl TemplateProgram {
             return;
         }
 
-        let layout = match layout_label(&content, template.font_size) {
-            Some(layout) => layout,
-            None => return,
-        };
-
         let (padding_x, padding_y) = template.padding();
         let available_width = column.width - 2.0 * padding_x;
         if !available_width.is_finite() || available_width <= 0.0 {
             return;
         }
 
-        let baseline_x = column.offset + padding_x;
-        let text_height = f64::from(layout.height);
-        let ascent = f64::from(layout.ascent);
-        let mut baseline_y = (height - text_height) * 0.5 + ascent;
-        if !baseline_y.is_finite() {
-            baseline_y = ascent;
-        }
+        let available_height = height - 2.0 * padding_y;
+        let options = ParagraphOptions {
+            max_width: available_width as f32,
+            max_height: if available_height.is_finite() {
+                available_height as f32
+            } else {
+                f32::INFINITY
+            },
+            ..template.paragraph
+        };
+        let Some(layout) = layout_paragraph(&content, &options) else {
+            return;
+        };
 
         let brush_color = template.foreground.unwrap_or_else(default_text_foreground);
 
-        scene
-            .draw_glyphs(label_font())
-            .font_size(template.font_size)
-            .transform(Affine::translate((baseline_x, baseline_y)))
-            .brush(Brush::Solid(brush_color))
-            .draw(Fill::NonZero, layout.glyphs.into_iter());
+        layout.draw(
+            scene,
+            options.font_size,
+            Affine::translate((column.offset + padding_x, padding_y)),
+            &Brush::Solid(brush_color),
+        );
     }
 
     fn apply_render_config(
@@ -778,6 +833,36 @@ fn parse_f32_value(instruction: &VelloTdgTemplateInstruction, raw: Option<&str>)
     }
 }
 
+fn parse_text_wrap(value: Option<&str>) -> Option<TextWrap> {
+    match value?.trim() {
+        v if v.eq_ignore_ascii_case("NoWrap") => Some(TextWrap::NoWrap),
+        v if v.eq_ignore_ascii_case("Wrap") || v.eq_ignore_ascii_case("WrapWithOverflow") => {
+            Some(TextWrap::Word)
+        }
+        v if v.eq_ignore_ascii_case("Character") => Some(TextWrap::Character),
+        _ => None,
+    }
+}
+
+fn parse_alignment(value: Option<&str>) -> Option<LayoutAlignment> {
+    match value?.trim() {
+        v if ["Left", "Top", "Start"]
+            .iter()
+            .any(|name| v.eq_ignore_ascii_case(name)) =>
+        {
+            Some(LayoutAlignment::Start)
+        }
+        v if v.eq_ignore_ascii_case("Center") => Some(LayoutAlignment::Center),
+        v if ["Right", "Bottom", "End"]
+            .iter()
+            .any(|name| v.eq_ignore_ascii_case(name)) =>
+        {
+            Some(LayoutAlignment::End)
+        }
+        _ => None,
+    }
+}
+
 fn normalize_access_text(value: &str) -> String {
     if !value.contains('_') {
         return value.to_owned();
@@ -0,0 +1,40 @@
+using System.Runtime.InteropServices;
+
+namespace VelloSharp.ChartData;
+
+/// <summary>
+/// Represents an open/high/low/close bar emitted for a candlestick or OHLC series.
+/// </summary>
+[StructLayout(LayoutKind.Sequential)]
+public readonly struct ChartOhlcSample
+{
+    public readonly int SeriesId;
+    public readonly double TimestampSeconds;
+    public readonly double Open;
+    public readonly double High;
+    public readonly double Low;
+    public readonly double Close;
+
+    /// <summary>
+    /// Traded volume for the bar, or <c>null</c> when the feed does not report it.
+    /// </summary>
+    public readonly double? Volume;
+
+    public ChartOhlcSample(
+        int seriesId,
+        double timestampSeconds,
+        double open,
+        double high,
+        double low,
+        double close,
+        double? volume = null)
+    {
+        SeriesId = seriesId;
+        TimestampSeconds = timestampSeconds;
+        Open = open;
+        High = high;
+        Low = low;
+        Close = close;
+        Volume = volume;
+    }
+}
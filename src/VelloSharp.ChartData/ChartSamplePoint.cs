@@ -4,6 +4,7 @@ namespace VelloSharp.ChartData;
 
 /// <summary>
 /// Represents a single time-series data point emitted for a specific chart series.
+/// A non-finite <see cref="Value"/> (such as <see cref="double.NaN"/>) marks a gap in the series.
 /// </summary>
 [StructLayout(LayoutKind.Sequential)]
 public readonly struct ChartSamplePoint
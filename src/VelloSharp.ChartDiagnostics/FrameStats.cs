@@ -10,4 +10,15 @@ namespace VelloSharp.ChartDiagnostics;
     TimeSpan GpuTime,
     TimeSpan QueueLatency,
     int EncodedPaths,
-    DateTimeOffset Timestamp);
+    DateTimeOffset Timestamp)
+{
+    /// <summary>
+    /// Gets the number of series points considered before level-of-detail decimation.
+    /// </summary>
+    public long SourcePoints { get; init; }
+
+    /// <summary>
+    /// Gets the number of series points rendered after decimation.
+    /// </summary>
+    public long RenderedPoints { get; init; }
+}
@@ -1,4 +1,5 @@
 using System;
+using System.Collections.Generic;
 
 namespace VelloSharp.ChartEngine.Annotations;
 
@@ -68,6 +69,11 @@ protected ChartAnnotation(AnnotationKind kind, string? label = null)
     /// Gets or sets the pane identifier this annotation targets. When null, the annotation applies to all panes.
     /// </summary>
     public string? TargetPaneId { get; init; }
+
+    /// <summary>
+    /// Gets or sets alternating dash and gap lengths for the annotation stroke. Solid when null or empty.
+    /// </summary>
+    public IReadOnlyList<double>? DashPattern { get; init; }
 }
 
 /// <summary>
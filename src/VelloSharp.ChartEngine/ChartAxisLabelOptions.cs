@@ -0,0 +1,31 @@
+using System;
+
+namespace VelloSharp.ChartEngine;
+
+/// <summary>
+/// Time zone and label formats used for the axes drawn by <see cref="ChartEngine"/>.
+/// </summary>
+public sealed class ChartAxisLabelOptions
+{
+    /// <summary>
+    /// Offset of the local zone from UTC, in whole minutes within ±18 hours. Time ticks
+    /// align to local day, week, month and year boundaries.
+    /// </summary>
+    public TimeSpan UtcOffset { get; init; } = TimeSpan.Zero;
+
+    /// <summary>
+    /// Time label pattern using <c>%Y %m %b %d %a %H %M %S %f %%</c>; <c>null</c> picks a format from the tick spacing.
+    /// </summary>
+    public string? TimeFormat { get; init; }
+
+    /// <summary>
+    /// Value label pattern with one <c>{}</c> or <c>{:.N}</c> placeholder, such as <c>"{:.1} %"</c>;
+    /// <c>null</c> picks decimals from the tick spacing.
+    /// </summary>
+    public string? ValueFormat { get; init; }
+
+    /// <summary>
+    /// Adds the date as a second line to the first time label of each local day.
+    /// </summary>
+    public bool ShowDateOnDayChange { get; init; }
+}
@@ -146,6 +146,30 @@ public ChartPaneBuilder ShareXAxisWithPrimary(bool share = true)
         return this;
     }
 
+    /// <summary>
+    /// Selects the value axis scale of the pane. <paramref name="symlogLinearThreshold"/> is the
+    /// half-width of the linear region around zero and only applies to <see cref="ChartValueScale.SymLog"/>.
+    /// </summary>
+    public ChartPaneBuilder WithValueScale(ChartValueScale scale, double symlogLinearThreshold = 1.0)
+    {
+        if (!Enum.IsDefined(scale))
+        {
+            throw new ArgumentOutOfRangeException(nameof(scale), scale, "Unknown value scale.");
+        }
+
+        if (!double.IsFinite(symlogLinearThreshold) || symlogLinearThreshold <= 0)
+        {
+            throw new ArgumentOutOfRangeException(
+                nameof(symlogLinearThreshold),
+                symlogLinearThreshold,
+                "Symlog linear threshold must be positive and finite.");
+        }
+
+        _pane.ValueScale = scale;
+        _pane.SymlogLinearThreshold = symlogLinearThreshold;
+        return this;
+    }
+
     public ChartPaneBuilder WithHeightRatio(double ratio)
     {
         if (!double.IsFinite(ratio) || ratio <= 0)
@@ -197,6 +221,17 @@ internal ChartPaneDefinition(string id)
     /// </summary>
     public bool ShareXAxisWithPrimary { get; set; } = true;
 
+    /// <summary>
+    /// Gets or sets the scale applied to the pane's value axis.
+    /// </summary>
+    public ChartValueScale ValueScale { get; set; } = ChartValueScale.Linear;
+
+    /// <summary>
+    /// Gets or sets the half-width of the linear region around zero when <see cref="ValueScale"/> is
+    /// <see cref="ChartValueScale.SymLog"/>.
+    /// </summary>
+    public double SymlogLinearThreshold { get; set; } = 1.0;
+
     internal void SetNormalizedRatio(double ratio) => NormalizedRatio = ratio;
 }
 
@@ -240,6 +275,16 @@ public CompositionAnnotationLayer ForPanes(params string[] paneIds)
     }
 }
 
+/// <summary>
+/// Selects how values are mapped onto a pane's value axis.
+/// </summary>
+public enum ChartValueScale : uint
+{
+    Linear = 0,
+    Log10 = 1,
+    SymLog = 2,
+}
+
 /// <summary>
 /// Determines the drawing order of composition annotation layers.
 /// </summary>
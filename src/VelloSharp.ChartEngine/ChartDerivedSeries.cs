@@ -0,0 +1,75 @@
+namespace VelloSharp.ChartEngine;
+
+public enum ChartDerivedOperator : uint
+{
+    MovingAverage = 0,
+    ExponentialMovingAverage = 1,
+    Derivative = 2,
+    Integral = 3,
+    MinMaxEnvelope = 4,
+    Bollinger = 5,
+}
+
+/// <summary>
+/// Describes a series the engine computes from the samples of another series. Appends to the
+/// source extend the result incrementally; inserts and updates recompute it.
+/// </summary>
+public sealed class ChartDerivedSeries
+{
+    public ChartDerivedSeries(uint sourceSeriesId, ChartDerivedOperator @operator)
+    {
+        SourceSeriesId = sourceSeriesId;
+        Operator = @operator;
+    }
+
+    public uint SourceSeriesId { get; }
+
+    public ChartDerivedOperator Operator { get; }
+
+    /// <summary>
+    /// Gets or sets the number of samples in the rolling window, or the period of the
+    /// exponential moving average. Ignored by the derivative and integral.
+    /// </summary>
+    public int Window { get; init; } = 20;
+
+    /// <summary>
+    /// Gets or sets how many standard deviations Bollinger bands sit from their average.
+    /// </summary>
+    public double Deviations { get; init; } = 2.0;
+
+    /// <summary>
+    /// Gets or sets the series receiving the lower edge of envelope and Bollinger bands; the
+    /// upper edge is written to the derived series itself. Required by those operators.
+    /// </summary>
+    public uint? LowerSeriesId { get; init; }
+
+    internal bool IsBand => Operator is ChartDerivedOperator.MinMaxEnvelope or ChartDerivedOperator.Bollinger;
+
+    internal VelloChartDerivedSeries ToNative(uint seriesId)
+    {
+        if (Window < 1)
+        {
+            throw new ArgumentOutOfRangeException(nameof(Window), Window, "Window must be at least one sample.");
+        }
+
+        if (!double.IsFinite(Deviations) || Deviations < 0)
+        {
+            throw new ArgumentOutOfRangeException(nameof(Deviations), Deviations, "Deviations must be finite and non-negative.");
+        }
+
+        if (IsBand && LowerSeriesId is null)
+        {
+            throw new InvalidOperationException($"The {Operator} operator requires a lower series id.");
+        }
+
+        return new VelloChartDerivedSeries
+        {
+            SeriesId = seriesId,
+            SourceSeriesId = SourceSeriesId,
+            Operator = (uint)Operator,
+            Window = (uint)Window,
+            Deviations = Deviations,
+            LowerSeriesId = LowerSeriesId ?? 0,
+        };
+    }
+}
@@ -7,6 +7,7 @@
 using VelloSharp;
 using VelloSharp.ChartData;
 using VelloSharp.ChartDiagnostics;
+using VelloSharp.ChartEngine.Annotations;
 using VelloSharp.ChartRuntime;
 
 namespace VelloSharp.ChartEngine;
@@ -59,6 +60,7 @@ public ChartEngine(ChartEngineOptions options)
         var nativeOptions = new VelloChartEngineOptions
         {
             VisibleDurationSeconds = Math.Max(options.VisibleDuration.TotalSeconds, 0.001),
+            RetentionDurationSeconds = options.RetentionDuration?.TotalSeconds ?? 0.0,
             VerticalPaddingRatio = options.VerticalPaddingRatio,
             StrokeWidth = NormalizeStrokeWidth(options.StrokeWidth),
             ShowAxes = options.ShowAxes ? 1u : 0u,
@@ -69,6 +71,21 @@ public ChartEngine(ChartEngineOptions options)
         {
             ThrowNativeFailure("Failed to create chart engine");
         }
+
+        if (options.Legend is not null)
+        {
+            SetLegend(options.Legend);
+        }
+
+        if (options.AxisLabels is not null)
+        {
+            SetAxisLabels(options.AxisLabels);
+        }
+
+        if (options.Theme is not null)
+        {
+            SetTheme(options.Theme);
+        }
     }
 
     public ChartEngineOptions Options => _options;
@@ -143,6 +160,51 @@ public void PumpData(ReadOnlySpan<ChartSamplePoint> samples)
         }
     }
 
+    /// <summary>
+    /// Publishes open/high/low/close bars for candlestick and OHLC series.
+    /// </summary>
+    public unsafe void PumpOhlcData(ReadOnlySpan<ChartOhlcSample> samples)
+    {
+        ThrowIfDisposed();
+
+        if (samples.IsEmpty)
+        {
+            return;
+        }
+
+        var rented = ArrayPool<VelloChartOhlcSample>.Shared.Rent(samples.Length);
+        try
+        {
+            for (var i = 0; i < samples.Length; i++)
+            {
+                var sample = samples[i];
+                rented[i] = new VelloChartOhlcSample
+                {
+                    SeriesId = unchecked((uint)sample.SeriesId),
+                    TimestampSeconds = sample.TimestampSeconds,
+                    Open = sample.Open,
+                    High = sample.High,
+                    Low = sample.Low,
+                    Close = sample.Close,
+                    Volume = sample.Volume ?? double.NaN,
+                };
+            }
+
+            fixed (VelloChartOhlcSample* ptr = rented)
+            {
+                var status = NativeMethods.vello_chart_engine_publish_ohlc_samples(
+                    _handle,
+                    ptr,
+                    (nuint)samples.Length);
+                ThrowOnStatus(status, "vello_chart_engine_publish_ohlc_samples");
+            }
+        }
+        finally
+        {
+            ArrayPool<VelloChartOhlcSample>.Shared.Return(rented);
+        }
+    }
+
     public void UpdatePalette(ReadOnlySpan<ChartColor> palette)
     {
         ThrowIfDisposed();
@@ -195,23 +257,45 @@ public unsafe void ConfigureSeries(ReadOnlySpan<ChartSeriesDefinition> definitio
             ? stackalloc VelloChartSeriesDefinition[count]
             : new VelloChartSeriesDefinition[count];
 
-        for (var i = 0; i < count; i++)
+        List<GCHandle>? pinnedStops = null;
+        try
         {
-            var definition = definitions[i] ?? throw new ArgumentNullException(
-                nameof(definitions),
-                "Series definitions cannot contain null entries.");
-            nativeDefinitions[i] = definition.ToNative();
-        }
+            for (var i = 0; i < count; i++)
+            {
+                var definition = definitions[i] ?? throw new ArgumentNullException(
+                    nameof(definitions),
+                    "Series definitions cannot contain null entries.");
+                nativeDefinitions[i] = definition.ToNative();
 
-        unsafe
+                if (definition.NativeColorStops() is { } stops)
+                {
+                    var handle = GCHandle.Alloc(stops, GCHandleType.Pinned);
+                    (pinnedStops ??= new List<GCHandle>()).Add(handle);
+                    nativeDefinitions[i].ColorStops = handle.AddrOfPinnedObject();
+                    nativeDefinitions[i].ColorStopCount = (nuint)stops.Length;
+                }
+            }
+
+            unsafe
+            {
+                fixed (VelloChartSeriesDefinition* ptr = nativeDefinitions)
+                {
+                    var status = NativeMethods.vello_chart_engine_set_series_definitions(
+                        _handle,
+                        ptr,
+                        (nuint)count);
+                    ThrowOnStatus(status, "vello_chart_engine_set_series_definitions");
+                }
+            }
+        }
+        finally
         {
-            fixed (VelloChartSeriesDefinition* ptr = nativeDefinitions)
+            if (pinnedStops is not null)
             {
-                var status = NativeMethods.vello_chart_engine_set_series_definitions(
-                    _handle,
-                    ptr,
-                    (nuint)count);
-                ThrowOnStatus(status, "vello_chart_engine_set_series_definitions");
+                foreach (var handle in pinnedStops)
+                {
+                    handle.Free();
+                }
             }
         }
     }
@@ -290,8 +374,10 @@ public unsafe void ConfigureComposition(ChartComposition? composition)
                     IdLength = (nuint)idBytes.Length,
                     HeightRatio = pane.NormalizedRatio,
                     ShareXAxisWithPrimary = pane.ShareXAxisWithPrimary ? 1u : 0u,
+                    ValueScale = (uint)pane.ValueScale,
                     SeriesIds = seriesHandle?.AddrOfPinnedObject() ?? nint.Zero,
                     SeriesIdCount = (nuint)seriesIds.Length,
+                    SymlogLinearThreshold = pane.SymlogLinearThreshold,
                 };
             }
 
@@ -479,6 +565,568 @@ public void Render(Scene scene, uint width, uint height)
         _diagnostics.Record(_lastStats);
     }
 
+    /// <summary>
+    /// Finds the sample drawn nearest to a point in the last rendered frame, or <c>null</c> when
+    /// nothing lies within <paramref name="radius"/> pixels.
+    /// </summary>
+    public ChartHitTestResult? HitTest(double x, double y, double radius)
+    {
+        ThrowIfDisposed();
+        var status = NativeMethods.vello_chart_engine_hit_test(_handle, x, y, radius, out var result);
+        ThrowOnStatus(status, "vello_chart_engine_hit_test");
+        if (result.Hit == 0)
+        {
+            return null;
+        }
+
+        return new ChartHitTestResult(
+            result.SeriesId,
+            (ChartSeriesKind)result.SeriesKind,
+            (int)result.PaneIndex,
+            result.TimestampSeconds,
+            result.Value,
+            result.X,
+            result.Y,
+            result.Distance);
+    }
+
+    /// <summary>
+    /// Configures where the native legend is drawn; <see cref="ChartLegendPlacement.None"/> hides it.
+    /// </summary>
+    public void SetLegend(ChartLegendOptions options)
+    {
+        ThrowIfDisposed();
+        ArgumentNullException.ThrowIfNull(options);
+        var native = new VelloChartLegendOptions
+        {
+            Placement = (uint)options.Placement,
+            FloatingPaneIndex = (uint)Math.Max(options.FloatingPaneIndex, 0),
+            FloatingCorner = (uint)options.FloatingCorner,
+        };
+        var status = NativeMethods.vello_chart_engine_set_legend(_handle, native);
+        ThrowOnStatus(status, "vello_chart_engine_set_legend");
+    }
+
+    /// <summary>
+    /// Sets the zone used to align and label time ticks and the time and value label formats.
+    /// </summary>
+    public unsafe void SetAxisLabels(ChartAxisLabelOptions options)
+    {
+        ThrowIfDisposed();
+        ArgumentNullException.ThrowIfNull(options);
+        if (options.UtcOffset.Ticks % TimeSpan.TicksPerMinute != 0 || options.UtcOffset.Duration() > TimeSpan.FromHours(18))
+        {
+            throw new ArgumentOutOfRangeException(nameof(options), "UTC offset must be whole minutes within 18 hours.");
+        }
+
+        var timeBytes = options.TimeFormat is { } timeFormat ? Encoding.UTF8.GetBytes(timeFormat) : null;
+        var valueBytes = options.ValueFormat is { } valueFormat ? Encoding.UTF8.GetBytes(valueFormat) : null;
+        var flags = options.ShowDateOnDayChange ? VelloChartAxisLabelFlags.DateOnDayChange : VelloChartAxisLabelFlags.None;
+
+        fixed (byte* timePtr = timeBytes)
+        fixed (byte* valuePtr = valueBytes)
+        {
+            var native = new VelloChartAxisLabelOptions
+            {
+                UtcOffsetMinutes = (int)(options.UtcOffset.Ticks / TimeSpan.TicksPerMinute),
+                Flags = (uint)flags,
+                TimeFormat = (nint)timePtr,
+                TimeFormatLength = (nuint)(timeBytes?.Length ?? 0),
+                ValueFormat = (nint)valuePtr,
+                ValueFormatLength = (nuint)(valueBytes?.Length ?? 0),
+            };
+            var status = NativeMethods.vello_chart_engine_set_axis_labels(_handle, native);
+            ThrowOnStatus(status, "vello_chart_engine_set_axis_labels");
+        }
+    }
+
+    /// <summary>
+    /// Restyles the background, grid, axes, labels, legend and tooltip. Cached series geometry is kept.
+    /// </summary>
+    public unsafe void SetTheme(ChartEngineTheme theme)
+    {
+        ThrowIfDisposed();
+        ArgumentNullException.ThrowIfNull(theme);
+        if (theme.MinorDivisions < 0)
+        {
+            throw new ArgumentOutOfRangeException(nameof(theme), "Minor divisions must not be negative.");
+        }
+
+        var flags = VelloChartThemeFlags.None;
+        if (theme.MajorGrid is not null)
+        {
+            flags |= VelloChartThemeFlags.MajorGrid;
+        }
+
+        if (theme.MinorGrid is not null)
+        {
+            flags |= VelloChartThemeFlags.MinorGrid;
+        }
+
+        if (theme.Axis is not null)
+        {
+            flags |= VelloChartThemeFlags.Axis;
+        }
+
+        var majorDashes = DashesOf(theme.MajorGrid);
+        var minorDashes = DashesOf(theme.MinorGrid);
+        var axisDashes = DashesOf(theme.Axis);
+        var crosshairDashes = DashesOf(theme.Crosshair);
+        var axisLabelFamily = FamilyOf(theme.AxisLabel);
+        var labelFamily = FamilyOf(theme.Label);
+
+        fixed (double* majorPtr = majorDashes)
+        fixed (double* minorPtr = minorDashes)
+        fixed (double* axisPtr = axisDashes)
+        fixed (double* crosshairPtr = crosshairDashes)
+        fixed (byte* axisLabelFamilyPtr = axisLabelFamily)
+        fixed (byte* labelFamilyPtr = labelFamily)
+        {
+            var callout = theme.Callout;
+            var native = new VelloChartTheme
+            {
+                Flags = flags,
+                BackgroundTop = theme.BackgroundTop.ToNative(),
+                BackgroundBottom = theme.BackgroundBottom.ToNative(),
+                FrameBorder = theme.FrameBorder.ToNative(),
+                PlotBackground = theme.PlotBackground.ToNative(),
+                PlotBorder = theme.PlotBorder.ToNative(),
+                MajorGrid = ToNative(theme.MajorGrid, majorPtr, majorDashes.Length),
+                MinorGrid = ToNative(theme.MinorGrid, minorPtr, minorDashes.Length),
+                MinorDivisions = (uint)theme.MinorDivisions,
+                Axis = ToNative(theme.Axis, axisPtr, axisDashes.Length),
+                MajorTickLength = theme.MajorTickLength,
+                MinorTickLength = theme.MinorTickLength,
+                AxisLabel = ToNativeText(theme.AxisLabel, axisLabelFamilyPtr, axisLabelFamily.Length),
+                Label = ToNativeText(theme.Label, labelFamilyPtr, labelFamily.Length),
+                Callout = new VelloChartCalloutStyle
+                {
+                    PaddingX = callout.PaddingX,
+                    PaddingY = callout.PaddingY,
+                    CornerRadius = callout.CornerRadius,
+                    OffsetX = callout.OffsetX,
+                    GapY = callout.GapY,
+                    FillOpacity = callout.FillOpacity,
+                    BorderOpacity = callout.BorderOpacity,
+                    BorderWidth = callout.BorderWidth,
+                },
+                LegendBackground = theme.LegendBackground.ToNative(),
+                TooltipBackground = theme.TooltipBackground.ToNative(),
+                PanelBorder = theme.PanelBorder.ToNative(),
+                Crosshair = ToNative(theme.Crosshair, crosshairPtr, crosshairDashes.Length),
+            };
+            var status = NativeMethods.vello_chart_engine_set_theme(_handle, &native);
+            ThrowOnStatus(status, "vello_chart_engine_set_theme");
+        }
+
+        static double[] DashesOf(ChartLineStyle? style) =>
+            style?.DashPattern is { Count: > 0 } pattern ? pattern.ToArray() : Array.Empty<double>();
+
+        static byte[] FamilyOf(ChartTextStyle style) =>
+            string.IsNullOrEmpty(style.FontFamily) ? Array.Empty<byte>() : Encoding.UTF8.GetBytes(style.FontFamily);
+
+        static VelloChartTextStyle ToNativeText(ChartTextStyle style, byte* family, int familyLength) =>
+            new()
+            {
+                FontFamily = (nint)family,
+                FontFamilyLength = (nuint)familyLength,
+                FontSize = style.FontSize,
+                Color = style.Color.ToNative(),
+            };
+
+        static VelloChartLineStyle ToNative(ChartLineStyle? style, double* dashes, int dashCount) =>
+            style is null
+                ? default
+                : new VelloChartLineStyle
+                {
+                    Color = style.Color.ToNative(),
+                    Width = style.Width,
+                    DashPattern = (nint)dashes,
+                    DashLength = (nuint)dashCount,
+                };
+    }
+
+    /// <summary>
+    /// Finds the series whose legend entry lies under a point in the last rendered frame.
+    /// </summary>
+    public uint? HitTestLegend(double x, double y)
+    {
+        ThrowIfDisposed();
+        var status = NativeMethods.vello_chart_engine_legend_hit_test(_handle, x, y, out var hit);
+        ThrowOnStatus(status, "vello_chart_engine_legend_hit_test");
+        return hit.Hit != 0 ? hit.SeriesId : null;
+    }
+
+    /// <summary>
+    /// Shows or hides a series. Hidden series keep ingesting samples but are excluded from rendering
+    /// and value-range computation.
+    /// </summary>
+    public void SetSeriesVisibility(uint seriesId, bool visible)
+    {
+        ThrowIfDisposed();
+        var status = NativeMethods.vello_chart_engine_set_series_visibility(_handle, seriesId, visible ? 1u : 0u);
+        ThrowOnStatus(status, "vello_chart_engine_set_series_visibility");
+    }
+
+    public bool IsSeriesVisible(uint seriesId)
+    {
+        ThrowIfDisposed();
+        var status = NativeMethods.vello_chart_engine_get_series_visibility(_handle, seriesId, out var visible);
+        ThrowOnStatus(status, "vello_chart_engine_get_series_visibility");
+        return visible != 0;
+    }
+
+    /// <summary>
+    /// Flips the visibility of the series whose legend entry lies under a point, returning the toggled
+    /// series identifier.
+    /// </summary>
+    public uint? ToggleSeriesAtLegend(double x, double y)
+    {
+        if (HitTestLegend(x, y) is not { } seriesId)
+        {
+            return null;
+        }
+
+        SetSeriesVisibility(seriesId, !IsSeriesVisible(seriesId));
+        return seriesId;
+    }
+
+    /// <summary>
+    /// Adds a native annotation under <paramref name="annotationId"/>. Horizontal and vertical lines,
+    /// value zones and time ranges are supported.
+    /// </summary>
+    public void AddAnnotation(uint annotationId, ChartAnnotation annotation)
+    {
+        ThrowIfDisposed();
+        ArgumentNullException.ThrowIfNull(annotation);
+        SubmitAnnotation(annotationId, annotation, update: false);
+    }
+
+    /// <summary>
+    /// Replaces the annotation previously added under <paramref name="annotationId"/>.
+    /// </summary>
+    public void UpdateAnnotation(uint annotationId, ChartAnnotation annotation)
+    {
+        ThrowIfDisposed();
+        ArgumentNullException.ThrowIfNull(annotation);
+        SubmitAnnotation(annotationId, annotation, update: true);
+    }
+
+    public void RemoveAnnotation(uint annotationId)
+    {
+        ThrowIfDisposed();
+        var status = NativeMethods.vello_chart_engine_remove_annotation(_handle, annotationId);
+        ThrowOnStatus(status, "vello_chart_engine_remove_annotation");
+    }
+
+    public void ClearAnnotations()
+    {
+        ThrowIfDisposed();
+        var status = NativeMethods.vello_chart_engine_clear_annotations(_handle);
+        ThrowOnStatus(status, "vello_chart_engine_clear_annotations");
+    }
+
+    /// <summary>
+    /// Computes <paramref name="seriesId"/> from the samples of another series, replacing any
+    /// samples it already holds. Samples published directly to derived series are ignored.
+    /// </summary>
+    public unsafe void AddDerivedSeries(uint seriesId, ChartDerivedSeries derived)
+    {
+        ThrowIfDisposed();
+        ArgumentNullException.ThrowIfNull(derived);
+        var native = derived.ToNative(seriesId);
+        var status = NativeMethods.vello_chart_engine_add_derived_series(_handle, &native);
+        ThrowOnStatus(status, "vello_chart_engine_add_derived_series");
+    }
+
+    /// <summary>
+    /// Stops computing <paramref name="seriesId"/>; the samples it already holds are kept.
+    /// </summary>
+    public void RemoveDerivedSeries(uint seriesId)
+    {
+        ThrowIfDisposed();
+        var status = NativeMethods.vello_chart_engine_remove_derived_series(_handle, seriesId);
+        ThrowOnStatus(status, "vello_chart_engine_remove_derived_series");
+    }
+
+    /// <summary>
+    /// Renders the current frame at <paramref name="width"/> by <paramref name="height"/> chart
+    /// pixels with the CPU renderer and returns it as PNG bytes. Axis labels drawn by a host
+    /// overlay are not part of the frame.
+    /// </summary>
+    public byte[] ExportPng(uint width, uint height, ChartExportOptions? options = null)
+        => Export(width, height, ChartExportFormat.Png, options);
+
+    /// <summary>
+    /// Renders the current frame to an SVG document of <paramref name="width"/> by
+    /// <paramref name="height"/> chart pixels.
+    /// </summary>
+    public string ExportSvg(uint width, uint height, ChartExportOptions? options = null)
+        => Encoding.UTF8.GetString(Export(width, height, ChartExportFormat.Svg, options));
+
+    private unsafe byte[] Export(uint width, uint height, ChartExportFormat format, ChartExportOptions? options)
+    {
+        ThrowIfDisposed();
+        var native = (options ?? new ChartExportOptions()).ToNative(format);
+
+        nint buffer = 0;
+        var status = NativeMethods.vello_chart_engine_export(_handle, width, height, &native, &buffer);
+        ThrowOnStatus(status, "vello_chart_engine_export");
+        return TakeBuffer(buffer);
+    }
+
+    /// <summary>
+    /// Captures series samples, definitions, overrides, the palette, composition panes and the
+    /// viewport as a versioned binary snapshot. Annotations and derived series configuration
+    /// are not included.
+    /// </summary>
+    public unsafe byte[] SaveSnapshot()
+    {
+        ThrowIfDisposed();
+        nint buffer = 0;
+        var status = NativeMethods.vello_chart_engine_snapshot(_handle, &buffer);
+        ThrowOnStatus(status, "vello_chart_engine_snapshot");
+        return TakeBuffer(buffer);
+    }
+
+    /// <summary>
+    /// Replaces the engine state with a snapshot from <see cref="SaveSnapshot"/>. Snapshots
+    /// written by an incompatible version are rejected and leave the engine unchanged.
+    /// </summary>
+    public unsafe void RestoreSnapshot(ReadOnlySpan<byte> snapshot)
+    {
+        ThrowIfDisposed();
+        fixed (byte* data = snapshot)
+        {
+            byte empty = 0;
+            var status = NativeMethods.vello_chart_engine_restore_snapshot(
+                _handle,
+                data is null ? &empty : data,
+                (nuint)snapshot.Length);
+            ThrowOnStatus(status, "vello_chart_engine_restore_snapshot");
+        }
+    }
+
+    private static unsafe byte[] TakeBuffer(nint buffer)
+    {
+        try
+        {
+            byte* data = null;
+            nuint length = 0;
+            var status = NativeMethods.vello_chart_engine_export_buffer_data(buffer, &data, &length);
+            ThrowOnStatus(status, "vello_chart_engine_export_buffer_data");
+            return new ReadOnlySpan<byte>(data, checked((int)length)).ToArray();
+        }
+        finally
+        {
+            NativeMethods.vello_chart_engine_export_buffer_destroy(buffer);
+        }
+    }
+
+    private unsafe void SubmitAnnotation(uint annotationId, ChartAnnotation annotation, bool update)
+    {
+        var native = new VelloChartAnnotation
+        {
+            AnnotationId = annotationId,
+            FillOpacity = 0.18f,
+        };
+        var color = annotation.Color;
+
+        switch (annotation)
+        {
+            case HorizontalLineAnnotation line:
+                native.Kind = (uint)VelloChartAnnotationKind.ValueLine;
+                native.Start = line.Value;
+                native.End = line.Value;
+                native.StrokeWidth = line.Thickness;
+                break;
+            case VerticalLineAnnotation marker:
+                native.Kind = (uint)VelloChartAnnotationKind.TimeMarker;
+                native.Start = marker.TimestampSeconds;
+                native.End = marker.TimestampSeconds;
+                native.StrokeWidth = marker.Thickness;
+                break;
+            case ValueZoneAnnotation zone:
+                native.Kind = (uint)VelloChartAnnotationKind.ValueBand;
+                native.Start = zone.MinValue;
+                native.End = zone.MaxValue;
+                native.StrokeWidth = zone.BorderThickness;
+                color ??= zone.Fill ?? zone.Border;
+                break;
+            case TimeRangeAnnotation range:
+                native.Kind = (uint)VelloChartAnnotationKind.TimeBand;
+                native.Start = range.StartSeconds;
+                native.End = range.EndSeconds;
+                native.StrokeWidth = range.BorderThickness;
+                color ??= range.Fill ?? range.Border;
+                break;
+            default:
+                throw new NotSupportedException($"Annotation kind '{annotation.Kind}' is not rendered natively.");
+        }
+
+        var flags = VelloChartAnnotationFlags.None;
+        if (color is { } resolved)
+        {
+            native.Color = resolved.ToNative();
+            flags |= VelloChartAnnotationFlags.ColorSet;
+        }
+
+        var labelBytes = annotation.Label is { } label ? Encoding.UTF8.GetBytes(label) : null;
+        if (labelBytes is not null)
+        {
+            flags |= VelloChartAnnotationFlags.LabelSet;
+        }
+
+        var paneBytes = annotation.TargetPaneId is { } paneId ? Encoding.UTF8.GetBytes(paneId) : null;
+        if (paneBytes is not null)
+        {
+            flags |= VelloChartAnnotationFlags.PaneSet;
+        }
+
+        native.Flags = (uint)flags;
+        var dashes = annotation.DashPattern is { Count: > 0 } pattern ? pattern.ToArray() : Array.Empty<double>();
+
+        fixed (double* dashPtr = dashes)
+        fixed (byte* labelPtr = labelBytes)
+        fixed (byte* panePtr = paneBytes)
+        {
+            native.DashPattern = (nint)dashPtr;
+            native.DashLength = (nuint)dashes.Length;
+            native.Label = (nint)labelPtr;
+            native.LabelLength = (nuint)(labelBytes?.Length ?? 0);
+            native.PaneId = (nint)panePtr;
+            native.PaneIdLength = (nuint)(paneBytes?.Length ?? 0);
+
+            if (update)
+            {
+                var status = NativeMethods.vello_chart_engine_update_annotation(_handle, &native);
+                ThrowOnStatus(status, "vello_chart_engine_update_annotation");
+            }
+            else
+            {
+                var status = NativeMethods.vello_chart_engine_add_annotation(_handle, &native);
+                ThrowOnStatus(status, "vello_chart_engine_add_annotation");
+            }
+        }
+    }
+
+    /// <summary>
+    /// Gets the time range the engine is currently drawing.
+    /// </summary>
+    public ChartViewport GetViewport()
+    {
+        ThrowIfDisposed();
+        var status = NativeMethods.vello_chart_engine_get_viewport(_handle, out var viewport);
+        ThrowOnStatus(status, "vello_chart_engine_get_viewport");
+        return new ChartViewport(viewport.RangeStart, viewport.RangeEnd, viewport.FollowLive != 0);
+    }
+
+    /// <summary>
+    /// Shows a fixed time range and stops following live samples.
+    /// </summary>
+    public void SetVisibleRange(double rangeStartSeconds, double rangeEndSeconds)
+    {
+        ThrowIfDisposed();
+        var status = NativeMethods.vello_chart_engine_set_visible_range(_handle, rangeStartSeconds, rangeEndSeconds);
+        ThrowOnStatus(status, "vello_chart_engine_set_visible_range");
+    }
+
+    /// <summary>
+    /// Resumes or pauses trailing the newest sample. Pausing freezes the current range.
+    /// </summary>
+    public void SetFollowLive(bool followLive)
+    {
+        ThrowIfDisposed();
+        var status = NativeMethods.vello_chart_engine_set_follow_live(_handle, followLive ? 1u : 0u);
+        ThrowOnStatus(status, "vello_chart_engine_set_follow_live");
+    }
+
+    /// <summary>
+    /// Scales the visible duration by <paramref name="factor"/> around <paramref name="anchorSeconds"/>;
+    /// factors below one zoom in. Leaves live follow mode.
+    /// </summary>
+    public void Zoom(double factor, double anchorSeconds)
+    {
+        ThrowIfDisposed();
+        var status = NativeMethods.vello_chart_engine_zoom(_handle, factor, anchorSeconds);
+        ThrowOnStatus(status, "vello_chart_engine_zoom");
+    }
+
+    /// <summary>
+    /// Shifts the visible range by <paramref name="delta"/>; negative values scroll back in time.
+    /// Leaves live follow mode.
+    /// </summary>
+    public void Pan(TimeSpan delta)
+    {
+        ThrowIfDisposed();
+        var status = NativeMethods.vello_chart_engine_pan(_handle, delta.TotalSeconds);
+        ThrowOnStatus(status, "vello_chart_engine_pan");
+    }
+
+    /// <summary>
+    /// Changes how much history is kept in memory; <c>null</c> keeps only the visible duration.
+    /// </summary>
+    public void SetRetentionDuration(TimeSpan? retention)
+    {
+        ThrowIfDisposed();
+        var status = NativeMethods.vello_chart_engine_set_retention_duration(
+            _handle,
+            retention?.TotalSeconds ?? 0.0);
+        ThrowOnStatus(status, "vello_chart_engine_set_retention_duration");
+    }
+
+    /// <summary>
+    /// Shows the crosshair and value tooltip for a pointer at <paramref name="x"/>, <paramref name="y"/>.
+    /// Only the overlay is re-encoded on the next render; cached series scenes are reused.
+    /// </summary>
+    public void SetCursor(double x, double y)
+    {
+        ThrowIfDisposed();
+        var status = NativeMethods.vello_chart_engine_set_cursor(_handle, x, y);
+        ThrowOnStatus(status, "vello_chart_engine_set_cursor");
+    }
+
+    /// <summary>
+    /// Hides the crosshair and tooltip overlay.
+    /// </summary>
+    public void ClearCursor()
+    {
+        ThrowIfDisposed();
+        var status = NativeMethods.vello_chart_engine_clear_cursor(_handle);
+        ThrowOnStatus(status, "vello_chart_engine_clear_cursor");
+    }
+
+    /// <summary>
+    /// Attaches a data bus whose queued batches are ingested at the start of every render.
+    /// </summary>
+    public void AttachDataBus(ChartEngineDataBus bus)
+    {
+        ThrowIfDisposed();
+        ArgumentNullException.ThrowIfNull(bus);
+        var busHandle = bus.Handle;
+        bool added = false;
+        try
+        {
+            busHandle.DangerousAddRef(ref added);
+            var status = NativeMethods.vello_chart_engine_attach_data_bus(_handle, busHandle.DangerousGetHandle());
+            ThrowOnStatus(status, "vello_chart_engine_attach_data_bus");
+        }
+        finally
+        {
+            if (added)
+            {
+                busHandle.DangerousRelease();
+            }
+        }
+    }
+
+    public void DetachDataBus()
+    {
+        ThrowIfDisposed();
+        var status = NativeMethods.vello_chart_engine_detach_data_bus(_handle);
+        ThrowOnStatus(status, "vello_chart_engine_detach_data_bus");
+    }
+
     public ChartFrameMetadata GetFrameMetadata()
     {
         ThrowIfDisposed();
@@ -555,7 +1203,7 @@ private static nint CreateWithPalette(VelloChartEngineOptions nativeOptions, IRe
         }
     }
 
-    private static void ThrowOnStatus(VelloChartEngineStatus status, string operation)
+    internal static void ThrowOnStatus(VelloChartEngineStatus status, string operation)
     {
         if (status == VelloChartEngineStatus.Success)
         {
@@ -0,0 +1,183 @@
+using System;
+using System.Buffers;
+using System.Runtime.InteropServices;
+
+namespace VelloSharp.ChartEngine;
+
+/// <summary>
+/// Bounded native ingest queue that producer threads can push into without touching the engine.
+/// </summary>
+/// <remarks>
+/// Every native call holds a reference on the underlying handle, so disposing the bus while a
+/// producer is mid-push defers the native release until that push returns.
+/// </remarks>
+public sealed class ChartEngineDataBus : IDisposable
+{
+    private const int StackThreshold = 256;
+
+    private readonly ChartDataBusHandle _handle;
+
+    public ChartEngineDataBus(int capacity)
+    {
+        if (capacity <= 0)
+        {
+            throw new ArgumentOutOfRangeException(nameof(capacity), capacity, "Capacity must be greater than zero.");
+        }
+
+        _handle = ChartDataBusHandle.Create((nuint)capacity);
+    }
+
+    internal ChartDataBusHandle Handle
+    {
+        get
+        {
+            ObjectDisposedException.ThrowIf(_handle.IsClosed, this);
+            return _handle;
+        }
+    }
+
+    /// <summary>
+    /// Queues a batch of points for a series. Returns <c>false</c> when the bus is full and the batch was dropped.
+    /// </summary>
+    public bool TryPush(uint seriesId, ReadOnlySpan<ChartDataPoint> points)
+    {
+        var handle = Handle;
+        if (points.IsEmpty)
+        {
+            return true;
+        }
+
+        if (points.Length <= StackThreshold)
+        {
+            Span<VelloChartDataPoint> span = stackalloc VelloChartDataPoint[points.Length];
+            return Push(handle, seriesId, span, points);
+        }
+
+        var rented = ArrayPool<VelloChartDataPoint>.Shared.Rent(points.Length);
+        try
+        {
+            return Push(handle, seriesId, rented.AsSpan(0, points.Length), points);
+        }
+        finally
+        {
+            ArrayPool<VelloChartDataPoint>.Shared.Return(rented);
+        }
+    }
+
+    public ChartDataBusStats GetStats()
+    {
+        var handle = Handle;
+        bool added = false;
+        try
+        {
+            handle.DangerousAddRef(ref added);
+            var status = NativeMethods.vello_chart_engine_data_bus_stats(handle.DangerousGetHandle(), out var stats);
+            ChartEngine.ThrowOnStatus(status, "vello_chart_engine_data_bus_stats");
+            return new ChartDataBusStats(
+                (long)stats.Capacity,
+                (long)stats.Queued,
+                (long)stats.Pushed,
+                (long)stats.Dropped,
+                (long)stats.DroppedPoints,
+                (long)stats.Drained);
+        }
+        finally
+        {
+            if (added)
+            {
+                handle.DangerousRelease();
+            }
+        }
+    }
+
+    public void Dispose()
+    {
+        _handle.Dispose();
+    }
+
+    private static unsafe bool Push(
+        ChartDataBusHandle handle,
+        uint seriesId,
+        Span<VelloChartDataPoint> destination,
+        ReadOnlySpan<ChartDataPoint> points)
+    {
+        for (var i = 0; i < points.Length; i++)
+        {
+            destination[i] = new VelloChartDataPoint(points[i].TimestampNs, points[i].Value);
+        }
+
+        bool added = false;
+        try
+        {
+            handle.DangerousAddRef(ref added);
+            fixed (VelloChartDataPoint* ptr = destination)
+            {
+                var status = NativeMethods.vello_chart_engine_data_bus_push(
+                    handle.DangerousGetHandle(),
+                    seriesId,
+                    ptr,
+                    (nuint)points.Length);
+                if (status == VelloChartEngineStatus.QueueFull)
+                {
+                    return false;
+                }
+
+                ChartEngine.ThrowOnStatus(status, "vello_chart_engine_data_bus_push");
+                return true;
+            }
+        }
+        finally
+        {
+            if (added)
+            {
+                handle.DangerousRelease();
+            }
+        }
+    }
+}
+
+internal sealed class ChartDataBusHandle : SafeHandle
+{
+    private ChartDataBusHandle()
+        : base(IntPtr.Zero, ownsHandle: true)
+    {
+    }
+
+    public override bool IsInvalid => handle == IntPtr.Zero;
+
+    protected override bool ReleaseHandle()
+    {
+        NativeMethods.vello_chart_engine_data_bus_destroy(handle);
+        SetHandle(IntPtr.Zero);
+        return true;
+    }
+
+    public static ChartDataBusHandle Create(nuint capacity)
+    {
+        var ptr = NativeMethods.vello_chart_engine_data_bus_create(capacity);
+        if (ptr == nint.Zero)
+        {
+            throw new InvalidOperationException("Failed to create chart data bus.");
+        }
+
+        var handle = new ChartDataBusHandle();
+        handle.SetHandle(ptr);
+        return handle;
+    }
+}
+
+/// <summary>
+/// A timestamped value pushed through a <see cref="ChartEngineDataBus"/>.
+/// </summary>
+public readonly record struct ChartDataPoint(ulong TimestampNs, double Value);
+
+/// <summary>
+/// Backpressure counters reported by a <see cref="ChartEngineDataBus"/>.
+/// </summary>
+public readonly record struct ChartDataBusStats(
+    long Capacity,
+    long Queued,
+    long Pushed,
+    long Dropped,
+    long DroppedPoints,
+    long Drained);
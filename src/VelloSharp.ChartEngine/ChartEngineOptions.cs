@@ -21,6 +21,12 @@ public sealed class ChartEngineOptions
 
     public TimeSpan VisibleDuration { get; init; } = TimeSpan.FromMinutes(2);
 
+    /// <summary>
+    /// History kept in memory for panning and zooming back in time. When <c>null</c> only
+    /// <see cref="VisibleDuration"/> is retained; shorter values are raised to it.
+    /// </summary>
+    public TimeSpan? RetentionDuration { get; init; }
+
     public double VerticalPaddingRatio { get; init; } = 0.08;
 
     public double StrokeWidth { get; init; } = 1.5;
@@ -29,6 +35,21 @@ public sealed class ChartEngineOptions
 
     public IReadOnlyList<ChartColor>? Palette { get; init; }
 
+    /// <summary>
+    /// Initial legend placement; the legend is hidden when <c>null</c>.
+    /// </summary>
+    public ChartLegendOptions? Legend { get; init; }
+
+    /// <summary>
+    /// Initial axis label zone and formats; labels use UTC and automatic formats when <c>null</c>.
+    /// </summary>
+    public ChartAxisLabelOptions? AxisLabels { get; init; }
+
+    /// <summary>
+    /// Initial chrome styling; the built-in look is used when <c>null</c>.
+    /// </summary>
+    public ChartEngineTheme? Theme { get; init; }
+
     /// <summary>
     /// When provided, the scheduler uses this external tick source instead of the default background driver.
     /// </summary>
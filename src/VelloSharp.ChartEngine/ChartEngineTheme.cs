@@ -0,0 +1,130 @@
+using System.Collections.Generic;
+
+namespace VelloSharp.ChartEngine;
+
+/// <summary>
+/// Stroke for grid lines, axes and the crosshair.
+/// </summary>
+public sealed class ChartLineStyle
+{
+    public ChartColor Color { get; init; }
+
+    public double Width { get; init; } = 1.0;
+
+    /// <summary>
+    /// Alternating dash and gap lengths; <c>null</c> or empty draws a solid line.
+    /// </summary>
+    public IReadOnlyList<double>? DashPattern { get; init; }
+}
+
+/// <summary>
+/// Family, size and colour of label text.
+/// </summary>
+public sealed class ChartTextStyle
+{
+    /// <summary>
+    /// Family tried before the engine's label font; <c>null</c> or empty uses the label font only.
+    /// </summary>
+    public string? FontFamily { get; init; }
+
+    public float FontSize { get; init; } = 14f;
+
+    public ChartColor Color { get; init; }
+}
+
+/// <summary>
+/// Geometry of the rounded callouts used for series labels and the pointer tooltip.
+/// </summary>
+public sealed class ChartCalloutStyle
+{
+    public double PaddingX { get; init; } = 6.0;
+
+    public double PaddingY { get; init; } = 4.0;
+
+    public double CornerRadius { get; init; } = 6.0;
+
+    public double OffsetX { get; init; } = 12.0;
+
+    public double GapY { get; init; } = 8.0;
+
+    /// <summary>
+    /// Opacity of the series colour filling a series label.
+    /// </summary>
+    public float FillOpacity { get; init; } = 0.28f;
+
+    /// <summary>
+    /// Opacity of the series colour outlining a series label.
+    /// </summary>
+    public float BorderOpacity { get; init; } = 0.6f;
+
+    public double BorderWidth { get; init; } = 1.0;
+}
+
+/// <summary>
+/// Colours, strokes and text styles for the chrome drawn by <see cref="ChartEngine"/>.
+/// The defaults reproduce the built-in look.
+/// </summary>
+public sealed class ChartEngineTheme
+{
+    private static readonly ChartColor AxisLabelColor = new(0xA8, 0xB7, 0xD6);
+
+    public static ChartEngineTheme Default { get; } = new();
+
+    public ChartColor BackgroundTop { get; init; } = new(0x10, 0x18, 0x28, 0xF0);
+
+    public ChartColor BackgroundBottom { get; init; } = new(0x0B, 0x11, 0x1C, 0xE6);
+
+    public ChartColor FrameBorder { get; init; } = new(0x2A, 0x34, 0x4A);
+
+    public ChartColor PlotBackground { get; init; } = new(0x12, 0x1B, 0x2A, 0xC8);
+
+    public ChartColor PlotBorder { get; init; } = new(0x24, 0x30, 0x45);
+
+    /// <summary>
+    /// Grid lines at labelled ticks; when <c>null</c> the host draws the grid.
+    /// </summary>
+    public ChartLineStyle? MajorGrid { get; init; }
+
+    /// <summary>
+    /// Grid lines between labelled ticks; none are drawn when <c>null</c>.
+    /// </summary>
+    public ChartLineStyle? MinorGrid { get; init; }
+
+    /// <summary>
+    /// Intervals each major step is split into by minor grid lines and ticks.
+    /// </summary>
+    public int MinorDivisions { get; init; }
+
+    /// <summary>
+    /// Axis lines and tick labels; when <c>null</c> the host draws the axes.
+    /// </summary>
+    public ChartLineStyle? Axis { get; init; }
+
+    public double MajorTickLength { get; init; }
+
+    public double MinorTickLength { get; init; }
+
+    public ChartTextStyle AxisLabel { get; init; } = new() { FontSize = 12f, Color = AxisLabelColor };
+
+    /// <summary>
+    /// Series labels, legend entries, tooltip rows and annotation labels.
+    /// </summary>
+    public ChartTextStyle Label { get; init; } = new() { FontSize = 14f, Color = new(0xF5, 0xF9, 0xFF) };
+
+    public ChartCalloutStyle Callout { get; init; } = new();
+
+    public ChartColor LegendBackground { get; init; } = new(0x10, 0x18, 0x28, 0xCC);
+
+    public ChartColor TooltipBackground { get; init; } = new(0x10, 0x18, 0x28, 0xE6);
+
+    /// <summary>
+    /// Outline of the floating legend and the tooltip.
+    /// </summary>
+    public ChartColor PanelBorder { get; init; } = new(0x45, 0x53, 0x72);
+
+    public ChartLineStyle Crosshair { get; init; } = new()
+    {
+        Color = AxisLabelColor with { A = 0xB3 },
+        DashPattern = new[] { 4.0, 3.0 },
+    };
+}
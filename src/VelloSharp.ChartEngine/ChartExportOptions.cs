@@ -0,0 +1,42 @@
+namespace VelloSharp.ChartEngine;
+
+public enum ChartExportFormat : uint
+{
+    Png = 0,
+    Svg = 1,
+}
+
+/// <summary>
+/// Options for rendering a chart frame to an image without a GPU.
+/// </summary>
+public sealed class ChartExportOptions
+{
+    /// <summary>
+    /// Gets or sets the device pixels per chart pixel. PNG output is <c>width * Scale</c> by
+    /// <c>height * Scale</c> pixels and records <c>96 * Scale</c> DPI; SVG output keeps the chart
+    /// size and is only scaled through its width and height attributes.
+    /// </summary>
+    public double Scale { get; init; } = 1.0;
+
+    /// <summary>
+    /// Gets or sets the colour painted behind the chart, or <c>null</c> for a transparent
+    /// background.
+    /// </summary>
+    public ChartColor? Background { get; init; }
+
+    internal VelloChartExportOptions ToNative(ChartExportFormat format)
+    {
+        if (!double.IsFinite(Scale) || Scale <= 0)
+        {
+            throw new ArgumentOutOfRangeException(nameof(Scale), Scale, "Scale must be finite and positive.");
+        }
+
+        return new VelloChartExportOptions
+        {
+            Format = (uint)format,
+            Flags = Background is null ? VelloChartExportFlags.None : VelloChartExportFlags.Background,
+            Scale = Scale,
+            Background = Background?.ToNative() ?? default,
+        };
+    }
+}
@@ -78,7 +78,8 @@ public sealed class ChartFrameMetadata
     public double PlotHeight { get; }
 
     /// <summary>
-    /// Gets tick metadata for the horizontal axis.
+    /// Gets tick metadata for the horizontal axis. With <see cref="ChartAxisLabelOptions.ShowDateOnDayChange"/>
+    /// the first label of each day carries the date on a second line.
     /// </summary>
     public IReadOnlyList<AxisTickMetadata> TimeTicks { get; }
 
@@ -147,17 +148,11 @@ internal static unsafe ChartFrameMetadata FromNative(in VelloChartFrameMetadata
                 var pane = panePtr[i];
                 var id = Marshal.PtrToStringUTF8(pane.Id, (int)pane.IdLength) ?? string.Empty;
                 var share = pane.ShareXAxisWithPrimary != 0;
-                var paneTicks = new AxisTickMetadata[(int)pane.ValueTickCount];
-                if (pane.ValueTickCount > 0 && pane.ValueTicks != IntPtr.Zero)
-                {
-                    var paneTickPtr = (VelloChartAxisTickMetadata*)pane.ValueTicks;
-                    for (var j = 0; j < paneTicks.Length; j++)
-                    {
-                        var tick = paneTickPtr[j];
-                        var label = Marshal.PtrToStringUTF8(tick.Label, (int)tick.LabelLength) ?? string.Empty;
-                        paneTicks[j] = new AxisTickMetadata(tick.Position, label);
-                    }
-                }
+                var paneTicks = ReadTicks(pane.ValueTicks, pane.ValueTickCount);
+                var hasRightAxis = pane.HasRightAxis != 0;
+                var rightTicks = hasRightAxis
+                    ? ReadTicks(pane.RightValueTicks, pane.RightValueTickCount)
+                    : null;
 
                 var dirtyTimeMin = double.IsNaN(pane.DirtyTimeMin) ? (double?)null : pane.DirtyTimeMin;
                 var dirtyTimeMax = double.IsNaN(pane.DirtyTimeMax) ? (double?)null : pane.DirtyTimeMax;
@@ -177,7 +172,12 @@ internal static unsafe ChartFrameMetadata FromNative(in VelloChartFrameMetadata
                     dirtyTimeMax,
                     dirtyValueMin,
                     dirtyValueMax,
-                    paneTicks);
+                    paneTicks,
+                    hasRightAxis ? pane.RightValueMin : null,
+                    hasRightAxis ? pane.RightValueMax : null,
+                    rightTicks,
+                    (ChartValueScale)pane.ValueScale,
+                    pane.ValueScale == (uint)ChartValueScale.SymLog ? pane.ValueScaleThreshold : null);
             }
         }
 
@@ -206,7 +206,11 @@ internal static unsafe ChartFrameMetadata FromNative(in VelloChartFrameMetadata
                     (int)item.PaneIndex,
                     item.BandLowerSeriesId,
                     item.HeatmapBucketIndex,
-                    item.HeatmapBucketCount);
+                    item.HeatmapBucketCount,
+                    item.Visible != 0,
+                    item.ValueAxis == (uint)ChartValueAxis.Right ? ChartValueAxis.Right : ChartValueAxis.Left,
+                    item.ColorDomainMin,
+                    item.ColorDomainMax);
             }
         }
 
@@ -225,6 +229,23 @@ internal static unsafe ChartFrameMetadata FromNative(in VelloChartFrameMetadata
             panes);
     }
 
+    private static unsafe AxisTickMetadata[] ReadTicks(nint ticks, nuint count)
+    {
+        var result = new AxisTickMetadata[(int)count];
+        if (count > 0 && ticks != IntPtr.Zero)
+        {
+            var tickPtr = (VelloChartAxisTickMetadata*)ticks;
+            for (var i = 0; i < result.Length; i++)
+            {
+                var tick = tickPtr[i];
+                var label = Marshal.PtrToStringUTF8(tick.Label, (int)tick.LabelLength) ?? string.Empty;
+                result[i] = new AxisTickMetadata(tick.Position, label);
+            }
+        }
+
+        return result;
+    }
+
     internal void SetCursorOverlay(ChartCursorOverlay? overlay) => CursorOverlay = overlay;
 
     internal void SetAnnotationOverlays(IReadOnlyList<ChartAnnotationOverlay> overlays)
@@ -248,7 +269,11 @@ internal void SetStreamingOverlays(IReadOnlyList<ChartStreamingOverlay> overlays
         int PaneIndex,
         uint BandLowerSeriesId,
         uint HeatmapBucketIndex,
-        uint HeatmapBucketCount);
+        uint HeatmapBucketCount,
+        bool IsVisible = true,
+        ChartValueAxis ValueAxis = ChartValueAxis.Left,
+        double ColorDomainMin = double.NaN,
+        double ColorDomainMax = double.NaN);
 
     public readonly record struct PaneMetadata(
         string Id,
@@ -263,7 +288,18 @@ internal void SetStreamingOverlays(IReadOnlyList<ChartStreamingOverlay> overlays
         double? DirtyTimeMax,
         double? DirtyValueMin,
         double? DirtyValueMax,
-        IReadOnlyList<AxisTickMetadata> ValueTicks);
+        IReadOnlyList<AxisTickMetadata> ValueTicks,
+        double? RightValueMin = null,
+        double? RightValueMax = null,
+        IReadOnlyList<AxisTickMetadata>? RightValueTicks = null,
+        ChartValueScale ValueScale = ChartValueScale.Linear,
+        double? SymlogLinearThreshold = null)
+    {
+        /// <summary>
+        /// Gets a value indicating whether any series in the pane is bound to the right value axis.
+        /// </summary>
+        public bool HasRightAxis => RightValueTicks is not null;
+    }
 
     public readonly record struct ChartCursorOverlay(
         double TimestampSeconds,
@@ -0,0 +1,17 @@
+namespace VelloSharp.ChartEngine;
+
+/// <summary>
+/// Sample located under a screen position by <see cref="ChartEngine.HitTest"/>.
+/// </summary>
+/// <param name="X">Horizontal screen position of the sample (cell centre for heatmaps).</param>
+/// <param name="Y">Vertical screen position of the sample.</param>
+/// <param name="Distance">Distance from the query point to the drawn geometry; zero when inside it.</param>
+public readonly record struct ChartHitTestResult(
+    uint SeriesId,
+    ChartSeriesKind SeriesKind,
+    int PaneIndex,
+    double TimestampSeconds,
+    double Value,
+    double X,
+    double Y,
+    double Distance);
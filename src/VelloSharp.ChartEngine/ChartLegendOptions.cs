@@ -0,0 +1,34 @@
+namespace VelloSharp.ChartEngine;
+
+public enum ChartLegendPlacement : uint
+{
+    None = 0,
+    Top = 1,
+    Bottom = 2,
+    Left = 3,
+    Right = 4,
+    Floating = 5,
+}
+
+public enum ChartLegendCorner : uint
+{
+    TopLeft = 0,
+    TopRight = 1,
+    BottomLeft = 2,
+    BottomRight = 3,
+}
+
+/// <summary>
+/// Placement of the legend drawn by <see cref="ChartEngine"/>.
+/// </summary>
+public sealed class ChartLegendOptions
+{
+    public ChartLegendPlacement Placement { get; init; } = ChartLegendPlacement.None;
+
+    /// <summary>
+    /// Pane a floating legend is drawn over. Indices past the last pane use the last pane.
+    /// </summary>
+    public int FloatingPaneIndex { get; init; }
+
+    public ChartLegendCorner FloatingCorner { get; init; } = ChartLegendCorner.TopLeft;
+}
@@ -1,4 +1,5 @@
 using System;
+using System.Collections.Generic;
 using System.Runtime.CompilerServices;
 
 namespace VelloSharp.ChartEngine;
@@ -14,8 +15,95 @@ public enum ChartSeriesKind : uint
     Bar = 3,
     Band = 4,
     Heatmap = 5,
+    Candlestick = 6,
+    Ohlc = 7,
 }
 
+/// <summary>
+/// Selects the pane value axis a series is scaled against.
+/// </summary>
+public enum ChartValueAxis : uint
+{
+    Left = 0,
+    Right = 1,
+}
+
+/// <summary>
+/// Selects how dense line and area series are reduced before rendering.
+/// </summary>
+public enum ChartSeriesDecimation : uint
+{
+    None = 0,
+    MinMax = 1,
+    Lttb = 2,
+}
+
+/// <summary>
+/// Selects how members of a stack group are accumulated.
+/// </summary>
+public enum ChartStackMode : uint
+{
+    /// <summary>Values are summed as-is.</summary>
+    Normal = 0,
+
+    /// <summary>Every timestamp is rescaled so the group totals 100.</summary>
+    Percent = 1,
+}
+
+/// <summary>
+/// Selects how gaps in line, area and band series are drawn.
+/// </summary>
+public enum ChartGapStyle : uint
+{
+    /// <summary>Nothing is drawn across the gap.</summary>
+    Break = 0,
+
+    /// <summary>The segments either side of the gap are joined by a faded dashed line.</summary>
+    Dashed = 1,
+}
+
+/// <summary>
+/// Selects the path shape drawn between consecutive samples of line, area and band series.
+/// </summary>
+public enum ChartInterpolation : uint
+{
+    /// <summary>Straight segments between samples.</summary>
+    Linear = 0,
+
+    /// <summary>The value changes at the previous sample and holds until the next one.</summary>
+    StepBefore = 1,
+
+    /// <summary>The value holds from a sample until the next one, then changes.</summary>
+    StepAfter = 2,
+
+    /// <summary>The value changes halfway between samples.</summary>
+    StepMiddle = 3,
+
+    /// <summary>Monotone cubic spline that never overshoots the samples.</summary>
+    Monotone = 4,
+}
+
+/// <summary>
+/// Built-in heatmap colour maps. The first five are perceptually ordered sequential maps; the rest
+/// diverge from a neutral midpoint.
+/// </summary>
+public enum ChartColorMap : uint
+{
+    Viridis = 0,
+    Magma = 1,
+    Inferno = 2,
+    Plasma = 3,
+    Turbo = 4,
+    RedBlue = 5,
+    PurpleGreen = 6,
+    CoolWarm = 7,
+}
+
+/// <summary>
+/// A gradient stop of a custom heatmap colour scale; <paramref name="Offset"/> lies in 0..1.
+/// </summary>
+public readonly record struct ChartColorStop(float Offset, ChartColor Color);
+
 [Flags]
 internal enum SeriesDefinitionFlags : uint
 {
@@ -27,6 +115,17 @@ internal enum SeriesDefinitionFlags : uint
     BarWidthSet = 1 << 4,
     BandLowerSet = 1 << 5,
     HeatmapBucketsSet = 1 << 6,
+    DecimationSet = 1 << 7,
+    ValueAxisSet = 1 << 8,
+    UpColorSet = 1 << 9,
+    DownColorSet = 1 << 10,
+    StackSet = 1 << 11,
+    MaxGapSet = 1 << 12,
+    GapStyleSet = 1 << 13,
+    InterpolationSet = 1 << 14,
+    ColorMapSet = 1 << 15,
+    ColorDomainMinSet = 1 << 16,
+    ColorDomainMaxSet = 1 << 17,
 }
 
 /// <summary>
@@ -62,6 +161,45 @@ public abstract record ChartSeriesDefinition(uint SeriesId)
     /// </summary>
     public double? BarWidthSeconds { get; init; }
 
+    /// <summary>
+    /// Gets or sets the level-of-detail reduction applied to line and area series.
+    /// </summary>
+    public ChartSeriesDecimation? Decimation { get; init; }
+
+    /// <summary>
+    /// Gets or sets the pane axis the series is scaled against. Series on the right axis share an
+    /// independent auto-range whose ticks are reported separately in the pane metadata.
+    /// </summary>
+    public ChartValueAxis? ValueAxis { get; init; }
+
+    /// <summary>
+    /// Gets or sets the stack group of a line, area or bar series. Visible members of the same group
+    /// within a pane are accumulated per timestamp in pane order; misaligned timestamps are interpolated.
+    /// </summary>
+    public uint? StackGroup { get; init; }
+
+    /// <summary>
+    /// Gets or sets how the stack group is accumulated. Only applies when <see cref="StackGroup"/> is set.
+    /// </summary>
+    public ChartStackMode StackMode { get; init; } = ChartStackMode.Normal;
+
+    /// <summary>
+    /// Gets or sets the longest interval, in seconds, between consecutive samples that line, area and
+    /// band series still connect. Samples with a non-finite value always break the series.
+    /// </summary>
+    public double? MaxGapSeconds { get; init; }
+
+    /// <summary>
+    /// Gets or sets how gaps are drawn. When omitted gaps are left empty.
+    /// </summary>
+    public ChartGapStyle? GapStyle { get; init; }
+
+    /// <summary>
+    /// Gets or sets how line, area and band series connect consecutive samples. When omitted samples
+    /// are joined by straight segments.
+    /// </summary>
+    public ChartInterpolation? Interpolation { get; init; }
+
     internal abstract ChartSeriesKind Kind { get; }
 
     protected virtual void Validate()
@@ -102,6 +240,14 @@ protected virtual void Validate()
                 throw new ArgumentOutOfRangeException(nameof(BarWidthSeconds), BarWidthSeconds, "Bar width must be positive and finite.");
             }
         }
+
+        if (MaxGapSeconds is { } maxGap)
+        {
+            if (!double.IsFinite(maxGap) || maxGap <= 0)
+            {
+                throw new ArgumentOutOfRangeException(nameof(MaxGapSeconds), MaxGapSeconds, "Max gap must be positive and finite.");
+            }
+        }
     }
 
     internal VelloChartSeriesDefinition ToNative()
@@ -151,6 +297,43 @@ internal VelloChartSeriesDefinition ToNative()
             definition.Flags |= SeriesDefinitionFlags.BarWidthSet;
         }
 
+        if (Decimation is { } decimation)
+        {
+            definition.DecimationMode = (uint)decimation;
+            definition.Flags |= SeriesDefinitionFlags.DecimationSet;
+        }
+
+        if (ValueAxis is { } valueAxis)
+        {
+            definition.ValueAxis = (uint)valueAxis;
+            definition.Flags |= SeriesDefinitionFlags.ValueAxisSet;
+        }
+
+        if (StackGroup is { } stackGroup)
+        {
+            definition.StackGroup = stackGroup;
+            definition.StackMode = (uint)StackMode;
+            definition.Flags |= SeriesDefinitionFlags.StackSet;
+        }
+
+        if (MaxGapSeconds is { } maxGapSeconds)
+        {
+            definition.MaxGapSeconds = maxGapSeconds;
+            definition.Flags |= SeriesDefinitionFlags.MaxGapSet;
+        }
+
+        if (GapStyle is { } gapStyle)
+        {
+            definition.GapStyle = (uint)gapStyle;
+            definition.Flags |= SeriesDefinitionFlags.GapStyleSet;
+        }
+
+        if (Interpolation is { } interpolation)
+        {
+            definition.Interpolation = (uint)interpolation;
+            definition.Flags |= SeriesDefinitionFlags.InterpolationSet;
+        }
+
         PopulateNative(ref definition);
 
         return definition;
@@ -159,6 +342,11 @@ internal VelloChartSeriesDefinition ToNative()
     internal virtual void PopulateNative(ref VelloChartSeriesDefinition definition)
     {
     }
+
+    /// <summary>
+    /// Custom colour stops the caller pins and passes alongside the native definition.
+    /// </summary>
+    internal virtual VelloChartColorStop[]? NativeColorStops() => null;
 }
 
 /// <summary>
@@ -217,8 +405,68 @@ public sealed record HeatmapSeriesDefinition(uint SeriesId) : ChartSeriesDefinit
 
     public uint BucketCount { get; init; }
 
+    /// <summary>
+    /// Gets or sets the named colour map cells are drawn with. When neither this nor
+    /// <see cref="ColorStops"/> is set, cells fade in the series colour.
+    /// </summary>
+    public ChartColorMap? ColorMap { get; init; }
+
+    /// <summary>
+    /// Gets or sets custom gradient stops, ordered by offset, that take precedence over <see cref="ColorMap"/>.
+    /// </summary>
+    public IReadOnlyList<ChartColorStop>? ColorStops { get; init; }
+
+    /// <summary>
+    /// Gets or sets the value mapped to the start of the colour scale. When omitted the smallest
+    /// visible value of the heatmap series in the pane is used.
+    /// </summary>
+    public double? ColorDomainMin { get; init; }
+
+    /// <summary>
+    /// Gets or sets the value mapped to the end of the colour scale. When omitted the largest
+    /// visible value of the heatmap series in the pane is used.
+    /// </summary>
+    public double? ColorDomainMax { get; init; }
+
     internal override ChartSeriesKind Kind => ChartSeriesKind.Heatmap;
 
+    protected override void Validate()
+    {
+        base.Validate();
+
+        if (ColorStops is { Count: > 0 } stops)
+        {
+            if (stops.Count < 2)
+            {
+                throw new ArgumentOutOfRangeException(nameof(ColorStops), stops.Count, "At least two colour stops are required.");
+            }
+
+            for (var i = 0; i < stops.Count; i++)
+            {
+                var offset = stops[i].Offset;
+                if (!float.IsFinite(offset) || offset is < 0 or > 1 || (i > 0 && offset < stops[i - 1].Offset))
+                {
+                    throw new ArgumentOutOfRangeException(nameof(ColorStops), offset, "Colour stop offsets must be ordered and between 0 and 1.");
+                }
+            }
+        }
+
+        if (ColorDomainMin is { } min && !double.IsFinite(min))
+        {
+            throw new ArgumentOutOfRangeException(nameof(ColorDomainMin), min, "Colour domain minimum must be finite.");
+        }
+
+        if (ColorDomainMax is { } max && !double.IsFinite(max))
+        {
+            throw new ArgumentOutOfRangeException(nameof(ColorDomainMax), max, "Colour domain maximum must be finite.");
+        }
+
+        if (ColorDomainMin is { } low && ColorDomainMax is { } high && low >= high)
+        {
+            throw new ArgumentOutOfRangeException(nameof(ColorDomainMin), low, "Colour domain minimum must be less than its maximum.");
+        }
+    }
+
     internal override void PopulateNative(ref VelloChartSeriesDefinition definition)
     {
         if (BucketCount == 0)
@@ -234,5 +482,91 @@ internal override void PopulateNative(ref VelloChartSeriesDefinition definition)
         definition.HeatmapBucketIndex = BucketIndex;
         definition.HeatmapBucketCount = BucketCount;
         definition.Flags |= SeriesDefinitionFlags.HeatmapBucketsSet;
+
+        if (ColorMap is not null || ColorStops is { Count: > 0 })
+        {
+            definition.ColorMap = (uint)(ColorMap ?? ChartColorMap.Viridis);
+            definition.Flags |= SeriesDefinitionFlags.ColorMapSet;
+        }
+
+        if (ColorDomainMin is { } min)
+        {
+            definition.ColorDomainMin = min;
+            definition.Flags |= SeriesDefinitionFlags.ColorDomainMinSet;
+        }
+
+        if (ColorDomainMax is { } max)
+        {
+            definition.ColorDomainMax = max;
+            definition.Flags |= SeriesDefinitionFlags.ColorDomainMaxSet;
+        }
     }
+
+    internal override VelloChartColorStop[]? NativeColorStops()
+    {
+        if (ColorStops is not { Count: > 0 } stops)
+        {
+            return null;
+        }
+
+        var native = new VelloChartColorStop[stops.Count];
+        for (var i = 0; i < native.Length; i++)
+        {
+            native[i] = new VelloChartColorStop
+            {
+                Offset = stops[i].Offset,
+                Color = stops[i].Color.ToNative(),
+            };
+        }
+
+        return native;
+    }
+}
+
+/// <summary>
+/// Shared configuration for series fed with <see cref="VelloSharp.ChartData.ChartOhlcSample"/> bars.
+/// The bucket width follows <see cref="ChartSeriesDefinition.BarWidthSeconds"/>, falling back to the data cadence.
+/// </summary>
+public abstract record CandleSeriesDefinition(uint SeriesId) : ChartSeriesDefinition(SeriesId)
+{
+    /// <summary>
+    /// Gets or sets the colour of bars that close at or above their open.
+    /// </summary>
+    public ChartColor? UpColor { get; init; }
+
+    /// <summary>
+    /// Gets or sets the colour of bars that close below their open.
+    /// </summary>
+    public ChartColor? DownColor { get; init; }
+
+    internal override void PopulateNative(ref VelloChartSeriesDefinition definition)
+    {
+        if (UpColor is { } upColor)
+        {
+            definition.UpColor = upColor.ToNative();
+            definition.Flags |= SeriesDefinitionFlags.UpColorSet;
+        }
+
+        if (DownColor is { } downColor)
+        {
+            definition.DownColor = downColor.ToNative();
+            definition.Flags |= SeriesDefinitionFlags.DownColorSet;
+        }
+    }
+}
+
+/// <summary>
+/// Configures a candlestick series with filled bodies between open and close.
+/// </summary>
+public sealed record CandlestickSeriesDefinition(uint SeriesId) : CandleSeriesDefinition(SeriesId)
+{
+    internal override ChartSeriesKind Kind => ChartSeriesKind.Candlestick;
+}
+
+/// <summary>
+/// Configures an OHLC bar series with open and close ticks.
+/// </summary>
+public sealed record OhlcSeriesDefinition(uint SeriesId) : CandleSeriesDefinition(SeriesId)
+{
+    internal override ChartSeriesKind Kind => ChartSeriesKind.Ohlc;
 }
@@ -0,0 +1,7 @@
+namespace VelloSharp.ChartEngine;
+
+/// <summary>
+/// Time range drawn by <see cref="ChartEngine"/>, in seconds.
+/// </summary>
+/// <param name="FollowLive">Whether the range trails the newest sample.</param>
+public readonly record struct ChartViewport(double RangeStartSeconds, double RangeEndSeconds, bool FollowLive);
@@ -12,6 +12,10 @@ public static FrameStats ToManaged(this VelloChartFrameStats stats)
         var queue = TimeSpan.FromMilliseconds(stats.QueueLatencyMs);
         var encoded = stats.EncodedPaths > int.MaxValue ? int.MaxValue : (int)stats.EncodedPaths;
         var timestamp = DateTimeOffset.FromUnixTimeMilliseconds(Math.Max(0, stats.TimestampMs));
-        return new FrameStats(cpu, gpu, queue, encoded, timestamp);
+        return new FrameStats(cpu, gpu, queue, encoded, timestamp)
+        {
+            SourcePoints = (long)Math.Min(stats.SourcePoints, long.MaxValue),
+            RenderedPoints = (long)Math.Min(stats.RenderedPoints, long.MaxValue),
+        };
     }
 }
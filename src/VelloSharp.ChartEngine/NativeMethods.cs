@@ -41,6 +41,13 @@ internal static partial class NativeMethods
     internal static unsafe partial VelloChartEngineStatus vello_chart_engine_set_trace_callback(
         delegate* unmanaged[Cdecl]<ChartTraceLevel, long, nint, nint, nint, void> callback);
 
+    [LibraryImport(LibraryName, EntryPoint = "vello_chart_engine_publish_ohlc_samples")]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static unsafe partial VelloChartEngineStatus vello_chart_engine_publish_ohlc_samples(
+        nint handle,
+        VelloChartOhlcSample* samples,
+        nuint sampleCount);
+
     [LibraryImport(LibraryName, EntryPoint = "vello_chart_engine_set_palette")]
     [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
     internal static unsafe partial VelloChartEngineStatus vello_chart_engine_set_palette(
@@ -73,6 +80,201 @@ internal static partial class NativeMethods
     internal static partial VelloChartEngineStatus vello_chart_engine_last_frame_metadata(
         nint handle,
         out VelloChartFrameMetadata metadata);
+
+    [LibraryImport(LibraryName, EntryPoint = "vello_chart_engine_hit_test")]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static partial VelloChartEngineStatus vello_chart_engine_hit_test(
+        nint handle,
+        double x,
+        double y,
+        double radius,
+        out VelloChartHitTestResult result);
+
+    [LibraryImport(LibraryName, EntryPoint = "vello_chart_engine_set_legend")]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static partial VelloChartEngineStatus vello_chart_engine_set_legend(
+        nint handle,
+        VelloChartLegendOptions options);
+
+    [LibraryImport(LibraryName, EntryPoint = "vello_chart_engine_set_axis_labels")]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static partial VelloChartEngineStatus vello_chart_engine_set_axis_labels(
+        nint handle,
+        VelloChartAxisLabelOptions options);
+
+    [LibraryImport(LibraryName, EntryPoint = "vello_chart_engine_set_theme")]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static unsafe partial VelloChartEngineStatus vello_chart_engine_set_theme(
+        nint handle,
+        VelloChartTheme* theme);
+
+    [LibraryImport(LibraryName, EntryPoint = "vello_chart_engine_legend_hit_test")]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static partial VelloChartEngineStatus vello_chart_engine_legend_hit_test(
+        nint handle,
+        double x,
+        double y,
+        out VelloChartLegendHit hit);
+
+    [LibraryImport(LibraryName, EntryPoint = "vello_chart_engine_set_series_visibility")]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static partial VelloChartEngineStatus vello_chart_engine_set_series_visibility(
+        nint handle,
+        uint seriesId,
+        uint visible);
+
+    [LibraryImport(LibraryName, EntryPoint = "vello_chart_engine_get_series_visibility")]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static partial VelloChartEngineStatus vello_chart_engine_get_series_visibility(
+        nint handle,
+        uint seriesId,
+        out uint visible);
+
+    [LibraryImport(LibraryName, EntryPoint = "vello_chart_engine_add_annotation")]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static unsafe partial VelloChartEngineStatus vello_chart_engine_add_annotation(
+        nint handle,
+        VelloChartAnnotation* annotation);
+
+    [LibraryImport(LibraryName, EntryPoint = "vello_chart_engine_update_annotation")]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static unsafe partial VelloChartEngineStatus vello_chart_engine_update_annotation(
+        nint handle,
+        VelloChartAnnotation* annotation);
+
+    [LibraryImport(LibraryName, EntryPoint = "vello_chart_engine_remove_annotation")]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static partial VelloChartEngineStatus vello_chart_engine_remove_annotation(
+        nint handle,
+        uint annotationId);
+
+    [LibraryImport(LibraryName, EntryPoint = "vello_chart_engine_add_derived_series")]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static unsafe partial VelloChartEngineStatus vello_chart_engine_add_derived_series(
+        nint handle,
+        VelloChartDerivedSeries* derived);
+
+    [LibraryImport(LibraryName, EntryPoint = "vello_chart_engine_remove_derived_series")]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static partial VelloChartEngineStatus vello_chart_engine_remove_derived_series(
+        nint handle,
+        uint seriesId);
+
+    [LibraryImport(LibraryName, EntryPoint = "vello_chart_engine_export")]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static unsafe partial VelloChartEngineStatus vello_chart_engine_export(
+        nint handle,
+        uint width,
+        uint height,
+        VelloChartExportOptions* options,
+        nint* outBuffer);
+
+    [LibraryImport(LibraryName, EntryPoint = "vello_chart_engine_export_buffer_data")]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static unsafe partial VelloChartEngineStatus vello_chart_engine_export_buffer_data(
+        nint buffer,
+        byte** data,
+        nuint* length);
+
+    [LibraryImport(LibraryName, EntryPoint = "vello_chart_engine_export_buffer_destroy")]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static partial void vello_chart_engine_export_buffer_destroy(nint buffer);
+
+    [LibraryImport(LibraryName, EntryPoint = "vello_chart_engine_snapshot")]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static unsafe partial VelloChartEngineStatus vello_chart_engine_snapshot(
+        nint handle,
+        nint* outBuffer);
+
+    [LibraryImport(LibraryName, EntryPoint = "vello_chart_engine_restore_snapshot")]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static unsafe partial VelloChartEngineStatus vello_chart_engine_restore_snapshot(
+        nint handle,
+        byte* data,
+        nuint length);
+
+    [LibraryImport(LibraryName, EntryPoint = "vello_chart_engine_clear_annotations")]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static partial VelloChartEngineStatus vello_chart_engine_clear_annotations(nint handle);
+
+    [LibraryImport(LibraryName, EntryPoint = "vello_chart_engine_get_viewport")]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static partial VelloChartEngineStatus vello_chart_engine_get_viewport(
+        nint handle,
+        out VelloChartViewport viewport);
+
+    [LibraryImport(LibraryName, EntryPoint = "vello_chart_engine_set_visible_range")]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static partial VelloChartEngineStatus vello_chart_engine_set_visible_range(
+        nint handle,
+        double rangeStart,
+        double rangeEnd);
+
+    [LibraryImport(LibraryName, EntryPoint = "vello_chart_engine_set_follow_live")]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static partial VelloChartEngineStatus vello_chart_engine_set_follow_live(
+        nint handle,
+        uint followLive);
+
+    [LibraryImport(LibraryName, EntryPoint = "vello_chart_engine_zoom")]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static partial VelloChartEngineStatus vello_chart_engine_zoom(
+        nint handle,
+        double factor,
+        double anchorSeconds);
+
+    [LibraryImport(LibraryName, EntryPoint = "vello_chart_engine_pan")]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static partial VelloChartEngineStatus vello_chart_engine_pan(
+        nint handle,
+        double deltaSeconds);
+
+    [LibraryImport(LibraryName, EntryPoint = "vello_chart_engine_set_retention_duration")]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static partial VelloChartEngineStatus vello_chart_engine_set_retention_duration(
+        nint handle,
+        double retentionSeconds);
+
+    [LibraryImport(LibraryName, EntryPoint = "vello_chart_engine_set_cursor")]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static partial VelloChartEngineStatus vello_chart_engine_set_cursor(
+        nint handle,
+        double x,
+        double y);
+
+    [LibraryImport(LibraryName, EntryPoint = "vello_chart_engine_clear_cursor")]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static partial VelloChartEngineStatus vello_chart_engine_clear_cursor(nint handle);
+
+    [LibraryImport(LibraryName, EntryPoint = "vello_chart_engine_data_bus_create")]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static partial nint vello_chart_engine_data_bus_create(nuint capacity);
+
+    [LibraryImport(LibraryName, EntryPoint = "vello_chart_engine_data_bus_destroy")]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static partial void vello_chart_engine_data_bus_destroy(nint handle);
+
+    [LibraryImport(LibraryName, EntryPoint = "vello_chart_engine_data_bus_push")]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static unsafe partial VelloChartEngineStatus vello_chart_engine_data_bus_push(
+        nint handle,
+        uint seriesId,
+        VelloChartDataPoint* points,
+        nuint count);
+
+    [LibraryImport(LibraryName, EntryPoint = "vello_chart_engine_data_bus_stats")]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static partial VelloChartEngineStatus vello_chart_engine_data_bus_stats(
+        nint handle,
+        out VelloChartDataBusStats stats);
+
+    [LibraryImport(LibraryName, EntryPoint = "vello_chart_engine_attach_data_bus")]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static partial VelloChartEngineStatus vello_chart_engine_attach_data_bus(nint handle, nint bus);
+
+    [LibraryImport(LibraryName, EntryPoint = "vello_chart_engine_detach_data_bus")]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static partial VelloChartEngineStatus vello_chart_engine_detach_data_bus(nint handle);
 }
 
 internal enum VelloChartEngineStatus
@@ -80,6 +282,7 @@ internal enum VelloChartEngineStatus
     Success = 0,
     NullPointer = 1,
     InvalidArgument = 2,
+    QueueFull = 3,
     Unknown = 255,
 }
 
@@ -87,6 +290,7 @@ internal enum VelloChartEngineStatus
 internal struct VelloChartEngineOptions
 {
     public double VisibleDurationSeconds;
+    public double RetentionDurationSeconds;
     public double VerticalPaddingRatio;
     public double StrokeWidth;
     public uint ShowAxes;
@@ -94,6 +298,18 @@ internal struct VelloChartEngineOptions
     public nint Palette;
 }
 
+[StructLayout(LayoutKind.Sequential)]
+internal struct VelloChartOhlcSample
+{
+    public uint SeriesId;
+    public double TimestampSeconds;
+    public double Open;
+    public double High;
+    public double Low;
+    public double Close;
+    public double Volume;
+}
+
 [StructLayout(LayoutKind.Sequential)]
 internal readonly struct VelloChartSamplePoint
 {
@@ -109,6 +325,212 @@ public VelloChartSamplePoint(uint seriesId, double timestampSeconds, double valu
     }
 }
 
+[StructLayout(LayoutKind.Sequential)]
+internal struct VelloChartLegendOptions
+{
+    public uint Placement;
+    public uint FloatingPaneIndex;
+    public uint FloatingCorner;
+}
+
+[StructLayout(LayoutKind.Sequential)]
+internal struct VelloChartLineStyle
+{
+    public VelloChartColor Color;
+    public double Width;
+    public nint DashPattern;
+    public nuint DashLength;
+}
+
+[StructLayout(LayoutKind.Sequential)]
+internal struct VelloChartTextStyle
+{
+    public nint FontFamily;
+    public nuint FontFamilyLength;
+    public float FontSize;
+    public VelloChartColor Color;
+}
+
+[StructLayout(LayoutKind.Sequential)]
+internal struct VelloChartCalloutStyle
+{
+    public double PaddingX;
+    public double PaddingY;
+    public double CornerRadius;
+    public double OffsetX;
+    public double GapY;
+    public float FillOpacity;
+    public float BorderOpacity;
+    public double BorderWidth;
+}
+
+[Flags]
+internal enum VelloChartThemeFlags : uint
+{
+    None = 0,
+    MajorGrid = 1 << 0,
+    MinorGrid = 1 << 1,
+    Axis = 1 << 2,
+}
+
+[StructLayout(LayoutKind.Sequential)]
+internal struct VelloChartTheme
+{
+    public VelloChartThemeFlags Flags;
+    public VelloChartColor BackgroundTop;
+    public VelloChartColor BackgroundBottom;
+    public VelloChartColor FrameBorder;
+    public VelloChartColor PlotBackground;
+    public VelloChartColor PlotBorder;
+    public VelloChartLineStyle MajorGrid;
+    public VelloChartLineStyle MinorGrid;
+    public uint MinorDivisions;
+    public VelloChartLineStyle Axis;
+    public double MajorTickLength;
+    public double MinorTickLength;
+    public VelloChartTextStyle AxisLabel;
+    public VelloChartTextStyle Label;
+    public VelloChartCalloutStyle Callout;
+    public VelloChartColor LegendBackground;
+    public VelloChartColor TooltipBackground;
+    public VelloChartColor PanelBorder;
+    public VelloChartLineStyle Crosshair;
+}
+
+[Flags]
+internal enum VelloChartAxisLabelFlags : uint
+{
+    None = 0,
+    DateOnDayChange = 1 << 0,
+}
+
+[StructLayout(LayoutKind.Sequential)]
+internal struct VelloChartAxisLabelOptions
+{
+    public int UtcOffsetMinutes;
+    public uint Flags;
+    public nint TimeFormat;
+    public nuint TimeFormatLength;
+    public nint ValueFormat;
+    public nuint ValueFormatLength;
+}
+
+[StructLayout(LayoutKind.Sequential)]
+internal struct VelloChartLegendHit
+{
+    public uint Hit;
+    public uint SeriesId;
+}
+
+[StructLayout(LayoutKind.Sequential)]
+internal struct VelloChartAnnotation
+{
+    public uint AnnotationId;
+    public uint Kind;
+    public uint Flags;
+    public double Start;
+    public double End;
+    public VelloChartColor Color;
+    public double StrokeWidth;
+    public float FillOpacity;
+    public nint DashPattern;
+    public nuint DashLength;
+    public nint Label;
+    public nuint LabelLength;
+    public nint PaneId;
+    public nuint PaneIdLength;
+}
+
+[StructLayout(LayoutKind.Sequential)]
+internal struct VelloChartDerivedSeries
+{
+    public uint SeriesId;
+    public uint SourceSeriesId;
+    public uint Operator;
+    public uint Window;
+    public double Deviations;
+    public uint LowerSeriesId;
+}
+
+[Flags]
+internal enum VelloChartExportFlags : uint
+{
+    None = 0,
+    Background = 1 << 0,
+}
+
+[StructLayout(LayoutKind.Sequential)]
+internal struct VelloChartExportOptions
+{
+    public uint Format;
+    public VelloChartExportFlags Flags;
+    public double Scale;
+    public VelloChartColor Background;
+}
+
+internal enum VelloChartAnnotationKind : uint
+{
+    ValueLine = 0,
+    TimeMarker = 1,
+    ValueBand = 2,
+    TimeBand = 3,
+}
+
+[Flags]
+internal enum VelloChartAnnotationFlags : uint
+{
+    None = 0,
+    ColorSet = 1 << 0,
+    LabelSet = 1 << 1,
+    PaneSet = 1 << 2,
+}
+
+[StructLayout(LayoutKind.Sequential)]
+internal struct VelloChartViewport
+{
+    public double RangeStart;
+    public double RangeEnd;
+    public uint FollowLive;
+}
+
+[StructLayout(LayoutKind.Sequential)]
+internal struct VelloChartHitTestResult
+{
+    public uint Hit;
+    public uint SeriesId;
+    public uint PaneIndex;
+    public uint SeriesKind;
+    public double TimestampSeconds;
+    public double Value;
+    public double X;
+    public double Y;
+    public double Distance;
+}
+
+[StructLayout(LayoutKind.Sequential)]
+internal readonly struct VelloChartDataPoint
+{
+    public readonly ulong TimestampNs;
+    public readonly double Value;
+
+    public VelloChartDataPoint(ulong timestampNs, double value)
+    {
+        TimestampNs = timestampNs;
+        Value = value;
+    }
+}
+
+[StructLayout(LayoutKind.Sequential)]
+internal struct VelloChartDataBusStats
+{
+    public nuint Capacity;
+    public nuint Queued;
+    public ulong Pushed;
+    public ulong Dropped;
+    public ulong DroppedPoints;
+    public ulong Drained;
+}
+
 [StructLayout(LayoutKind.Sequential)]
 internal readonly struct VelloChartColor
 {
@@ -134,6 +556,8 @@ internal struct VelloChartFrameStats
     public float QueueLatencyMs;
     public uint EncodedPaths;
     public long TimestampMs;
+    public ulong SourcePoints;
+    public ulong RenderedPoints;
 }
 
 internal enum ChartTraceLevel
@@ -171,8 +595,10 @@ internal struct VelloChartCompositionPane
     public nuint IdLength;
     public double HeightRatio;
     public uint ShareXAxisWithPrimary;
+    public uint ValueScale;
     public nint SeriesIds;
     public nuint SeriesIdCount;
+    public double SymlogLinearThreshold;
 }
 
 [StructLayout(LayoutKind.Sequential)]
@@ -188,6 +614,7 @@ internal struct VelloChartPaneMetadata
     public nint Id;
     public nuint IdLength;
     public uint ShareXAxisWithPrimary;
+    public uint ValueScale;
     public double PlotLeft;
     public double PlotTop;
     public double PlotWidth;
@@ -200,6 +627,12 @@ internal struct VelloChartPaneMetadata
     public double DirtyValueMax;
     public nint ValueTicks;
     public nuint ValueTickCount;
+    public double ValueScaleThreshold;
+    public uint HasRightAxis;
+    public double RightValueMin;
+    public double RightValueMax;
+    public nint RightValueTicks;
+    public nuint RightValueTickCount;
 }
 
 [StructLayout(LayoutKind.Sequential)]
@@ -219,6 +652,10 @@ internal struct VelloChartSeriesMetadataNative
     public uint BandLowerSeriesId;
     public uint HeatmapBucketIndex;
     public uint HeatmapBucketCount;
+    public uint Visible;
+    public uint ValueAxis;
+    public double ColorDomainMin;
+    public double ColorDomainMax;
 }
 
 [StructLayout(LayoutKind.Sequential)]
@@ -257,5 +694,26 @@ internal struct VelloChartSeriesDefinition
     public uint BandLowerSeriesId;
     public uint HeatmapBucketIndex;
     public uint HeatmapBucketCount;
+    public uint DecimationMode;
+    public uint ValueAxis;
+    public VelloChartColor UpColor;
+    public VelloChartColor DownColor;
+    public uint StackGroup;
+    public uint StackMode;
+    public double MaxGapSeconds;
+    public uint GapStyle;
+    public uint Interpolation;
+    public uint ColorMap;
+    public nint ColorStops;
+    public nuint ColorStopCount;
+    public double ColorDomainMin;
+    public double ColorDomainMax;
+}
+
+[StructLayout(LayoutKind.Sequential)]
+internal struct VelloChartColorStop
+{
+    public float Offset;
+    public VelloChartColor Color;
 }
 
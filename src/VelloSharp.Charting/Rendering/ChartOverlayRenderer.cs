@@ -31,7 +31,12 @@ public sealed class ChartOverlayRenderer : IDisposable
         double ValueMin,
         double ValueMax,
         bool ShareXAxisWithPrimary,
-        IReadOnlyList<ChartFrameMetadata.AxisTickMetadata> ValueTicks);
+        IReadOnlyList<ChartFrameMetadata.AxisTickMetadata> ValueTicks,
+        double? RightValueMin = null,
+        double? RightValueMax = null,
+        IReadOnlyList<ChartFrameMetadata.AxisTickMetadata>? RightValueTicks = null,
+        ChartValueScale ValueScale = ChartValueScale.Linear,
+        double SymlogLinearThreshold = 1d);
 
     private sealed class PaneAnnotations
     {
@@ -72,6 +77,7 @@ public PaneAnnotations(string paneId)
         var paneAnnotations = BuildPaneAnnotations(paneViews, composition, annotations);
 
         var leftThickness = Math.Max(metadata.PlotLeft, 0d);
+        var rightThickness = Math.Max(width - (metadata.PlotLeft + metadata.PlotWidth), 0d);
         var bottomThickness = Math.Max(height - (metadata.PlotTop + metadata.PlotHeight), 0d);
 
         var timeStart = DateTimeOffset.FromUnixTimeMilliseconds(
@@ -138,7 +144,7 @@ public PaneAnnotations(string paneId)
 
         foreach (var pane in paneViews)
         {
-            var valueScale = new LinearScale(pane.ValueMin, pane.ValueMax);
+            var valueScale = CreateValueScale(pane, pane.ValueMin, pane.ValueMax);
             var valueTickTarget = Math.Max(pane.ValueTicks.Count, 4);
 
             var valueAxis = new AxisDefinition<double>(
@@ -160,9 +166,28 @@ public PaneAnnotations(string paneId)
                 leftLayoutRect,
                 Math.Max(leftThickness, 0d));
 
-            var paneSurface = new AxisRenderSurface(
-                pane.Bounds,
-                new[] { valueAxis.Build(leftLayout, _tickRegistry) });
+            var paneAxes = new List<AxisRenderModel>(2) { valueAxis.Build(leftLayout, _tickRegistry) };
+            if (pane.RightValueTicks is { } rightTicks &&
+                pane.RightValueMin is { } rightMin &&
+                pane.RightValueMax is { } rightMax)
+            {
+                var rightAxis = new AxisDefinition<double>(
+                    $"axis-value-right-{pane.Index}",
+                    AxisOrientation.Right,
+                    rightThickness,
+                    CreateValueScale(pane, rightMin, rightMax),
+                    theme.Axis,
+                    new TickGenerationOptions<double> { TargetTickCount = Math.Max(rightTicks.Count, 4) });
+
+                var rightLayout = new AxisLayout(
+                    AxisOrientation.Right,
+                    new LayoutRect(pane.Bounds.X + pane.Bounds.Width, pane.Bounds.Y, rightThickness, pane.Bounds.Height),
+                    rightThickness);
+
+                paneAxes.Add(rightAxis.Build(rightLayout, _tickRegistry));
+            }
+
+            var paneSurface = new AxisRenderSurface(pane.Bounds, paneAxes);
 
             var paneResult = _axisRenderer.Render(paneSurface);
             paneAnnotations.TryGetValue(pane.Index, out var annotationsForPane);
@@ -253,6 +278,18 @@ public PaneAnnotations(string paneId)
         }
     }
 
+    /// <summary>
+    /// Creates a value scale matching the one the engine projected the pane with; both axes of a
+    /// pane share its scale kind.
+    /// </summary>
+    private static IScale<double> CreateValueScale(PaneView pane, double min, double max) =>
+        pane.ValueScale switch
+        {
+            ChartValueScale.Log10 when min > 0d && max > 0d => new LogarithmicScale(min, max),
+            ChartValueScale.SymLog => new SymmetricLogarithmicScale(min, max, pane.SymlogLinearThreshold),
+            _ => new LinearScale(min, max),
+        };
+
     private static List<PaneView> BuildPaneViews(ChartFrameMetadata metadata)
     {
         var panes = new List<PaneView>();
@@ -269,7 +306,12 @@ private static List<PaneView> BuildPaneViews(ChartFrameMetadata metadata)
                     pane.ValueMin,
                     pane.ValueMax,
                     pane.ShareXAxisWithPrimary,
-                    pane.ValueTicks));
+                    pane.ValueTicks,
+                    pane.RightValueMin,
+                    pane.RightValueMax,
+                    pane.RightValueTicks,
+                    pane.ValueScale,
+                    pane.SymlogLinearThreshold ?? 1d));
             }
         }
         else
@@ -545,6 +587,16 @@ private static void DrawLegendMarker(VScene scene, LegendItemVisual item, Legend
                 break;
             }
 
+            case ChartSeriesKind.Candlestick:
+            case ChartSeriesKind.Ohlc:
+            {
+                var centerX = item.MarkerX + size / 2d;
+                DrawLine(scene, centerX, item.MarkerY, centerX, item.MarkerY + size, item.Color, 1.0);
+                var body = new LayoutRect(item.MarkerX + size * 0.3, item.MarkerY + size * 0.25, size * 0.4, size * 0.5);
+                FillRectangle(scene, body, item.Color);
+                break;
+            }
+
             default:
                 FillRectangle(scene, markerRect, item.Color);
                 break;
@@ -7,6 +7,7 @@ public enum ScaleKind
 {
     Linear,
     Logarithmic,
+    SymmetricLogarithmic,
     Time,
     Ordinal,
 }
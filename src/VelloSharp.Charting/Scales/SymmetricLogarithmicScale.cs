@@ -0,0 +1,96 @@
+using System;
+using VelloSharp.Charting.Primitives;
+
+namespace VelloSharp.Charting.Scales;
+
+/// <summary>
+/// Projects numeric values logarithmically beyond a threshold and linearly through zero inside it.
+/// </summary>
+public sealed class SymmetricLogarithmicScale : IScale<double>
+{
+    private readonly double _scaledStart;
+    private readonly double _scaledSpan;
+
+    public SymmetricLogarithmicScale(double start, double end, double linearThreshold = 1d, bool clampToDomain = true)
+        : this(new Range<double>(start, end), linearThreshold, clampToDomain)
+    {
+    }
+
+    public SymmetricLogarithmicScale(Range<double> domain, double linearThreshold = 1d, bool clampToDomain = true)
+    {
+        if (!double.IsFinite(linearThreshold) || linearThreshold <= 0d)
+        {
+            throw new ArgumentOutOfRangeException(nameof(linearThreshold), linearThreshold, "Linear threshold must be positive and finite.");
+        }
+
+        Domain = domain;
+        ClampToDomain = clampToDomain;
+        LinearThreshold = linearThreshold;
+        _scaledStart = Transform(domain.Start);
+        _scaledSpan = Transform(domain.End) - _scaledStart;
+    }
+
+    public ScaleKind Kind => ScaleKind.SymmetricLogarithmic;
+
+    public bool ClampToDomain { get; }
+
+    public Type DomainType => typeof(double);
+
+    public Range<double> Domain { get; }
+
+    /// <summary>
+    /// Gets the half-width of the linear region around zero.
+    /// </summary>
+    public double LinearThreshold { get; }
+
+    public double Project(double value)
+    {
+        if (!double.IsFinite(value))
+        {
+            return double.NaN;
+        }
+
+        if (ClampToDomain)
+        {
+            var (min, max) = Domain.Normalize();
+            value = Math.Clamp(value, min, max);
+        }
+
+        if (Math.Abs(_scaledSpan) < double.Epsilon)
+        {
+            return 0d;
+        }
+
+        return (Transform(value) - _scaledStart) / _scaledSpan;
+    }
+
+    public bool TryProject(double value, out double unit)
+    {
+        unit = Project(value);
+        return double.IsFinite(unit);
+    }
+
+    public double Unproject(double unit)
+    {
+        if (!double.IsFinite(unit))
+        {
+            throw new ArgumentOutOfRangeException(nameof(unit), unit, "Unit value must be finite.");
+        }
+
+        if (ClampToDomain)
+        {
+            unit = Math.Clamp(unit, 0d, 1d);
+        }
+
+        if (Math.Abs(_scaledSpan) < double.Epsilon)
+        {
+            return Domain.Start;
+        }
+
+        var scaled = _scaledStart + unit * _scaledSpan;
+        return Math.Sign(scaled) * LinearThreshold * (Math.Pow(10d, Math.Abs(scaled)) - 1d);
+    }
+
+    private double Transform(double value) =>
+        Math.Sign(value) * Math.Log10(1d + Math.Abs(value) / LinearThreshold);
+}
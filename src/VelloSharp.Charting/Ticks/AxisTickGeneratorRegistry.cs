@@ -34,6 +34,7 @@ public static AxisTickGeneratorRegistry CreateDefault()
         return new AxisTickGeneratorRegistry()
             .Register(ScaleKind.Linear, new LinearTickGenerator())
             .Register(ScaleKind.Logarithmic, new LinearTickGenerator())
+            .Register(ScaleKind.SymmetricLogarithmic, new LinearTickGenerator())
             .Register(ScaleKind.Time, new TimeTickGenerator())
             .Register(ScaleKind.Ordinal, new OrdinalTickGenerator<string>())
             .Register(ScaleKind.Ordinal, new OrdinalTickGenerator<int>())
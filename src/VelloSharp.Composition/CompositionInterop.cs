@@ -1,6 +1,7 @@
 using System;
 using System.Buffers;
 using System.Collections.Generic;
+using System.Numerics;
 using System.Runtime.CompilerServices;
 using System.Runtime.InteropServices;
 using System.Text;
@@ -89,6 +90,179 @@ private static LabelMetrics MeasureLabelCore(Span<byte> utf8, float fontSize)
         }
     }
 
+    public static LabelMetrics MeasureLabel(string text, in LabelStyle style)
+    {
+        ArgumentNullException.ThrowIfNull(text);
+        if (!(style.FontSize > 0f))
+        {
+            throw new ArgumentOutOfRangeException(nameof(style), "Font size must be positive.");
+        }
+
+        if (text.Length == 0)
+        {
+            return default;
+        }
+
+        var variations = new VelloCompositionFontVariation[style.Variations?.Count ?? 0];
+        for (int i = 0; i < variations.Length; i++)
+        {
+            var variation = style.Variations![i];
+            if (variation.Tag is not { Length: 4 } tag)
+            {
+                throw new ArgumentException("Variation axis tags must be four characters.", nameof(style));
+            }
+
+            variations[i] = new VelloCompositionFontVariation
+            {
+                Tag = ((uint)tag[0] << 24) | ((uint)tag[1] << 16) | ((uint)tag[2] << 8) | tag[3],
+                Value = variation.Value,
+            };
+        }
+
+        byte[] utf8 = Encoding.UTF8.GetBytes(text);
+        byte[] family = style.Family is { Length: > 0 } name ? Encoding.UTF8.GetBytes(name) : Array.Empty<byte>();
+        unsafe
+        {
+            fixed (byte* textPtr = utf8)
+            fixed (byte* familyPtr = family)
+            fixed (VelloCompositionFontVariation* variationsPtr = variations)
+            {
+                var native = new VelloCompositionLabelStyle
+                {
+                    Family = familyPtr,
+                    FamilyLength = (nuint)family.Length,
+                    FontSize = style.FontSize,
+                    Weight = style.Weight,
+                    Width = style.Width,
+                    Slant = (VelloCompositionFontSlant)style.Slant,
+                    Variations = variationsPtr,
+                    VariationCount = (nuint)variations.Length,
+                };
+
+                if (!NativeMethods.vello_composition_measure_label_styled(
+                        textPtr,
+                        (nuint)utf8.Length,
+                        in native,
+                        out var metrics))
+                {
+                    return default;
+                }
+
+                return new LabelMetrics(metrics.Width, metrics.Height, metrics.Ascent);
+            }
+        }
+    }
+
+    /// <summary>
+    /// Wraps, trims and aligns <paramref name="text"/> inside the box described by
+    /// <paramref name="options"/>. Returns <c>null</c> when the text has nothing to draw.
+    /// </summary>
+    public static ParagraphLayout? LayoutParagraph(string text, in ParagraphOptions options)
+    {
+        ArgumentNullException.ThrowIfNull(text);
+        if (!(options.FontSize > 0f))
+        {
+            throw new ArgumentOutOfRangeException(nameof(options), "Font size must be positive.");
+        }
+
+        if (options.MaxLines < 0)
+        {
+            throw new ArgumentOutOfRangeException(nameof(options), "Max lines must not be negative.");
+        }
+
+        if (text.Length == 0)
+        {
+            return null;
+        }
+
+        byte[] utf8 = Encoding.UTF8.GetBytes(text);
+        var native = new VelloCompositionParagraphOptions
+        {
+            FontSize = options.FontSize,
+            MaxWidth = options.MaxWidth,
+            MaxHeight = options.MaxHeight,
+            LineHeight = options.LineHeight,
+            MaxLines = (uint)options.MaxLines,
+            Wrap = (VelloCompositionTextWrap)options.Wrap,
+            Ellipsis = options.Ellipsis ? 1 : 0,
+            HorizontalAlignment = (VelloCompositionLayoutAlignment)options.HorizontalAlignment,
+            VerticalAlignment = (VelloCompositionLayoutAlignment)options.VerticalAlignment,
+        };
+
+        VelloCompositionParagraphMetrics metrics;
+        VelloCompositionParagraphLine[] nativeLines;
+        VelloCompositionGlyph[] nativeGlyphs;
+        unsafe
+        {
+            fixed (byte* textPtr = utf8)
+            {
+                if (!NativeMethods.vello_composition_layout_paragraph(
+                        textPtr,
+                        (nuint)utf8.Length,
+                        in native,
+                        out metrics,
+                        null,
+                        0,
+                        null,
+                        0))
+                {
+                    return null;
+                }
+
+                nativeLines = new VelloCompositionParagraphLine[metrics.LineCount];
+                nativeGlyphs = new VelloCompositionGlyph[metrics.GlyphCount];
+                fixed (VelloCompositionParagraphLine* linesPtr = nativeLines)
+                fixed (VelloCompositionGlyph* glyphsPtr = nativeGlyphs)
+                {
+                    if (!NativeMethods.vello_composition_layout_paragraph(
+                            textPtr,
+                            (nuint)utf8.Length,
+                            in native,
+                            out metrics,
+                            linesPtr,
+                            (nuint)nativeLines.Length,
+                            glyphsPtr,
+                            (nuint)nativeGlyphs.Length))
+                    {
+                        return null;
+                    }
+                }
+            }
+        }
+
+        var lines = new ParagraphLine[nativeLines.Length];
+        for (int i = 0; i < lines.Length; i++)
+        {
+            var line = nativeLines[i];
+            int start = Encoding.UTF8.GetCharCount(utf8, 0, (int)line.TextStart);
+            int length = Encoding.UTF8.GetCharCount(utf8, (int)line.TextStart, (int)line.TextLength);
+            lines[i] = new ParagraphLine(
+                start,
+                length,
+                line.X,
+                line.Baseline,
+                line.Width,
+                (int)line.GlyphStart,
+                (int)line.GlyphCount,
+                line.Ellipsized != 0);
+        }
+
+        var glyphs = new ParagraphGlyph[nativeGlyphs.Length];
+        for (int i = 0; i < glyphs.Length; i++)
+        {
+            var glyph = nativeGlyphs[i];
+            glyphs[i] = new ParagraphGlyph(glyph.FontId, glyph.GlyphId, glyph.X, glyph.Y);
+        }
+
+        return new ParagraphLayout(
+            metrics.Width,
+            metrics.Height,
+            metrics.LineAdvance,
+            metrics.Truncated != 0,
+            lines,
+            glyphs);
+    }
+
     public static int SolveLinearLayout(
         ReadOnlySpan<LinearLayoutChild> children,
         double available,
@@ -998,6 +1172,99 @@ public static bool TryResolveColor(uint materialId, out CompositionColor color)
     }
 }
 
+/// <summary>
+/// Fonts used to shape composition labels. Characters missing from the default
+/// families fall back to the next family in the list, then to the built-in label font.
+/// </summary>
+public static class CompositionFontRegistry
+{
+    /// <summary>
+    /// Registers the face at <paramref name="faceIndex"/> of a font or collection file
+    /// under <paramref name="family"/> and returns its identifier.
+    /// </summary>
+    public static uint Register(string family, ReadOnlySpan<byte> fontData, uint faceIndex = 0)
+    {
+        ArgumentException.ThrowIfNullOrWhiteSpace(family);
+        if (fontData.IsEmpty)
+        {
+            throw new ArgumentException("Font data must not be empty.", nameof(fontData));
+        }
+
+        byte[] familyUtf8 = Encoding.UTF8.GetBytes(family);
+        unsafe
+        {
+            fixed (byte* familyPtr = familyUtf8)
+            fixed (byte* dataPtr = fontData)
+            {
+                if (!NativeMethods.vello_composition_font_register(
+                        familyPtr,
+                        (nuint)familyUtf8.Length,
+                        dataPtr,
+                        (nuint)fontData.Length,
+                        faceIndex,
+                        out var fontId))
+                {
+                    throw new InvalidOperationException("Failed to register composition font.");
+                }
+
+                return fontId;
+            }
+        }
+    }
+
+    /// <summary>
+    /// Sets the families tried, in order, before the built-in label font. An empty list
+    /// restores the built-in font alone.
+    /// </summary>
+    public static void SetDefaultFamilies(IReadOnlyList<string> families)
+    {
+        ArgumentNullException.ThrowIfNull(families);
+
+        var names = new GCHandle[families.Count];
+        var native = new VelloCompositionFontFamily[families.Count];
+        try
+        {
+            for (int i = 0; i < families.Count; i++)
+            {
+                var name = families[i] ?? throw new ArgumentException("Family names must not be null.", nameof(families));
+                byte[] utf8 = Encoding.UTF8.GetBytes(name);
+                names[i] = GCHandle.Alloc(utf8, GCHandleType.Pinned);
+                unsafe
+                {
+                    native[i] = new VelloCompositionFontFamily
+                    {
+                        Name = (byte*)names[i].AddrOfPinnedObject(),
+                        NameLength = (nuint)utf8.Length,
+                    };
+                }
+            }
+
+            unsafe
+            {
+                fixed (VelloCompositionFontFamily* familiesPtr = native)
+                {
+                    if (!NativeMethods.vello_composition_font_set_default_families(
+                            familiesPtr,
+                            (nuint)native.Length))
+                    {
+                        throw new InvalidOperationException("Failed to set composition font families.");
+                    }
+                }
+            }
+        }
+        finally
+        {
+            foreach (var handle in names)
+            {
+                if (handle.IsAllocated)
+                {
+                    handle.Free();
+                }
+            }
+        }
+    }
+}
+
 public readonly record struct DirtyRegion(double MinX, double MaxX, double MinY, double MaxY)
 {
     public bool IsEmpty => MinX > MaxX || MinY > MaxY;
@@ -1042,6 +1309,9 @@ public uint CreateNode(uint? parentId = null)
         return node;
     }
 
+    /// <summary>
+    /// Releases the node and all of its descendants for reuse.
+    /// </summary>
     public void DisposeNode(uint nodeId)
     {
         ThrowIfInvalid();
@@ -1091,6 +1361,87 @@ public void Clear(uint nodeId)
         NativeMethods.vello_composition_scene_cache_clear(handle, nodeId);
     }
 
+    /// <summary>
+    /// Sets the transform from the node's space into its parent's. Dirty regions taken from an
+    /// ancestor are mapped through it.
+    /// </summary>
+    public void SetTransform(uint nodeId, Matrix3x2 transform)
+    {
+        ThrowIfInvalid();
+        var native = new VelloCompositionAffine
+        {
+            M11 = transform.M11,
+            M12 = transform.M12,
+            M21 = transform.M21,
+            M22 = transform.M22,
+            Dx = transform.M31,
+            Dy = transform.M32,
+        };
+        if (!NativeMethods.vello_composition_scene_cache_set_transform(handle, nodeId, native))
+        {
+            throw new InvalidOperationException("Failed to set scene cache node transform.");
+        }
+    }
+
+    public void SetClip(uint nodeId, double left, double top, double width, double height, double cornerRadius = 0)
+    {
+        ThrowIfInvalid();
+        if (!NativeMethods.vello_composition_scene_cache_set_clip_rect(
+                handle,
+                nodeId,
+                left,
+                top,
+                width,
+                height,
+                cornerRadius))
+        {
+            throw new InvalidOperationException("Failed to set scene cache node clip.");
+        }
+    }
+
+    public void ClearClip(uint nodeId)
+    {
+        ThrowIfInvalid();
+        NativeMethods.vello_composition_scene_cache_clear_clip(handle, nodeId);
+    }
+
+    /// <summary>
+    /// Declares the area the node's subtree draws in, in the node's space. Transform, clip, opacity and
+    /// z-index changes mark this area dirty on the parent; nodes without bounds mark the whole parent.
+    /// </summary>
+    public void SetBounds(uint nodeId, double left, double top, double width, double height)
+    {
+        ThrowIfInvalid();
+        if (!NativeMethods.vello_composition_scene_cache_set_bounds(handle, nodeId, left, top, width, height))
+        {
+            throw new InvalidOperationException("Failed to set scene cache node bounds.");
+        }
+    }
+
+    public void ClearBounds(uint nodeId)
+    {
+        ThrowIfInvalid();
+        NativeMethods.vello_composition_scene_cache_clear_bounds(handle, nodeId);
+    }
+
+    public void SetOpacity(uint nodeId, float opacity)
+    {
+        ThrowIfInvalid();
+        if (!NativeMethods.vello_composition_scene_cache_set_opacity(handle, nodeId, opacity))
+        {
+            throw new InvalidOperationException("Failed to set scene cache node opacity.");
+        }
+    }
+
+    public void SetZIndex(uint nodeId, int zIndex)
+    {
+        ThrowIfInvalid();
+        if (!NativeMethods.vello_composition_scene_cache_set_z_index(handle, nodeId, zIndex))
+        {
+            throw new InvalidOperationException("Failed to set scene cache node z-index.");
+        }
+    }
+
     private void ThrowIfInvalid()
     {
         if (IsInvalid)
@@ -0,0 +1,29 @@
+using System.Collections.Generic;
+
+namespace VelloSharp.Composition;
+
+public enum FontSlant
+{
+    Normal = 0,
+    Italic = 1,
+    Oblique = 2,
+}
+
+/// <summary>
+/// A variation axis value in the axis' user units, for example <c>new FontVariation("wght", 650f)</c>.
+/// </summary>
+public readonly record struct FontVariation(string Tag, float Value);
+
+/// <summary>
+/// Face selection and variable font instance for <see cref="CompositionInterop.MeasureLabel(string, in LabelStyle)"/>.
+/// <paramref name="Family"/> is tried before the default families; <paramref name="Weight"/> and
+/// <paramref name="Width"/> (a percentage of normal) pick faces and drive the <c>wght</c> and
+/// <c>wdth</c> axes, and <paramref name="Variations"/> override any axis afterwards.
+/// </summary>
+public readonly record struct LabelStyle(
+    float FontSize,
+    string? Family = null,
+    float Weight = 400f,
+    float Width = 100f,
+    FontSlant Slant = FontSlant.Normal,
+    IReadOnlyList<FontVariation>? Variations = null);
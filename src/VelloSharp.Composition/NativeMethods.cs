@@ -25,6 +25,46 @@ internal static partial class NativeMethods
         float fontSize,
         out VelloCompositionLabelMetrics metrics);
 
+    [LibraryImport(LibraryName, EntryPoint = "vello_composition_measure_label_styled")]
+    [return: MarshalAs(UnmanagedType.I1)]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static unsafe partial bool vello_composition_measure_label_styled(
+        byte* textUtf8,
+        nuint textLength,
+        in VelloCompositionLabelStyle style,
+        out VelloCompositionLabelMetrics metrics);
+
+    [LibraryImport(LibraryName, EntryPoint = "vello_composition_font_register")]
+    [return: MarshalAs(UnmanagedType.I1)]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static unsafe partial bool vello_composition_font_register(
+        byte* familyUtf8,
+        nuint familyLength,
+        byte* data,
+        nuint dataLength,
+        uint index,
+        out uint fontId);
+
+    [LibraryImport(LibraryName, EntryPoint = "vello_composition_font_set_default_families")]
+    [return: MarshalAs(UnmanagedType.I1)]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static unsafe partial bool vello_composition_font_set_default_families(
+        VelloCompositionFontFamily* families,
+        nuint familyCount);
+
+    [LibraryImport(LibraryName, EntryPoint = "vello_composition_layout_paragraph")]
+    [return: MarshalAs(UnmanagedType.I1)]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static unsafe partial bool vello_composition_layout_paragraph(
+        byte* textUtf8,
+        nuint textLength,
+        in VelloCompositionParagraphOptions options,
+        out VelloCompositionParagraphMetrics metrics,
+        VelloCompositionParagraphLine* lines,
+        nuint lineLength,
+        VelloCompositionGlyph* glyphs,
+        nuint glyphLength);
+
     [LibraryImport(LibraryName, EntryPoint = "vello_composition_solve_linear_layout")]
     [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
     internal static unsafe partial nuint vello_composition_solve_linear_layout(
@@ -169,6 +209,65 @@ internal static partial class NativeMethods
         nint cache,
         uint nodeId);
 
+    [LibraryImport(LibraryName, EntryPoint = "vello_composition_scene_cache_set_transform")]
+    [return: MarshalAs(UnmanagedType.I1)]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static partial bool vello_composition_scene_cache_set_transform(
+        nint cache,
+        uint nodeId,
+        VelloCompositionAffine transform);
+
+    [LibraryImport(LibraryName, EntryPoint = "vello_composition_scene_cache_set_clip_rect")]
+    [return: MarshalAs(UnmanagedType.I1)]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static partial bool vello_composition_scene_cache_set_clip_rect(
+        nint cache,
+        uint nodeId,
+        double left,
+        double top,
+        double width,
+        double height,
+        double cornerRadius);
+
+    [LibraryImport(LibraryName, EntryPoint = "vello_composition_scene_cache_clear_clip")]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static partial void vello_composition_scene_cache_clear_clip(
+        nint cache,
+        uint nodeId);
+
+    [LibraryImport(LibraryName, EntryPoint = "vello_composition_scene_cache_set_bounds")]
+    [return: MarshalAs(UnmanagedType.I1)]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static partial bool vello_composition_scene_cache_set_bounds(
+        nint cache,
+        uint nodeId,
+        double left,
+        double top,
+        double width,
+        double height);
+
+    [LibraryImport(LibraryName, EntryPoint = "vello_composition_scene_cache_clear_bounds")]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static partial void vello_composition_scene_cache_clear_bounds(
+        nint cache,
+        uint nodeId);
+
+    [LibraryImport(LibraryName, EntryPoint = "vello_composition_scene_cache_set_opacity")]
+    [return: MarshalAs(UnmanagedType.I1)]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static partial bool vello_composition_scene_cache_set_opacity(
+        nint cache,
+        uint nodeId,
+        float opacity);
+
+    [LibraryImport(LibraryName, EntryPoint = "vello_composition_scene_cache_set_z_index")]
+    [return: MarshalAs(UnmanagedType.I1)]
+    [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
+    internal static partial bool vello_composition_scene_cache_set_z_index(
+        nint cache,
+        uint nodeId,
+        int zIndex);
+
     [LibraryImport(LibraryName, EntryPoint = "vello_composition_timeline_system_create")]
     [UnmanagedCallConv(CallConvs = new[] { typeof(CallConvCdecl) })]
     internal static partial nint vello_composition_timeline_system_create();
@@ -336,6 +435,94 @@ internal struct VelloCompositionLabelMetrics
     public float Ascent;
 }
 
+internal enum VelloCompositionFontSlant : uint
+{
+    Normal = 0,
+    Italic = 1,
+    Oblique = 2,
+}
+
+[StructLayout(LayoutKind.Sequential)]
+internal struct VelloCompositionFontVariation
+{
+    public uint Tag;
+    public float Value;
+}
+
+[StructLayout(LayoutKind.Sequential)]
+internal unsafe struct VelloCompositionLabelStyle
+{
+    public byte* Family;
+    public nuint FamilyLength;
+    public float FontSize;
+    public float Weight;
+    public float Width;
+    public VelloCompositionFontSlant Slant;
+    public VelloCompositionFontVariation* Variations;
+    public nuint VariationCount;
+}
+
+internal enum VelloCompositionTextWrap : uint
+{
+    NoWrap = 0,
+    Word = 1,
+    Character = 2,
+}
+
+[StructLayout(LayoutKind.Sequential)]
+internal struct VelloCompositionParagraphOptions
+{
+    public float FontSize;
+    public float MaxWidth;
+    public float MaxHeight;
+    public float LineHeight;
+    public uint MaxLines;
+    public VelloCompositionTextWrap Wrap;
+    public int Ellipsis;
+    public VelloCompositionLayoutAlignment HorizontalAlignment;
+    public VelloCompositionLayoutAlignment VerticalAlignment;
+}
+
+[StructLayout(LayoutKind.Sequential)]
+internal struct VelloCompositionParagraphMetrics
+{
+    public float Width;
+    public float Height;
+    public float LineAdvance;
+    public uint LineCount;
+    public uint GlyphCount;
+    public int Truncated;
+}
+
+[StructLayout(LayoutKind.Sequential)]
+internal struct VelloCompositionParagraphLine
+{
+    public uint TextStart;
+    public uint TextLength;
+    public float X;
+    public float Baseline;
+    public float Width;
+    public uint GlyphStart;
+    public uint GlyphCount;
+    public int Ellipsized;
+}
+
+[StructLayout(LayoutKind.Sequential)]
+internal struct VelloCompositionGlyph
+{
+    public uint FontId;
+    public uint GlyphId;
+    public float X;
+    public float Y;
+}
+
+[StructLayout(LayoutKind.Sequential)]
+internal unsafe struct VelloCompositionFontFamily
+{
+    public byte* Name;
+    public nuint NameLength;
+}
+
 [StructLayout(LayoutKind.Sequential)]
 internal struct VelloCompositionLinearLayoutItem
 {
@@ -524,6 +711,17 @@ internal struct VelloCompositionDirtyRegion
     public double MaxY;
 }
 
+[StructLayout(LayoutKind.Sequential)]
+internal struct VelloCompositionAffine
+{
+    public double M11;
+    public double M12;
+    public double M21;
+    public double M22;
+    public double Dx;
+    public double Dy;
+}
+
 [StructLayout(LayoutKind.Sequential)]
 internal struct VelloCompositionColor
 {
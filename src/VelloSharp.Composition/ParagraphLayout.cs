@@ -0,0 +1,56 @@
+using System.Collections.Generic;
+
+namespace VelloSharp.Composition;
+
+public enum TextWrap
+{
+    NoWrap = 0,
+    Word = 1,
+    Character = 2,
+}
+
+/// <summary>
+/// Wrapping, trimming and alignment for <see cref="CompositionInterop.LayoutParagraph"/>.
+/// Infinite extents leave lines unbounded; a zero <paramref name="LineHeight"/> uses the
+/// font's natural line height and a zero <paramref name="MaxLines"/> keeps every line.
+/// </summary>
+public readonly record struct ParagraphOptions(
+    float FontSize,
+    float MaxWidth = float.PositiveInfinity,
+    float MaxHeight = float.PositiveInfinity,
+    TextWrap Wrap = TextWrap.NoWrap,
+    float LineHeight = 0f,
+    int MaxLines = 0,
+    bool Ellipsis = false,
+    LayoutAlignment HorizontalAlignment = LayoutAlignment.Start,
+    LayoutAlignment VerticalAlignment = LayoutAlignment.Start);
+
+/// <summary>
+/// A laid out line. <see cref="Start"/> and <see cref="Length"/> index the source string
+/// and exclude any ellipsis; the line's glyphs are
+/// <c>Glyphs[GlyphStart..(GlyphStart + GlyphCount)]</c>.
+/// </summary>
+public readonly record struct ParagraphLine(
+    int Start,
+    int Length,
+    float X,
+    float Baseline,
+    float Width,
+    int GlyphStart,
+    int GlyphCount,
+    bool Ellipsized);
+
+/// <summary>
+/// A positioned glyph relative to the paragraph box's top-left corner. <see cref="FontId"/>
+/// is the identifier returned by <see cref="CompositionFontRegistry.Register"/>, or zero for
+/// the built-in label font.
+/// </summary>
+public readonly record struct ParagraphGlyph(uint FontId, uint GlyphId, float X, float Y);
+
+public sealed record ParagraphLayout(
+    float Width,
+    float Height,
+    float LineAdvance,
+    bool Truncated,
+    IReadOnlyList<ParagraphLine> Lines,
+    IReadOnlyList<ParagraphGlyph> Glyphs);
@@ -90,6 +90,51 @@ public void Composition_WithMultiplePanes_ProducesPaneMetadata()
         Assert.Contains(metadata.Series, s => s.SeriesId == 1 && s.PaneIndex == 1);
     }
 
+    [Fact]
+    public void Composition_ValueScale_IsReportedInPaneMetadata()
+    {
+        _engine.ConfigureSeries(new ChartSeriesDefinition[]
+        {
+            new LineSeriesDefinition(0),
+            new LineSeriesDefinition(1),
+        });
+
+        var composition = ChartComposition.Create(builder =>
+        {
+            builder
+                .Pane("latency")
+                .WithSeries(0)
+                .WithValueScale(ChartValueScale.Log10)
+                .Done();
+
+            builder
+                .Pane("delta")
+                .WithSeries(1)
+                .WithValueScale(ChartValueScale.SymLog, 10.0)
+                .Done();
+        });
+
+        _engine.ConfigureComposition(composition);
+
+        var now = DateTimeOffset.UtcNow.ToUnixTimeMilliseconds() / 1000.0;
+        _engine.PumpData(new[]
+        {
+            new ChartSamplePoint(0, now - 10, 10.0),
+            new ChartSamplePoint(0, now, 1000.0),
+            new ChartSamplePoint(1, now - 10, -500.0),
+            new ChartSamplePoint(1, now, 500.0),
+        });
+
+        using var scene = new VelloSharp.Scene();
+        _engine.Render(scene, 800, 600);
+
+        var metadata = _engine.GetFrameMetadata();
+        Assert.Equal(ChartValueScale.Log10, metadata.Panes[0].ValueScale);
+        Assert.Null(metadata.Panes[0].SymlogLinearThreshold);
+        Assert.Equal(ChartValueScale.SymLog, metadata.Panes[1].ValueScale);
+        Assert.Equal(10.0, metadata.Panes[1].SymlogLinearThreshold);
+    }
+
     [Fact]
     public void BandSeries_ProducesBandMetadata()
     {
@@ -44,6 +44,24 @@ public void RejectsNonPositiveDomains()
     }
 }
 
+public sealed class SymmetricLogarithmicScaleTests
+{
+    [Fact]
+    public void ProjectsSymmetricallyAroundZero()
+    {
+        var scale = new SymmetricLogarithmicScale(-99d, 99d);
+        Assert.Equal(0.5d, scale.Project(0d), 6);
+        Assert.Equal(0.75d, scale.Project(9d), 6);
+        Assert.Equal(-9d, scale.Unproject(0.25d), 6);
+    }
+
+    [Fact]
+    public void RejectsNonPositiveThresholds()
+    {
+        Assert.Throws<ArgumentOutOfRangeException>(() => new SymmetricLogarithmicScale(-1d, 1d, 0d));
+    }
+}
+
 public sealed class TimeScaleTests
 {
     [Fact]